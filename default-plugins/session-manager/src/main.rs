@@ -367,6 +367,10 @@ impl State {
                 BareKey::Char('x') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                     disconnect_other_clients()
                 },
+                BareKey::Char('a') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.join_selected_pane();
+                    should_render = true;
+                },
                 BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                     if !self.search_term.is_empty() {
                         self.search_term.clear();
@@ -529,6 +533,26 @@ impl State {
             },
         }
     }
+    fn join_selected_pane(&mut self) {
+        let selected_pane = self.sessions.get_selected_pane_id();
+        match selected_pane {
+            Some((pane_id, is_plugin)) if self.sessions.selected_is_current_session() => {
+                let pane_id = if is_plugin {
+                    PaneId::Plugin(pane_id)
+                } else {
+                    PaneId::Terminal(pane_id)
+                };
+                let should_float = false;
+                join_pane_to_focused_tab(pane_id, should_float);
+                self.reset_selected_index();
+                self.search_term.clear();
+                self.sessions
+                    .update_search_term(&self.search_term, &self.colors);
+                hide_self();
+            },
+            _ => self.show_error("Must select a pane of this session to join it here."),
+        }
+    }
     fn toggle_active_screen(&mut self) {
         self.active_screen = match self.active_screen {
             ActiveScreen::NewSession => ActiveScreen::AttachToSession,
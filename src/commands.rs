@@ -1,5 +1,11 @@
 use dialoguer::Confirm;
-use std::{fs::File, io::prelude::*, path::PathBuf, process, time::Duration};
+use std::{
+    fs::File,
+    io::prelude::*,
+    path::PathBuf,
+    process,
+    time::{Duration, Instant},
+};
 
 use crate::sessions::{
     assert_dead_session, assert_session, assert_session_ne, delete_session as delete_session_impl,
@@ -18,7 +24,8 @@ use zellij_client::{
 };
 use zellij_server::{os_input_output::get_server_os_input, start_server as start_server_impl};
 use zellij_utils::{
-    cli::{CliArgs, Command, SessionCommand, Sessions},
+    asciicast::{parse_asciicast, AsciicastEvent},
+    cli::{CliAction, CliArgs, Command, SessionCommand, Sessions},
     data::{ConnectToSession, LayoutInfo},
     envs,
     input::{
@@ -28,11 +35,11 @@ use zellij_utils::{
         options::Options,
     },
     miette::{Report, Result},
-    nix,
+    nix::{self, sys::termios},
     setup::{find_default_config_dir, get_layout_dir, Setup},
 };
 
-pub(crate) use crate::sessions::list_sessions;
+pub(crate) use crate::sessions::{list_sessions, list_sessions_as_json};
 
 pub(crate) fn kill_all_sessions(yes: bool) {
     match get_sessions() {
@@ -175,6 +182,18 @@ pub(crate) fn send_action_to_session(
     cli_action: zellij_utils::cli::CliAction,
     requested_session_name: Option<String>,
     config: Option<Config>,
+) {
+    send_targeted_action_to_session(cli_action, None, None, requested_session_name, config)
+}
+
+/// Like [`send_action_to_session`], but the action is sent to the given pane or to the tab at
+/// the given position rather than to the focused one
+pub(crate) fn send_targeted_action_to_session(
+    cli_action: zellij_utils::cli::CliAction,
+    pane_id: Option<String>,
+    tab_index: Option<usize>,
+    requested_session_name: Option<String>,
+    config: Option<Config>,
 ) {
     match get_active_session() {
         ActiveSession::None => {
@@ -192,7 +211,7 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             }
-            attach_with_cli_client(cli_action, &session_name, config);
+            attach_with_cli_client(cli_action, pane_id, tab_index, &session_name, config);
         },
         ActiveSession::Many => {
             let existing_sessions: Vec<String> = get_sessions()
@@ -202,7 +221,7 @@ pub(crate) fn send_action_to_session(
                 .collect();
             if let Some(session_name) = requested_session_name {
                 if existing_sessions.contains(&session_name) {
-                    attach_with_cli_client(cli_action, &session_name, config);
+                    attach_with_cli_client(cli_action, pane_id, tab_index, &session_name, config);
                 } else {
                     eprintln!(
                         "Session '{}' not found. The following sessions are active:",
@@ -212,7 +231,7 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             } else if let Ok(session_name) = envs::get_session_name() {
-                attach_with_cli_client(cli_action, &session_name, config);
+                attach_with_cli_client(cli_action, pane_id, tab_index, &session_name, config);
             } else {
                 eprintln!("Please specify the session name to send actions to. The following sessions are active:");
                 list_sessions(false, false, true);
@@ -290,14 +309,119 @@ pub(crate) fn convert_old_theme_file(old_theme_file: PathBuf) {
     }
 }
 
+pub(crate) fn replay(
+    file: PathBuf,
+    here: bool,
+    speed: f64,
+    session_name: Option<String>,
+    config: Option<Config>,
+) {
+    let recording = match std::fs::read_to_string(&file) {
+        Ok(recording) => recording,
+        Err(e) => {
+            eprintln!("Failed to open file: {}", e);
+            process::exit(1);
+        },
+    };
+    let (header, events) = match parse_asciicast(&recording) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Failed to parse recording: {:?}", e);
+            process::exit(1);
+        },
+    };
+    if speed <= 0.0 {
+        eprintln!("Speed must be a positive number");
+        process::exit(1);
+    }
+    let is_inside_session = session_name.is_some() || envs::get_session_name().is_ok();
+    if here || !is_inside_session {
+        play_recording(events, speed);
+        return;
+    }
+    // we play the recording from a command pane running this same command in the current
+    // terminal, sized so that the recording fits in it
+    let file = std::env::current_dir()
+        .map(|cwd| cwd.join(&file))
+        .unwrap_or(file);
+    let zellij_executable = std::env::current_exe()
+        .map(|exe| exe.to_string_lossy().to_string())
+        .unwrap_or_else(|_| "zellij".to_owned());
+    let command = vec![
+        zellij_executable,
+        "replay".to_owned(),
+        "--here".to_owned(),
+        "--speed".to_owned(),
+        speed.to_string(),
+        file.to_string_lossy().to_string(),
+    ];
+    let replay_cli_action = CliAction::NewPane {
+        command,
+        plugin: None,
+        direction: None,
+        cwd: None,
+        floating: true,
+        in_place: false,
+        name: Some(format!("Replay: {}", file.display())),
+        close_on_exit: false,
+        start_suspended: false,
+        configuration: None,
+        skip_plugin_cache: false,
+        x: None,
+        y: None,
+        width: Some((header.width + 2).to_string()), // + 2 for the pane frame
+        height: Some((header.height + 2).to_string()),
+        pinned: None,
+        popup: false,
+        print_output: false,
+    };
+    send_action_to_session(replay_cli_action, session_name, config);
+}
+
+fn play_recording(events: Vec<AsciicastEvent>, speed: f64) {
+    // the replay is read-only, so we make sure whatever is typed while it plays does not get
+    // mixed with its output
+    let orig_termios = termios::tcgetattr(0).ok();
+    if let Some(orig_termios) = orig_termios.as_ref() {
+        let mut termios = orig_termios.clone();
+        termios
+            .local_flags
+            .remove(termios::LocalFlags::ECHO | termios::LocalFlags::ICANON);
+        let _ = termios::tcsetattr(0, termios::SetArg::TCSANOW, &termios);
+    }
+    let mut stdout = std::io::stdout();
+    let started_at = Instant::now();
+    for event in events {
+        if let AsciicastEvent::Output(time, output) = event {
+            let time = time.div_f64(speed);
+            if let Some(time_to_wait) = time.checked_sub(started_at.elapsed()) {
+                std::thread::sleep(time_to_wait);
+            }
+            let _ = stdout.write_all(output.as_bytes());
+            let _ = stdout.flush();
+        }
+    }
+    if let Some(orig_termios) = orig_termios {
+        let _ = termios::tcsetattr(0, termios::SetArg::TCSANOW, &orig_termios);
+    }
+}
+
 fn attach_with_cli_client(
     cli_action: zellij_utils::cli::CliAction,
+    pane_id: Option<String>,
+    tab_index: Option<usize>,
     session_name: &str,
     config: Option<Config>,
 ) {
     let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
     let get_current_dir = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    match Action::actions_from_cli(cli_action, Box::new(get_current_dir), config) {
+    match Action::actions_from_cli_with_target(
+        cli_action,
+        pane_id,
+        tab_index,
+        Box::new(get_current_dir),
+        config,
+    ) {
         Ok(actions) => {
             zellij_client::cli_client::start_cli_client(Box::new(os_input), session_name, actions);
             std::process::exit(0);
@@ -20,8 +20,19 @@ fn main() {
 
     {
         let config = Config::try_from(&opts).ok();
-        if let Some(Command::Sessions(Sessions::Action(cli_action))) = opts.command {
-            commands::send_action_to_session(cli_action, opts.session, config);
+        if let Some(Command::Sessions(Sessions::Action {
+            pane_id,
+            tab_index,
+            action,
+        })) = opts.command
+        {
+            commands::send_targeted_action_to_session(
+                action,
+                pane_id,
+                tab_index,
+                opts.session,
+                config,
+            );
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Run {
@@ -38,6 +49,8 @@ fn main() {
             width,
             height,
             pinned,
+            popup,
+            print_output,
         })) = opts.command
         {
             let cwd = cwd.or_else(|| std::env::current_dir().ok());
@@ -59,6 +72,8 @@ fn main() {
                 width,
                 height,
                 pinned,
+                popup,
+                print_output,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
@@ -94,6 +109,8 @@ fn main() {
                 width,
                 height,
                 pinned,
+                popup: false,
+                print_output: false,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
@@ -135,6 +152,10 @@ fn main() {
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
         }
+        if let Some(Command::Sessions(Sessions::Replay { file, here, speed })) = opts.command {
+            commands::replay(file, here, speed, opts.session, config);
+            std::process::exit(0);
+        }
         if let Some(Command::Sessions(Sessions::ConvertConfig { old_config_file })) = opts.command {
             commands::convert_old_config_file(old_config_file);
             std::process::exit(0);
@@ -178,9 +199,14 @@ fn main() {
         no_formatting,
         short,
         reverse,
+        json,
     })) = opts.command
     {
-        commands::list_sessions(no_formatting, short, reverse);
+        if json {
+            commands::list_sessions_as_json(reverse);
+        } else {
+            commands::list_sessions(no_formatting, short, reverse);
+        }
     } else if let Some(Command::Sessions(Sessions::ListAliases)) = opts.command {
         commands::list_aliases(opts);
     } else if let Some(Command::Sessions(Sessions::KillAllSessions { yes })) = opts.command {
@@ -6,14 +6,16 @@ use suggest::Suggest;
 use zellij_utils::{
     anyhow,
     consts::{
-        session_info_folder_for_session, session_layout_cache_file_name,
-        ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+        session_info_cache_file_name, session_info_folder_for_session,
+        session_layout_cache_file_name, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
     },
+    data::SessionInfo,
     envs,
     humantime::format_duration,
     input::layout::Layout,
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
+    serde_json,
 };
 
 pub(crate) fn get_sessions() -> Result<Vec<(String, Duration)>, io::ErrorKind> {
@@ -322,6 +324,73 @@ pub(crate) fn list_sessions(no_formatting: bool, short: bool, reverse: bool) {
     process::exit(exit_code);
 }
 
+pub(crate) fn list_sessions_as_json(reverse: bool) {
+    let exit_code = match get_sessions() {
+        Ok(running_sessions) => {
+            let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
+            let mut all_sessions: Vec<(String, Duration, bool)> = get_resurrectable_sessions()
+                .into_iter()
+                .filter(|(name, _timestamp, _layout)| {
+                    !running_sessions.iter().any(|(running, _)| running == name)
+                })
+                .map(|(name, timestamp, _layout)| (name, timestamp, true))
+                .collect();
+            all_sessions.extend(
+                running_sessions
+                    .into_iter()
+                    .map(|(name, timestamp)| (name, timestamp, false)),
+            );
+            all_sessions.sort_by(|a, b| {
+                if reverse {
+                    a.1.cmp(&b.1)
+                } else {
+                    b.1.cmp(&a.1)
+                }
+            });
+            // the session info is serialized up front so that a failure is reported rather than
+            // panicking in the json! macro
+            let sessions: Result<Vec<serde_json::Value>, serde_json::Error> = all_sessions
+                .iter()
+                .map(|(session_name, timestamp, is_dead)| {
+                    // the tabs and panes of running sessions are read from the metadata they
+                    // periodically write to disk
+                    let session_info = if *is_dead {
+                        None
+                    } else {
+                        fs::read_to_string(session_info_cache_file_name(session_name))
+                            .ok()
+                            .and_then(|raw_session_info| {
+                                SessionInfo::from_string(&raw_session_info, &curr_session).ok()
+                            })
+                    };
+                    Ok(serde_json::json!({
+                        "name": session_name,
+                        "created_seconds_ago": timestamp.as_secs(),
+                        "is_current_session": curr_session == *session_name,
+                        "exited": is_dead,
+                        "session_info": serde_json::to_value(session_info)?,
+                    }))
+                })
+                .collect();
+            match sessions.and_then(|sessions| serde_json::to_string(&sessions)) {
+                Ok(sessions) => {
+                    println!("{}", sessions);
+                    0
+                },
+                Err(e) => {
+                    eprintln!("Failed to serialize sessions: {}", e);
+                    1
+                },
+            }
+        },
+        Err(e) => {
+            eprintln!("Error occurred: {:?}", e);
+            1
+        },
+    };
+    process::exit(exit_code);
+}
+
 #[derive(Debug, Clone)]
 pub enum SessionNameMatch {
     AmbiguousPrefix(Vec<String>),
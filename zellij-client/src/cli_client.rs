@@ -3,7 +3,8 @@
 use std::collections::BTreeMap;
 use std::io::BufRead;
 use std::process;
-use std::{fs, path::PathBuf};
+use std::time::Duration;
+use std::{fs, path::PathBuf, thread};
 
 use crate::os_input_output::ClientOsApi;
 use zellij_utils::{
@@ -63,6 +64,23 @@ pub fn start_cli_client(
                     pane_title,
                 );
             },
+            Action::NewFloatingPane(Some(ref run_command_action), ..)
+                if run_command_action.output_pipe_id.is_some() =>
+            {
+                let output_pipe_id = run_command_action
+                    .output_pipe_id
+                    .clone()
+                    .unwrap_or_default();
+                popup_client(&mut os_input, action, output_pipe_id, pane_id);
+            },
+            Action::WaitFor {
+                ref pipe_id,
+                timeout,
+                ..
+            } => {
+                let pipe_id = pipe_id.clone();
+                wait_for_client(&mut os_input, action, pipe_id, timeout, pane_id);
+            },
             action => {
                 individual_messages_client(&mut os_input, action, pane_id);
             },
@@ -199,6 +217,128 @@ fn pipe_client(
     }
 }
 
+fn popup_client(
+    os_input: &mut Box<dyn ClientOsApi>,
+    action: Action,
+    output_pipe_id: String,
+    pane_id: Option<u32>,
+) {
+    let msg = ClientToServerMsg::Action(action, pane_id, None);
+    os_input.send_to_server(msg);
+    let mut exit_status = 0;
+    loop {
+        // the popup command writes its output to this pipe once it exits successfully, and its
+        // exit status is sent right before the pipe is unblocked whichever way it exits
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::CliPipeOutput(pipe_name, output), _)) => {
+                let err_context = "Failed to write to stdout";
+                if pipe_name == output_pipe_id {
+                    let mut stdout = os_input.get_stdout_writer();
+                    stdout
+                        .write_all(output.as_bytes())
+                        .context(err_context)
+                        .non_fatal();
+                    stdout.flush().context(err_context).non_fatal();
+                }
+            },
+            Some((ServerToClientMsg::CliPipeExitStatus(pipe_name, status), _)) => {
+                if pipe_name == output_pipe_id {
+                    exit_status = status;
+                }
+            },
+            Some((ServerToClientMsg::UnblockCliPipeInput(pipe_name), _)) => {
+                if pipe_name == output_pipe_id {
+                    if exit_status == 0 {
+                        break;
+                    } else {
+                        os_input.send_to_server(ClientToServerMsg::ClientExited);
+                        process::exit(exit_status);
+                    }
+                }
+            },
+            Some((ServerToClientMsg::Log(log_lines), _)) => {
+                log_lines.iter().for_each(|line| println!("{line}"));
+                break;
+            },
+            Some((ServerToClientMsg::LogError(log_lines), _)) => {
+                log_lines.iter().for_each(|line| eprintln!("{line}"));
+                process::exit(2);
+            },
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => match exit_reason {
+                ExitReason::Error(e) => {
+                    eprintln!("{}", e);
+                    process::exit(2);
+                },
+                _ => {
+                    break;
+                },
+            },
+            _ => {},
+        }
+    }
+}
+
+fn wait_for_client(
+    os_input: &mut Box<dyn ClientOsApi>,
+    action: Action,
+    pipe_id: String,
+    timeout: Option<u64>,
+    pane_id: Option<u32>,
+) {
+    if let Some(timeout) = timeout {
+        let os_input = os_input.box_clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_secs(timeout));
+            // so that the server stops waiting on our behalf
+            os_input.send_to_server(ClientToServerMsg::ClientExited);
+            eprintln!("Timed out after {} seconds", timeout);
+            // same as the timeout(1) utility
+            process::exit(124);
+        });
+    }
+    let msg = ClientToServerMsg::Action(action, pane_id, None);
+    os_input.send_to_server(msg);
+    let mut exit_status = 0;
+    loop {
+        // the exit status of the condition is sent to this pipe right before it is unblocked
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::CliPipeExitStatus(pipe_name, status), _)) => {
+                if pipe_name == pipe_id {
+                    exit_status = status;
+                }
+            },
+            Some((ServerToClientMsg::UnblockCliPipeInput(pipe_name), _)) => {
+                if pipe_name == pipe_id {
+                    if exit_status == 0 {
+                        break;
+                    } else {
+                        os_input.send_to_server(ClientToServerMsg::ClientExited);
+                        process::exit(exit_status);
+                    }
+                }
+            },
+            Some((ServerToClientMsg::Log(log_lines), _)) => {
+                log_lines.iter().for_each(|line| println!("{line}"));
+                break;
+            },
+            Some((ServerToClientMsg::LogError(log_lines), _)) => {
+                log_lines.iter().for_each(|line| eprintln!("{line}"));
+                process::exit(2);
+            },
+            Some((ServerToClientMsg::Exit(exit_reason), _)) => match exit_reason {
+                ExitReason::Error(e) => {
+                    eprintln!("{}", e);
+                    process::exit(2);
+                },
+                _ => {
+                    break;
+                },
+            },
+            _ => {},
+        }
+    }
+}
+
 fn individual_messages_client(
     os_input: &mut Box<dyn ClientOsApi>,
     action: Action,
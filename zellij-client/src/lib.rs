@@ -9,6 +9,7 @@ mod stdin_ansi_parser;
 mod stdin_handler;
 
 use log::info;
+use std::collections::HashSet;
 use std::env::current_exe;
 use std::io::{self, Write};
 use std::path::Path;
@@ -53,8 +54,9 @@ pub(crate) enum ClientInstruction {
     LogError(Vec<String>),
     SwitchSession(ConnectToSession),
     SetSynchronizedOutput(Option<SyncOutput>),
-    UnblockCliPipeInput(String),   // String -> pipe name
-    CliPipeOutput(String, String), // String -> pipe name, String -> output
+    UnblockCliPipeInput(String),    // String -> pipe name
+    CliPipeOutput(String, String),  // String -> pipe name, String -> output
+    CliPipeExitStatus(String, i32), // String -> pipe name, i32 -> exit status
     QueryTerminalSize,
     WriteConfigToDisk { config: String },
 }
@@ -78,6 +80,9 @@ impl From<ServerToClientMsg> for ClientInstruction {
             ServerToClientMsg::CliPipeOutput(pipe_name, output) => {
                 ClientInstruction::CliPipeOutput(pipe_name, output)
             },
+            ServerToClientMsg::CliPipeExitStatus(pipe_name, exit_status) => {
+                ClientInstruction::CliPipeExitStatus(pipe_name, exit_status)
+            },
             ServerToClientMsg::QueryTerminalSize => ClientInstruction::QueryTerminalSize,
             ServerToClientMsg::WriteConfigToDisk { config } => {
                 ClientInstruction::WriteConfigToDisk { config }
@@ -103,6 +108,7 @@ impl From<&ClientInstruction> for ClientContext {
             ClientInstruction::SetSynchronizedOutput(..) => ClientContext::SetSynchronisedOutput,
             ClientInstruction::UnblockCliPipeInput(..) => ClientContext::UnblockCliPipeInput,
             ClientInstruction::CliPipeOutput(..) => ClientContext::CliPipeOutput,
+            ClientInstruction::CliPipeExitStatus(..) => ClientContext::CliPipeExitStatus,
             ClientInstruction::QueryTerminalSize => ClientContext::QueryTerminalSize,
             ClientInstruction::WriteConfigToDisk { .. } => ClientContext::WriteConfigToDisk,
         }
@@ -675,15 +681,26 @@ fn report_changes_in_config_file(
 ) -> Option<Box<dyn Watcher>> {
     match Config::config_file_path(&opts) {
         Some(config_file_path) => {
+            let config_file_and_includes = Config::config_file_and_includes(&config_file_path);
+            let watched_files = Arc::new(Mutex::new(config_file_and_includes.clone()));
             let mut watcher = notify::recommended_watcher({
                 let os_input = os_input.clone();
                 let opts = opts.clone();
                 let config_file_path = config_file_path.clone();
+                let watched_files = watched_files.clone();
                 move |res: Result<Event, _>| match res {
                     Ok(event)
                         if (event.kind.is_create() || event.kind.is_modify())
-                            && event.paths.contains(&config_file_path) =>
+                            && event.paths.iter().any(|path| {
+                                Config::is_config_file_or_include(
+                                    &watched_files.lock().unwrap(),
+                                    path,
+                                )
+                            }) =>
                     {
+                        // the changed file might have added or removed includes
+                        *watched_files.lock().unwrap() =
+                            Config::config_file_and_includes(&config_file_path);
                         match Setup::from_cli_args(&opts) {
                             Ok((
                                 new_config,
@@ -708,7 +725,23 @@ fn report_changes_in_config_file(
             .unwrap();
             if let Some(config_file_parent_folder) = config_file_path.parent() {
                 match watcher.watch(&config_file_parent_folder, RecursiveMode::Recursive) {
-                    Ok(_) => Some(Box::new(watcher)),
+                    Ok(_) => {
+                        // folders of includes added after this point are only watched once the
+                        // client is restarted
+                        for include_folder in config_file_and_includes
+                            .iter()
+                            .filter_map(|include| include.parent())
+                            .filter(|folder| !folder.starts_with(config_file_parent_folder))
+                            .collect::<HashSet<_>>()
+                        {
+                            if let Err(e) =
+                                watcher.watch(include_folder, RecursiveMode::NonRecursive)
+                            {
+                                log::error!("Failed to watch included config folder: {}", e);
+                            }
+                        }
+                        Some(Box::new(watcher))
+                    },
                     Err(e) => {
                         log::error!("Failed to watch config file folder: {}", e);
                         None
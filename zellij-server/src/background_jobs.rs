@@ -55,6 +55,7 @@ pub enum BackgroundJob {
         Vec<u8>,                  // body
         BTreeMap<String, String>, // context
     ),
+    RenderThrottledPanes(Duration), // Duration - the render interval of throttled panes
     Exit,
 }
 
@@ -74,6 +75,7 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
             BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
             BackgroundJob::ReportPluginList(..) => BackgroundJobContext::ReportPluginList,
+            BackgroundJob::RenderThrottledPanes(..) => BackgroundJobContext::RenderThrottledPanes,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
@@ -360,6 +362,18 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
+            BackgroundJob::RenderThrottledPanes(interval) => {
+                // panes whose renders were deferred need one more render once the interval
+                // elapses, otherwise their last output would not be shown until something else
+                // triggers a render
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        task::sleep(interval).await;
+                        let _ = senders.send_to_screen(ScreenInstruction::Render);
+                    }
+                });
+            },
             BackgroundJob::Exit => {
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
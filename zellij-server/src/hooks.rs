@@ -0,0 +1,84 @@
+//! Runs the commands declared in the `hooks` block of the config when session lifecycle events
+//! happen.
+
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::error;
+use zellij_utils::envs;
+use zellij_utils::input::hooks::{HookContext, HookEvent, Hooks};
+
+// the session is only torn down once the hooks that run before it are done, or after this long
+const SESSION_KILL_HOOK_TIMEOUT: Duration = Duration::from_secs(5);
+const SESSION_KILL_HOOK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+pub(crate) fn run_hooks(hooks: &Hooks, event: HookEvent, context: HookContext) {
+    run_hooks_with_session_kill_timeout(hooks, event, context, SESSION_KILL_HOOK_TIMEOUT)
+}
+
+fn run_hooks_with_session_kill_timeout(
+    hooks: &Hooks,
+    event: HookEvent,
+    mut context: HookContext,
+    session_kill_timeout: Duration,
+) {
+    let commands = hooks.get(event);
+    if commands.is_empty() {
+        return;
+    }
+    if context.session_name.is_none() {
+        context.session_name = envs::get_session_name().ok();
+    }
+    let env_variables = context.env_variables(event);
+    let children: Vec<Child> = commands
+        .iter()
+        .filter_map(|command| {
+            Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .envs(&env_variables)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| {
+                    error!(
+                        "Failed to run {} hook {:?}: {}",
+                        event.kdl_name(),
+                        command,
+                        e
+                    )
+                })
+                .ok()
+        })
+        .collect();
+    match event {
+        HookEvent::SessionKill => wait_for_hooks(children, session_kill_timeout),
+        _ => {
+            // reap the hooks once they are done so they do not linger as zombies
+            thread::spawn(move || {
+                for mut child in children {
+                    let _ = child.wait();
+                }
+            });
+        },
+    }
+}
+
+fn wait_for_hooks(mut children: Vec<Child>, timeout: Duration) {
+    let start = Instant::now();
+    while !children.is_empty() && start.elapsed() < timeout {
+        children.retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+        thread::sleep(SESSION_KILL_HOOK_POLL_INTERVAL);
+    }
+    for mut child in children {
+        error!("Hook did not finish in {:?}, killing it", timeout);
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/hooks_tests.rs"]
+mod hooks_tests;
@@ -4,16 +4,20 @@ pub mod panes;
 pub mod tab;
 
 mod background_jobs;
+mod hooks;
 mod logging_pipe;
 mod plugins;
 mod pty;
 mod pty_writer;
+mod recently_closed;
+mod recording;
 mod route;
 mod screen;
 mod session_layout_metadata;
 mod terminal_bytes;
 mod thread_bus;
 mod ui;
+mod wait_for;
 
 use background_jobs::{background_jobs_main, BackgroundJob};
 use log::info;
@@ -31,6 +35,7 @@ use zellij_utils::pane_size::Size;
 use wasmtime::{Config as WasmtimeConfig, Engine, Strategy};
 
 use crate::{
+    hooks::run_hooks,
     os_input_output::ServerOsApi,
     plugins::{plugin_thread_main, PluginInstruction},
     pty::{get_default_shell, pty_thread_main, Pty, PtyInstruction},
@@ -52,6 +57,7 @@ use zellij_utils::{
         command::{RunCommand, TerminalAction},
         config::Config,
         get_mode_info,
+        hooks::{HookContext, HookEvent, Hooks},
         keybinds::Keybinds,
         layout::{FloatingPaneLayout, Layout, PluginAlias, Run, RunPluginOrAlias},
         options::Options,
@@ -96,8 +102,9 @@ pub enum ServerInstruction {
     Log(Vec<String>, ClientId),
     LogError(Vec<String>, ClientId),
     SwitchSession(ConnectToSession, ClientId),
-    UnblockCliPipeInput(String),   // String -> Pipe name
-    CliPipeOutput(String, String), // String -> Pipe name, String -> Output
+    UnblockCliPipeInput(String),    // String -> Pipe name
+    CliPipeOutput(String, String),  // String -> Pipe name, String -> Output
+    CliPipeExitStatus(String, i32), // String -> Pipe name, i32 -> Exit status
     AssociatePipeWithClient {
         pipe_id: String,
         client_id: ClientId,
@@ -118,6 +125,7 @@ pub enum ServerInstruction {
         keys_to_unbind: Vec<(InputMode, KeyWithModifier)>,
         write_config_to_disk: bool,
     },
+    RunHook(HookEvent, HookContext),
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -139,6 +147,7 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::SwitchSession(..) => ServerContext::SwitchSession,
             ServerInstruction::UnblockCliPipeInput(..) => ServerContext::UnblockCliPipeInput,
             ServerInstruction::CliPipeOutput(..) => ServerContext::CliPipeOutput,
+            ServerInstruction::CliPipeExitStatus(..) => ServerContext::CliPipeExitStatus,
             ServerInstruction::AssociatePipeWithClient { .. } => {
                 ServerContext::AssociatePipeWithClient
             },
@@ -155,6 +164,7 @@ impl From<&ServerInstruction> for ServerContext {
                 ServerContext::FailedToWriteConfigToDisk
             },
             ServerInstruction::RebindKeys { .. } => ServerContext::RebindKeys,
+            ServerInstruction::RunHook(..) => ServerContext::RunHook,
         }
     }
 }
@@ -304,6 +314,7 @@ pub(crate) struct SessionMetaData {
     pub layout: Box<Layout>,
     pub current_input_modes: HashMap<ClientId, InputMode>,
     pub session_configuration: SessionConfiguration,
+    pub hooks: Hooks,
 
     screen_thread: Option<thread::JoinHandle<()>>,
     pty_thread: Option<thread::JoinHandle<()>>,
@@ -342,6 +353,7 @@ impl SessionMetaData {
         config_was_written_to_disk: bool,
     ) {
         for (client_id, new_config) in config_changes {
+            self.hooks = new_config.hooks.clone();
             self.default_shell = new_config.options.default_shell.as_ref().map(|shell| {
                 TerminalAction::RunCommand(RunCommand {
                     command: shell.clone(),
@@ -361,7 +373,7 @@ impl SessionMetaData {
                         .theme_config(new_config.options.theme.as_ref())
                         .unwrap_or_else(|| default_palette().into()),
                     simplified_ui: new_config.options.simplified_ui.unwrap_or(false),
-                    default_shell: new_config.options.default_shell,
+                    default_shell: new_config.options.default_shell.clone(),
                     pane_frames: new_config.options.pane_frames.unwrap_or(true),
                     copy_command: new_config.options.copy_command,
                     copy_to_clipboard: new_config.options.copy_clipboard,
@@ -371,6 +383,8 @@ impl SessionMetaData {
                     hide_session_name: new_config.ui.pane_frames.hide_session_name,
                     stacked_resize: new_config.options.stacked_resize.unwrap_or(true),
                     default_editor: new_config.options.scrollback_editor.clone(),
+                    scratchpads: new_config.scratchpads.clone(),
+                    hooks: new_config.hooks.clone(),
                 })
                 .unwrap();
             self.senders
@@ -386,6 +400,8 @@ impl SessionMetaData {
                 .send_to_pty(PtyInstruction::Reconfigure {
                     client_id,
                     default_editor: new_config.options.scrollback_editor,
+                    ssh_command: new_config.options.ssh_command,
+                    default_shell: new_config.options.default_shell,
                 })
                 .unwrap();
         }
@@ -652,6 +668,17 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .write()
                     .unwrap()
                     .set_client_size(client_id, client_attributes.size);
+                session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .senders
+                    .send_to_screen(ScreenInstruction::ClientTerminalResize(
+                        client_id,
+                        client_attributes.size,
+                    ))
+                    .unwrap();
 
                 let default_shell = runtime_config_options.default_shell.map(|shell| {
                     TerminalAction::RunCommand(RunCommand {
@@ -737,6 +764,14 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .senders
                     .send_to_plugin(PluginInstruction::AddClient(client_id))
                     .unwrap();
+                run_hooks(
+                    &session_data.read().unwrap().as_ref().unwrap().hooks,
+                    HookEvent::SessionCreate,
+                    HookContext {
+                        client_id: Some(client_id),
+                        ..Default::default()
+                    },
+                );
             },
             ServerInstruction::AttachClient(
                 attrs,
@@ -772,6 +807,12 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .unwrap()
                     .min_client_terminal_size()
                     .unwrap();
+                session_data
+                    .senders
+                    .send_to_screen(ScreenInstruction::ClientTerminalResize(
+                        client_id, attrs.size,
+                    ))
+                    .unwrap();
                 session_data
                     .senders
                     .send_to_screen(ScreenInstruction::TerminalResize(min_size))
@@ -810,6 +851,14 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         Event::ModeUpdate(mode_info),
                     )]))
                     .unwrap();
+                run_hooks(
+                    &session_data.hooks,
+                    HookEvent::SessionAttach,
+                    HookContext {
+                        client_id: Some(client_id),
+                        ..Default::default()
+                    },
+                );
             },
             ServerInstruction::UnblockInputThread => {
                 let client_ids = session_state.read().unwrap().client_ids();
@@ -872,6 +921,34 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     },
                 }
             },
+            ServerInstruction::CliPipeExitStatus(pipe_name, exit_status) => {
+                let pipe = session_state.read().unwrap().get_pipe(&pipe_name);
+                match pipe {
+                    Some(client_id) => {
+                        send_to_client!(
+                            client_id,
+                            os_input,
+                            ServerToClientMsg::CliPipeExitStatus(pipe_name.clone(), exit_status),
+                            session_state
+                        );
+                    },
+                    None => {
+                        // send to all clients, this pipe might not have been associated yet
+                        let client_ids = session_state.read().unwrap().client_ids();
+                        for client_id in client_ids {
+                            send_to_client!(
+                                client_id,
+                                os_input,
+                                ServerToClientMsg::CliPipeExitStatus(
+                                    pipe_name.clone(),
+                                    exit_status
+                                ),
+                                session_state
+                            );
+                        }
+                    },
+                }
+            },
             ServerInstruction::ClientExit(client_id) => {
                 let _ =
                     os_input.send_to_client(client_id, ServerToClientMsg::Exit(ExitReason::Normal));
@@ -903,6 +980,14 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .send_to_plugin(PluginInstruction::RemoveClient(client_id))
                     .unwrap();
                 if !session_state.read().unwrap().active_clients_are_connected() {
+                    // the last client left, so the session ends just as if it were killed
+                    if let Some(session_data) = session_data.read().unwrap().as_ref() {
+                        run_hooks(
+                            &session_data.hooks,
+                            HookEvent::SessionKill,
+                            HookContext::default(),
+                        );
+                    }
                     *session_data.write().unwrap() = None;
                     let client_ids_to_cleanup: Vec<ClientId> = session_state
                         .read()
@@ -948,6 +1033,13 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .unwrap();
             },
             ServerInstruction::KillSession => {
+                if let Some(session_data) = session_data.read().unwrap().as_ref() {
+                    run_hooks(
+                        &session_data.hooks,
+                        HookEvent::SessionKill,
+                        HookContext::default(),
+                    );
+                }
                 let client_ids = session_state.read().unwrap().client_ids();
                 for client_id in client_ids {
                     let _ = os_input
@@ -1003,6 +1095,16 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         .senders
                         .send_to_plugin(PluginInstruction::RemoveClient(client_id))
                         .unwrap();
+                    if let Some(session_data) = session_data.read().unwrap().as_ref() {
+                        run_hooks(
+                            &session_data.hooks,
+                            HookEvent::SessionDetach,
+                            HookContext {
+                                client_id: Some(client_id),
+                                ..Default::default()
+                            },
+                        );
+                    }
                 }
             },
             ServerInstruction::Render(serialized_output) => {
@@ -1253,6 +1355,11 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     }
                 }
             },
+            ServerInstruction::RunHook(event, context) => {
+                if let Some(session_data) = session_data.read().unwrap().as_ref() {
+                    run_hooks(&session_data.hooks, event, context);
+                }
+            },
         }
     }
 
@@ -1283,6 +1390,7 @@ fn init_session(
         layout,
     } = options;
     config.options = config.options.merge(*config_options.clone());
+    let hooks = config.hooks.clone();
 
     let _ = SCROLL_BUFFER_SIZE.set(
         config_options
@@ -1351,6 +1459,8 @@ fn init_session(
                 ),
                 opts.debug,
                 config_options.scrollback_editor.clone(),
+                config_options.ssh_command.clone(),
+                config_options.default_shell.clone(),
             );
 
             move || pty_thread_main(pty, layout.clone()).fatal()
@@ -1493,6 +1603,7 @@ fn init_session(
         layout,
         session_configuration: Default::default(),
         current_input_modes: HashMap::new(),
+        hooks,
         screen_thread: Some(screen_thread),
         pty_thread: Some(pty_thread),
         plugin_thread: Some(plugin_thread),
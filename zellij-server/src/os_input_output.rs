@@ -123,9 +123,8 @@ fn handle_command_exit(mut child: Child) -> Result<Option<i32>> {
     }
 }
 
-fn command_exists(cmd: &RunCommand) -> bool {
-    let command = &cmd.command;
-    match cmd.cwd.as_ref() {
+fn command_exists(command: &PathBuf, cwd: Option<&PathBuf>) -> bool {
+    match cwd {
         Some(cwd) => {
             let full_command = cwd.join(&command);
             if full_command.exists() && full_command.is_file() {
@@ -167,10 +166,12 @@ fn handle_openpty(
     let pid_primary = open_pty_res.master;
     let pid_secondary = open_pty_res.slave;
 
-    if command_exists(&cmd) {
+    // remote commands are spawned through the command connecting to their host
+    let (local_command, local_args) = cmd.local_command_and_args();
+    if command_exists(&local_command, cmd.cwd.as_ref()) {
         let mut child = unsafe {
             let cmd = cmd.clone();
-            let command = &mut Command::new(cmd.command);
+            let command = &mut Command::new(&local_command);
             if let Some(current_dir) = cmd.cwd {
                 if current_dir.exists() && current_dir.is_dir() {
                     command.current_dir(current_dir);
@@ -182,7 +183,8 @@ fn handle_openpty(
                 }
             }
             command
-                .args(&cmd.args)
+                .args(&local_args)
+                .envs(&cmd.env)
                 .env("ZELLIJ_PANE_ID", &format!("{}", terminal_id))
                 .pre_exec(move || -> std::io::Result<()> {
                     if libc::login_tty(pid_secondary) != 0 {
@@ -209,7 +211,7 @@ fn handle_openpty(
     } else {
         Err(ZellijError::CommandNotFound {
             terminal_id,
-            command: cmd.command.to_string_lossy().to_string(),
+            command: local_command.to_string_lossy().to_string(),
         })
         .with_context(|| err_context(&cmd))
     }
@@ -338,6 +340,7 @@ fn spawn_terminal(
                 cwd: payload.cwd,
                 hold_on_close: false,
                 hold_on_start: false,
+                env: payload.env,
                 ..Default::default()
             }
         },
@@ -516,6 +519,8 @@ pub trait ServerOsApi: Send + Sync {
     }
     /// Writes the given buffer to a string
     fn write_to_file(&mut self, buf: String, file: Option<String>) -> Result<()>;
+    /// Creates a file, truncating it if it exists, and returns a handle to write to it
+    fn create_file(&mut self, file: String) -> Result<Box<dyn Write + Send>>;
 
     fn re_run_command_in_terminal(
         &self,
@@ -812,6 +817,11 @@ impl ServerOsApi for ServerOsInputOutput {
         write!(f, "{}", buf).with_context(err_context)
     }
 
+    fn create_file(&mut self, file: String) -> Result<Box<dyn Write + Send>> {
+        let file = File::create(&file).with_context(|| format!("failed to create file {file}"))?;
+        Ok(Box::new(file))
+    }
+
     fn re_run_command_in_terminal(
         &self,
         terminal_id: u32,
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use zellij_utils::data::{Direction, ResizeStrategy};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::pane_size::{Dimension, PaneGeom, Size, Viewport};
+use zellij_utils::position::Position;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -14,6 +15,12 @@ const MOVE_INCREMENT_VERTICAL: usize = 5;
 
 const MAX_PANES: usize = 100;
 
+const SNAP_DISTANCE_HORIZONTAL: usize = 2;
+const SNAP_DISTANCE_VERTICAL: usize = 1;
+
+const CASCADE_OFFSET_HORIZONTAL: usize = 4;
+const CASCADE_OFFSET_VERTICAL: usize = 2;
+
 // For error reporting
 fn no_pane_id(pane_id: &PaneId) -> String {
     format!("no floating pane with ID {:?} found", pane_id)
@@ -848,6 +855,217 @@ impl<'a> FloatingPaneGrid<'a> {
         );
         return None;
     }
+    pub fn snap_pane_to_viewport_area(
+        &mut self,
+        pane_id: PaneId,
+        position: &Position,
+    ) -> Result<bool> {
+        // true => pane was dropped on an edge of the viewport and now fills its half or quarter
+        let err_context = || format!("failed to snap pane {pane_id:?} to {position:?}");
+
+        let viewport = self.viewport;
+        let at_left = position.column() <= viewport.x;
+        let at_right = position.column() + 1 >= viewport.x + viewport.cols;
+        let at_top = position.line() <= viewport.y as isize;
+        let at_bottom = position.line() + 1 >= (viewport.y + viewport.rows) as isize;
+        if !at_left && !at_right && !at_top && !at_bottom {
+            return Ok(false);
+        }
+        let half_cols = viewport.cols / 2;
+        let half_rows = viewport.rows / 2;
+        let (x, cols) = if at_left {
+            (viewport.x, half_cols)
+        } else if at_right {
+            (viewport.x + half_cols, viewport.cols - half_cols)
+        } else {
+            (viewport.x, viewport.cols)
+        };
+        let (y, rows) = if at_top {
+            (viewport.y, half_rows)
+        } else if at_bottom {
+            (viewport.y + half_rows, viewport.rows - half_rows)
+        } else {
+            (viewport.y, viewport.rows)
+        };
+        let mut new_pane_geom = self
+            .panes
+            .borrow()
+            .get(&pane_id)
+            .with_context(|| no_pane_id(&pane_id))
+            .with_context(err_context)?
+            .position_and_size();
+        new_pane_geom.x = x;
+        new_pane_geom.y = y;
+        new_pane_geom.cols = Dimension::fixed(cols);
+        new_pane_geom.rows = Dimension::fixed(rows);
+        if !pane_geom_is_big_enough(&new_pane_geom) {
+            return Ok(false);
+        }
+        self.set_pane_geom(pane_id, new_pane_geom)
+            .with_context(err_context)?;
+        Ok(true)
+    }
+    pub fn snap_pane_to_edges(&mut self, pane_id: PaneId) -> Result<()> {
+        let err_context = || format!("failed to snap pane {pane_id:?} to nearby edges");
+
+        let (current_pane_geom, new_pane_geom) = {
+            let panes = self.panes.borrow();
+            let pane_geom = panes
+                .get(&pane_id)
+                .with_context(|| no_pane_id(&pane_id))
+                .with_context(err_context)?
+                .position_and_size();
+            // the pane snaps to the edges and middle of the viewport and to the edges of the other
+            // floating panes
+            let mut x_edges = vec![
+                self.viewport.x,
+                self.viewport.x + self.viewport.cols / 2,
+                self.viewport.x + self.viewport.cols,
+            ];
+            let mut y_edges = vec![
+                self.viewport.y,
+                self.viewport.y + self.viewport.rows / 2,
+                self.viewport.y + self.viewport.rows,
+            ];
+            for (_, other_pane) in panes.iter().filter(|(p_id, _)| **p_id != pane_id) {
+                let other_geom = other_pane.position_and_size();
+                x_edges.push(other_geom.x);
+                x_edges.push(other_geom.x + other_geom.cols.as_usize());
+                y_edges.push(other_geom.y);
+                y_edges.push(other_geom.y + other_geom.rows.as_usize());
+            }
+            let mut new_pane_geom = pane_geom;
+            if let Some(x) = snapped_offset(
+                pane_geom.x,
+                pane_geom.cols.as_usize(),
+                (self.viewport.x, self.viewport.x + self.viewport.cols),
+                &x_edges,
+                SNAP_DISTANCE_HORIZONTAL,
+            ) {
+                new_pane_geom.x = x;
+            }
+            if let Some(y) = snapped_offset(
+                pane_geom.y,
+                pane_geom.rows.as_usize(),
+                (self.viewport.y, self.viewport.y + self.viewport.rows),
+                &y_edges,
+                SNAP_DISTANCE_VERTICAL,
+            ) {
+                new_pane_geom.y = y;
+            }
+            (pane_geom, new_pane_geom)
+        };
+        if new_pane_geom != current_pane_geom {
+            self.set_pane_geom(pane_id, new_pane_geom)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn arrange_in_grid(&mut self, pane_ids: &[PaneId]) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to arrange {} floating panes in a grid",
+                pane_ids.len()
+            )
+        };
+
+        if pane_ids.is_empty() {
+            return Ok(());
+        }
+        let grid_columns = (pane_ids.len() as f64).sqrt().ceil() as usize;
+        let grid_rows = pane_ids.len().div_ceil(grid_columns);
+        if self.viewport.cols / grid_columns < MIN_TERMINAL_WIDTH
+            || self.viewport.rows / grid_rows < MIN_TERMINAL_HEIGHT
+        {
+            return Err(anyhow!("not enough room in the viewport")).with_context(err_context);
+        }
+        for (row_index, row_pane_ids) in pane_ids.chunks(grid_columns).enumerate() {
+            let (y, rows) =
+                evenly_split_part(self.viewport.y, self.viewport.rows, grid_rows, row_index);
+            for (column_index, pane_id) in row_pane_ids.iter().enumerate() {
+                // the panes of the last row share its whole width between them
+                let (x, cols) = evenly_split_part(
+                    self.viewport.x,
+                    self.viewport.cols,
+                    row_pane_ids.len(),
+                    column_index,
+                );
+                self.set_arranged_pane_geom(*pane_id, x, y, cols, rows)
+                    .with_context(err_context)?;
+            }
+        }
+        Ok(())
+    }
+    pub fn cascade(&mut self, pane_ids: &[PaneId]) -> Result<()> {
+        let err_context = || format!("failed to cascade {} floating panes", pane_ids.len());
+
+        let cols = std::cmp::max(self.viewport.cols * 2 / 3, MIN_TERMINAL_WIDTH);
+        let rows = std::cmp::max(self.viewport.rows * 2 / 3, MIN_TERMINAL_HEIGHT);
+        let horizontal_steps = self.viewport.cols.saturating_sub(cols) / CASCADE_OFFSET_HORIZONTAL;
+        let vertical_steps = self.viewport.rows.saturating_sub(rows) / CASCADE_OFFSET_VERTICAL;
+        // once the cascade reaches the bottom right of the viewport, it starts again from the top
+        // left
+        let steps = std::cmp::min(horizontal_steps, vertical_steps) + 1;
+        for (index, pane_id) in pane_ids.iter().enumerate() {
+            let step = index % steps;
+            self.set_arranged_pane_geom(
+                *pane_id,
+                self.viewport.x + step * CASCADE_OFFSET_HORIZONTAL,
+                self.viewport.y + step * CASCADE_OFFSET_VERTICAL,
+                cols,
+                rows,
+            )
+            .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    fn set_arranged_pane_geom(
+        &mut self,
+        pane_id: PaneId,
+        x: usize,
+        y: usize,
+        cols: usize,
+        rows: usize,
+    ) -> Result<()> {
+        let mut new_pane_geom = self
+            .panes
+            .borrow()
+            .get(&pane_id)
+            .with_context(|| no_pane_id(&pane_id))?
+            .position_and_size();
+        new_pane_geom.x = x;
+        new_pane_geom.y = y;
+        new_pane_geom.cols = Dimension::fixed(cols);
+        new_pane_geom.rows = Dimension::fixed(rows);
+        self.set_pane_geom(pane_id, new_pane_geom)
+    }
+}
+
+// the start and size of part number `index` out of `parts` equal parts of the range starting at
+// `start` (the last parts absorb the remainder of the division)
+fn evenly_split_part(start: usize, size: usize, parts: usize, index: usize) -> (usize, usize) {
+    let part_start = start + size * index / parts;
+    let part_end = start + size * (index + 1) / parts;
+    (part_start, part_end - part_start)
+}
+
+// the closest offset within `snap_distance` of `offset` at which either side of a pane of `size`
+// lies on one of `edges`, as long as the pane stays inside `bounds`
+fn snapped_offset(
+    offset: usize,
+    size: usize,
+    bounds: (usize, usize),
+    edges: &[usize],
+    snap_distance: usize,
+) -> Option<usize> {
+    let (min_offset, max_offset) = (bounds.0, bounds.1.saturating_sub(size));
+    edges
+        .iter()
+        .flat_map(|edge| [Some(*edge), edge.checked_sub(size)])
+        .flatten()
+        .filter(|candidate| *candidate >= min_offset && *candidate <= max_offset)
+        .filter(|candidate| candidate.abs_diff(offset) <= snap_distance)
+        .min_by_key(|candidate| candidate.abs_diff(offset))
 }
 
 pub fn half_size_middle_geom(space: &Viewport, offset: usize) -> PaneGeom {
@@ -1,6 +1,8 @@
 pub mod floating_pane_grid;
 use zellij_utils::{
-    data::{Direction, FloatingPaneCoordinates, PaneInfo, ResizeStrategy},
+    data::{
+        Direction, FloatingPaneCoordinates, FloatingPanesArrangement, PaneInfo, ResizeStrategy,
+    },
     position::Position,
 };
 
@@ -48,6 +50,7 @@ pub struct FloatingPanes {
     active_panes: ActivePanes,
     show_panes: bool,
     pane_being_moved_with_mouse: Option<(PaneId, Position)>,
+    pane_was_moved_with_mouse: bool, // as opposed to only clicking on its frame
     senders: ThreadSenders,
 }
 
@@ -83,6 +86,7 @@ impl FloatingPanes {
             show_panes: false,
             active_panes: ActivePanes::new(&os_input),
             pane_being_moved_with_mouse: None,
+            pane_was_moved_with_mouse: false,
             senders,
         }
     }
@@ -728,6 +732,29 @@ impl FloatingPanes {
             let _ = self.set_pane_frames();
         }
     }
+    pub fn swap_panes(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) {
+        let (Some(first_pane), Some(second_pane)) = (
+            self.panes.get(&first_pane_id),
+            self.panes.get(&second_pane_id),
+        ) else {
+            log::error!(
+                "Failed to find panes {:?} and {:?}",
+                first_pane_id,
+                second_pane_id
+            );
+            return;
+        };
+        let first_geom = first_pane.position_and_size();
+        let second_geom = second_pane.position_and_size();
+        for (pane_id, geom) in [(first_pane_id, second_geom), (second_pane_id, first_geom)] {
+            if let Some(pane) = self.panes.get_mut(&pane_id) {
+                pane.set_geom(geom);
+                pane.set_should_render(true);
+            }
+            self.desired_pane_positions.insert(pane_id, geom);
+        }
+        let _ = self.set_pane_frames();
+    }
     pub fn change_pane_coordinates(
         &mut self,
         pane_id: PaneId,
@@ -959,6 +986,7 @@ impl FloatingPanes {
         if click_position == &previous_position {
             return false;
         }
+        self.pane_was_moved_with_mouse = true;
         let move_x_by = click_position.column() as isize - previous_position.column() as isize;
         let move_y_by = click_position.line() as isize - previous_position.line() as isize;
         let mut floating_pane_grid = FloatingPaneGrid::new(
@@ -998,11 +1026,66 @@ impl FloatingPanes {
         false
     }
     pub fn stop_moving_pane_with_mouse(&mut self, position: Position) {
-        if self.pane_being_moved_with_mouse.is_some() {
+        if let Some((pane_id, _)) = self.pane_being_moved_with_mouse {
             self.move_pane_to_position(&position);
+            if self.pane_was_moved_with_mouse {
+                self.snap_pane(pane_id, &position).non_fatal();
+            }
             self.set_force_render();
         };
         self.pane_being_moved_with_mouse = None;
+        self.pane_was_moved_with_mouse = false;
+    }
+    fn snap_pane(&mut self, pane_id: PaneId, drop_position: &Position) -> Result<()> {
+        // a pane dropped on an edge of the viewport fills its half (or quarter in the corners),
+        // otherwise it snaps to nearby edges of the viewport and of other floating panes
+        let err_context = || format!("failed to snap pane {pane_id:?}");
+        let display_area = *self.display_area.borrow();
+        let viewport = *self.viewport.borrow();
+        let mut floating_pane_grid = FloatingPaneGrid::new(
+            &mut self.panes,
+            &mut self.desired_pane_positions,
+            display_area,
+            viewport,
+        );
+        if !floating_pane_grid
+            .snap_pane_to_viewport_area(pane_id, drop_position)
+            .with_context(err_context)?
+        {
+            floating_pane_grid
+                .snap_pane_to_edges(pane_id)
+                .with_context(err_context)?;
+        }
+        if let Some(pane) = self.panes.get_mut(&pane_id) {
+            resize_pty!(pane, os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn arrange_panes(&mut self, arrangement: FloatingPanesArrangement) -> Result<()> {
+        let err_context = || format!("failed to arrange floating panes as {arrangement:?}");
+        let display_area = *self.display_area.borrow();
+        let viewport = *self.viewport.borrow();
+        // arranged from the bottom of the stack up, so that the top-most pane stays on top
+        let pane_ids = self.z_indices.clone();
+        let mut floating_pane_grid = FloatingPaneGrid::new(
+            &mut self.panes,
+            &mut self.desired_pane_positions,
+            display_area,
+            viewport,
+        );
+        let arranged = match arrangement {
+            FloatingPanesArrangement::Grid => floating_pane_grid.arrange_in_grid(&pane_ids),
+            FloatingPanesArrangement::Cascade => floating_pane_grid.cascade(&pane_ids),
+        };
+        arranged.with_context(err_context)?;
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+        }
+        self.set_pane_frames().with_context(err_context)?;
+        self.set_force_render();
+        Ok(())
     }
     pub fn get_active_pane_id(&self, client_id: ClientId) -> Option<PaneId> {
         self.active_panes.get(&client_id).copied()
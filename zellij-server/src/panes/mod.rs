@@ -7,6 +7,7 @@ pub mod terminal_character;
 
 mod active_panes;
 pub mod floating_panes;
+mod pane_logger;
 mod plugin_pane;
 mod search;
 mod terminal_pane;
@@ -17,6 +18,7 @@ pub use alacritty_functions::*;
 pub use floating_panes::*;
 pub use grid::*;
 pub use link_handler::*;
+pub use pane_logger::*;
 pub(crate) use plugin_pane::*;
 pub use sixel::*;
 pub(crate) use terminal_character::*;
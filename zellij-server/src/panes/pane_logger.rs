@@ -0,0 +1,160 @@
+//! Tees the output of a terminal pane to a file (see the `log_to_file` pane attribute).
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use zellij_utils::{errors::prelude::*, humantime, input::layout::PaneLogging, vte};
+
+// how many rotated log files (eg. `pane.log.1`, `pane.log.2`...) we keep around
+const MAX_ROTATED_LOG_FILES: usize = 5;
+
+pub struct PaneLogger {
+    pane_logging: PaneLogging,
+    file: BufWriter<File>,
+    bytes_written: u64,
+    ansi_stripper: Option<(vte::Parser, AnsiStripper)>,
+    is_at_line_start: bool,
+}
+
+impl PaneLogger {
+    pub fn new(pane_logging: PaneLogging) -> Result<Self> {
+        let file = open_log_file(&pane_logging.file)?;
+        let bytes_written = file.get_ref().metadata().map(|m| m.len()).unwrap_or(0);
+        let ansi_stripper = if pane_logging.strip_ansi {
+            Some((vte::Parser::new(), AnsiStripper::default()))
+        } else {
+            None
+        };
+        Ok(PaneLogger {
+            pane_logging,
+            file,
+            bytes_written,
+            ansi_stripper,
+            is_at_line_start: true,
+        })
+    }
+    pub fn pane_logging(&self) -> &PaneLogging {
+        &self.pane_logging
+    }
+    pub fn log(&mut self, bytes: &[u8]) -> Result<()> {
+        let bytes = match self.ansi_stripper.as_mut() {
+            Some((parser, ansi_stripper)) => {
+                for byte in bytes {
+                    parser.advance(ansi_stripper, *byte);
+                }
+                std::mem::take(&mut ansi_stripper.output)
+            },
+            None => bytes.to_vec(),
+        };
+        let bytes = if self.pane_logging.timestamps {
+            self.prefix_lines_with_timestamp(&bytes)
+        } else {
+            bytes
+        };
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        if let Some(max_size) = self.pane_logging.max_size {
+            if self.bytes_written > 0 && self.bytes_written + bytes.len() as u64 > max_size {
+                self.rotate()?;
+            }
+        }
+        self.file
+            .write_all(&bytes)
+            .with_context(|| format!("failed to log to {}", self.pane_logging.file.display()))?;
+        self.bytes_written += bytes.len() as u64;
+        Ok(())
+    }
+    fn prefix_lines_with_timestamp(&mut self, bytes: &[u8]) -> Vec<u8> {
+        let timestamp = format!(
+            "[{}] ",
+            humantime::format_rfc3339_seconds(SystemTime::now())
+        );
+        let mut prefixed = Vec::with_capacity(bytes.len());
+        for byte in bytes {
+            if self.is_at_line_start {
+                prefixed.extend_from_slice(timestamp.as_bytes());
+                self.is_at_line_start = false;
+            }
+            prefixed.push(*byte);
+            if *byte == b'\n' {
+                self.is_at_line_start = true;
+            }
+        }
+        prefixed
+    }
+    fn rotate(&mut self) -> Result<()> {
+        let file = &self.pane_logging.file;
+        // what is still buffered belongs to the file being rotated
+        self.file
+            .flush()
+            .with_context(|| format!("failed to log to {}", file.display()))?;
+        for i in (1..MAX_ROTATED_LOG_FILES).rev() {
+            rename_if_exists(&rotated_file_name(file, i), &rotated_file_name(file, i + 1))?;
+        }
+        rename_if_exists(file, &rotated_file_name(file, 1))?;
+        self.file = open_log_file(file)?;
+        self.bytes_written = 0;
+        Ok(())
+    }
+}
+
+impl Drop for PaneLogger {
+    fn drop(&mut self) {
+        if let Err(e) = self.file.flush() {
+            log::error!(
+                "Failed to log to {}: {}",
+                self.pane_logging.file.display(),
+                e
+            );
+        }
+    }
+}
+
+fn open_log_file(file: &Path) -> Result<BufWriter<File>> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file)
+        .map(BufWriter::new)
+        .with_context(|| format!("failed to open log file {}", file.display()))
+}
+
+fn rotated_file_name(file: &Path, index: usize) -> PathBuf {
+    let mut file_name = file.as_os_str().to_owned();
+    file_name.push(format!(".{}", index));
+    PathBuf::from(file_name)
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> Result<()> {
+    match fs::rename(from, to) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e)
+            .with_context(|| format!("failed to rotate {} to {}", from.display(), to.display())),
+        _ => Ok(()),
+    }
+}
+
+// keeps only the printable characters, newlines and tabs of the output
+#[derive(Default)]
+struct AnsiStripper {
+    output: Vec<u8>,
+}
+
+impl vte::Perform for AnsiStripper {
+    fn print(&mut self, c: char) {
+        let mut buf = [0; 4];
+        self.output
+            .extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+    fn execute(&mut self, byte: u8) {
+        if byte == b'\n' || byte == b'\t' {
+            self.output.push(byte);
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/pane_logger_tests.rs"]
+mod pane_logger_tests;
@@ -1,6 +1,7 @@
 use crate::output::{CharacterChunk, SixelImageChunk};
 use crate::panes::sixel::SixelImageStore;
 use crate::panes::LinkHandler;
+use crate::panes::PaneLogger;
 use crate::panes::{
     grid::Grid,
     terminal_character::{render_first_run_banner, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
@@ -9,7 +10,7 @@ use crate::pty::VteBytes;
 use crate::tab::{AdjustedInput, Pane};
 use crate::ClientId;
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::time::{self, Instant};
@@ -22,7 +23,7 @@ use zellij_utils::{
         Style, Styling,
     },
     errors::prelude::*,
-    input::layout::Run,
+    input::layout::{PaneLogging, Run},
     pane_size::PaneGeom,
     pane_size::SizeInPixels,
     position::Position,
@@ -140,6 +141,10 @@ pub struct TerminalPane {
     invoked_with: Option<Run>,
     #[allow(dead_code)]
     arrow_fonts: bool,
+    is_throttled: bool,  // renders are being coalesced because of high output
+    in_sync_group: bool, // input to any pane of the group is written to all of them
+    pane_logger: Option<PaneLogger>,
+    env: BTreeMap<String, String>, // set by the layout on top of the inherited environment
 }
 
 impl Pane for TerminalPane {
@@ -190,6 +195,12 @@ impl Pane for TerminalPane {
     }
     fn handle_pty_bytes(&mut self, bytes: VteBytes) {
         self.set_should_render(true);
+        if let Some(pane_logger) = self.pane_logger.as_mut() {
+            if let Err(e) = pane_logger.log(&bytes) {
+                log::error!("Stopped logging pane output: {:?}", e);
+                self.pane_logger = None;
+            }
+        }
         for &byte in &bytes {
             self.vte_parser.advance(&mut self.grid, byte);
         }
@@ -371,10 +382,18 @@ impl Pane for TerminalPane {
             }
             format!("SEARCHING: {}{}", self.search_term, modifier_text)
         } else if self.pane_name.is_empty() {
-            self.grid
+            let title = self
+                .grid
                 .title
                 .clone()
-                .unwrap_or_else(|| self.pane_title.clone())
+                .unwrap_or_else(|| self.pane_title.clone());
+            // panes running on a remote host always show which host it is
+            match self.remote_destination() {
+                Some(destination) if !title.starts_with(&destination) => {
+                    format!("[{}] {}", destination, title)
+                },
+                _ => title,
+            }
         } else {
             self.pane_name.clone()
         };
@@ -387,7 +406,9 @@ impl Pane for TerminalPane {
             pane_title,
             frame_params,
         )
-        .is_pinned(is_pinned);
+        .is_pinned(is_pinned)
+        .is_throttled(self.is_throttled)
+        .is_synced(self.in_sync_group);
         if let Some((exit_status, is_first_run, _run_command)) = &self.is_held {
             if *is_first_run {
                 frame.indicate_first_run();
@@ -867,6 +888,34 @@ impl Pane for TerminalPane {
     fn reset_logical_position(&mut self) {
         self.geom.logical_position = None;
     }
+    fn set_throttled(&mut self, is_throttled: bool) {
+        self.is_throttled = is_throttled;
+    }
+    fn set_in_sync_group(&mut self, in_sync_group: bool) {
+        self.in_sync_group = in_sync_group;
+        self.set_should_render(true);
+    }
+    fn in_sync_group(&self) -> bool {
+        self.in_sync_group
+    }
+    fn start_logging(&mut self, pane_logging: PaneLogging) -> Result<()> {
+        self.pane_logger = Some(PaneLogger::new(pane_logging)?);
+        Ok(())
+    }
+    fn stop_logging(&mut self) {
+        self.pane_logger = None;
+    }
+    fn log_to_file(&self) -> Option<&PaneLogging> {
+        self.pane_logger
+            .as_ref()
+            .map(|pane_logger| pane_logger.pane_logging())
+    }
+    fn set_env(&mut self, env: BTreeMap<String, String>) {
+        self.env = env;
+    }
+    fn env(&self) -> Option<&BTreeMap<String, String>> {
+        Some(&self.env)
+    }
 }
 
 impl TerminalPane {
@@ -929,6 +978,10 @@ impl TerminalPane {
             pane_frame_color_override: None,
             invoked_with,
             arrow_fonts,
+            is_throttled: false,
+            in_sync_group: false,
+            pane_logger: None,
+            env: BTreeMap::new(),
         }
     }
     pub fn get_x(&self) -> usize {
@@ -976,6 +1029,15 @@ impl TerminalPane {
         }
         self.grid.cursor_coordinates()
     }
+    fn remote_destination(&self) -> Option<String> {
+        match &self.invoked_with {
+            Some(Run::Command(RunCommand {
+                remote: Some(remote),
+                ..
+            })) => Some(remote.destination()),
+            _ => None,
+        }
+    }
     fn render_first_run_banner(&mut self) {
         let columns = self.get_content_columns();
         let rows = self.get_content_rows();
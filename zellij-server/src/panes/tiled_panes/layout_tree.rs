@@ -0,0 +1,276 @@
+//! The tiled panes of a tab seen as nested rows and columns, so that panes can be sized relative
+//! to their siblings rather than one resize step at a time.
+
+use crate::panes::PaneId;
+use std::collections::HashMap;
+use zellij_utils::{
+    input::layout::SplitDirection,
+    pane_size::{Dimension, PaneGeom},
+};
+
+// how much of its row or column the focused pane gets in golden ratio mode
+pub const GOLDEN_RATIO_SHARE: f64 = 0.618;
+
+#[derive(Debug)]
+pub enum LayoutTree {
+    Pane(PaneId, PaneGeom),
+    /// Children laid out next to each other along the direction, each with its share of the
+    /// flexible space of the split (fixed size children have no share)
+    Split {
+        direction: SplitDirection,
+        children: Vec<(f64, LayoutTree)>,
+    },
+    /// Panes that can't be told apart by a straight line running across them, they keep their
+    /// sizes
+    Tangled(Vec<(PaneId, PaneGeom)>),
+}
+
+impl LayoutTree {
+    /// Expects the geoms of all the tiled panes, with each stack of panes represented by a single
+    /// pane that has the geom of the whole stack
+    pub fn new(panes: Vec<(PaneId, PaneGeom)>) -> Option<Self> {
+        if panes.len() <= 1 {
+            return panes
+                .into_iter()
+                .next()
+                .map(|(pane_id, geom)| LayoutTree::Pane(pane_id, geom));
+        }
+        for direction in [SplitDirection::Horizontal, SplitDirection::Vertical] {
+            let groups = split_panes(&panes, direction);
+            if groups.len() > 1 {
+                let children: Vec<LayoutTree> =
+                    groups.into_iter().filter_map(LayoutTree::new).collect();
+                let flexible_space: usize = children
+                    .iter()
+                    .filter(|child| child.is_flexible(direction))
+                    .map(|child| child.size(direction))
+                    .sum();
+                let children = children
+                    .into_iter()
+                    .map(|child| {
+                        let share = if child.is_flexible(direction) && flexible_space > 0 {
+                            child.size(direction) as f64 / flexible_space as f64
+                        } else {
+                            0.0
+                        };
+                        (share, child)
+                    })
+                    .collect();
+                return Some(LayoutTree::Split {
+                    direction,
+                    children,
+                });
+            }
+        }
+        Some(LayoutTree::Tangled(panes))
+    }
+    /// Gives the flexible children of every split an equal share
+    pub fn equalize(&mut self) {
+        if let LayoutTree::Split {
+            direction,
+            children,
+        } = self
+        {
+            let flexible_count = children
+                .iter()
+                .filter(|(_, child)| child.is_flexible(*direction))
+                .count();
+            for (share, child) in children.iter_mut() {
+                if child.is_flexible(*direction) {
+                    *share = 1.0 / flexible_count as f64;
+                }
+                child.equalize();
+            }
+        }
+    }
+    /// Changes the share of the pane among its closest siblings along the direction, `new_share`
+    /// gets the percent of the tab their split takes and its flexible size in cells
+    ///
+    /// Returns false if the pane has no such siblings
+    pub fn set_share_of_pane(
+        &mut self,
+        pane_id: PaneId,
+        direction: SplitDirection,
+        min_size: usize,
+        new_share: &dyn Fn(f64, usize) -> f64,
+    ) -> bool {
+        self.set_share_of_pane_in_split(pane_id, direction, min_size, 100.0, new_share)
+            == Some(true)
+    }
+    /// The size each pane should have, as a percent of the tab in each direction (horizontal,
+    /// vertical)
+    pub fn percents(&self) -> HashMap<PaneId, (f64, f64)> {
+        let mut percents = HashMap::new();
+        self.collect_percents((100.0, 100.0), &mut percents);
+        percents
+    }
+    // None: the pane is not in this part of the tree, Some(false): it is but its share has not
+    // been set yet, Some(true): its share was set
+    fn set_share_of_pane_in_split(
+        &mut self,
+        pane_id: PaneId,
+        direction: SplitDirection,
+        min_size: usize,
+        percent: f64,
+        new_share: &dyn Fn(f64, usize) -> f64,
+    ) -> Option<bool> {
+        match self {
+            LayoutTree::Pane(id, _) => (*id == pane_id).then_some(false),
+            LayoutTree::Tangled(panes) => {
+                panes.iter().any(|(id, _)| *id == pane_id).then_some(false)
+            },
+            LayoutTree::Split {
+                direction: split_direction,
+                children,
+            } => {
+                let split_direction = *split_direction;
+                let (index, share_is_set) =
+                    children
+                        .iter_mut()
+                        .enumerate()
+                        .find_map(|(index, (share, child))| {
+                            let child_percent = if split_direction == direction {
+                                percent * *share
+                            } else {
+                                percent
+                            };
+                            child
+                                .set_share_of_pane_in_split(
+                                    pane_id,
+                                    direction,
+                                    min_size,
+                                    child_percent,
+                                    new_share,
+                                )
+                                .map(|share_is_set| (index, share_is_set))
+                        })?;
+                if share_is_set
+                    || split_direction != direction
+                    || !children[index].1.is_flexible(direction)
+                {
+                    return Some(share_is_set);
+                }
+                let flexible_siblings = children
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, (_, child))| *i != index && child.is_flexible(direction))
+                    .count();
+                if flexible_siblings == 0 {
+                    return Some(false);
+                }
+                let flexible_space: usize = children
+                    .iter()
+                    .filter(|(_, child)| child.is_flexible(direction))
+                    .map(|(_, child)| child.size(direction))
+                    .sum();
+                let min_share = min_size as f64 / flexible_space.max(1) as f64;
+                let max_share = (1.0 - min_share * flexible_siblings as f64).max(min_share);
+                let share = new_share(percent, flexible_space).clamp(min_share, max_share);
+                let previous_share = children[index].0;
+                let previous_siblings_share = 1.0 - previous_share;
+                for (i, (sibling_share, sibling)) in children.iter_mut().enumerate() {
+                    if i == index {
+                        *sibling_share = share;
+                    } else if sibling.is_flexible(direction) {
+                        // the siblings keep their proportions among themselves
+                        *sibling_share = if previous_siblings_share > 0.0 {
+                            *sibling_share / previous_siblings_share * (1.0 - share)
+                        } else {
+                            (1.0 - share) / flexible_siblings as f64
+                        };
+                    }
+                }
+                Some(true)
+            },
+        }
+    }
+    fn collect_percents(&self, percent: (f64, f64), percents: &mut HashMap<PaneId, (f64, f64)>) {
+        match self {
+            LayoutTree::Pane(pane_id, _) => {
+                percents.insert(*pane_id, percent);
+            },
+            LayoutTree::Tangled(_) => {},
+            LayoutTree::Split {
+                direction,
+                children,
+            } => {
+                for (share, child) in children {
+                    let child_percent = match direction {
+                        SplitDirection::Horizontal => (percent.0 * share, percent.1),
+                        SplitDirection::Vertical => (percent.0, percent.1 * share),
+                    };
+                    child.collect_percents(child_percent, percents);
+                }
+            },
+        }
+    }
+    fn is_flexible(&self, direction: SplitDirection) -> bool {
+        match self {
+            LayoutTree::Pane(_, geom) => dimension(geom, direction).is_percent(),
+            LayoutTree::Tangled(panes) => panes
+                .iter()
+                .any(|(_, geom)| dimension(geom, direction).is_percent()),
+            LayoutTree::Split { children, .. } => children
+                .iter()
+                .any(|(_, child)| child.is_flexible(direction)),
+        }
+    }
+    fn size(&self, direction: SplitDirection) -> usize {
+        let (start, end) = self.span(direction);
+        end.saturating_sub(start)
+    }
+    fn span(&self, direction: SplitDirection) -> (usize, usize) {
+        let spans: Vec<(usize, usize)> = match self {
+            LayoutTree::Pane(_, geom) => vec![span(geom, direction)],
+            LayoutTree::Tangled(panes) => panes
+                .iter()
+                .map(|(_, geom)| span(geom, direction))
+                .collect(),
+            LayoutTree::Split { children, .. } => children
+                .iter()
+                .map(|(_, child)| child.span(direction))
+                .collect(),
+        };
+        let start = spans.iter().map(|(start, _)| *start).min().unwrap_or(0);
+        let end = spans.iter().map(|(_, end)| *end).max().unwrap_or(0);
+        (start, end)
+    }
+}
+
+// groups of panes that can be divided by straight lines across the direction, in order
+fn split_panes(
+    panes: &[(PaneId, PaneGeom)],
+    direction: SplitDirection,
+) -> Vec<Vec<(PaneId, PaneGeom)>> {
+    let mut panes = panes.to_vec();
+    panes.sort_by_key(|(_, geom)| span(geom, direction));
+    let mut groups: Vec<Vec<(PaneId, PaneGeom)>> = vec![];
+    let mut group_end = 0;
+    for (pane_id, geom) in panes {
+        let (start, end) = span(&geom, direction);
+        match groups.last_mut() {
+            Some(group) if start < group_end => group.push((pane_id, geom)),
+            _ => groups.push(vec![(pane_id, geom)]),
+        }
+        group_end = group_end.max(end);
+    }
+    groups
+}
+
+fn span(geom: &PaneGeom, direction: SplitDirection) -> (usize, usize) {
+    match direction {
+        SplitDirection::Horizontal => (geom.x, geom.x + geom.cols.as_usize()),
+        SplitDirection::Vertical => (geom.y, geom.y + geom.rows.as_usize()),
+    }
+}
+
+fn dimension(geom: &PaneGeom, direction: SplitDirection) -> Dimension {
+    match direction {
+        SplitDirection::Horizontal => geom.cols,
+        SplitDirection::Vertical => geom.rows,
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/layout_tree_tests.rs"]
+mod layout_tree_tests;
@@ -1,3 +1,4 @@
+mod layout_tree;
 mod pane_resizer;
 mod stacked_panes;
 mod tiled_pane_grid;
@@ -18,11 +19,14 @@ use crate::{
 };
 use stacked_panes::StackedPanes;
 use zellij_utils::{
-    data::{Direction, ModeInfo, PaneInfo, Resize, ResizeStrategy, Style, Styling},
+    data::{
+        Direction, MirrorDirection, ModeInfo, PaneInfo, Resize, ResizeStrategy, RotateDirection,
+        Style, Styling,
+    },
     errors::prelude::*,
     input::{
         command::RunCommand,
-        layout::{Run, RunPluginOrAlias, SplitDirection},
+        layout::{Run, RunPluginOrAlias, SplitDirection, SplitSize},
     },
     pane_size::{Offset, PaneGeom, Size, SizeInPixels, Viewport},
 };
@@ -1821,6 +1825,173 @@ impl TiledPanes {
             self.set_pane_frames(self.draw_pane_frames);
         }
     }
+    pub fn swap_panes(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) {
+        let (Some(first_pane), Some(second_pane)) = (
+            self.panes.get(&first_pane_id),
+            self.panes.get(&second_pane_id),
+        ) else {
+            log::error!(
+                "Failed to find panes {:?} and {:?}",
+                first_pane_id,
+                second_pane_id
+            );
+            return;
+        };
+        let first_position = (first_pane.position_and_size(), first_pane.geom_override());
+        let second_position = (second_pane.position_and_size(), second_pane.geom_override());
+        self.set_pane_position(first_pane_id, second_position);
+        self.set_pane_position(second_pane_id, first_position);
+        self.set_pane_frames(self.draw_pane_frames);
+    }
+    pub fn rotate_panes(&mut self, rotate_direction: RotateDirection) {
+        let viewport = *self.viewport.borrow();
+        let mut panes: Vec<(PaneId, PaneGeom)> = self
+            .panes
+            .iter()
+            .filter(|(pane_id, pane)| {
+                pane.selectable()
+                    && !self.panes_to_hide.contains(pane_id)
+                    && pane_geom_is_inside_viewport(&viewport, &pane.position_and_size())
+            })
+            .map(|(pane_id, pane)| (*pane_id, pane.position_and_size()))
+            .collect();
+        if panes.len() < 2 {
+            return;
+        }
+        // panes go around the average of their centers, coordinates are doubled to find the
+        // centers of panes with an odd size
+        let center = |geom: &PaneGeom| {
+            (
+                (geom.x * 2 + geom.cols.as_usize()) as f64,
+                (geom.y * 2 + geom.rows.as_usize()) as f64,
+            )
+        };
+        let (layout_x, layout_y) = panes.iter().fold((0.0, 0.0), |(x, y), (_, geom)| {
+            let (pane_x, pane_y) = center(geom);
+            (x + pane_x, y + pane_y)
+        });
+        let (layout_x, layout_y) = (layout_x / panes.len() as f64, layout_y / panes.len() as f64);
+        let angle = |geom: &PaneGeom| {
+            let (pane_x, pane_y) = center(geom);
+            // rows grow downwards, so this is the clockwise angle from the right
+            (pane_y - layout_y).atan2(pane_x - layout_x)
+        };
+        panes.sort_by(|(_, a), (_, b)| {
+            angle(a)
+                .total_cmp(&angle(b))
+                .then((a.y, a.x).cmp(&(b.y, b.x)))
+        });
+        let positions: Vec<(PaneGeom, Option<PaneGeom>)> = panes
+            .iter()
+            .map(|(pane_id, geom)| {
+                (
+                    *geom,
+                    self.panes.get(pane_id).and_then(|p| p.geom_override()),
+                )
+            })
+            .collect();
+        let pane_count = panes.len();
+        for (i, (pane_id, _)) in panes.iter().enumerate() {
+            let new_position = match rotate_direction {
+                RotateDirection::Clockwise => (i + 1) % pane_count,
+                RotateDirection::CounterClockwise => (i + pane_count - 1) % pane_count,
+            };
+            self.set_pane_position(*pane_id, positions[new_position]);
+        }
+        self.set_pane_frames(self.draw_pane_frames);
+    }
+    pub fn mirror_panes(&mut self, mirror_direction: MirrorDirection) {
+        let viewport = *self.viewport.borrow();
+        let mirror = |mut geom: PaneGeom| {
+            match mirror_direction {
+                MirrorDirection::Horizontal => {
+                    geom.x = (viewport.x * 2 + viewport.cols)
+                        .saturating_sub(geom.x + geom.cols.as_usize());
+                },
+                MirrorDirection::Vertical => {
+                    geom.y = (viewport.y * 2 + viewport.rows)
+                        .saturating_sub(geom.y + geom.rows.as_usize());
+                },
+            }
+            geom
+        };
+        let positions: Vec<(PaneId, (PaneGeom, Option<PaneGeom>))> = self
+            .panes
+            .iter()
+            .filter(|(_, pane)| pane_geom_is_inside_viewport(&viewport, &pane.position_and_size()))
+            .map(|(pane_id, pane)| {
+                (
+                    *pane_id,
+                    (
+                        mirror(pane.position_and_size()),
+                        pane.geom_override().map(mirror),
+                    ),
+                )
+            })
+            .collect();
+        for (pane_id, position) in positions {
+            self.set_pane_position(pane_id, position);
+        }
+        self.set_pane_frames(self.draw_pane_frames);
+    }
+    pub fn equalize_panes(&mut self) -> Result<()> {
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        pane_grid
+            .equalize_panes()
+            .context("failed to equalize tiled panes")?;
+        self.clear_tombstones();
+        self.set_pane_frames(self.draw_pane_frames);
+        Ok(())
+    }
+    pub fn resize_pane_to(
+        &mut self,
+        pane_id: PaneId,
+        direction: SplitDirection,
+        size: SplitSize,
+    ) -> Result<bool> {
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        let pane_size_changed = pane_grid
+            .resize_pane_to(&pane_id, direction, size)
+            .with_context(|| format!("failed to resize pane {:?} to {:?}", pane_id, size))?;
+        self.clear_tombstones();
+        self.set_pane_frames(self.draw_pane_frames);
+        Ok(pane_size_changed)
+    }
+    pub fn resize_pane_to_golden_ratio(&mut self, pane_id: PaneId) -> Result<()> {
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        pane_grid
+            .resize_pane_to_golden_ratio(&pane_id)
+            .with_context(|| format!("failed to resize pane {:?} to the golden ratio", pane_id))?;
+        self.clear_tombstones();
+        self.set_pane_frames(self.draw_pane_frames);
+        Ok(())
+    }
+    fn set_pane_position(&mut self, pane_id: PaneId, position: (PaneGeom, Option<PaneGeom>)) {
+        if let Some(pane) = self.panes.get_mut(&pane_id) {
+            let (geom, geom_override) = position;
+            pane.set_geom(geom);
+            match geom_override {
+                Some(geom_override) => pane.set_geom_override(geom_override),
+                None => pane.reset_size_and_position_override(),
+            }
+            pane.set_should_render(true);
+        }
+    }
     pub fn move_active_pane(&mut self, search_backwards: bool, client_id: ClientId) {
         let active_pane_id = self.get_active_pane_id(client_id).unwrap();
         self.move_pane(search_backwards, active_pane_id)
@@ -1,4 +1,5 @@
 use super::is_inside_viewport;
+use super::layout_tree::{LayoutTree, GOLDEN_RATIO_SHARE};
 use super::pane_resizer::PaneResizer;
 use super::stacked_panes::StackedPanes;
 use crate::tab::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
@@ -8,7 +9,7 @@ use std::collections::{HashMap, HashSet};
 use zellij_utils::data::{Direction, Resize, ResizeStrategy};
 use zellij_utils::{
     errors::prelude::*,
-    input::layout::SplitDirection,
+    input::layout::{SplitDirection, SplitSize},
     pane_size::{Dimension, PaneGeom, Size, Viewport},
 };
 
@@ -54,6 +55,140 @@ impl<'a> TiledPaneGrid<'a> {
         let mut pane_resizer = PaneResizer::new(self.panes.clone());
         pane_resizer.layout(direction, space)
     }
+    /// Gives the panes that share a row or a column equal sizes
+    pub fn equalize_panes(&mut self) -> Result<()> {
+        let mut layout_tree = self
+            .layout_tree()
+            .context("no panes to equalize")
+            .context("failed to equalize panes")?;
+        layout_tree.equalize();
+        self.apply_layout_tree(&layout_tree)
+            .context("failed to equalize panes")
+    }
+    /// Sets the size of the pane to a number of cells or a percent of the whole tab, taking the
+    /// difference from its closest siblings in this direction
+    ///
+    /// Returns false if there are no such siblings
+    pub fn resize_pane_to(
+        &mut self,
+        pane_id: &PaneId,
+        direction: SplitDirection,
+        size: SplitSize,
+    ) -> Result<bool> {
+        let err_context = || format!("failed to resize pane {pane_id:?} to {size:?}");
+        let pane_id = self.flexible_pane_id(pane_id);
+        let mut layout_tree = self
+            .layout_tree()
+            .with_context(|| no_pane_id(&pane_id))
+            .with_context(err_context)?;
+        let share_is_set = layout_tree.set_share_of_pane(
+            pane_id,
+            direction,
+            min_size(direction),
+            &|percent, flexible_space| match size {
+                SplitSize::Percent(size) => size as f64 / percent,
+                SplitSize::Fixed(size) => size as f64 / flexible_space.max(1) as f64,
+            },
+        );
+        if share_is_set {
+            self.apply_layout_tree(&layout_tree)
+                .with_context(err_context)?;
+        }
+        Ok(share_is_set)
+    }
+    /// Gives the pane the larger part of the golden ratio among its closest siblings in both
+    /// directions
+    pub fn resize_pane_to_golden_ratio(&mut self, pane_id: &PaneId) -> Result<()> {
+        let err_context = || format!("failed to resize pane {pane_id:?} to the golden ratio");
+        let pane_id = self.flexible_pane_id(pane_id);
+        let mut layout_tree = self
+            .layout_tree()
+            .with_context(|| no_pane_id(&pane_id))
+            .with_context(err_context)?;
+        let mut share_is_set = false;
+        for direction in [SplitDirection::Horizontal, SplitDirection::Vertical] {
+            share_is_set |= layout_tree.set_share_of_pane(
+                pane_id,
+                direction,
+                min_size(direction),
+                &|_percent, _flexible_space| GOLDEN_RATIO_SHARE,
+            );
+        }
+        if share_is_set {
+            self.apply_layout_tree(&layout_tree)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    fn layout_tree(&self) -> Option<LayoutTree> {
+        // each stack is represented by its flexible pane, with the geom of the whole stack
+        let pane_ids: Vec<PaneId> = self
+            .panes
+            .borrow()
+            .iter()
+            .filter(|(_, pane)| {
+                let geom = pane.current_geom();
+                !geom.is_stacked() || geom.rows.is_percent()
+            })
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        let panes = pane_ids
+            .into_iter()
+            .filter_map(|pane_id| self.get_pane_geom(&pane_id).map(|geom| (pane_id, geom)))
+            .collect();
+        LayoutTree::new(panes)
+    }
+    fn apply_layout_tree(&mut self, layout_tree: &LayoutTree) -> Result<()> {
+        for (pane_id, (horizontal_percent, vertical_percent)) in layout_tree.percents() {
+            let Some(geom) = self.get_pane_geom(&pane_id) else {
+                continue;
+            };
+            if let Some(current_percent) = geom.cols.as_percent() {
+                let change = horizontal_percent - current_percent;
+                if geom.is_stacked() {
+                    StackedPanes::new(self.panes.clone()).increase_stack_width(&pane_id, change)?;
+                } else if let Some(pane) = self.panes.borrow_mut().get_mut(&pane_id) {
+                    pane.increase_width(change);
+                }
+            }
+            if let Some(current_percent) = geom.rows.as_percent() {
+                let change = vertical_percent - current_percent;
+                if geom.is_stacked() {
+                    StackedPanes::new(self.panes.clone())
+                        .increase_stack_height(&pane_id, change)?;
+                } else if let Some(pane) = self.panes.borrow_mut().get_mut(&pane_id) {
+                    pane.increase_height(change);
+                }
+            }
+        }
+        for (direction, space) in [
+            (SplitDirection::Horizontal, self.display_area.cols),
+            (SplitDirection::Vertical, self.display_area.rows),
+        ] {
+            if let Err(e) = self.layout(direction, space) {
+                match e.downcast_ref::<ZellijError>() {
+                    Some(ZellijError::PaneSizeUnchanged) => {}, // nothing to change this way
+                    _ => return Err(e),
+                }
+            }
+        }
+        Ok(())
+    }
+    fn flexible_pane_id(&self, pane_id: &PaneId) -> PaneId {
+        let pane_is_stacked = self
+            .panes
+            .borrow()
+            .get(pane_id)
+            .map(|pane| pane.current_geom().is_stacked())
+            .unwrap_or(false);
+        if pane_is_stacked {
+            StackedPanes::new(self.panes.clone())
+                .flexible_pane_id_in_stack(pane_id)
+                .unwrap_or(*pane_id)
+        } else {
+            *pane_id
+        }
+    }
     pub fn get_pane_geom(&self, pane_id: &PaneId) -> Option<PaneGeom> {
         let panes = self.panes.borrow();
         let pane_to_check = panes.get(pane_id)?;
@@ -2352,6 +2487,13 @@ impl<'a> TiledPaneGrid<'a> {
     }
 }
 
+fn min_size(direction: SplitDirection) -> usize {
+    match direction {
+        SplitDirection::Horizontal => MIN_TERMINAL_WIDTH,
+        SplitDirection::Vertical => MIN_TERMINAL_HEIGHT,
+    }
+}
+
 pub fn split(direction: SplitDirection, rect: &PaneGeom) -> Option<(PaneGeom, PaneGeom)> {
     let space = match direction {
         SplitDirection::Vertical => rect.cols,
@@ -0,0 +1,128 @@
+use super::*;
+
+fn pane(id: u32, x: usize, y: usize, cols: usize, rows: usize) -> (PaneId, PaneGeom) {
+    let mut geom = PaneGeom {
+        x,
+        y,
+        cols: Dimension::percent(cols as f64),
+        rows: Dimension::percent(rows as f64),
+        ..Default::default()
+    };
+    geom.cols.set_inner(cols);
+    geom.rows.set_inner(rows);
+    (PaneId::Terminal(id), geom)
+}
+
+fn fixed_rows_pane(id: u32, x: usize, y: usize, cols: usize, rows: usize) -> (PaneId, PaneGeom) {
+    let (pane_id, mut geom) = pane(id, x, y, cols, rows);
+    geom.rows = Dimension::fixed(rows);
+    (pane_id, geom)
+}
+
+fn assert_percents(tree: &LayoutTree, expected: Vec<(u32, (f64, f64))>) {
+    let percents = tree.percents();
+    for (id, (horizontal, vertical)) in expected {
+        let (h, v) = percents.get(&PaneId::Terminal(id)).unwrap();
+        assert!(
+            (h - horizontal).abs() < 0.01 && (v - vertical).abs() < 0.01,
+            "pane {} should be {:?} but is {:?}",
+            id,
+            (horizontal, vertical),
+            (h, v)
+        );
+    }
+}
+
+#[test]
+fn three_columns_are_equalized() {
+    let mut tree = LayoutTree::new(vec![
+        pane(1, 0, 0, 20, 20),
+        pane(2, 20, 0, 60, 20),
+        pane(3, 80, 0, 20, 20),
+    ])
+    .unwrap();
+    assert_percents(&tree, vec![(1, (20.0, 100.0)), (2, (60.0, 100.0))]);
+    tree.equalize();
+    assert_percents(
+        &tree,
+        vec![
+            (1, (33.33, 100.0)),
+            (2, (33.33, 100.0)),
+            (3, (33.33, 100.0)),
+        ],
+    );
+}
+
+#[test]
+fn nested_splits_are_equalized_among_siblings() {
+    // 1 on the left, 2 and 3 stacked on top of each other on the right
+    let mut tree = LayoutTree::new(vec![
+        pane(1, 0, 0, 70, 20),
+        pane(2, 70, 0, 30, 5),
+        pane(3, 70, 5, 30, 15),
+    ])
+    .unwrap();
+    tree.equalize();
+    assert_percents(
+        &tree,
+        vec![(1, (50.0, 100.0)), (2, (50.0, 50.0)), (3, (50.0, 50.0))],
+    );
+}
+
+#[test]
+fn fixed_size_panes_keep_out_of_equalizing() {
+    // a one line tab bar above two panes
+    let mut tree = LayoutTree::new(vec![
+        fixed_rows_pane(1, 0, 0, 100, 1),
+        pane(2, 0, 1, 100, 4),
+        pane(3, 0, 5, 100, 15),
+    ])
+    .unwrap();
+    tree.equalize();
+    assert_percents(&tree, vec![(2, (100.0, 50.0)), (3, (100.0, 50.0))]);
+}
+
+#[test]
+fn pane_share_is_set_among_its_closest_siblings() {
+    let mut tree = LayoutTree::new(vec![
+        pane(1, 0, 0, 50, 20),
+        pane(2, 50, 0, 25, 20),
+        pane(3, 75, 0, 25, 20),
+    ])
+    .unwrap();
+    let share_is_set = tree.set_share_of_pane(
+        PaneId::Terminal(1),
+        SplitDirection::Horizontal,
+        5,
+        &|percent, _flexible_space| 40.0 / percent,
+    );
+    assert!(share_is_set);
+    assert_percents(
+        &tree,
+        vec![(1, (40.0, 100.0)), (2, (30.0, 100.0)), (3, (30.0, 100.0))],
+    );
+}
+
+#[test]
+fn pane_share_leaves_room_for_its_siblings() {
+    let mut tree = LayoutTree::new(vec![pane(1, 0, 0, 50, 20), pane(2, 50, 0, 50, 20)]).unwrap();
+    tree.set_share_of_pane(
+        PaneId::Terminal(1),
+        SplitDirection::Horizontal,
+        5,
+        &|_percent, _flexible_space| 1.0,
+    );
+    assert_percents(&tree, vec![(1, (95.0, 100.0)), (2, (5.0, 100.0))]);
+}
+
+#[test]
+fn pane_without_siblings_has_no_share_to_set() {
+    let mut tree = LayoutTree::new(vec![pane(1, 0, 0, 50, 20), pane(2, 50, 0, 50, 20)]).unwrap();
+    let share_is_set = tree.set_share_of_pane(
+        PaneId::Terminal(1),
+        SplitDirection::Vertical,
+        5,
+        &|_percent, _flexible_space| GOLDEN_RATIO_SHARE,
+    );
+    assert!(!share_is_set);
+}
@@ -0,0 +1,107 @@
+use super::*;
+use tempfile::tempdir;
+
+#[test]
+fn logs_raw_output() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("pane.log");
+    let mut pane_logger = PaneLogger::new(PaneLogging::new(file.clone())).unwrap();
+    pane_logger.log(b"\x1b[31mred\x1b[0m\r\n").unwrap();
+    pane_logger.log(b"plain").unwrap();
+    // the output is buffered until the logger is closed
+    drop(pane_logger);
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "\x1b[31mred\x1b[0m\r\nplain"
+    );
+}
+
+#[test]
+fn strips_ansi_escape_codes() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("pane.log");
+    let mut pane_logger = PaneLogger::new(PaneLogging {
+        strip_ansi: true,
+        ..PaneLogging::new(file.clone())
+    })
+    .unwrap();
+    pane_logger
+        .log(b"\x1b[31mred\x1b[0m\r\n\x1b]0;title\x07\tafter tab\r\n")
+        .unwrap();
+    // escape sequences can be split across reads
+    pane_logger.log(b"\x1b[3").unwrap();
+    pane_logger.log(b"2mgreen").unwrap();
+    drop(pane_logger);
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "red\n\tafter tab\ngreen"
+    );
+}
+
+#[test]
+fn prefixes_lines_with_timestamps() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("pane.log");
+    let mut pane_logger = PaneLogger::new(PaneLogging {
+        timestamps: true,
+        ..PaneLogging::new(file.clone())
+    })
+    .unwrap();
+    pane_logger.log(b"first\nsec").unwrap();
+    pane_logger.log(b"ond\nthird\n").unwrap();
+    drop(pane_logger);
+    let logged = fs::read_to_string(&file).unwrap();
+    let lines: Vec<&str> = logged.lines().collect();
+    assert_eq!(lines.len(), 3);
+    for (line, expected_text) in lines.iter().zip(["first", "second", "third"]) {
+        let (timestamp, text) = line.split_once("] ").unwrap();
+        assert!(timestamp.starts_with('['), "line is prefixed: {}", line);
+        assert!(
+            humantime::parse_rfc3339(&timestamp[1..]).is_ok(),
+            "prefix is a timestamp: {}",
+            line
+        );
+        assert_eq!(text, expected_text);
+    }
+}
+
+#[test]
+fn rotates_log_file_when_it_reaches_max_size() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("pane.log");
+    let mut pane_logger = PaneLogger::new(PaneLogging {
+        max_size: Some(10),
+        ..PaneLogging::new(file.clone())
+    })
+    .unwrap();
+    pane_logger.log(b"aaaaaaaa").unwrap();
+    pane_logger.log(b"bbbbbbbb").unwrap();
+    pane_logger.log(b"cccccccc").unwrap();
+    // rotated files are written out when they are rotated
+    assert_eq!(
+        fs::read_to_string(rotated_file_name(&file, 1)).unwrap(),
+        "bbbbbbbb"
+    );
+    assert_eq!(
+        fs::read_to_string(rotated_file_name(&file, 2)).unwrap(),
+        "aaaaaaaa"
+    );
+    drop(pane_logger);
+    assert_eq!(fs::read_to_string(&file).unwrap(), "cccccccc");
+}
+
+#[test]
+fn keeps_a_limited_number_of_rotated_files() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("pane.log");
+    let mut pane_logger = PaneLogger::new(PaneLogging {
+        max_size: Some(1),
+        ..PaneLogging::new(file.clone())
+    })
+    .unwrap();
+    for _ in 0..MAX_ROTATED_LOG_FILES + 3 {
+        pane_logger.log(b"a").unwrap();
+    }
+    assert!(rotated_file_name(&file, MAX_ROTATED_LOG_FILES).exists());
+    assert!(!rotated_file_name(&file, MAX_ROTATED_LOG_FILES + 1).exists());
+}
@@ -25,7 +25,7 @@ use wasm_bridge::WasmBridge;
 use zellij_utils::{
     async_std::{channel, future::timeout, task},
     data::{
-        ClientInfo, Event, EventType, InputMode, MessageToPlugin, PermissionStatus, PermissionType,
+        Event, EventType, InputMode, MessageToPlugin, PermissionStatus, PermissionType,
         PipeMessage, PipeSource, PluginCapabilities,
     },
     errors::{prelude::*, ContextType, PluginContext},
@@ -108,7 +108,7 @@ pub enum PluginInstruction {
         Option<PathBuf>,
     ),
     DumpLayout(SessionLayoutMetadata, ClientId),
-    ListClientsMetadata(SessionLayoutMetadata, ClientId),
+    ListClientsMetadata(SessionLayoutMetadata, bool, ClientId), // bool -> json
     DumpLayoutToPlugin(SessionLayoutMetadata, PluginId),
     LogLayoutToHd(SessionLayoutMetadata),
     CliPipe {
@@ -570,7 +570,11 @@ pub(crate) fn plugin_thread_main(
                     client_id,
                 )));
             },
-            PluginInstruction::ListClientsMetadata(mut session_layout_metadata, client_id) => {
+            PluginInstruction::ListClientsMetadata(
+                mut session_layout_metadata,
+                json,
+                client_id,
+            ) => {
                 populate_session_layout_metadata(
                     &mut session_layout_metadata,
                     &wasm_bridge,
@@ -578,6 +582,7 @@ pub(crate) fn plugin_thread_main(
                 );
                 drop(bus.senders.send_to_pty(PtyInstruction::ListClientsMetadata(
                     session_layout_metadata,
+                    json,
                     client_id,
                 )));
             },
@@ -621,18 +626,7 @@ pub(crate) fn plugin_thread_main(
                     &wasm_bridge,
                     &plugin_aliases,
                 );
-                let mut clients_metadata = session_layout_metadata.all_clients_metadata();
-                let mut client_list_for_plugin = vec![];
-                let default_editor = session_layout_metadata.default_editor.clone();
-                for (client_metadata_id, client_metadata) in clients_metadata.iter_mut() {
-                    let is_current_client = client_metadata_id == &client_id;
-                    client_list_for_plugin.push(ClientInfo::new(
-                        *client_metadata_id,
-                        client_metadata.get_pane_id().into(),
-                        client_metadata.stringify_command(&default_editor),
-                        is_current_client,
-                    ));
-                }
+                let client_list_for_plugin = session_layout_metadata.client_infos(client_id);
                 let updates = vec![(
                     Some(plugin_id),
                     Some(client_id),
@@ -19,7 +19,8 @@ use std::{
 use wasmtime::{Caller, Linker};
 use zellij_utils::data::{
     CommandType, ConnectToSession, FloatingPaneCoordinates, HttpVerb, KeyWithModifier, LayoutInfo,
-    MessageToPlugin, OriginatingPlugin, PermissionStatus, PermissionType, PluginPermission,
+    MessageToPlugin, MirrorDirection, OriginatingPlugin, PermissionStatus, PermissionType,
+    PluginPermission, RotateDirection,
 };
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::{
@@ -38,7 +39,7 @@ use zellij_utils::{
     },
     errors::prelude::*,
     input::{
-        actions::Action,
+        actions::{Action, JoinPanePlacement},
         command::{OpenFilePayload, RunCommand, RunCommandAction, TerminalAction},
         layout::{Layout, RunPluginOrAlias},
     },
@@ -416,6 +417,21 @@ fn host_run_plugin_command(caller: Caller<'_, PluginEnv>) {
                     PluginCommand::OpenFileInPlaceOfPlugin(file_to_open, context) => {
                         open_file_in_place_of_plugin(env, file_to_open, context)
                     },
+                    PluginCommand::JoinPaneToFocusedTab(pane_id, should_float) => {
+                        join_pane_to_focused_tab(env, pane_id.into(), should_float)
+                    },
+                    PluginCommand::SwapPanes(first_pane_id, second_pane_id) => {
+                        swap_panes(env, first_pane_id.into(), second_pane_id.into())
+                    },
+                    PluginCommand::RotatePanes(rotate_direction) => {
+                        rotate_panes(env, rotate_direction)
+                    },
+                    PluginCommand::MirrorLayout(mirror_direction) => {
+                        mirror_layout(env, mirror_direction)
+                    },
+                    PluginCommand::OpenCommandPanePopup(command_to_run, context) => {
+                        open_command_pane_popup(env, command_to_run, context)
+                    },
                 },
                 (PermissionStatus::Denied, permission) => {
                     log::error!(
@@ -871,6 +887,7 @@ fn open_command_pane_in_place_of_plugin(
             env.client_id,
             context,
         )),
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env
@@ -907,6 +924,7 @@ fn open_command_pane(
             env.client_id,
             context,
         )),
+        ..Default::default()
     };
     let action = Action::NewTiledPane(direction, Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -937,6 +955,7 @@ fn open_command_pane_near_plugin(
             env.client_id,
             context,
         )),
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -975,11 +994,49 @@ fn open_command_pane_floating(
             env.client_id,
             context,
         )),
+        ..Default::default()
     };
     let action = Action::NewFloatingPane(Some(run_command_action), name, floating_pane_coordinates);
     apply_action!(action, error_msg, env);
 }
 
+fn open_command_pane_popup(
+    env: &PluginEnv,
+    command_to_run: CommandToRun,
+    context: BTreeMap<String, String>,
+) {
+    let error_msg = || format!("failed to open command in plugin {}", env.name());
+    let command = command_to_run.path;
+    let cwd = command_to_run.cwd.map(|cwd| env.plugin_cwd.join(cwd));
+    let args = command_to_run.args;
+    let direction = None;
+    let hold_on_close = true;
+    let hold_on_start = false;
+    let close_on_success = true;
+    let name = None;
+    let run_command_action = RunCommandAction {
+        command,
+        args,
+        cwd,
+        direction,
+        hold_on_close,
+        hold_on_start,
+        close_on_success,
+        originating_plugin: Some(OriginatingPlugin::new(
+            env.plugin_id,
+            env.client_id,
+            context,
+        )),
+        ..Default::default()
+    };
+    let action = Action::NewFloatingPane(
+        Some(run_command_action),
+        name,
+        Some(FloatingPaneCoordinates::popup()),
+    );
+    apply_action!(action, error_msg, env);
+}
+
 fn open_command_pane_floating_near_plugin(
     env: &PluginEnv,
     command_to_run: CommandToRun,
@@ -1006,6 +1063,7 @@ fn open_command_pane_floating_near_plugin(
             env.client_id,
             context,
         )),
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1043,6 +1101,7 @@ fn open_command_pane_in_place(
             env.client_id,
             context,
         )),
+        ..Default::default()
     };
     let action = Action::NewInPlacePane(Some(run_command_action), name);
     apply_action!(action, error_msg, env);
@@ -1076,6 +1135,7 @@ fn open_command_pane_background(
             env.client_id,
             context,
         )),
+        ..Default::default()
     };
     let run_cmd = TerminalAction::RunCommand(run_command_action.into());
     let _ = env.senders.send_to_pty(PtyInstruction::SpawnTerminal(
@@ -1854,6 +1914,39 @@ fn change_floating_panes_coordinates(
         ));
 }
 
+fn join_pane_to_focused_tab(env: &PluginEnv, pane_id: PaneId, should_float: bool) {
+    let placement = if should_float {
+        JoinPanePlacement::Floating
+    } else {
+        JoinPanePlacement::default()
+    };
+    let _ = env.senders.send_to_screen(ScreenInstruction::JoinPane(
+        pane_id,
+        placement,
+        env.client_id,
+    ));
+}
+
+fn swap_panes(env: &PluginEnv, first_pane_id: PaneId, second_pane_id: PaneId) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::SwapPanes(first_pane_id, second_pane_id));
+}
+
+fn rotate_panes(env: &PluginEnv, rotate_direction: RotateDirection) {
+    let _ = env.senders.send_to_screen(ScreenInstruction::RotatePanes(
+        rotate_direction,
+        env.client_id,
+    ));
+}
+
+fn mirror_layout(env: &PluginEnv, mirror_direction: MirrorDirection) {
+    let _ = env.senders.send_to_screen(ScreenInstruction::MirrorLayout(
+        mirror_direction,
+        env.client_id,
+    ));
+}
+
 fn scan_host_folder(env: &PluginEnv, folder_to_scan: PathBuf) {
     if !folder_to_scan.starts_with("/host") {
         log::error!(
@@ -2200,6 +2293,7 @@ fn check_command_permission(
         | PluginCommand::OpenCommandPaneNearPlugin(..)
         | PluginCommand::OpenCommandPaneFloating(..)
         | PluginCommand::OpenCommandPaneFloatingNearPlugin(..)
+        | PluginCommand::OpenCommandPanePopup(..)
         | PluginCommand::OpenCommandPaneInPlace(..)
         | PluginCommand::OpenCommandPaneInPlaceOfPlugin(..)
         | PluginCommand::OpenCommandPaneBackground(..)
@@ -2284,6 +2378,10 @@ fn check_command_permission(
         | PluginCommand::SetFloatingPanePinned(..)
         | PluginCommand::StackPanes(..)
         | PluginCommand::ChangeFloatingPanesCoordinates(..)
+        | PluginCommand::JoinPaneToFocusedTab(..)
+        | PluginCommand::SwapPanes(..)
+        | PluginCommand::RotatePanes(..)
+        | PluginCommand::MirrorLayout(..)
         | PluginCommand::KillSessions(..) => PermissionType::ChangeApplicationState,
         PluginCommand::UnblockCliPipeInput(..)
         | PluginCommand::BlockCliPipeInput(..)
@@ -3,23 +3,34 @@ use crate::terminal_bytes::TerminalBytes;
 use crate::{
     panes::PaneId,
     plugins::{PluginId, PluginInstruction},
+    recently_closed::{ClosedFrom, ClosedItem, ClosedPane},
     screen::ScreenInstruction,
     session_layout_metadata::SessionLayoutMetadata,
     thread_bus::{Bus, ThreadSenders},
     ClientId, ServerInstruction,
 };
 use async_std::task::{self, JoinHandle};
-use std::sync::Arc;
-use std::{collections::HashMap, os::unix::io::RawFd, path::PathBuf};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    os::unix::io::RawFd,
+    path::PathBuf,
+    time::Duration,
+};
 use zellij_utils::nix::unistd::Pid;
 use zellij_utils::{
-    async_std,
+    async_std, channels,
     data::{Event, FloatingPaneCoordinates, OriginatingPlugin},
     errors::prelude::*,
     errors::{ContextType, PtyContext},
     input::{
-        command::{OpenFilePayload, RunCommand, TerminalAction},
+        command::{OpenFilePayload, RemoteHost, RunCommand, TerminalAction},
+        hooks::{HookContext, HookEvent},
         layout::{FloatingPaneLayout, Layout, Run, RunPluginOrAlias, TiledPaneLayout},
+        scratchpad::Scratchpad,
     },
     pane_size::Size,
     session_serialization,
@@ -28,6 +39,9 @@ use zellij_utils::{
 pub type VteBytes = Vec<u8>;
 pub type TabIndex = u32;
 
+// processes a command leaves running in the background keep its pty open after it exits
+const ALL_PANE_OUTPUT_READ_TIMEOUT: Duration = Duration::from_secs(2);
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ClientTabIndexOrPaneId {
     ClientId(ClientId),
@@ -96,12 +110,19 @@ pub enum PtyInstruction {
         Option<PathBuf>, // if Some, will not fill cwd but just forward the message
         Option<FloatingPaneCoordinates>,
     ),
-    ListClientsMetadata(SessionLayoutMetadata, ClientId),
+    ListClientsMetadata(SessionLayoutMetadata, bool, ClientId), // bool -> json
     Reconfigure {
         client_id: ClientId,
         default_editor: Option<PathBuf>,
+        ssh_command: Option<PathBuf>,
+        default_shell: Option<PathBuf>,
     },
     ListClientsToPlugin(SessionLayoutMetadata, PluginId, ClientId),
+    PauseReading(u32),  // u32 - terminal_id
+    ResumeReading(u32), // u32 - terminal_id
+    FillClosedPanesMetadata(SessionLayoutMetadata, ClosedFrom),
+    ReopenClosedPane(ClosedPane, ClientId),
+    SpawnScratchpad(String, Scratchpad, ClientId), // String -> scratchpad name
     Exit,
 }
 
@@ -127,6 +148,11 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::ListClientsMetadata(..) => PtyContext::ListClientsMetadata,
             PtyInstruction::Reconfigure { .. } => PtyContext::Reconfigure,
             PtyInstruction::ListClientsToPlugin(..) => PtyContext::ListClientsToPlugin,
+            PtyInstruction::PauseReading(..) => PtyContext::PauseReading,
+            PtyInstruction::ResumeReading(..) => PtyContext::ResumeReading,
+            PtyInstruction::FillClosedPanesMetadata(..) => PtyContext::FillClosedPanesMetadata,
+            PtyInstruction::ReopenClosedPane(..) => PtyContext::ReopenClosedPane,
+            PtyInstruction::SpawnScratchpad(..) => PtyContext::SpawnScratchpad,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -139,7 +165,11 @@ pub(crate) struct Pty {
     originating_plugins: HashMap<u32, OriginatingPlugin>,
     debug_to_file: bool,
     task_handles: HashMap<u32, JoinHandle<()>>, // terminal_id to join-handle
+    reading_paused: HashMap<u32, Arc<AtomicBool>>, // terminal_id to whether we stopped reading
     default_editor: Option<PathBuf>,
+    ssh_command: Option<PathBuf>, // what remote panes connect with, ssh if None
+    remote_panes: HashMap<u32, RemoteHost>, // terminal_id to the host its command runs on
+    default_shell: Option<PathBuf>, // the configured one, if any
 }
 
 pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
@@ -157,6 +187,8 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             ) => {
                 let err_context =
                     || format!("failed to spawn terminal for {:?}", client_or_tab_index);
+                // new panes opened from a remote pane open on the same host
+                let terminal_action = pty.remote_shell_or(terminal_action, &client_or_tab_index);
 
                 let (hold_on_close, run_command, pane_title, open_file_payload) =
                     match &terminal_action {
@@ -242,6 +274,18 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                     Err(err) => match err.downcast_ref::<ZellijError>() {
                         Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            if let Some(output_pipe_id) =
+                                run_command.as_ref().and_then(|r| r.output_pipe_id.clone())
+                            {
+                                // the command will never exit, so we release the cli client
+                                // waiting for its output
+                                pty.bus
+                                    .senders
+                                    .send_to_server(ServerInstruction::UnblockCliPipeInput(
+                                        output_pipe_id,
+                                    ))
+                                    .with_context(err_context)?;
+                            }
                             if hold_on_close {
                                 let hold_for_command = None; // we do not hold an "error" pane
                                 pty.bus
@@ -386,6 +430,10 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             PtyInstruction::SpawnTerminalVertically(terminal_action, name, client_id) => {
                 let err_context =
                     || format!("failed to spawn terminal vertically for client {client_id}");
+                let terminal_action = pty.remote_shell_or(
+                    terminal_action,
+                    &ClientTabIndexOrPaneId::ClientId(client_id),
+                );
 
                 let (hold_on_close, run_command, pane_title) = match &terminal_action {
                     Some(TerminalAction::RunCommand(run_command)) => (
@@ -455,6 +503,10 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             PtyInstruction::SpawnTerminalHorizontally(terminal_action, name, client_id) => {
                 let err_context =
                     || format!("failed to spawn terminal horizontally for client {client_id}");
+                let terminal_action = pty.remote_shell_or(
+                    terminal_action,
+                    &ClientTabIndexOrPaneId::ClientId(client_id),
+                );
 
                 let (hold_on_close, run_command, pane_title) = match &terminal_action {
                     Some(TerminalAction::RunCommand(run_command)) => (
@@ -689,18 +741,20 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
-            PtyInstruction::ListClientsMetadata(mut session_layout_metadata, client_id) => {
+            PtyInstruction::ListClientsMetadata(mut session_layout_metadata, json, client_id) => {
                 let err_context = || format!("Failed to dump layout");
                 pty.populate_session_layout_metadata(&mut session_layout_metadata);
+                let clients_metadata = if json {
+                    match serde_json::to_string(&session_layout_metadata.client_infos(client_id)) {
+                        Ok(clients_metadata) => clients_metadata,
+                        Err(e) => format!("Failed to serialize clients: {}", e),
+                    }
+                } else {
+                    session_layout_metadata.list_clients_metadata()
+                };
                 pty.bus
                     .senders
-                    .send_to_server(ServerInstruction::Log(
-                        vec![format!(
-                            "{}",
-                            session_layout_metadata.list_clients_metadata()
-                        )],
-                        client_id,
-                    ))
+                    .send_to_server(ServerInstruction::Log(vec![clients_metadata], client_id))
                     .with_context(err_context)
                     .non_fatal();
             },
@@ -783,9 +837,138 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             },
             PtyInstruction::Reconfigure {
                 default_editor,
+                ssh_command,
+                default_shell,
                 client_id: _,
             } => {
-                pty.reconfigure(default_editor);
+                pty.reconfigure(default_editor, ssh_command, default_shell);
+            },
+            PtyInstruction::PauseReading(terminal_id) => {
+                pty.set_reading_paused(terminal_id, true);
+            },
+            PtyInstruction::ResumeReading(terminal_id) => {
+                pty.set_reading_paused(terminal_id, false);
+            },
+            PtyInstruction::FillClosedPanesMetadata(mut session_layout_metadata, closed_from) => {
+                pty.populate_session_layout_metadata(&mut session_layout_metadata);
+                if let Some(closed_item) = ClosedItem::new(session_layout_metadata, closed_from) {
+                    pty.bus
+                        .senders
+                        .send_to_screen(ScreenInstruction::AddRecentlyClosed(closed_item))
+                        .context("failed to remember closed panes")
+                        .non_fatal();
+                }
+            },
+            PtyInstruction::ReopenClosedPane(closed_pane, client_id) => {
+                let err_context = || format!("failed to reopen closed pane for client {client_id}");
+
+                let mut terminal_action = match closed_pane.run() {
+                    Some(Run::Command(run_command)) => {
+                        Some(TerminalAction::RunCommand(run_command))
+                    },
+                    Some(Run::EditFile(path_to_file, line_number, cwd)) => {
+                        Some(TerminalAction::OpenFile(OpenFilePayload::new(
+                            path_to_file,
+                            line_number,
+                            cwd,
+                        )))
+                    },
+                    Some(Run::Cwd(cwd)) => Some(pty.get_default_terminal(Some(cwd), None)),
+                    Some(Run::Plugin(..)) | None => None,
+                };
+                if let Some(terminal_action) = terminal_action.as_mut() {
+                    terminal_action.add_env(&closed_pane.pane.env);
+                }
+                let run_command = match &terminal_action {
+                    Some(TerminalAction::RunCommand(run_command)) => Some(run_command.clone()),
+                    _ => None,
+                };
+                match pty
+                    .spawn_terminal(
+                        terminal_action,
+                        ClientTabIndexOrPaneId::TabIndex(closed_pane.tab_index),
+                    )
+                    .with_context(err_context)
+                {
+                    Ok((pid, starts_held)) => {
+                        let hold_for_command = if starts_held { run_command } else { None };
+                        pty.bus
+                            .senders
+                            .send_to_screen(ScreenInstruction::ClosedPaneReopened(
+                                PaneId::Terminal(pid),
+                                hold_for_command,
+                                closed_pane,
+                                client_id,
+                            ))
+                            .with_context(err_context)?;
+                    },
+                    Err(err) => {
+                        if let Some(ZellijError::CommandNotFound { terminal_id, .. }) =
+                            err.downcast_ref::<ZellijError>()
+                        {
+                            pty.close_pane(PaneId::Terminal(*terminal_id))
+                                .with_context(err_context)?;
+                        }
+                        Err::<(), _>(err).non_fatal();
+                        pty.bus
+                            .senders
+                            .send_to_server(ServerInstruction::UnblockInputThread)
+                            .with_context(err_context)?;
+                    },
+                }
+            },
+            PtyInstruction::SpawnScratchpad(name, scratchpad, client_id) => {
+                let err_context = || format!("failed to open scratchpad {name:?}");
+
+                let terminal_action = match scratchpad.run {
+                    Some(run_command) if !run_command.command.as_os_str().is_empty() => {
+                        TerminalAction::RunCommand(run_command)
+                    },
+                    // no command, so we open the default shell (in the cwd if there is one)
+                    run_command => pty.get_default_terminal(
+                        run_command.and_then(|run_command| run_command.cwd),
+                        None,
+                    ),
+                };
+                let run_command = match &terminal_action {
+                    TerminalAction::RunCommand(run_command) => Some(run_command.clone()),
+                    _ => None,
+                };
+                match pty
+                    .spawn_terminal(
+                        Some(terminal_action),
+                        ClientTabIndexOrPaneId::ClientId(client_id),
+                    )
+                    .with_context(err_context)
+                {
+                    Ok((pid, starts_held)) => {
+                        let hold_for_command = if starts_held { run_command } else { None };
+                        pty.bus
+                            .senders
+                            .send_to_screen(ScreenInstruction::ScratchpadSpawned(
+                                PaneId::Terminal(pid),
+                                hold_for_command,
+                                name.clone(),
+                                client_id,
+                            ))
+                            .with_context(err_context)?;
+                    },
+                    Err(err) => {
+                        if let Some(ZellijError::CommandNotFound { terminal_id, .. }) =
+                            err.downcast_ref::<ZellijError>()
+                        {
+                            pty.close_pane(PaneId::Terminal(*terminal_id))
+                                .with_context(err_context)?;
+                        }
+                        Err::<(), _>(err).non_fatal();
+                        pty.bus
+                            .senders
+                            .send_to_screen(ScreenInstruction::ScratchpadFailedToSpawn(
+                                name.clone(),
+                            ))
+                            .with_context(err_context)?;
+                    },
+                }
             },
             PtyInstruction::Exit => break,
         }
@@ -798,6 +981,8 @@ impl Pty {
         bus: Bus<PtyInstruction>,
         debug_to_file: bool,
         default_editor: Option<PathBuf>,
+        ssh_command: Option<PathBuf>,
+        default_shell: Option<PathBuf>,
     ) -> Self {
         Pty {
             active_panes: HashMap::new(),
@@ -805,8 +990,12 @@ impl Pty {
             id_to_child_pid: HashMap::new(),
             debug_to_file,
             task_handles: HashMap::new(),
+            reading_paused: HashMap::new(),
             default_editor,
             originating_plugins: HashMap::new(),
+            ssh_command,
+            remote_panes: HashMap::new(),
+            default_shell,
         }
     }
     pub fn get_default_terminal(
@@ -879,6 +1068,65 @@ impl Pty {
             };
         };
     }
+    /// New panes opened from a remote pane open a shell on the same host, unless they were asked
+    /// to run something other than the default shell
+    fn remote_shell_or(
+        &self,
+        terminal_action: Option<TerminalAction>,
+        client_or_tab_index: &ClientTabIndexOrPaneId,
+    ) -> Option<TerminalAction> {
+        let is_default_shell = match &terminal_action {
+            None => true,
+            Some(TerminalAction::RunCommand(run_command)) => self.is_default_shell(run_command),
+            Some(TerminalAction::OpenFile(..)) => false,
+        };
+        if is_default_shell {
+            self.remote_shell_for(client_or_tab_index)
+                .or(terminal_action)
+        } else {
+            terminal_action
+        }
+    }
+    fn is_default_shell(&self, run_command: &RunCommand) -> bool {
+        self.default_shell.as_ref() == Some(&run_command.command)
+            && run_command.args.is_empty()
+            && !run_command.hold_on_close
+            && run_command.remote.is_none()
+    }
+    fn remote_shell_for(
+        &self,
+        client_or_tab_index: &ClientTabIndexOrPaneId,
+    ) -> Option<TerminalAction> {
+        let pane_id = match client_or_tab_index {
+            ClientTabIndexOrPaneId::ClientId(client_id) => self.active_panes.get(client_id)?,
+            ClientTabIndexOrPaneId::PaneId(pane_id) => pane_id,
+            ClientTabIndexOrPaneId::TabIndex(_) => return None,
+        };
+        match pane_id {
+            PaneId::Terminal(id) => self
+                .remote_panes
+                .get(id)
+                .map(|remote| TerminalAction::RunCommand(RunCommand::remote_shell(remote.clone()))),
+            PaneId::Plugin(..) => None,
+        }
+    }
+    fn fill_ssh_command(&self, run_command: &mut RunCommand) {
+        if let Some(remote) = run_command.remote.as_mut() {
+            if remote.connect_with.is_none() {
+                remote.connect_with = self.ssh_command.clone();
+            }
+        }
+    }
+    fn track_remote_pane(&mut self, terminal_id: u32, remote: Option<&RemoteHost>) {
+        match remote {
+            Some(remote) => {
+                self.remote_panes.insert(terminal_id, remote.clone());
+            },
+            None => {
+                self.remote_panes.remove(&terminal_id);
+            },
+        }
+    }
     pub fn spawn_terminal(
         &mut self,
         terminal_action: Option<TerminalAction>,
@@ -907,6 +1155,14 @@ impl Pty {
                 terminal_action
             },
         };
+        let mut terminal_action = terminal_action;
+        let remote = match &mut terminal_action {
+            TerminalAction::RunCommand(run_command) => {
+                self.fill_ssh_command(run_command);
+                run_command.remote.clone()
+            },
+            TerminalAction::OpenFile(..) => None,
+        };
         let (hold_on_start, hold_on_close, originating_command_plugin, originating_edit_plugin) =
             match &terminal_action {
                 TerminalAction::RunCommand(run_command) => (
@@ -922,6 +1178,13 @@ impl Pty {
                     open_file_payload.originating_plugin.clone(),
                 ),
             };
+        let (close_on_success, output_pipe_id) = match &terminal_action {
+            TerminalAction::RunCommand(run_command) => (
+                run_command.close_on_success,
+                run_command.output_pipe_id.clone(),
+            ),
+            TerminalAction::OpenFile(..) => (false, None),
+        };
 
         if hold_on_start {
             // we don't actually open a terminal in this case, just wait for the user to run it
@@ -933,11 +1196,14 @@ impl Pty {
                 .context("couldn't get mutable reference to OS interface")
                 .and_then(|os_input| os_input.reserve_terminal_id())
                 .with_context(err_context)?;
+            self.track_remote_pane(terminal_id, remote.as_ref());
             return Ok((terminal_id, starts_held));
         }
 
         let originating_command_plugin = Arc::new(originating_command_plugin.clone());
         let originating_edit_plugin = Arc::new(originating_edit_plugin.clone());
+        // disconnected once the pty reader has sent the last bytes of the pane to the screen
+        let (all_output_read, all_output_read_receiver) = channels::bounded::<()>(0);
         let quit_cb = Box::new({
             let senders = self.bus.senders.clone();
             move |pane_id, exit_status, command| {
@@ -970,15 +1236,17 @@ impl Pty {
                     }
                 }
 
-                if hold_on_close {
-                    let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
-                        pane_id,
-                        exit_status,
-                        command,
-                    ));
-                } else {
-                    let _ = senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
-                }
+                handle_command_exit(
+                    &senders,
+                    pane_id,
+                    exit_status,
+                    command,
+                    hold_on_close,
+                    close_on_success,
+                    output_pipe_id
+                        .as_ref()
+                        .map(|output_pipe_id| (output_pipe_id, &all_output_read_receiver)),
+                );
             }
         });
         let (terminal_id, pid_primary, child_fd): (u32, RawFd, RawFd) = self
@@ -990,6 +1258,7 @@ impl Pty {
                 os_input.spawn_terminal(terminal_action, quit_cb, self.default_editor.clone())
             })
             .with_context(err_context)?;
+        self.track_remote_pane(terminal_id, remote.as_ref());
         let terminal_bytes = task::spawn({
             let err_context =
                 |terminal_id: u32| format!("failed to run async task for terminal {terminal_id}");
@@ -1002,12 +1271,21 @@ impl Pty {
                 .fatal()
                 .clone();
             let debug_to_file = self.debug_to_file;
+            let reading_paused = self.new_reading_paused_flag(terminal_id);
             async move {
-                TerminalBytes::new(pid_primary, senders, os_input, debug_to_file, terminal_id)
-                    .listen()
-                    .await
-                    .with_context(|| err_context(terminal_id))
-                    .fatal();
+                TerminalBytes::new(
+                    pid_primary,
+                    senders,
+                    os_input,
+                    debug_to_file,
+                    terminal_id,
+                    reading_paused,
+                )
+                .listen()
+                .await
+                .with_context(|| err_context(terminal_id))
+                .fatal();
+                drop(all_output_read);
             }
         });
 
@@ -1032,11 +1310,11 @@ impl Pty {
         let mut default_shell =
             default_shell.unwrap_or_else(|| self.get_default_terminal(cwd, None));
         self.fill_cwd(&mut default_shell, client_id);
-        let extracted_run_instructions = layout.extract_run_instructions();
+        let extracted_run_instructions = layout.extract_run_instructions_and_env();
         let extracted_floating_run_instructions = floating_panes_layout
             .iter()
             .filter(|f| !f.already_running)
-            .map(|f| f.run.clone());
+            .map(|f| (f.run.clone(), f.env.clone()));
         let mut new_pane_pids: Vec<(u32, bool, Option<RunCommand>, Result<RawFd>)> = vec![]; // (terminal_id,
                                                                                              // starts_held,
                                                                                              // run_command,
@@ -1045,16 +1323,16 @@ impl Pty {
             vec![]; // same
                     // as
                     // new_pane_pids
-        for run_instruction in extracted_run_instructions {
+        for (run_instruction, env) in extracted_run_instructions {
             if let Some(new_pane_data) =
-                self.apply_run_instruction(run_instruction, default_shell.clone())?
+                self.apply_run_instruction(run_instruction, env, default_shell.clone())?
             {
                 new_pane_pids.push(new_pane_data);
             }
         }
-        for run_instruction in extracted_floating_run_instructions {
+        for (run_instruction, env) in extracted_floating_run_instructions {
             if let Some(new_pane_data) =
-                self.apply_run_instruction(run_instruction, default_shell.clone())?
+                self.apply_run_instruction(run_instruction, env, default_shell.clone())?
             {
                 new_floating_panes_pids.push(new_pane_data);
             }
@@ -1112,6 +1390,7 @@ impl Pty {
                             .with_context(err_context)?
                             .clone();
                         let debug_to_file = self.debug_to_file;
+                        let reading_paused = self.new_reading_paused_flag(terminal_id);
                         async move {
                             TerminalBytes::new(
                                 pid_primary,
@@ -1119,6 +1398,7 @@ impl Pty {
                                 os_input,
                                 debug_to_file,
                                 terminal_id,
+                                reading_paused,
                             )
                             .listen()
                             .await
@@ -1154,16 +1434,21 @@ impl Pty {
     fn apply_run_instruction(
         &mut self,
         run_instruction: Option<Run>,
-        default_shell: TerminalAction,
+        env: BTreeMap<String, String>,
+        mut default_shell: TerminalAction,
     ) -> Result<Option<(u32, bool, Option<RunCommand>, Result<i32>)>> {
         // terminal_id,
         // starts_held,
         // command
         // successfully opened
         let err_context = || format!("failed to apply run instruction");
+        default_shell.add_env(&env);
         let quit_cb = Box::new({
             let senders = self.bus.senders.clone();
-            move |pane_id, _exit_status, _command| {
+            move |pane_id, exit_status, _command| {
+                let _ =
+                    senders.send_to_screen(ScreenInstruction::CommandExited(pane_id, exit_status));
+                send_pane_exit_hook_to_server(&senders, pane_id, exit_status);
                 let _ = senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
             }
         });
@@ -1174,6 +1459,9 @@ impl Pty {
                 let quit_cb = Box::new({
                     let senders = self.bus.senders.clone();
                     move |pane_id, exit_status, command| {
+                        let _ = senders
+                            .send_to_screen(ScreenInstruction::CommandExited(pane_id, exit_status));
+                        send_pane_exit_hook_to_server(&senders, pane_id, exit_status);
                         if hold_on_close {
                             let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                                 pane_id,
@@ -1191,6 +1479,8 @@ impl Pty {
                         command.cwd = cmd.cwd;
                     }
                 }
+                command.env.extend(env);
+                self.fill_ssh_command(&mut command);
                 let cmd = TerminalAction::RunCommand(command.clone());
                 if starts_held {
                     // we don't actually open a terminal in this case, just wait for the user to run it
@@ -1203,6 +1493,7 @@ impl Pty {
                         .reserve_terminal_id()
                     {
                         Ok(terminal_id) => {
+                            self.track_remote_pane(terminal_id, command.remote.as_ref());
                             Ok(Some((
                                 terminal_id,
                                 starts_held,
@@ -1225,6 +1516,7 @@ impl Pty {
                     {
                         Ok((terminal_id, pid_primary, child_fd)) => {
                             self.id_to_child_pid.insert(terminal_id, child_fd);
+                            self.track_remote_pane(terminal_id, command.remote.as_ref());
                             Ok(Some((
                                 terminal_id,
                                 starts_held,
@@ -1276,11 +1568,10 @@ impl Pty {
                     .context("no OS I/O interface found")
                     .with_context(err_context)?
                     .spawn_terminal(
-                        TerminalAction::OpenFile(OpenFilePayload::new(
-                            path_to_file,
-                            line_number,
-                            cwd,
-                        )),
+                        TerminalAction::OpenFile(OpenFilePayload {
+                            env,
+                            ..OpenFilePayload::new(path_to_file, line_number, cwd)
+                        }),
                         quit_cb,
                         self.default_editor.clone(),
                     )
@@ -1330,6 +1621,10 @@ impl Pty {
         match id {
             PaneId::Terminal(id) => {
                 self.task_handles.remove(&id);
+                self.remote_panes.remove(&id);
+                // make sure the reader is not paused so that it can notice the pty closing
+                self.set_reading_paused(id, false);
+                self.reading_paused.remove(&id);
                 if let Some(child_fd) = self.id_to_child_pid.remove(&id) {
                     task::block_on(async {
                         let err_context = || format!("failed to run async task for pane {id}");
@@ -1384,9 +1679,15 @@ impl Pty {
                 }
                 let _ = self.task_handles.remove(&id); // if all is well, this shouldn't be here
                 let _ = self.id_to_child_pid.remove(&id); // if all is wlel, this shouldn't be here
+                self.fill_ssh_command(&mut run_command);
+                self.track_remote_pane(id, run_command.remote.as_ref());
 
                 let hold_on_close = run_command.hold_on_close;
+                let close_on_success = run_command.close_on_success;
+                let output_pipe_id = run_command.output_pipe_id.clone();
                 let originating_plugin = Arc::new(run_command.originating_plugin.clone());
+                // disconnected once the pty reader has sent the last bytes of the pane to the screen
+                let (all_output_read, all_output_read_receiver) = channels::bounded::<()>(0);
                 let quit_cb = Box::new({
                     let senders = self.bus.senders.clone();
                     move |pane_id, exit_status, command| {
@@ -1404,16 +1705,17 @@ impl Pty {
                                 )]));
                             }
                         }
-                        if hold_on_close {
-                            let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
-                                pane_id,
-                                exit_status,
-                                command,
-                            ));
-                        } else {
-                            let _ =
-                                senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
-                        }
+                        handle_command_exit(
+                            &senders,
+                            pane_id,
+                            exit_status,
+                            command,
+                            hold_on_close,
+                            close_on_success,
+                            output_pipe_id
+                                .as_ref()
+                                .map(|output_pipe_id| (output_pipe_id, &all_output_read_receiver)),
+                        );
                     }
                 });
                 let (pid_primary, child_fd): (RawFd, RawFd) = self
@@ -1437,12 +1739,21 @@ impl Pty {
                         .fatal()
                         .clone();
                     let debug_to_file = self.debug_to_file;
+                    let reading_paused = self.new_reading_paused_flag(id);
                     async move {
-                        TerminalBytes::new(pid_primary, senders, os_input, debug_to_file, id)
-                            .listen()
-                            .await
-                            .with_context(|| err_context(pane_id))
-                            .fatal();
+                        TerminalBytes::new(
+                            pid_primary,
+                            senders,
+                            os_input,
+                            debug_to_file,
+                            id,
+                            reading_paused,
+                        )
+                        .listen()
+                        .await
+                        .with_context(|| err_context(pane_id))
+                        .fatal();
+                        drop(all_output_read);
                     }
                 });
 
@@ -1560,8 +1871,26 @@ impl Pty {
         ))?;
         Ok(())
     }
-    pub fn reconfigure(&mut self, default_editor: Option<PathBuf>) {
+    pub fn reconfigure(
+        &mut self,
+        default_editor: Option<PathBuf>,
+        ssh_command: Option<PathBuf>,
+        default_shell: Option<PathBuf>,
+    ) {
         self.default_editor = default_editor;
+        self.ssh_command = ssh_command;
+        self.default_shell = default_shell;
+    }
+    fn new_reading_paused_flag(&mut self, terminal_id: u32) -> Arc<AtomicBool> {
+        let reading_paused = Arc::new(AtomicBool::new(false));
+        self.reading_paused
+            .insert(terminal_id, reading_paused.clone());
+        reading_paused
+    }
+    pub fn set_reading_paused(&mut self, terminal_id: u32, paused: bool) {
+        if let Some(reading_paused) = self.reading_paused.get(&terminal_id) {
+            reading_paused.store(paused, Ordering::SeqCst);
+        }
     }
 }
 
@@ -1576,6 +1905,57 @@ impl Drop for Pty {
     }
 }
 
+// panes that exit with a non-zero status trigger the on_pane_exit hook, which the server runs
+fn send_pane_exit_hook_to_server(
+    senders: &ThreadSenders,
+    pane_id: PaneId,
+    exit_status: Option<i32>,
+) {
+    if let PaneId::Terminal(terminal_id) = pane_id {
+        if exit_status.map(|status| status != 0).unwrap_or(false) {
+            let _ = senders.send_to_server(ServerInstruction::RunHook(
+                HookEvent::PaneExit,
+                HookContext {
+                    pane_id: Some(terminal_id),
+                    exit_code: exit_status,
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+// pipes the contents of the exited command pane to the cli waiting for them once all of its
+// output was read, then holds the pane or closes it
+fn handle_command_exit(
+    senders: &ThreadSenders,
+    pane_id: PaneId,
+    exit_status: Option<i32>,
+    command: RunCommand,
+    hold_on_close: bool,
+    close_on_success: bool,
+    output_pipe: Option<(&String, &channels::Receiver<()>)>,
+) {
+    if let Some((output_pipe_id, all_output_read_receiver)) = output_pipe {
+        // the contents are sent to the screen after the last bytes of the command so that they
+        // include all of its output
+        let _ = all_output_read_receiver.recv_timeout(ALL_PANE_OUTPUT_READ_TIMEOUT);
+        let _ = senders.send_to_screen(ScreenInstruction::PipePaneContentsToCli(
+            pane_id,
+            output_pipe_id.clone(),
+            exit_status,
+        ));
+    }
+    let _ = senders.send_to_screen(ScreenInstruction::CommandExited(pane_id, exit_status));
+    send_pane_exit_hook_to_server(senders, pane_id, exit_status);
+    let exited_successfully = exit_status == Some(0);
+    if hold_on_close && !(close_on_success && exited_successfully) {
+        let _ = senders.send_to_screen(ScreenInstruction::HoldPane(pane_id, exit_status, command));
+    } else {
+        let _ = senders.send_to_screen(ScreenInstruction::ClosePane(pane_id, None));
+    }
+}
+
 fn send_command_not_found_to_screen(
     senders: ThreadSenders,
     terminal_id: u32,
@@ -1606,3 +1986,7 @@ pub fn get_default_shell() -> PathBuf {
         "/bin/sh".to_string()
     }))
 }
+
+#[cfg(test)]
+#[path = "./unit/pty_tests.rs"]
+mod pty_tests;
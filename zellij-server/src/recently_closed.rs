@@ -0,0 +1,199 @@
+//! Panes and tabs closed by the user, kept around for a while so that they can be reopened.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::session_layout_metadata::SessionLayoutMetadata;
+use zellij_utils::{
+    data::FloatingPaneCoordinates,
+    input::layout::{FloatingPaneLayout, Run, SplitSize, TiledPaneLayout},
+    session_serialization::{
+        tab_layout_from_manifest, GlobalLayoutManifest, PaneLayoutManifest, TabLayoutManifest,
+    },
+};
+
+pub const DEFAULT_CLOSED_PANE_RETENTION: Duration = Duration::from_secs(300);
+
+// closed panes keep their whole scrollback, so we only keep so many of them around
+const MAX_RECENTLY_CLOSED: usize = 20;
+
+/// Where the closed panes were, so that they can be reopened there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClosedFrom {
+    Pane { tab_index: usize },
+    Tab { tab_position: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct ClosedPane {
+    pub tab_index: usize,
+    pub is_floating: bool,
+    pub pane: PaneLayoutManifest,
+}
+
+impl ClosedPane {
+    /// What to respawn the pane with, the default shell is started in its cwd if it was not
+    /// running a command
+    pub fn run(&self) -> Option<Run> {
+        let mut run = self.pane.run.clone();
+        if let Some(cwd) = &self.pane.cwd {
+            match run.as_mut() {
+                Some(run) => run.add_cwd(cwd),
+                None => run = Some(Run::Cwd(cwd.clone())),
+            }
+        }
+        run
+    }
+    pub fn floating_pane_coordinates(&self) -> Option<FloatingPaneCoordinates> {
+        if !self.is_floating {
+            return None;
+        }
+        let geom = &self.pane.geom;
+        Some(FloatingPaneCoordinates {
+            x: Some(SplitSize::Fixed(geom.x)),
+            y: Some(SplitSize::Fixed(geom.y)),
+            width: Some(SplitSize::Fixed(geom.cols.as_usize())),
+            height: Some(SplitSize::Fixed(geom.rows.as_usize())),
+            pinned: Some(geom.is_pinned),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ClosedTab {
+    pub position: usize,
+    pub name: String,
+    pub tab: TabLayoutManifest,
+}
+
+impl ClosedTab {
+    pub fn layout(&self) -> Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)> {
+        tab_layout_from_manifest(&self.tab)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ClosedItem {
+    Pane(ClosedPane),
+    Tab(ClosedTab),
+}
+
+impl ClosedItem {
+    /// Expects the metadata of a single tab, holding either the closed pane or all the panes of
+    /// the closed tab, once their cwds and commands were filled
+    pub fn new(
+        session_layout_metadata: SessionLayoutMetadata,
+        closed_from: ClosedFrom,
+    ) -> Option<Self> {
+        let global_layout_manifest: GlobalLayoutManifest = session_layout_metadata.into();
+        let global_cwd = global_layout_manifest.global_cwd;
+        let (name, mut tab) = global_layout_manifest.tabs.into_iter().next()?;
+        // the cwds of the panes are relative to the cwd they have in common, we make them whole
+        // again since they will be reopened in a session that can have moved on to other cwds
+        for pane in tab
+            .tiled_panes
+            .iter_mut()
+            .chain(tab.floating_panes.iter_mut())
+        {
+            pane.cwd = absolute_cwd(global_cwd.as_ref(), pane.cwd.take());
+        }
+        match closed_from {
+            ClosedFrom::Pane { tab_index } => {
+                let is_floating = tab.tiled_panes.is_empty();
+                let pane = tab
+                    .tiled_panes
+                    .into_iter()
+                    .chain(tab.floating_panes.into_iter())
+                    .next()?;
+                Some(ClosedItem::Pane(ClosedPane {
+                    tab_index,
+                    is_floating,
+                    pane,
+                }))
+            },
+            ClosedFrom::Tab { tab_position } => Some(ClosedItem::Tab(ClosedTab {
+                position: tab_position,
+                name,
+                tab,
+            })),
+        }
+    }
+}
+
+fn absolute_cwd(global_cwd: Option<&PathBuf>, cwd: Option<PathBuf>) -> Option<PathBuf> {
+    match (global_cwd, cwd) {
+        (Some(global_cwd), Some(cwd)) if !cwd.as_os_str().is_empty() => Some(global_cwd.join(cwd)),
+        (Some(global_cwd), _) => Some(global_cwd.clone()),
+        (None, cwd) => cwd,
+    }
+}
+
+/// The panes and tabs closed in this session, most recent last
+#[derive(Debug)]
+pub struct RecentlyClosed {
+    panes: VecDeque<(Instant, ClosedPane)>,
+    tabs: VecDeque<(Instant, ClosedTab)>,
+    retention: Duration,
+}
+
+impl RecentlyClosed {
+    pub fn new(retention: Duration) -> Self {
+        RecentlyClosed {
+            panes: VecDeque::new(),
+            tabs: VecDeque::new(),
+            retention,
+        }
+    }
+    /// A retention of 0 means closed panes and tabs are not kept
+    pub fn is_enabled(&self) -> bool {
+        !self.retention.is_zero()
+    }
+    pub fn push(&mut self, closed_item: ClosedItem) {
+        self.push_closed_at(closed_item, Instant::now());
+    }
+    pub fn pop_pane(&mut self) -> Option<ClosedPane> {
+        self.pop_pane_at(Instant::now())
+    }
+    pub fn pop_tab(&mut self) -> Option<ClosedTab> {
+        self.pop_tab_at(Instant::now())
+    }
+    fn push_closed_at(&mut self, closed_item: ClosedItem, closed_at: Instant) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.remove_expired(closed_at);
+        match closed_item {
+            ClosedItem::Pane(closed_pane) => {
+                self.panes.push_back((closed_at, closed_pane));
+                if self.panes.len() > MAX_RECENTLY_CLOSED {
+                    self.panes.pop_front();
+                }
+            },
+            ClosedItem::Tab(closed_tab) => {
+                self.tabs.push_back((closed_at, closed_tab));
+                if self.tabs.len() > MAX_RECENTLY_CLOSED {
+                    self.tabs.pop_front();
+                }
+            },
+        }
+    }
+    fn pop_pane_at(&mut self, now: Instant) -> Option<ClosedPane> {
+        self.remove_expired(now);
+        self.panes.pop_back().map(|(_, closed_pane)| closed_pane)
+    }
+    fn pop_tab_at(&mut self, now: Instant) -> Option<ClosedTab> {
+        self.remove_expired(now);
+        self.tabs.pop_back().map(|(_, closed_tab)| closed_tab)
+    }
+    fn remove_expired(&mut self, now: Instant) {
+        let retention = self.retention;
+        let is_retained = |closed_at: &Instant| now.duration_since(*closed_at) <= retention;
+        self.panes.retain(|(closed_at, _)| is_retained(closed_at));
+        self.tabs.retain(|(closed_at, _)| is_retained(closed_at));
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/recently_closed_tests.rs"]
+mod recently_closed_tests;
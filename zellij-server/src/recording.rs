@@ -0,0 +1,70 @@
+//! Recording of pane or session output to asciicast files.
+
+use crate::os_input_output::ServerOsApi;
+use std::io::{BufWriter, Write};
+use zellij_utils::{asciicast::AsciicastRecorder, errors::prelude::*, pane_size::Size};
+
+pub struct Recording {
+    recorder: AsciicastRecorder,
+    file: String,
+    // kept open and buffered, so that recording a busy pane does not write to disk on every chunk
+    // of its output
+    writer: BufWriter<Box<dyn Write + Send>>,
+    size: Size,
+}
+
+impl Recording {
+    /// Creates the recording file and writes its header, as well as the initial contents if any
+    pub fn start(
+        file: String,
+        size: Size,
+        title: Option<String>,
+        initial_contents: Option<String>,
+        os_api: &mut Box<dyn ServerOsApi>,
+    ) -> Result<Self> {
+        let err_context = || format!("failed to start recording to {file}");
+
+        let (mut recorder, header) =
+            AsciicastRecorder::new(size.cols, size.rows, title).with_context(err_context)?;
+        let mut contents = header;
+        if let Some(event) = initial_contents.and_then(|c| recorder.output(c.as_bytes())) {
+            contents.push_str(&event);
+        }
+        let mut writer =
+            BufWriter::new(os_api.create_file(file.clone()).with_context(err_context)?);
+        writer
+            .write_all(contents.as_bytes())
+            .with_context(err_context)?;
+        Ok(Recording {
+            recorder,
+            file,
+            writer,
+            size,
+        })
+    }
+    /// Records these bytes, preceded by a resize event if the size changed since the last write
+    pub fn record_output(&mut self, bytes: &[u8], current_size: Size) -> Result<()> {
+        let mut events = String::new();
+        if current_size != self.size {
+            self.size = current_size;
+            events.push_str(&self.recorder.resize(current_size.cols, current_size.rows));
+        }
+        if let Some(output_event) = self.recorder.output(bytes) {
+            events.push_str(&output_event);
+        }
+        if events.is_empty() {
+            return Ok(());
+        }
+        self.writer
+            .write_all(events.as_bytes())
+            .with_context(|| format!("failed to write to recording {}", self.file))
+    }
+    /// Writes what is left of the recording to its file
+    pub fn stop(mut self) -> Result<()> {
+        self.writer
+            .flush()
+            .with_context(|| format!("failed to write to recording {}", self.file))?;
+        log::info!("Stopped recording to {}", self.file);
+        Ok(())
+    }
+}
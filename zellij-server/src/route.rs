@@ -76,6 +76,23 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::SendKeys(keys) => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearScroll(client_id))
+                .with_context(err_context)?;
+            for key in keys {
+                let (raw_bytes, is_kitty_keyboard_protocol) =
+                    key.serialize_for_write().unwrap_or_default();
+                senders
+                    .send_to_screen(ScreenInstruction::WriteCharacter(
+                        Some(key),
+                        raw_bytes,
+                        is_kitty_keyboard_protocol,
+                        client_id,
+                    ))
+                    .with_context(err_context)?;
+            }
+        },
         Action::WriteChars(val) => {
             senders
                 .send_to_screen(ScreenInstruction::ClearScroll(client_id))
@@ -179,13 +196,17 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full))
                 .with_context(err_context)?;
         },
-        Action::DumpLayout => {
+        Action::DumpLayout(json) => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
                 _ => None,
             };
             senders
-                .send_to_screen(ScreenInstruction::DumpLayout(default_shell, client_id))
+                .send_to_screen(ScreenInstruction::DumpLayout(
+                    default_shell,
+                    json,
+                    client_id,
+                ))
                 .with_context(err_context)?;
         },
         Action::EditScrollback => {
@@ -365,6 +386,17 @@ pub(crate) fn route_action(
         },
         Action::NewFloatingPane(run_command, name, floating_pane_coordinates) => {
             let should_float = true;
+            if let Some(output_pipe_id) =
+                run_command.as_ref().and_then(|r| r.output_pipe_id.clone())
+            {
+                // so that the output of the command reaches the cli client waiting for it
+                senders
+                    .send_to_server(ServerInstruction::AssociatePipeWithClient {
+                        pipe_id: output_pipe_id,
+                        client_id,
+                    })
+                    .with_context(err_context)?;
+            }
             let run_cmd = run_command
                 .map(|cmd| TerminalAction::RunCommand(cmd.into()))
                 .or_else(|| default_shell.clone());
@@ -654,9 +686,9 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::NextSwapLayout(client_id))
                 .with_context(err_context)?;
         },
-        Action::QueryTabNames => {
+        Action::QueryTabNames(json) => {
             senders
-                .send_to_screen(ScreenInstruction::QueryTabNames(client_id))
+                .send_to_screen(ScreenInstruction::QueryTabNames(json, client_id))
                 .with_context(err_context)?;
         },
         Action::NewTiledPluginPane(run_plugin, name, skip_cache, cwd) => {
@@ -911,7 +943,7 @@ pub(crate) fn route_action(
                 log::error!("Message must have a name");
             }
         },
-        Action::ListClients => {
+        Action::ListClients(json) => {
             let default_shell = match default_shell {
                 Some(TerminalAction::RunCommand(run_command)) => Some(run_command.command),
                 _ => None,
@@ -919,6 +951,7 @@ pub(crate) fn route_action(
             senders
                 .send_to_screen(ScreenInstruction::ListClientsMetadata(
                     default_shell,
+                    json,
                     client_id,
                 ))
                 .with_context(err_context)?;
@@ -943,6 +976,289 @@ pub(crate) fn route_action(
                 )]))
                 .with_context(err_context)?;
         },
+        Action::StartRecording(file, whole_session) => {
+            senders
+                .send_to_screen(ScreenInstruction::StartRecording(
+                    file,
+                    whole_session,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::StopRecording(whole_session) => {
+            senders
+                .send_to_screen(ScreenInstruction::StopRecording(whole_session, client_id))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneLogging(pane_logging) => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneLogging(
+                    pane_logging,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ReopenClosedPane => {
+            senders
+                .send_to_screen(ScreenInstruction::ReopenClosedPane(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ReopenClosedTab => {
+            senders
+                .send_to_screen(ScreenInstruction::ReopenClosedTab(client_id))
+                .with_context(err_context)?;
+        },
+        Action::JoinPane(pane_id, placement) => {
+            senders
+                .send_to_screen(ScreenInstruction::JoinPane(
+                    pane_id.into(),
+                    placement,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SwapPanes(first_pane_id, second_pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::SwapPanes(
+                    first_pane_id.into(),
+                    second_pane_id.into(),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::RotatePanes(rotate_direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::RotatePanes(rotate_direction, client_id))
+                .with_context(err_context)?;
+        },
+        Action::MirrorLayout(mirror_direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::MirrorLayout(mirror_direction, client_id))
+                .with_context(err_context)?;
+        },
+        Action::EqualizePanes => {
+            senders
+                .send_to_screen(ScreenInstruction::EqualizePanes(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ResizeTo(size, dimension) => {
+            senders
+                .send_to_screen(ScreenInstruction::ResizeTo(size, dimension, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ToggleGoldenRatio => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleGoldenRatio(client_id))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneInSyncGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneInSyncGroup(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ClearSyncGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearSyncGroup(client_id))
+                .with_context(err_context)?;
+        },
+        Action::PaneJump(pane_jump_mode, all_tabs) => {
+            senders
+                .send_to_screen(ScreenInstruction::PaneJump(
+                    pane_jump_mode,
+                    all_tabs,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ToggleScratchpad(name) => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleScratchpad(name, client_id))
+                .with_context(err_context)?;
+        },
+        Action::GoToTabGroup(group) => {
+            senders
+                .send_to_screen(ScreenInstruction::GoToTabGroup(group, client_id))
+                .with_context(err_context)?;
+        },
+        Action::GoToNextTabInGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::SwitchTabInGroupNext(client_id))
+                .with_context(err_context)?;
+        },
+        Action::GoToPreviousTabInGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::SwitchTabInGroupPrev(client_id))
+                .with_context(err_context)?;
+        },
+        Action::MoveTabToGroup(group) => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTabToGroup(group, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ArrangeFloatingPanes(arrangement) => {
+            senders
+                .send_to_screen(ScreenInstruction::ArrangeFloatingPanes(
+                    arrangement,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ListTabs(json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListTabs(json, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ListPanes(json) => {
+            senders
+                .send_to_screen(ScreenInstruction::ListPanes(json, client_id))
+                .with_context(err_context)?;
+        },
+        Action::WriteToPaneId(bytes, pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::WriteToPaneId(bytes, pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::SendKeysToPaneId(keys, pane_id) => {
+            for key in keys {
+                let (raw_bytes, is_kitty_keyboard_protocol) =
+                    key.serialize_for_write().unwrap_or_default();
+                senders
+                    .send_to_screen(ScreenInstruction::WriteKeyToPaneId(
+                        key,
+                        raw_bytes,
+                        is_kitty_keyboard_protocol,
+                        pane_id.into(),
+                    ))
+                    .with_context(err_context)?;
+            }
+        },
+        Action::ResizePaneWithId(resize, direction, pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::ResizePaneWithId(
+                    ResizeStrategy::new(resize, direction),
+                    pane_id.into(),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::MovePaneWithPaneId(direction, pane_id) => {
+            let screen_instr = match direction {
+                Some(direction) => {
+                    ScreenInstruction::MovePaneWithPaneIdInDirection(pane_id.into(), direction)
+                },
+                None => ScreenInstruction::MovePaneWithPaneId(pane_id.into()),
+            };
+            senders
+                .send_to_screen(screen_instr)
+                .with_context(err_context)?;
+        },
+        Action::ClearScreenForPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearScreenForPaneId(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::DumpScreenForPaneId(file, full, pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::DumpScreenForPaneId(
+                    file,
+                    pane_id.into(),
+                    full,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::EditScrollbackForPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::EditScrollbackForPaneWithId(
+                    pane_id.into(),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ScrollUpInPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollUpInPaneId(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::ScrollDownInPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollDownInPaneId(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::ScrollToTopInPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToTopInPaneId(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::ScrollToBottomInPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToBottomInPaneId(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::PageScrollUpInPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::PageScrollUpInPaneId(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::PageScrollDownInPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::PageScrollDownInPaneId(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneIdFullscreen(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneIdFullscreen(pane_id.into()))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneEmbedOrFloatingForPaneId(pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneEmbedOrEjectForPaneId(
+                    pane_id.into(),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ClosePaneWithId(pane_id) => {
+            let pane_id: PaneId = pane_id.into();
+            senders
+                .send_to_screen(ScreenInstruction::ClosePane(pane_id, None))
+                .with_context(err_context)?;
+            senders
+                .send_to_pty(PtyInstruction::ClosePane(pane_id))
+                .with_context(err_context)?;
+        },
+        Action::RenamePaneWithId(pane_id, name_bytes) => {
+            senders
+                .send_to_screen(ScreenInstruction::RenamePane(pane_id.into(), name_bytes))
+                .with_context(err_context)?;
+        },
+        Action::WaitFor {
+            pipe_id, condition, ..
+        } => {
+            // so that the cli client waiting on this pipe is the one unblocked
+            senders
+                .send_to_server(ServerInstruction::AssociatePipeWithClient {
+                    pipe_id: pipe_id.clone(),
+                    client_id,
+                })
+                .with_context(err_context)?;
+            senders
+                .send_to_screen(ScreenInstruction::WaitFor(condition, pipe_id, client_id))
+                .with_context(err_context)?;
+        },
+        Action::SendWaitForSignal(name) => {
+            senders
+                .send_to_screen(ScreenInstruction::SendWaitForSignal(name))
+                .with_context(err_context)?;
+        },
+        Action::CloseTabAtPosition(tab_position) => {
+            senders
+                .send_to_screen(ScreenInstruction::CloseTabAtPosition(tab_position))
+                .with_context(err_context)?;
+        },
+        Action::RenameTabAtPosition(tab_position, name_bytes) => {
+            senders
+                .send_to_screen(ScreenInstruction::RenameTabAtPosition(
+                    tab_position,
+                    name_bytes,
+                ))
+                .with_context(err_context)?;
+        },
     }
     Ok(should_break)
 }
@@ -1064,6 +1380,15 @@ pub(crate) fn route_thread_main(
                                 .to_anyhow()
                                 .with_context(err_context)?
                                 .set_client_size(client_id, new_size);
+                            // sent first so that tabs are resized straight to their clients' size
+                            rlocked_sessions
+                                .as_ref()
+                                .context("couldn't get reference to read-locked session")?
+                                .senders
+                                .send_to_screen(ScreenInstruction::ClientTerminalResize(
+                                    client_id, new_size,
+                                ))
+                                .with_context(err_context)?;
                             session_state
                                 .read()
                                 .to_anyhow()
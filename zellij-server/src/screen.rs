@@ -9,28 +9,35 @@ use std::time::Duration;
 
 use log::{debug, warn};
 use zellij_utils::data::{
-    Direction, KeyWithModifier, PaneManifest, PluginPermission, Resize, ResizeStrategy,
-    SessionInfo, Styling,
+    Direction, FloatingPanesArrangement, KeyWithModifier, LayoutInfo, MirrorDirection,
+    PaneManifest, PluginPermission, Resize, ResizeStrategy, RotateDirection, SessionInfo, Styling,
 };
 use zellij_utils::errors::prelude::*;
+use zellij_utils::input::actions::{
+    JoinPanePlacement, PaneDimension, PaneJumpMode, WaitForCondition,
+};
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::config::Config;
+use zellij_utils::input::hooks::{HookContext, HookEvent, Hooks};
 use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::mouse::MouseEvent;
 use zellij_utils::input::options::Clipboard;
+use zellij_utils::input::scratchpad::Scratchpads;
 use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::regex::Regex;
 use zellij_utils::{
     consts::{session_info_folder_for_session, ZELLIJ_SOCK_DIR},
     envs::set_session_name,
     input::command::TerminalAction,
     input::layout::{
-        FloatingPaneLayout, Layout, Run, RunPluginOrAlias, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        FloatingPaneLayout, Layout, PaneLogging, Run, RunPluginOrAlias, SplitSize,
+        SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
     position::Position,
 };
 
 use crate::background_jobs::BackgroundJob;
+use crate::hooks::run_hooks;
 use crate::os_input_output::ResizeCache;
 use crate::panes::alacritty_functions::xparse_color;
 use crate::panes::terminal_character::AnsiCode;
@@ -42,12 +49,20 @@ use crate::{
     panes::PaneId,
     plugins::{PluginId, PluginInstruction, PluginRenderAsset},
     pty::{get_default_shell, ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
-    tab::{SuppressedPanes, Tab},
+    recently_closed::{
+        ClosedFrom, ClosedItem, ClosedPane, RecentlyClosed, DEFAULT_CLOSED_PANE_RETENTION,
+    },
+    recording::Recording,
+    tab::{Pane, RenderThrottleConfig, SuppressedPanes, Tab},
     thread_bus::Bus,
     ui::{
         loading_indication::LoadingIndication,
-        overlay::{Overlay, OverlayWindow},
+        overlay::{
+            pane_jump::{PaneJump, PaneJumpPosition},
+            Overlay, OverlayType, OverlayWindow, Overlayable,
+        },
     },
+    wait_for::{CliWaiters, MetCondition},
     ClientId, ServerInstruction,
 };
 use zellij_utils::{
@@ -60,6 +75,9 @@ use zellij_utils::{
     ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
 };
 
+// what `zellij run --popup` exits with when its command was killed by a signal
+const POPUP_KILLED_EXIT_STATUS: i32 = 1;
+
 /// Get the active tab and call a closure on it
 ///
 /// If no active tab can be found, an error is logged instead.
@@ -184,8 +202,8 @@ pub enum ScreenInstruction {
     Exit,
     ClearScreen(ClientId),
     DumpScreen(String, ClientId, bool),
-    DumpLayout(Option<PathBuf>, ClientId), // PathBuf is the default configured
-    // shell
+    DumpLayout(Option<PathBuf>, bool, ClientId), // PathBuf is the default configured
+    // shell, bool -> json
     DumpLayoutToPlugin(PluginId),
     EditScrollback(ClientId),
     ScrollUp(ClientId),
@@ -273,7 +291,7 @@ pub enum ScreenInstruction {
     ClearPaneFrameColorOverride(Vec<PaneId>),
     PreviousSwapLayout(ClientId),
     NextSwapLayout(ClientId),
-    QueryTabNames(ClientId),
+    QueryTabNames(bool, ClientId), // bool -> json
     NewTiledPluginPane(
         RunPluginOrAlias,
         Option<String>,
@@ -353,7 +371,8 @@ pub enum ScreenInstruction {
     ),
     DumpLayoutToHd,
     RenameSession(String, ClientId), // String -> new name
-    ListClientsMetadata(Option<PathBuf>, ClientId), // Option<PathBuf> - default shell
+    ListClientsMetadata(Option<PathBuf>, bool, ClientId), // Option<PathBuf> - default shell,
+    // bool -> json
     Reconfigure {
         client_id: ClientId,
         keybinds: Keybinds,
@@ -370,6 +389,8 @@ pub enum ScreenInstruction {
         hide_session_name: bool,
         stacked_resize: bool,
         default_editor: Option<PathBuf>,
+        scratchpads: Scratchpads,
+        hooks: Hooks,
     },
     RerunCommandPane(u32), // u32 - terminal pane id
     ResizePaneWithId(ResizeStrategy, PaneId),
@@ -405,6 +426,42 @@ pub enum ScreenInstruction {
     SetFloatingPanePinned(PaneId, bool),
     StackPanes(Vec<PaneId>),
     ChangeFloatingPanesCoordinates(Vec<(PaneId, FloatingPaneCoordinates)>),
+    StartRecording(PathBuf, bool, ClientId), // bool -> record the whole session
+    StopRecording(bool, ClientId),           // bool -> stop recording the whole session
+    TogglePaneLogging(PaneLogging, ClientId),
+    ClientTerminalResize(ClientId, Size),
+    ReopenClosedPane(ClientId),
+    ReopenClosedTab(ClientId),
+    AddRecentlyClosed(ClosedItem),
+    ClosedPaneReopened(PaneId, HoldForCommand, ClosedPane, ClientId),
+    JoinPane(PaneId, JoinPanePlacement, ClientId),
+    SwapPanes(PaneId, PaneId),
+    RotatePanes(RotateDirection, ClientId),
+    MirrorLayout(MirrorDirection, ClientId),
+    EqualizePanes(ClientId),
+    ResizeTo(SplitSize, PaneDimension, ClientId),
+    ToggleGoldenRatio(ClientId),
+    TogglePaneInSyncGroup(ClientId),
+    ClearSyncGroup(ClientId),
+    PaneJump(PaneJumpMode, bool, ClientId), // bool -> all tabs
+    ToggleScratchpad(String, ClientId),     // String -> scratchpad name
+    ScratchpadSpawned(PaneId, HoldForCommand, String, ClientId), // String -> scratchpad name
+    ScratchpadFailedToSpawn(String),        // String -> scratchpad name
+    GoToTabGroup(String, ClientId),         // String -> group name
+    SwitchTabInGroupNext(ClientId),
+    SwitchTabInGroupPrev(ClientId),
+    MoveTabToGroup(Option<String>, ClientId), // None -> out of its group
+    ArrangeFloatingPanes(FloatingPanesArrangement, ClientId),
+    PipePaneContentsToCli(PaneId, String, Option<i32>), // String -> cli pipe id, i32 -> exit status
+    ListTabs(bool, ClientId),                           // bool -> json
+    ListPanes(bool, ClientId),                          // bool -> json
+    DumpScreenForPaneId(String, PaneId, bool),          // bool -> full scrollback
+    CloseTabAtPosition(usize),
+    RenameTabAtPosition(usize, Vec<u8>),
+    WriteKeyToPaneId(KeyWithModifier, Vec<u8>, bool, PaneId), // bool -> bytes are kitty
+    WaitFor(WaitForCondition, String, ClientId),              // String -> cli pipe id
+    SendWaitForSignal(String),
+    CommandExited(PaneId, Option<i32>), // i32 -> exit status
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -615,6 +672,44 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ChangeFloatingPanesCoordinates(..) => {
                 ScreenContext::ChangeFloatingPanesCoordinates
             },
+            ScreenInstruction::StartRecording(..) => ScreenContext::StartRecording,
+            ScreenInstruction::StopRecording(..) => ScreenContext::StopRecording,
+            ScreenInstruction::TogglePaneLogging(..) => ScreenContext::TogglePaneLogging,
+            ScreenInstruction::ClientTerminalResize(..) => ScreenContext::ClientTerminalResize,
+            ScreenInstruction::ReopenClosedPane(..) => ScreenContext::ReopenClosedPane,
+            ScreenInstruction::ReopenClosedTab(..) => ScreenContext::ReopenClosedTab,
+            ScreenInstruction::AddRecentlyClosed(..) => ScreenContext::AddRecentlyClosed,
+            ScreenInstruction::ClosedPaneReopened(..) => ScreenContext::ClosedPaneReopened,
+            ScreenInstruction::JoinPane(..) => ScreenContext::JoinPane,
+            ScreenInstruction::SwapPanes(..) => ScreenContext::SwapPanes,
+            ScreenInstruction::RotatePanes(..) => ScreenContext::RotatePanes,
+            ScreenInstruction::MirrorLayout(..) => ScreenContext::MirrorLayout,
+            ScreenInstruction::EqualizePanes(..) => ScreenContext::EqualizePanes,
+            ScreenInstruction::ResizeTo(..) => ScreenContext::ResizeTo,
+            ScreenInstruction::ToggleGoldenRatio(..) => ScreenContext::ToggleGoldenRatio,
+            ScreenInstruction::TogglePaneInSyncGroup(..) => ScreenContext::TogglePaneInSyncGroup,
+            ScreenInstruction::ClearSyncGroup(..) => ScreenContext::ClearSyncGroup,
+            ScreenInstruction::PaneJump(..) => ScreenContext::PaneJump,
+            ScreenInstruction::ToggleScratchpad(..) => ScreenContext::ToggleScratchpad,
+            ScreenInstruction::ScratchpadSpawned(..) => ScreenContext::ScratchpadSpawned,
+            ScreenInstruction::ScratchpadFailedToSpawn(..) => {
+                ScreenContext::ScratchpadFailedToSpawn
+            },
+            ScreenInstruction::GoToTabGroup(..) => ScreenContext::GoToTabGroup,
+            ScreenInstruction::SwitchTabInGroupNext(..) => ScreenContext::SwitchTabInGroupNext,
+            ScreenInstruction::SwitchTabInGroupPrev(..) => ScreenContext::SwitchTabInGroupPrev,
+            ScreenInstruction::MoveTabToGroup(..) => ScreenContext::MoveTabToGroup,
+            ScreenInstruction::ArrangeFloatingPanes(..) => ScreenContext::ArrangeFloatingPanes,
+            ScreenInstruction::PipePaneContentsToCli(..) => ScreenContext::PipePaneContentsToCli,
+            ScreenInstruction::ListTabs(..) => ScreenContext::ListTabs,
+            ScreenInstruction::ListPanes(..) => ScreenContext::ListPanes,
+            ScreenInstruction::DumpScreenForPaneId(..) => ScreenContext::DumpScreenForPaneId,
+            ScreenInstruction::CloseTabAtPosition(..) => ScreenContext::CloseTabAtPosition,
+            ScreenInstruction::RenameTabAtPosition(..) => ScreenContext::RenameTabAtPosition,
+            ScreenInstruction::WriteKeyToPaneId(..) => ScreenContext::WriteKeyToPaneId,
+            ScreenInstruction::WaitFor(..) => ScreenContext::WaitFor,
+            ScreenInstruction::SendWaitForSignal(..) => ScreenContext::SendWaitForSignal,
+            ScreenInstruction::CommandExited(..) => ScreenContext::CommandExited,
         }
     }
 }
@@ -680,6 +775,7 @@ pub(crate) struct Screen {
     session_serialization: bool,
     serialize_pane_viewport: bool,
     scrollback_lines_to_serialize: Option<usize>,
+    redact_serialized_env: bool,
     session_is_mirrored: bool,
     copy_options: CopyOptions,
     debug: bool,
@@ -696,6 +792,18 @@ pub(crate) struct Screen {
     default_layout_name: Option<String>,
     explicitly_disable_kitty_keyboard_protocol: bool,
     default_editor: Option<PathBuf>,
+    render_throttle_config: RenderThrottleConfig,
+    pane_recordings: HashMap<u32, Recording>, // u32 - terminal_id
+    session_recording: Option<(ClientId, Recording)>, // the client whose output is recorded
+    /// The terminal sizes of the connected clients, unless the session is mirrored each tab is
+    /// sized to the smallest of the clients viewing it
+    client_sizes: HashMap<ClientId, Size>,
+    recently_closed: RecentlyClosed,
+    scratchpads: Scratchpads,
+    scratchpad_panes: HashMap<String, PaneId>, // String is the scratchpad name
+    pending_scratchpads: HashSet<String>,      // scratchpads being opened, by name
+    cli_waiters: CliWaiters,
+    hooks: Hooks,
 }
 
 impl Screen {
@@ -716,12 +824,17 @@ impl Screen {
         session_serialization: bool,
         serialize_pane_viewport: bool,
         scrollback_lines_to_serialize: Option<usize>,
+        redact_serialized_env: bool,
         styled_underlines: bool,
         arrow_fonts: bool,
         layout_dir: Option<PathBuf>,
         explicitly_disable_kitty_keyboard_protocol: bool,
         stacked_resize: bool,
         default_editor: Option<PathBuf>,
+        render_throttle_config: RenderThrottleConfig,
+        closed_pane_retention: Duration,
+        scratchpads: Scratchpads,
+        hooks: Hooks,
     ) -> Self {
         let session_name = mode_info.session_name.clone().unwrap_or_default();
         let session_info = SessionInfo::new(session_name.clone());
@@ -759,12 +872,23 @@ impl Screen {
             session_serialization,
             serialize_pane_viewport,
             scrollback_lines_to_serialize,
+            redact_serialized_env,
             styled_underlines,
             arrow_fonts,
             resurrectable_sessions,
             layout_dir,
             explicitly_disable_kitty_keyboard_protocol,
             default_editor,
+            render_throttle_config,
+            pane_recordings: HashMap::new(),
+            session_recording: None,
+            client_sizes: HashMap::new(),
+            recently_closed: RecentlyClosed::new(closed_pane_retention),
+            scratchpads,
+            scratchpad_panes: HashMap::new(),
+            pending_scratchpads: HashSet::new(),
+            cli_waiters: CliWaiters::default(),
+            hooks,
         }
     }
 
@@ -1071,11 +1195,353 @@ impl Screen {
         self.switch_active_tab_name(name, client_id)
     }
 
+    /// Goes to the tab of this group the client was on last, or to the first tab of the group
+    pub fn go_to_tab_group(&mut self, group: String, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to go to tab group {group:?}");
+        let Some(client_id) = self.client_id(client_id) else {
+            return Ok(());
+        };
+        let active_tab = self.get_active_tab(client_id).with_context(err_context)?;
+        if active_tab.group.as_ref() == Some(&group) {
+            return Ok(());
+        }
+        let is_in_group = |tab: &Tab| tab.group.as_ref() == Some(&group);
+        let last_visited_tab_position = self.tab_history.get(&client_id).and_then(|history| {
+            history
+                .iter()
+                .rev()
+                .filter_map(|tab_index| self.tabs.get(tab_index))
+                .find(|tab| is_in_group(tab))
+                .map(|tab| tab.position)
+        });
+        let first_tab_position = self
+            .tabs
+            .values()
+            .filter(|tab| is_in_group(tab))
+            .map(|tab| tab.position)
+            .min();
+        match last_visited_tab_position.or(first_tab_position) {
+            Some(tab_position) => self
+                .switch_active_tab(tab_position, None, true, client_id)
+                .with_context(err_context),
+            None => {
+                log::error!("No tab group named {:?}", group);
+                Ok(())
+            },
+        }
+    }
+
+    /// Cycles through the tabs in the group of the active tab, tabs that are not in a group are
+    /// cycled through together
+    pub fn switch_tab_in_group(&mut self, forward: bool, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to switch tab in group for client {client_id}");
+        let Some(client_id) = self.client_id(client_id) else {
+            return Ok(());
+        };
+        let active_tab = self.get_active_tab(client_id).with_context(err_context)?;
+        let active_tab_position = active_tab.position;
+        let mut tab_positions_in_group: Vec<usize> = self
+            .tabs
+            .values()
+            .filter(|tab| tab.group == active_tab.group)
+            .map(|tab| tab.position)
+            .collect();
+        tab_positions_in_group.sort_unstable();
+        let tab_count = tab_positions_in_group.len();
+        let Some(index_in_group) = tab_positions_in_group
+            .iter()
+            .position(|position| *position == active_tab_position)
+        else {
+            return Ok(());
+        };
+        let new_index_in_group = if forward {
+            (index_in_group + 1) % tab_count
+        } else {
+            (index_in_group + tab_count - 1) % tab_count
+        };
+        self.switch_active_tab(
+            tab_positions_in_group[new_index_in_group],
+            None,
+            true,
+            client_id,
+        )
+        .with_context(err_context)
+    }
+
+    pub fn move_active_tab_to_group(
+        &mut self,
+        group: Option<String>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to move tab to group {group:?}");
+        let Some(client_id) = self.client_id(client_id) else {
+            return Ok(());
+        };
+        let active_tab = self
+            .get_active_tab_mut(client_id)
+            .with_context(err_context)?;
+        active_tab.group = group.clone().filter(|group| !group.is_empty());
+        self.log_and_report_session_state()
+            .with_context(err_context)
+    }
+    pub fn pipe_pane_contents_to_cli(
+        &mut self,
+        pane_id: PaneId,
+        pipe_id: String,
+        exit_status: Option<i32>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to pipe contents of pane {pane_id:?} to {pipe_id}");
+        // a failed command is left held on screen for the user to inspect, so only the output of a
+        // successful one is sent
+        if exit_status == Some(0) {
+            let contents = self
+                .tabs
+                .values()
+                .find_map(|tab| tab.get_pane_with_id(pane_id))
+                .map(|pane| pane.dump_screen(true));
+            match contents {
+                Some(contents) if !contents.trim_end().is_empty() => {
+                    self.bus
+                        .senders
+                        .send_to_server(ServerInstruction::CliPipeOutput(
+                            pipe_id.clone(),
+                            format!("{}\n", contents.trim_end()),
+                        ))
+                        .with_context(err_context)?;
+                },
+                Some(_) => {},
+                None => log::error!("Failed to find pane {:?} to pipe its contents", pane_id),
+            }
+        }
+        // so that the cli client exits with the status of the command
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::CliPipeExitStatus(
+                pipe_id.clone(),
+                exit_status.unwrap_or(POPUP_KILLED_EXIT_STATUS),
+            ))
+            .with_context(err_context)?;
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::UnblockCliPipeInput(pipe_id.clone()))
+            .with_context(err_context)
+    }
+
+    pub fn wait_for(
+        &mut self,
+        condition: WaitForCondition,
+        pipe_id: String,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to wait for {condition:?}");
+        let mut met_condition = None;
+        let mut error = None;
+        match condition.clone() {
+            WaitForCondition::Signal(name) => {
+                met_condition = self.cli_waiters.wait_for_signal(name, pipe_id, client_id);
+            },
+            WaitForCondition::PaneExit(pane_id) => {
+                let pane_id = pane_id.into();
+                match self.get_pane_with_id(pane_id) {
+                    Some(pane) if pane.exited() => {
+                        met_condition = Some((pipe_id, pane.exit_status().unwrap_or(1)));
+                    },
+                    Some(_) => self
+                        .cli_waiters
+                        .wait_for_pane_exit(pane_id, pipe_id, client_id),
+                    None => error = Some(format!("Pane {:?} not found", pane_id)),
+                }
+            },
+            WaitForCondition::PaneClose(pane_id) => {
+                let pane_id = pane_id.into();
+                match self.get_pane_with_id(pane_id) {
+                    Some(_) => self
+                        .cli_waiters
+                        .wait_for_pane_close(pane_id, pipe_id, client_id),
+                    None => met_condition = Some((pipe_id, 0)),
+                }
+            },
+            WaitForCondition::Pattern(pattern, pane_id) => {
+                let pane_id = pane_id.into();
+                match (Regex::new(&pattern), self.get_pane_with_id(pane_id)) {
+                    (Err(e), _) => error = Some(format!("Invalid pattern: {}", e)),
+                    (Ok(_), None) => error = Some(format!("Pane {:?} not found", pane_id)),
+                    (Ok(pattern), Some(pane)) if pattern.is_match(&pane.dump_screen(false)) => {
+                        met_condition = Some((pipe_id, 0));
+                    },
+                    (Ok(pattern), Some(_)) => self
+                        .cli_waiters
+                        .wait_for_pattern(pane_id, pattern, pipe_id, client_id),
+                }
+            },
+        }
+        if let Some(error) = error {
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::LogError(vec![error], client_id))
+                .with_context(err_context)?;
+        }
+        self.unblock_cli_waiters(met_condition.into_iter().collect())
+            .with_context(err_context)
+    }
+    fn unblock_cli_waiters(&self, met_conditions: Vec<MetCondition>) -> Result<()> {
+        let err_context = || "failed to unblock cli clients waiting for a condition";
+        for (pipe_id, exit_status) in met_conditions {
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::CliPipeExitStatus(
+                    pipe_id.clone(),
+                    exit_status,
+                ))
+                .with_context(err_context)?;
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::UnblockCliPipeInput(pipe_id))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    fn unblock_cli_waiters_for_pattern_in(&mut self, pane_id: PaneId) -> Result<()> {
+        if !self.cli_waiters.is_waiting_for_pattern_in(pane_id) {
+            return Ok(());
+        }
+        let pane_contents = self
+            .get_pane_with_id(pane_id)
+            .map(|pane| pane.dump_screen(false))
+            .unwrap_or_default();
+        let met_conditions = self.cli_waiters.pattern_appeared(pane_id, &pane_contents);
+        self.unblock_cli_waiters(met_conditions)
+    }
+    fn unblock_cli_waiters_for_closed_panes(&mut self) -> Result<()> {
+        if !self.cli_waiters.is_waiting_for_panes() {
+            return Ok(());
+        }
+        let all_pane_ids: HashSet<PaneId> = self
+            .tabs
+            .values()
+            .flat_map(|tab| tab.get_all_pane_ids())
+            .collect();
+        let met_conditions = self
+            .cli_waiters
+            .panes_closed(|pane_id| all_pane_ids.contains(&pane_id));
+        self.unblock_cli_waiters(met_conditions)
+    }
+    fn get_pane_with_id(&self, pane_id: PaneId) -> Option<&dyn Pane> {
+        self.tabs
+            .values()
+            .find_map(|tab| tab.get_pane_with_id(pane_id))
+    }
+
+    pub fn list_tabs(&mut self, json: bool, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to list tabs for client {client_id}");
+        let tab_infos = self.tab_infos_for_client(self.connected_client_id_or_first(client_id));
+        let output = if json {
+            serde_json::to_string(&tab_infos).with_context(err_context)?
+        } else {
+            let mut lines = vec![String::from("TAB_POSITION ACTIVE TAB_NAME")];
+            for tab_info in tab_infos {
+                // 12 - TAB_POSITION, 6 - ACTIVE
+                lines.push(format!(
+                    "{0: <12} {1: <6} {2}",
+                    tab_info.position, tab_info.active, tab_info.name
+                ));
+            }
+            lines.join("\n")
+        };
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::Log(vec![output], client_id))
+            .with_context(err_context)
+    }
+
+    pub fn list_panes(&mut self, json: bool, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to list panes for client {client_id}");
+        let pane_manifest = self.pane_manifest();
+        let output = if json {
+            serde_json::to_string(&pane_manifest).with_context(err_context)?
+        } else {
+            let mut lines = vec![String::from(
+                "TAB_POSITION ZELLIJ_PANE_ID FLOATING FOCUSED TITLE",
+            )];
+            let mut tab_positions: Vec<&usize> = pane_manifest.panes.keys().collect();
+            tab_positions.sort();
+            for tab_position in tab_positions {
+                for pane_info in &pane_manifest.panes[tab_position] {
+                    let pane_id = if pane_info.is_plugin {
+                        format!("plugin_{}", pane_info.id)
+                    } else {
+                        format!("terminal_{}", pane_info.id)
+                    };
+                    // 12 - TAB_POSITION, 14 - ZELLIJ_PANE_ID, 8 - FLOATING, 7 - FOCUSED
+                    lines.push(format!(
+                        "{0: <12} {1: <14} {2: <8} {3: <7} {4}",
+                        tab_position,
+                        pane_id,
+                        pane_info.is_floating,
+                        pane_info.is_focused,
+                        pane_info.title
+                    ));
+                }
+            }
+            lines.join("\n")
+        };
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::Log(vec![output], client_id))
+            .with_context(err_context)
+    }
+
+    pub fn dump_session_info_as_json(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to dump session info for client {client_id}");
+        let session_info = SessionInfo {
+            name: self.session_name.clone(),
+            tabs: self.tab_infos_for_client(self.connected_client_id_or_first(client_id)),
+            panes: self.pane_manifest(),
+            connected_clients: self.active_tab_indices.keys().len(),
+            is_current_session: true,
+            available_layouts: self.available_layouts(),
+            plugins: Default::default(),
+        };
+        let output = serde_json::to_string(&session_info).with_context(err_context)?;
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::Log(vec![output], client_id))
+            .with_context(err_context)
+    }
+
+    // cli clients are not attached to a tab, so their queries are answered from the point of view
+    // of the first connected client
+    fn connected_client_id_or_first(&self, client_id: ClientId) -> ClientId {
+        if self.active_tab_indices.contains_key(&client_id) {
+            client_id
+        } else {
+            self.get_first_client_id().unwrap_or(client_id)
+        }
+    }
+
+    // tabs are keyed by their index, which stays the same when the tabs before them are closed -
+    // unlike their position, which is what users see
+    fn tab_index_at_position(&self, tab_position: usize) -> Option<usize> {
+        self.tabs
+            .values()
+            .find(|t| t.position == tab_position)
+            .map(|t| t.index)
+    }
+
     fn close_tab_at_index(&mut self, tab_index: usize) -> Result<()> {
         let err_context = || format!("failed to close tab at index {tab_index:?}");
 
         let mut tab_to_close = self.tabs.remove(&tab_index).with_context(err_context)?;
         let mut pane_ids = tab_to_close.get_all_pane_ids();
+        run_hooks(
+            &self.hooks,
+            HookEvent::TabClose,
+            HookContext {
+                tab_index: Some(tab_to_close.position),
+                tab_name: Some(tab_to_close.name.clone()),
+                ..Default::default()
+            },
+        );
 
         // here we extract the suppressed panes (these are background panes that don't care which
         // tab they are in, and in the future we should probably make them global to screen rather
@@ -1093,6 +1559,9 @@ impl Screen {
                 .collect(),
         ));
 
+        for pane_id in &pane_ids {
+            self.stop_recording(*pane_id);
+        }
         // below we don't check the result of sending the CloseTab instruction to the pty thread
         // because this might be happening when the app is closing, at which point the pty thread
         // has already closed and this would result in an error
@@ -1145,6 +1614,9 @@ impl Screen {
                     .active_tab_indices
                     .get(&client_id)
                     .with_context(err_context)?;
+                self.remember_closed_tab(active_tab_index)
+                    .with_context(err_context)
+                    .non_fatal();
                 self.close_tab_at_index(active_tab_index)
                     .with_context(err_context)
             },
@@ -1152,15 +1624,231 @@ impl Screen {
         }
     }
 
+    /// Keeps the pane this client is about to close around so that it can be reopened, this has
+    /// to happen before it is closed for pty to still be able to tell its cwd
+    fn remember_focused_pane(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to remember focused pane of client {client_id}");
+        if !self.recently_closed.is_enabled() {
+            return Ok(());
+        }
+        let Some(client_id) = self.client_id(client_id) else {
+            return Ok(());
+        };
+        let tab = self.get_active_tab(client_id).with_context(err_context)?;
+        let Some(pane) = tab.get_active_pane(client_id) else {
+            return Ok(());
+        };
+        let pane_id = pane.pid();
+        if let PaneId::Plugin(..) = pane_id {
+            // plugins can be reopened through their keybindings or the plugin manager
+            return Ok(());
+        }
+        let is_floating = tab.get_floating_panes().any(|(id, _)| *id == pane_id);
+        let pane_layout_metadata = closed_pane_layout_metadata(pane_id, pane, is_floating);
+        let (tiled_panes, floating_panes) = if is_floating {
+            (vec![], vec![pane_layout_metadata])
+        } else {
+            (vec![pane_layout_metadata], vec![])
+        };
+        self.remember_closed_panes(
+            tab.name.clone(),
+            false,
+            tab.group.clone(),
+            tiled_panes,
+            floating_panes,
+            ClosedFrom::Pane {
+                tab_index: tab.index,
+            },
+        )
+        .with_context(err_context)
+    }
+
+    fn remember_closed_tab(&self, tab_index: usize) -> Result<()> {
+        let err_context = || format!("failed to remember tab {tab_index}");
+        if !self.recently_closed.is_enabled() {
+            return Ok(());
+        }
+        let tab = self.tabs.get(&tab_index).with_context(err_context)?;
+        let tiled_panes = tab
+            .get_tiled_panes()
+            .map(|(pane_id, p)| closed_pane_layout_metadata(*pane_id, p.as_ref(), false))
+            .collect();
+        let floating_panes = tab
+            .get_floating_panes()
+            .map(|(pane_id, p)| closed_pane_layout_metadata(*pane_id, p.as_ref(), true))
+            .collect();
+        self.remember_closed_panes(
+            tab.name.clone(),
+            !tab.are_floating_panes_visible(),
+            tab.group.clone(),
+            tiled_panes,
+            floating_panes,
+            ClosedFrom::Tab {
+                tab_position: tab.position,
+            },
+        )
+        .with_context(err_context)
+    }
+
+    // pty fills in the cwds and commands of the panes before they make it to the recently closed
+    // panes and tabs
+    fn remember_closed_panes(
+        &self,
+        tab_name: String,
+        hide_floating_panes: bool,
+        tab_group: Option<String>,
+        tiled_panes: Vec<PaneLayoutMetadata>,
+        floating_panes: Vec<PaneLayoutMetadata>,
+        closed_from: ClosedFrom,
+    ) -> Result<()> {
+        let mut session_layout_metadata = SessionLayoutMetadata::new(self.default_layout.clone());
+        session_layout_metadata.add_tab(
+            tab_name,
+            true,
+            hide_floating_panes,
+            tab_group,
+            tiled_panes,
+            floating_panes,
+        );
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::FillClosedPanesMetadata(
+                session_layout_metadata,
+                closed_from,
+            ))
+            .context("failed to remember closed panes")
+    }
+
+    /// Returns false if there is no recently closed pane to reopen
+    pub fn reopen_closed_pane(&mut self, client_id: ClientId) -> Result<bool> {
+        let err_context = || format!("failed to reopen closed pane for client {client_id}");
+        let Some(client_id) = self.client_id(client_id) else {
+            return Ok(false);
+        };
+        let Some(mut closed_pane) = self.recently_closed.pop_pane() else {
+            return Ok(false);
+        };
+        if !self.tabs.contains_key(&closed_pane.tab_index) {
+            // the tab it was closed in is gone, so we reopen it where the user is
+            closed_pane.tab_index = self
+                .get_active_tab(client_id)
+                .with_context(err_context)?
+                .index;
+        }
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::ReopenClosedPane(closed_pane, client_id))
+            .with_context(err_context)?;
+        Ok(true)
+    }
+
+    /// Places the respawned pane back in its tab, along with its scrollback
+    pub fn add_reopened_pane(
+        &mut self,
+        pid: PaneId,
+        hold_for_command: HoldForCommand,
+        closed_pane: ClosedPane,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to add reopened pane {pid:?}");
+
+        let client_id = self.client_id(client_id);
+        let tab_index = match (self.tabs.get(&closed_pane.tab_index), client_id) {
+            (Some(tab), _) => tab.index,
+            (None, Some(client_id)) => {
+                self.get_active_tab(client_id)
+                    .with_context(err_context)?
+                    .index
+            },
+            (None, None) => bail!("no tab to reopen pane in"),
+        };
+        if let Some(client_id) = client_id {
+            if self.active_tab_indices.get(&client_id) != Some(&tab_index) {
+                let tab_position = self
+                    .tabs
+                    .get(&tab_index)
+                    .with_context(err_context)?
+                    .position;
+                self.go_to_tab(tab_position + 1, client_id)
+                    .with_context(err_context)?;
+            }
+        }
+        let invoked_with = match closed_pane.run() {
+            Some(Run::Cwd(..)) => None,
+            run => run,
+        };
+        let tab = self.tabs.get_mut(&tab_index).with_context(err_context)?;
+        // the space of a closed tiled pane goes to its neighbour, which it is split back out of
+        let neighbour = if closed_pane.is_floating {
+            None
+        } else {
+            tab.tiled_pane_covering(&closed_pane.pane.geom)
+        };
+        tab.new_pane(
+            pid,
+            closed_pane.pane.title.clone(),
+            Some(closed_pane.is_floating),
+            invoked_with,
+            closed_pane.floating_pane_coordinates(),
+            false,
+            client_id,
+        )
+        .with_context(err_context)?;
+        if let (Some((neighbour_id, direction)), Some(client_id)) = (neighbour, client_id) {
+            tab.move_tiled_pane_next_to(pid, neighbour_id, direction, client_id)
+                .with_context(err_context)?;
+        }
+        if let Some(pane) = tab.get_pane_with_id_mut(pid) {
+            pane.set_env(closed_pane.pane.env);
+        }
+        if let (PaneId::Terminal(terminal_id), Some(pane_contents)) =
+            (pid, closed_pane.pane.pane_contents)
+        {
+            tab.handle_pty_bytes(terminal_id, pane_contents.into_bytes())
+                .with_context(err_context)?;
+        }
+        if let Some(hold_for_command) = hold_for_command {
+            let is_first_run = true;
+            tab.hold_pane(pid, None, is_first_run, hold_for_command);
+        }
+        Ok(())
+    }
+
+    /// Moves this tab to the given position, shifting the tabs that were there to the right
+    fn move_tab_to_position(&mut self, tab_index: usize, position: usize) {
+        let Some(current_position) = self.tabs.get(&tab_index).map(|tab| tab.position) else {
+            return;
+        };
+        let position = position.min(current_position);
+        for tab in self.tabs.values_mut() {
+            if tab.index == tab_index {
+                tab.position = position;
+            } else if tab.position >= position && tab.position < current_position {
+                tab.position += 1;
+            }
+        }
+    }
+
     pub fn resize_to_screen(&mut self, new_screen_size: Size) -> Result<()> {
         let err_context = || format!("failed to resize to screen size: {new_screen_size:#?}");
 
         if self.size != new_screen_size {
             self.size = new_screen_size;
-            for tab in self.tabs.values_mut() {
-                tab.resize_whole_tab(new_screen_size)
-                    .with_context(err_context)?;
-                tab.set_force_render();
+            let tab_sizes: Vec<(usize, Size)> = self
+                .tabs
+                .keys()
+                .map(|tab_index| {
+                    let tab_size = self
+                        .tab_size_for_its_clients(*tab_index)
+                        .unwrap_or(new_screen_size);
+                    (*tab_index, tab_size)
+                })
+                .collect();
+            for (tab_index, tab_size) in tab_sizes {
+                if let Some(tab) = self.tabs.get_mut(&tab_index) {
+                    tab.resize_whole_tab(tab_size).with_context(err_context)?;
+                    tab.set_force_render();
+                }
             }
             self.log_and_report_session_state()
                 .with_context(err_context)?;
@@ -1170,6 +1858,50 @@ impl Screen {
         }
     }
 
+    pub fn set_client_size(&mut self, client_id: ClientId, size: Size) {
+        self.client_sizes.insert(client_id, size);
+    }
+
+    /// The smallest size of the clients viewing this tab, or None if its size is shared by the
+    /// whole session (the session is mirrored or none of its clients reported their size)
+    fn tab_size_for_its_clients(&self, tab_index: usize) -> Option<Size> {
+        if self.session_is_mirrored {
+            return None;
+        }
+        let client_sizes: Vec<Size> = self
+            .active_tab_indices
+            .iter()
+            .filter(|(_client_id, active_tab_index)| **active_tab_index == tab_index)
+            .filter_map(|(client_id, _)| self.client_sizes.get(client_id).copied())
+            .collect();
+        let rows = client_sizes.iter().map(|size| size.rows).min()?;
+        let cols = client_sizes.iter().map(|size| size.cols).min()?;
+        Some(Size { rows, cols })
+    }
+
+    // clients move between tabs in many places, so rather than tracking all of them we make sure
+    // tabs are sized for their clients before rendering them
+    fn resize_tabs_to_their_clients(&mut self) -> Result<()> {
+        let tab_sizes: Vec<(usize, Size)> = self
+            .tabs
+            .keys()
+            .filter_map(|tab_index| {
+                self.tab_size_for_its_clients(*tab_index)
+                    .map(|tab_size| (*tab_index, tab_size))
+            })
+            .collect();
+        for (tab_index, tab_size) in tab_sizes {
+            if let Some(tab) = self.tabs.get_mut(&tab_index) {
+                if tab.get_display_area() != tab_size {
+                    tab.resize_whole_tab(tab_size)
+                        .with_context(|| format!("failed to resize tab {tab_index}"))?;
+                    tab.set_force_render();
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn update_pixel_dimensions(&mut self, pixel_dimensions: PixelDimensions) {
         self.pixel_dimensions.merge(pixel_dimensions);
         if let Some(character_cell_size) = self.pixel_dimensions.character_cell_size {
@@ -1214,6 +1946,7 @@ impl Screen {
     pub fn render(&mut self, plugin_render_assets: Option<Vec<PluginRenderAsset>>) -> Result<()> {
         let err_context = "failed to render screen";
 
+        self.resize_tabs_to_their_clients().context(err_context)?;
         let mut output = Output::new(
             self.sixel_image_store.clone(),
             self.character_cell_size.clone(),
@@ -1227,6 +1960,16 @@ impl Screen {
                 tabs_to_close.push(*tab_index);
             }
         }
+        for overlay in &self.overlay.overlay_stack {
+            if let Some(client_id) = overlay.client_id() {
+                let size = self
+                    .get_active_tab(client_id)
+                    .map(|tab| tab.get_display_area())
+                    .unwrap_or(self.size);
+                let vte_output = overlay.generate_overlay(size).context(err_context)?;
+                output.add_post_vte_instruction_to_client(client_id, &vte_output);
+            }
+        }
         for tab_index in tabs_to_close {
             // cleanup as needed
             self.close_tab_at_index(tab_index)
@@ -1235,6 +1978,7 @@ impl Screen {
         }
         if output.is_dirty() {
             let serialized_output = output.serialize().context(err_context)?;
+            self.record_session_output(&serialized_output);
             let _ = self
                 .bus
                 .senders
@@ -1365,11 +2109,19 @@ impl Screen {
             self.styled_underlines,
             self.explicitly_disable_kitty_keyboard_protocol,
             self.default_editor.clone(),
+            self.render_throttle_config,
         );
         for (client_id, mode_info) in &self.mode_info {
             tab.change_mode_info(mode_info.clone(), *client_id);
         }
+        let hook_context = HookContext {
+            client_id,
+            tab_index: Some(position),
+            tab_name: Some(tab.name.clone()),
+            ..Default::default()
+        };
         self.tabs.insert(tab_index, tab);
+        run_hooks(&self.hooks, HookEvent::TabCreate, hook_context);
         Ok(())
     }
     pub fn apply_layout(
@@ -1521,6 +2273,7 @@ impl Screen {
         if self.tab_history.contains_key(&client_id) {
             self.tab_history.remove(&client_id);
         }
+        self.client_sizes.remove(&client_id);
         self.connected_clients.borrow_mut().remove(&client_id);
         self.log_and_report_session_state()
             .with_context(err_context)
@@ -1555,45 +2308,12 @@ impl Screen {
                 viewport_columns: tab_viewport.cols,
                 display_area_rows: tab_display_area.rows,
                 display_area_columns: tab_display_area.cols,
+                group: tab.group.clone(),
             };
             tab_infos_for_screen_state.insert(tab.position, tab_info_for_screen);
         }
-        for (client_id, active_tab_index) in self.active_tab_indices.iter() {
-            let mut plugin_tab_updates = vec![];
-            for tab in self.tabs.values() {
-                let other_focused_clients: Vec<ClientId> = if self.session_is_mirrored {
-                    vec![]
-                } else {
-                    self.active_tab_indices
-                        .iter()
-                        .filter(|(c_id, tab_position)| {
-                            **tab_position == tab.index && *c_id != client_id
-                        })
-                        .map(|(c_id, _)| c_id)
-                        .copied()
-                        .collect()
-                };
-                let (active_swap_layout_name, is_swap_layout_dirty) = tab.swap_layout_info();
-                let tab_viewport = tab.get_viewport();
-                let tab_display_area = tab.get_display_area();
-                let tab_info_for_plugins = TabInfo {
-                    position: tab.position,
-                    name: tab.name.clone(),
-                    active: *active_tab_index == tab.index,
-                    panes_to_hide: tab.panes_to_hide_count(),
-                    is_fullscreen_active: tab.is_fullscreen_active(),
-                    is_sync_panes_active: tab.is_sync_panes_active(),
-                    are_floating_panes_visible: tab.are_floating_panes_visible(),
-                    other_focused_clients,
-                    active_swap_layout_name,
-                    is_swap_layout_dirty,
-                    viewport_rows: tab_viewport.rows,
-                    viewport_columns: tab_viewport.cols,
-                    display_area_rows: tab_display_area.rows,
-                    display_area_columns: tab_display_area.cols,
-                };
-                plugin_tab_updates.push(tab_info_for_plugins);
-            }
+        for client_id in self.active_tab_indices.keys() {
+            let plugin_tab_updates = self.tab_infos_for_client(*client_id);
             plugin_updates.push((None, Some(*client_id), Event::TabUpdate(plugin_tab_updates)));
         }
         self.bus
@@ -1602,11 +2322,55 @@ impl Screen {
             .context("failed to update tabs")?;
         Ok(tab_infos_for_screen_state.values().cloned().collect())
     }
-    fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
+    /// The tabs as they are reported to the plugins of this client
+    fn tab_infos_for_client(&self, client_id: ClientId) -> Vec<TabInfo> {
+        let active_tab_index = self.active_tab_indices.get(&client_id);
+        let mut tab_infos = vec![];
+        for tab in self.tabs.values() {
+            let other_focused_clients: Vec<ClientId> = if self.session_is_mirrored {
+                vec![]
+            } else {
+                self.active_tab_indices
+                    .iter()
+                    .filter(|(c_id, tab_position)| {
+                        **tab_position == tab.index && **c_id != client_id
+                    })
+                    .map(|(c_id, _)| c_id)
+                    .copied()
+                    .collect()
+            };
+            let (active_swap_layout_name, is_swap_layout_dirty) = tab.swap_layout_info();
+            let tab_viewport = tab.get_viewport();
+            let tab_display_area = tab.get_display_area();
+            tab_infos.push(TabInfo {
+                position: tab.position,
+                name: tab.name.clone(),
+                active: active_tab_index == Some(&tab.index),
+                panes_to_hide: tab.panes_to_hide_count(),
+                is_fullscreen_active: tab.is_fullscreen_active(),
+                is_sync_panes_active: tab.is_sync_panes_active(),
+                are_floating_panes_visible: tab.are_floating_panes_visible(),
+                other_focused_clients,
+                active_swap_layout_name,
+                is_swap_layout_dirty,
+                viewport_rows: tab_viewport.rows,
+                viewport_columns: tab_viewport.cols,
+                display_area_rows: tab_display_area.rows,
+                display_area_columns: tab_display_area.cols,
+                group: tab.group.clone(),
+            });
+        }
+        tab_infos
+    }
+    fn pane_manifest(&self) -> PaneManifest {
         let mut pane_manifest = PaneManifest::default();
         for tab in self.tabs.values() {
             pane_manifest.panes.insert(tab.position, tab.pane_infos());
         }
+        pane_manifest
+    }
+    fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
+        let pane_manifest = self.pane_manifest();
         self.bus
             .senders
             .send_to_plugin(PluginInstruction::Update(vec![(
@@ -1620,24 +2384,18 @@ impl Screen {
     }
     fn log_and_report_session_state(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
+        self.unblock_cli_waiters_for_closed_panes()
+            .with_context(err_context)?;
         // generate own session info
         let pane_manifest = self.generate_and_report_pane_state()?;
         let tab_infos = self.generate_and_report_tab_state()?;
-        // in the context of unit/integration tests, we don't need to list available layouts
-        // because this is mostly about HD access - it does however throw off the timing in the
-        // tests and causes them to flake, which is why we skip it here
-        #[cfg(not(test))]
-        let available_layouts =
-            Layout::list_available_layouts(self.layout_dir.clone(), &self.default_layout_name);
-        #[cfg(test)]
-        let available_layouts = vec![];
         let session_info = SessionInfo {
             name: self.session_name.clone(),
             tabs: tab_infos,
             panes: pane_manifest,
             connected_clients: self.active_tab_indices.keys().len(),
             is_current_session: true,
-            available_layouts,
+            available_layouts: self.available_layouts(),
             plugins: Default::default(), // these are filled in by the wasm thread
         };
         self.bus
@@ -1654,6 +2412,17 @@ impl Screen {
             .with_context(err_context)?;
         Ok(())
     }
+    fn available_layouts(&self) -> Vec<LayoutInfo> {
+        // in the context of unit/integration tests, we don't need to list available layouts
+        // because this is mostly about HD access - it does however throw off the timing in the
+        // tests and causes them to flake, which is why we skip it here
+        #[cfg(not(test))]
+        let available_layouts =
+            Layout::list_available_layouts(self.layout_dir.clone(), &self.default_layout_name);
+        #[cfg(test)]
+        let available_layouts = vec![];
+        available_layouts
+    }
     fn dump_layout_to_hd(&mut self) -> Result<()> {
         let err_context = || format!("Failed to log and report session state");
         let session_layout_metadata = self.get_layout_metadata(Some(self.default_shell.clone()));
@@ -1732,6 +2501,15 @@ impl Screen {
             None => Ok(()),
         }
     }
+    /// Renames the tab with this index (rather than position), if there is one
+    pub fn rename_tab(&mut self, tab_index: usize, new_name: Vec<u8>) -> Result<()> {
+        match self.tabs.get_mut(&tab_index) {
+            Some(tab) => tab.name = String::from_utf8_lossy(&new_name).to_string(),
+            None => log::error!("Failed to find tab with index: {:?}", tab_index),
+        }
+        self.log_and_report_session_state()
+            .with_context(|| format!("failed to rename tab with index {tab_index}"))
+    }
     pub fn undo_active_rename_tab(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to undo active tab rename for client {}", client_id);
 
@@ -2383,6 +3161,41 @@ impl Screen {
         self.log_and_report_session_state()?;
         Ok(())
     }
+    /// Moves a pane from whichever tab it is in to the focused tab of this client
+    pub fn join_pane(
+        &mut self,
+        pane_id: PaneId,
+        placement: JoinPanePlacement,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to join pane {pane_id:?} to the focused tab of client {client_id}");
+        let active_tab_index = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .index;
+        let Some(source_tab) = self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+        else {
+            log::error!("Could not find pane with id: {:?}", pane_id);
+            return Ok(());
+        };
+        if source_tab.index == active_tab_index {
+            log::error!("Pane {:?} is already in the focused tab", pane_id);
+            return Ok(());
+        }
+        // an emptied source tab is closed on the next render
+        let pane = source_tab
+            .extract_pane(pane_id, true)
+            .with_context(err_context)?;
+        self.get_active_tab_mut(client_id)
+            .and_then(|tab| tab.join_pane(pane, placement, client_id))
+            .with_context(err_context)?;
+        self.log_and_report_session_state()
+            .with_context(err_context)
+    }
     pub fn replace_pane(
         &mut self,
         new_pane_id: PaneId,
@@ -2458,6 +3271,8 @@ impl Screen {
         hide_session_name: bool,
         stacked_resize: bool,
         default_editor: Option<PathBuf>,
+        scratchpads: Scratchpads,
+        hooks: Hooks,
         client_id: ClientId,
     ) -> Result<()> {
         let should_support_arrow_fonts = !simplified_ui;
@@ -2469,6 +3284,8 @@ impl Screen {
         self.default_shell = default_shell.clone().unwrap_or_else(|| get_default_shell());
         self.default_editor = default_editor.clone().or_else(|| get_default_editor());
         self.auto_layout = auto_layout;
+        self.scratchpads = scratchpads;
+        self.hooks = hooks;
         self.copy_options.command = copy_command.clone();
         self.copy_options.copy_on_select = copy_on_select;
         self.draw_pane_frames = pane_frames;
@@ -2527,6 +3344,274 @@ impl Screen {
         );
         self.unblock_input().non_fatal();
     }
+    pub fn toggle_pane_logging(&mut self, pane_logging: PaneLogging, client_id: ClientId) {
+        active_tab_and_connected_client_id!(
+            self,
+            client_id,
+            |tab: &mut Tab, client_id: ClientId| {
+                tab.toggle_active_pane_logging(pane_logging, client_id)
+                    .non_fatal();
+            }
+        );
+    }
+    pub fn active_pane_is_in_sync_group(&self, client_id: ClientId) -> bool {
+        self.get_active_tab(client_id)
+            .map(|tab| tab.active_pane_is_in_sync_group(client_id))
+            .unwrap_or(false)
+    }
+    pub fn write_to_sync_group(
+        &mut self,
+        key_with_modifier: &Option<KeyWithModifier>,
+        raw_bytes: Vec<u8>,
+        is_kitty_keyboard_protocol: bool,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        // returns true if a UI update should be triggered (eg. when closing a command pane with
+        // ctrl-c)
+        let err_context = || format!("failed to write to sync group for client {client_id}");
+        let mut should_trigger_ui_change = false;
+        for tab in self.tabs.values_mut() {
+            for pane_id in tab.pane_ids_in_sync_group() {
+                let ui_change_triggered = tab
+                    .write_to_pane_id(
+                        key_with_modifier,
+                        raw_bytes.clone(),
+                        is_kitty_keyboard_protocol,
+                        pane_id,
+                        Some(client_id),
+                    )
+                    .with_context(err_context)?;
+                if ui_change_triggered {
+                    should_trigger_ui_change = true;
+                }
+            }
+        }
+        Ok(should_trigger_ui_change)
+    }
+    pub fn clear_sync_group(&mut self) {
+        for tab in self.tabs.values_mut() {
+            tab.clear_sync_group();
+        }
+    }
+    pub fn show_pane_jump_labels(
+        &mut self,
+        pane_jump_mode: PaneJumpMode,
+        all_tabs: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to show pane jump labels for client {client_id}");
+        let active_tab = self.get_active_tab(client_id).with_context(err_context)?;
+        let active_tab_position = active_tab.position;
+        let mut candidates: Vec<(usize, PaneId, String, PaneJumpPosition)> = active_tab
+            .pane_jump_candidates()
+            .into_iter()
+            .map(|(pane_id, title, position)| (active_tab_position, pane_id, title, position))
+            .collect();
+        if all_tabs {
+            let mut other_tabs: Vec<&Tab> = self
+                .tabs
+                .values()
+                .filter(|tab| tab.position != active_tab_position)
+                .collect();
+            other_tabs.sort_by_key(|tab| tab.position);
+            for tab in other_tabs {
+                candidates.extend(
+                    tab.pane_jump_candidates()
+                        .into_iter()
+                        .map(|(pane_id, title, _position)| (tab.position, pane_id, title, None)),
+                );
+            }
+        }
+        self.take_pane_jump(client_id); // replaces the labels this client was shown before
+        self.overlay
+            .overlay_stack
+            .push(Overlay::new(OverlayType::PaneJump(PaneJump::new(
+                client_id,
+                pane_jump_mode,
+                candidates,
+            ))));
+        Ok(())
+    }
+    pub fn pane_jump_is_active(&self, client_id: ClientId) -> bool {
+        self.overlay
+            .overlay_stack
+            .iter()
+            .any(|overlay| overlay.client_id() == Some(client_id))
+    }
+    /// Picks the pane with this label from the labels shown to the client, any other input just
+    /// removes the labels
+    pub fn jump_to_pane_with_label(
+        &mut self,
+        label: Option<char>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to jump to pane with label {label:?}");
+        let Some(pane_jump) = self.take_pane_jump(client_id) else {
+            return Ok(());
+        };
+        let Some(target) = label
+            .and_then(|label| pane_jump.target_with_label(label))
+            .cloned()
+        else {
+            return Ok(());
+        };
+        match pane_jump.mode {
+            PaneJumpMode::Focus => {
+                self.focus_pane_with_id(target.pane_id, true, client_id)
+                    .with_context(err_context)?;
+            },
+            PaneJumpMode::Swap => {
+                let active_tab = self
+                    .get_active_tab_mut(client_id)
+                    .with_context(err_context)?;
+                let Some(active_pane_id) = active_tab.get_active_pane_id(client_id) else {
+                    return Ok(());
+                };
+                if active_tab.position == target.tab_position {
+                    active_tab
+                        .swap_panes(active_pane_id, target.pane_id)
+                        .with_context(err_context)?;
+                } else {
+                    // the picked pane is in another tab, so we move the focused pane there
+                    self.break_multiple_panes_to_tab_with_index(
+                        vec![active_pane_id],
+                        target.tab_position,
+                        true,
+                        client_id,
+                    )
+                    .with_context(err_context)?;
+                    self.focus_pane_with_id(active_pane_id, false, client_id)
+                        .with_context(err_context)?;
+                }
+            },
+        }
+        Ok(())
+    }
+    fn take_pane_jump(&mut self, client_id: ClientId) -> Option<PaneJump> {
+        let index = self
+            .overlay
+            .overlay_stack
+            .iter()
+            .position(|overlay| overlay.client_id() == Some(client_id))?;
+        let overlay = self.overlay.overlay_stack.remove(index);
+        for tab in self.tabs.values_mut() {
+            // so that the labels are rendered over
+            tab.set_force_render();
+        }
+        match overlay.overlay_type {
+            OverlayType::PaneJump(pane_jump) => Some(pane_jump),
+            _ => None,
+        }
+    }
+    /// Shows the scratchpad in the active tab of the client, bringing it over from wherever it
+    /// was last shown, or hides it if it is already shown there - the scratchpad is opened the
+    /// first time it is toggled (and again if its pane was closed)
+    ///
+    /// Returns false if the scratchpad is being opened, input is unblocked once it is (toggling
+    /// it again meanwhile does nothing)
+    pub fn toggle_scratchpad(&mut self, name: String, client_id: ClientId) -> Result<bool> {
+        let err_context = || format!("failed to toggle scratchpad {name:?}");
+        let client_id = self
+            .client_id(client_id)
+            .with_context(|| "no client to toggle the scratchpad for")
+            .with_context(err_context)?;
+        let active_tab_index = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .index;
+        let scratchpad_location = self.scratchpad_panes.get(&name).and_then(|pane_id| {
+            self.tabs
+                .values()
+                .find(|tab| tab.has_pane_with_pid(pane_id))
+                .map(|tab| (tab.index, *pane_id))
+        });
+        match scratchpad_location {
+            Some((tab_index, pane_id)) if tab_index == active_tab_index => {
+                let active_tab = self
+                    .get_active_tab_mut(client_id)
+                    .with_context(err_context)?;
+                if active_tab.floating_pane_is_visible(&pane_id) {
+                    active_tab.suppress_pane(pane_id, Some(client_id));
+                } else if let Some(pane) = active_tab.extract_pane(pane_id, true) {
+                    active_tab
+                        .show_pane_as_floating(pane, pane_id)
+                        .with_context(err_context)?;
+                }
+            },
+            Some((tab_index, pane_id)) => {
+                let pane = self
+                    .tabs
+                    .get_mut(&tab_index)
+                    .and_then(|tab| tab.extract_pane(pane_id, true))
+                    .with_context(err_context)?;
+                self.get_active_tab_mut(client_id)
+                    .with_context(err_context)?
+                    .show_pane_as_floating(pane, pane_id)
+                    .with_context(err_context)?;
+            },
+            None if self.pending_scratchpads.contains(&name) => {},
+            None => {
+                let scratchpad = self
+                    .scratchpads
+                    .get(&name)
+                    .cloned()
+                    .with_context(|| format!("no scratchpad named {name:?} in the config"))
+                    .with_context(err_context)?;
+                self.bus
+                    .senders
+                    .send_to_pty(PtyInstruction::SpawnScratchpad(
+                        name.clone(),
+                        scratchpad,
+                        client_id,
+                    ))
+                    .with_context(err_context)?;
+                self.pending_scratchpads.insert(name);
+                return Ok(false);
+            },
+        }
+        Ok(true)
+    }
+    /// Places the newly opened scratchpad as a floating pane in the active tab of the client
+    pub fn add_scratchpad_pane(
+        &mut self,
+        pid: PaneId,
+        hold_for_command: HoldForCommand,
+        name: String,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to add pane {pid:?} of scratchpad {name:?}");
+        self.pending_scratchpads.remove(&name);
+        let client_id = self
+            .client_id(client_id)
+            .with_context(|| "no client to open the scratchpad for")
+            .with_context(err_context)?;
+        let scratchpad = self.scratchpads.get(&name).cloned().unwrap_or_default();
+        let invoked_with = scratchpad
+            .run
+            .filter(|run_command| !run_command.command.as_os_str().is_empty())
+            .map(Run::Command);
+        let active_tab = self
+            .get_active_tab_mut(client_id)
+            .with_context(err_context)?;
+        active_tab.stash_hidden_floating_panes(pid);
+        active_tab
+            .new_pane(
+                pid,
+                Some(name.clone()),
+                Some(true),
+                invoked_with,
+                scratchpad.coordinates,
+                false,
+                Some(client_id),
+            )
+            .with_context(err_context)?;
+        if let Some(hold_for_command) = hold_for_command {
+            let is_first_run = true;
+            active_tab.hold_pane(pid, None, is_first_run, hold_for_command);
+        }
+        self.scratchpad_panes.insert(name, pid);
+        Ok(())
+    }
     pub fn set_floating_pane_pinned(&mut self, pane_id: PaneId, should_be_pinned: bool) {
         let mut found = false;
         for tab in self.tabs.values_mut() {
@@ -2613,6 +3698,167 @@ impl Screen {
             }
         }
     }
+    pub fn start_recording(
+        &mut self,
+        file: PathBuf,
+        whole_session: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to start recording for client {client_id}");
+
+        let client_id = self
+            .client_id_or_first_connected_client(client_id)
+            .with_context(err_context)?;
+        let file = file.to_string_lossy().to_string();
+        if whole_session {
+            let title = Some(self.session_name.clone());
+            let os_input = self
+                .bus
+                .os_input
+                .as_mut()
+                .ok_or_else(|| anyhow!("os input not found"))
+                .with_context(err_context)?;
+            let recording = Recording::start(file, self.size, title, None, os_input)
+                .with_context(err_context)?;
+            self.session_recording = Some((client_id, recording));
+            // so that the recording starts with the whole screen rather than with what changed
+            // since the last render
+            for tab in self.tabs.values_mut() {
+                tab.set_force_render();
+            }
+            return Ok(());
+        }
+        let active_tab = self.get_active_tab(client_id).with_context(err_context)?;
+        let (terminal_id, pane) = match active_tab.get_active_pane_id(client_id) {
+            Some(PaneId::Terminal(terminal_id)) => (
+                terminal_id,
+                active_tab
+                    .get_pane_with_id(PaneId::Terminal(terminal_id))
+                    .with_context(err_context)?,
+            ),
+            _ => bail!("only terminal panes can be recorded"),
+        };
+        let size = Size {
+            rows: pane.get_content_rows(),
+            cols: pane.get_content_columns(),
+        };
+        let title = Some(pane.current_title());
+        // the recording starts with what the pane currently displays
+        let initial_contents = pane.serialize(None).map(|viewport| {
+            let mut initial_contents = String::from("\u{1b}[2J\u{1b}[H");
+            initial_contents.push_str(viewport.strip_prefix("\n\r").unwrap_or(&viewport));
+            if let Some((x, y)) = pane.cursor_coordinates() {
+                initial_contents.push_str(&format!(
+                    "\u{1b}[{};{}H",
+                    y.saturating_sub(pane.get_content_y()) + 1,
+                    x.saturating_sub(pane.get_content_x()) + 1
+                ));
+            }
+            initial_contents
+        });
+        let os_input = self
+            .bus
+            .os_input
+            .as_mut()
+            .ok_or_else(|| anyhow!("os input not found"))
+            .with_context(err_context)?;
+        let recording = Recording::start(file, size, title, initial_contents, os_input)
+            .with_context(err_context)?;
+        self.pane_recordings.insert(terminal_id, recording);
+        Ok(())
+    }
+    pub fn stop_session_recording(&mut self, client_id: ClientId) {
+        let Some(client_id) = self.client_id_or_first_connected_client(client_id) else {
+            return;
+        };
+        if self
+            .session_recording
+            .as_ref()
+            .map(|(recording_client_id, _)| *recording_client_id == client_id)
+            .unwrap_or(false)
+        {
+            if let Some((_, recording)) = self.session_recording.take() {
+                recording
+                    .stop()
+                    .context("failed to stop recording the session")
+                    .non_fatal();
+            }
+        }
+    }
+    pub fn stop_focused_pane_recording(&mut self, client_id: ClientId) {
+        match self.focused_pane_id(client_id) {
+            Some(PaneId::Terminal(terminal_id))
+                if self.pane_recordings.contains_key(&terminal_id) =>
+            {
+                self.stop_recording(PaneId::Terminal(terminal_id))
+            },
+            _ => log::error!("Focused pane is not being recorded"),
+        }
+    }
+    /// Stops recording the pane if it is being recorded, writing out the rest of the recording
+    pub fn stop_recording(&mut self, pane_id: PaneId) {
+        let PaneId::Terminal(terminal_id) = pane_id else {
+            return;
+        };
+        if let Some(recording) = self.pane_recordings.remove(&terminal_id) {
+            recording
+                .stop()
+                .with_context(|| format!("failed to stop recording pane {terminal_id}"))
+                .non_fatal();
+        }
+    }
+    // actions sent through the cli come from a client that is not displaying anything, so we
+    // act on behalf of the first connected client instead
+    fn client_id_or_first_connected_client(&self, client_id: ClientId) -> Option<ClientId> {
+        if self.active_tab_indices.contains_key(&client_id) {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        }
+    }
+    fn focused_pane_id(&self, client_id: ClientId) -> Option<PaneId> {
+        let client_id = self.client_id_or_first_connected_client(client_id)?;
+        self.get_active_tab(client_id)
+            .ok()
+            .and_then(|tab| tab.get_active_pane_id(client_id))
+    }
+    fn record_pty_bytes(&mut self, terminal_id: u32, bytes: &[u8]) {
+        let Some(recording) = self.pane_recordings.get_mut(&terminal_id) else {
+            return;
+        };
+        let pane_size = self.tabs.values().find_map(|tab| {
+            tab.get_pane_with_id(PaneId::Terminal(terminal_id))
+                .map(|pane| Size {
+                    rows: pane.get_content_rows(),
+                    cols: pane.get_content_columns(),
+                })
+        });
+        let recorded = match pane_size {
+            Some(pane_size) => recording.record_output(bytes, pane_size),
+            None => Err(anyhow!("pane {} no longer exists", terminal_id)),
+        };
+        if recorded.is_err() {
+            self.pane_recordings.remove(&terminal_id);
+        }
+        recorded
+            .with_context(|| format!("failed to record output of pane {terminal_id}"))
+            .non_fatal();
+    }
+    fn record_session_output(&mut self, serialized_output: &HashMap<ClientId, String>) {
+        let Some((client_id, recording)) = self.session_recording.as_mut() else {
+            return;
+        };
+        let Some(client_output) = serialized_output.get(client_id) else {
+            return;
+        };
+        let recorded = recording.record_output(client_output.as_bytes(), self.size);
+        if recorded.is_err() {
+            self.session_recording = None;
+        }
+        recorded
+            .context("failed to record session output")
+            .non_fatal();
+    }
     fn unblock_input(&self) -> Result<()> {
         self.bus
             .senders
@@ -2621,6 +3867,7 @@ impl Screen {
     }
     fn get_layout_metadata(&self, default_shell: Option<PathBuf>) -> SessionLayoutMetadata {
         let mut session_layout_metadata = SessionLayoutMetadata::new(self.default_layout.clone());
+        session_layout_metadata.redact_env = self.redact_serialized_env;
         if let Some(default_shell) = default_shell {
             session_layout_metadata.update_default_shell(default_shell);
         }
@@ -2687,6 +3934,8 @@ impl Screen {
                             None
                         },
                         focused_clients,
+                        p.log_to_file().cloned(),
+                        p.env().cloned().unwrap_or_default(),
                     )
                 })
                 .collect();
@@ -2725,6 +3974,8 @@ impl Screen {
                             None
                         },
                         focused_clients,
+                        p.log_to_file().cloned(),
+                        p.env().cloned().unwrap_or_default(),
                     )
                 })
                 .collect();
@@ -2732,6 +3983,7 @@ impl Screen {
                 tab.name.clone(),
                 tab_is_focused,
                 hide_floating_panes,
+                tab.group.clone(),
                 tiled_panes,
                 floating_panes,
             );
@@ -2759,6 +4011,28 @@ impl Screen {
     }
 }
 
+// closed panes keep their whole scrollback, regardless of how much of it is serialized with
+// the session
+fn closed_pane_layout_metadata(
+    pane_id: PaneId,
+    pane: &dyn Pane,
+    is_floating: bool,
+) -> PaneLayoutMetadata {
+    let scrollback_lines_to_serialize = Some(0); // all of them
+    PaneLayoutMetadata::new(
+        pane_id,
+        pane.position_and_size(),
+        !is_floating && pane.borderless(),
+        pane.invoked_with().clone(),
+        pane.custom_title(),
+        true,
+        pane.serialize(scrollback_lines_to_serialize),
+        vec![],
+        pane.log_to_file().cloned(),
+        pane.env().cloned().unwrap_or_default(),
+    )
+}
+
 #[cfg(not(test))]
 fn get_default_editor() -> Option<PathBuf> {
     std::env::var("EDITOR")
@@ -2790,6 +4064,7 @@ pub(crate) fn screen_thread_main(
     let session_serialization = config_options.session_serialization.unwrap_or(true);
     let serialize_pane_viewport = config_options.serialize_pane_viewport.unwrap_or(false);
     let scrollback_lines_to_serialize = config_options.scrollback_lines_to_serialize;
+    let redact_serialized_env = config_options.redact_serialized_env.unwrap_or(false);
     let session_is_mirrored = config_options.mirror_session.unwrap_or(false);
     let layout_dir = config_options.layout_dir;
     #[cfg(test)]
@@ -2823,6 +4098,14 @@ pub(crate) fn screen_thread_main(
         .unwrap_or(false); // by default, we try to support this if the terminal supports it and
                            // the program running inside a pane requests it
     let stacked_resize = config_options.stacked_resize.unwrap_or(true);
+    let render_throttle_config = RenderThrottleConfig::new(
+        config_options.unfocused_pane_render_interval,
+        config_options.hidden_pane_pause_threshold,
+    );
+    let closed_pane_retention = config_options
+        .closed_pane_retention
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_CLOSED_PANE_RETENTION);
 
     let thread_senders = bus.senders.clone();
     let mut screen = Screen::new(
@@ -2850,12 +4133,17 @@ pub(crate) fn screen_thread_main(
         session_serialization,
         serialize_pane_viewport,
         scrollback_lines_to_serialize,
+        redact_serialized_env,
         styled_underlines,
         arrow_fonts,
         layout_dir,
         explicitly_disable_kitty_keyboard_protocol,
         stacked_resize,
         default_editor,
+        render_throttle_config,
+        closed_pane_retention,
+        config.scratchpads.clone(),
+        config.hooks.clone(),
     );
 
     let mut pending_tab_ids: HashSet<usize> = HashSet::new();
@@ -2876,6 +4164,7 @@ pub(crate) fn screen_thread_main(
 
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
+                screen.record_pty_bytes(pid, &vte_bytes);
                 let all_tabs = screen.get_tabs_mut();
                 for tab in all_tabs.values_mut() {
                     if tab.has_terminal_pid(pid) {
@@ -2884,6 +4173,7 @@ pub(crate) fn screen_thread_main(
                         break;
                     }
                 }
+                screen.unblock_cli_waiters_for_pattern_in(PaneId::Terminal(pid))?;
             },
             ScreenInstruction::PluginBytes(mut plugin_render_assets) => {
                 for plugin_render_asset in plugin_render_assets.iter_mut() {
@@ -3109,21 +4399,35 @@ pub(crate) fn screen_thread_main(
                 client_id,
             ) => {
                 let mut state_changed = false;
-                active_tab_and_connected_client_id!(
-                    screen,
-                    client_id,
-                    |tab: &mut Tab, client_id: ClientId| {
-                        let write_result = match tab.is_sync_panes_active() {
-                            true => tab.write_to_terminals_on_current_tab(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
-                            false => tab.write_to_active_terminal(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
-                        };
-                        if let Ok(true) = write_result {
-                            state_changed = true;
-                        }
-                        write_result
-                    },
-                    ?
-                );
+                if screen.pane_jump_is_active(client_id) {
+                    let label = PaneJump::label_from_input(&key_with_modifier, &raw_bytes);
+                    screen.jump_to_pane_with_label(label, client_id)?;
+                    screen.render(None)?;
+                    state_changed = true;
+                } else if screen.active_pane_is_in_sync_group(client_id) {
+                    state_changed = screen.write_to_sync_group(
+                        &key_with_modifier,
+                        raw_bytes,
+                        is_kitty_keyboard_protocol,
+                        client_id,
+                    )?;
+                } else {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| {
+                            let write_result = match tab.is_sync_panes_active() {
+                                true => tab.write_to_terminals_on_current_tab(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
+                                false => tab.write_to_active_terminal(&key_with_modifier, raw_bytes, is_kitty_keyboard_protocol, client_id),
+                            };
+                            if let Ok(true) = write_result {
+                                state_changed = true;
+                            }
+                            write_result
+                        },
+                        ?
+                    );
+                }
                 if state_changed {
                     screen.log_and_report_session_state()?;
                 }
@@ -3250,19 +4554,23 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::DumpLayout(default_shell, client_id) => {
+            ScreenInstruction::DumpLayout(default_shell, json, client_id) => {
                 let err_context = || format!("Failed to dump layout");
-                let session_layout_metadata = screen.get_layout_metadata(default_shell);
-                screen
-                    .bus
-                    .senders
-                    .send_to_plugin(PluginInstruction::DumpLayout(
-                        session_layout_metadata,
-                        client_id,
-                    ))
-                    .with_context(err_context)?;
+                if json {
+                    screen.dump_session_info_as_json(client_id)?;
+                } else {
+                    let session_layout_metadata = screen.get_layout_metadata(default_shell);
+                    screen
+                        .bus
+                        .senders
+                        .send_to_plugin(PluginInstruction::DumpLayout(
+                            session_layout_metadata,
+                            client_id,
+                        ))
+                        .with_context(err_context)?;
+                }
             },
-            ScreenInstruction::ListClientsMetadata(default_shell, client_id) => {
+            ScreenInstruction::ListClientsMetadata(default_shell, json, client_id) => {
                 let err_context = || format!("Failed to dump layout");
                 let session_layout_metadata = screen.get_layout_metadata(default_shell);
                 screen
@@ -3270,6 +4578,7 @@ pub(crate) fn screen_thread_main(
                     .senders
                     .send_to_plugin(PluginInstruction::ListClientsMetadata(
                         session_layout_metadata,
+                        json,
                         client_id,
                     ))
                     .with_context(err_context)?;
@@ -3482,11 +4791,16 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
             },
             ScreenInstruction::CloseFocusedPane(client_id) => {
+                screen.remember_focused_pane(client_id).non_fatal();
+                let focused_pane_id = screen.focused_pane_id(client_id);
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab.close_focused_pane(client_id), ?
                 );
+                if let Some(focused_pane_id) = focused_pane_id {
+                    screen.stop_recording(focused_pane_id);
+                }
                 screen.render(None)?;
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
@@ -3523,6 +4837,7 @@ pub(crate) fn screen_thread_main(
                         }
                     },
                 }
+                screen.stop_recording(id);
 
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
@@ -3885,6 +5200,8 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
             },
             ScreenInstruction::RemoveClient(client_id) => {
+                screen.stop_session_recording(client_id);
+                screen.cli_waiters.client_exited(client_id);
                 screen.remove_client(client_id)?;
                 screen.log_and_report_session_state()?;
                 screen.render(None)?;
@@ -4015,12 +5332,18 @@ pub(crate) fn screen_thread_main(
                 screen.log_and_report_session_state()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::QueryTabNames(client_id) => {
+            ScreenInstruction::QueryTabNames(json, client_id) => {
                 let tab_names = screen
                     .get_tabs_mut()
                     .values()
                     .map(|tab| tab.name.clone())
                     .collect::<Vec<String>>();
+                let tab_names = if json {
+                    vec![serde_json::to_string(&tab_names)
+                        .context("failed to serialize tab names")?]
+                } else {
+                    tab_names
+                };
                 screen
                     .bus
                     .senders
@@ -4453,17 +5776,10 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.log_and_report_session_state()?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::RenameTab(tab_index, new_name) => {
-                match screen.tabs.get_mut(&tab_index.saturating_sub(1)) {
-                    Some(tab) => {
-                        tab.name = String::from_utf8_lossy(&new_name).to_string();
-                    },
-                    None => {
-                        log::error!("Failed to find tab with index: {:?}", tab_index);
-                    },
-                }
-                screen.log_and_report_session_state()?;
+                screen.rename_tab(tab_index.saturating_sub(1), new_name)?;
             },
             ScreenInstruction::RequestPluginPermissions(plugin_id, plugin_permission) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4599,6 +5915,8 @@ pub(crate) fn screen_thread_main(
                 hide_session_name,
                 stacked_resize,
                 default_editor,
+                scratchpads,
+                hooks,
             } => {
                 screen
                     .reconfigure(
@@ -4616,6 +5934,8 @@ pub(crate) fn screen_thread_main(
                         hide_session_name,
                         stacked_resize,
                         default_editor,
+                        scratchpads,
+                        hooks,
                         client_id,
                     )
                     .non_fatal();
@@ -4624,7 +5944,8 @@ pub(crate) fn screen_thread_main(
                 screen.rerun_command_pane_with_id(terminal_pane_id)
             },
             ScreenInstruction::ResizePaneWithId(resize, pane_id) => {
-                screen.resize_pane_with_id(resize, pane_id)
+                screen.resize_pane_with_id(resize, pane_id);
+                screen.unblock_input()?;
             },
             ScreenInstruction::EditScrollbackForPaneWithId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4635,6 +5956,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::WriteToPaneId(bytes, pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4646,6 +5968,42 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::WriteKeyToPaneId(
+                key_with_modifier,
+                raw_bytes,
+                is_kitty_keyboard_protocol,
+                pane_id,
+            ) => {
+                let all_tabs = screen.get_tabs_mut();
+                for tab in all_tabs.values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.write_to_pane_id(
+                            &Some(key_with_modifier),
+                            raw_bytes,
+                            is_kitty_keyboard_protocol,
+                            pane_id,
+                            None,
+                        )
+                        .non_fatal();
+                        break;
+                    }
+                }
+                screen.render(None)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::WaitFor(condition, pipe_id, client_id) => {
+                screen.wait_for(condition, pipe_id, client_id)?;
+            },
+            ScreenInstruction::SendWaitForSignal(name) => {
+                let met_conditions = screen.cli_waiters.signal(&name);
+                screen.unblock_cli_waiters(met_conditions)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CommandExited(pane_id, exit_status) => {
+                let met_conditions = screen.cli_waiters.pane_exited(pane_id, exit_status);
+                screen.unblock_cli_waiters(met_conditions)?;
             },
             ScreenInstruction::MovePaneWithPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4656,6 +6014,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::MovePaneWithPaneIdInDirection(pane_id, direction) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4671,6 +6030,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::ClearScreenForPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4681,6 +6041,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::ScrollUpInPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4699,6 +6060,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::ScrollDownInPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4717,6 +6079,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::ScrollToTopInPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4735,6 +6098,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::ScrollToBottomInPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4751,6 +6115,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::PageScrollUpInPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4769,6 +6134,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::PageScrollDownInPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4787,6 +6153,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::TogglePaneIdFullscreen(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4797,6 +6164,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::TogglePaneEmbedOrEjectForPaneId(pane_id) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -4808,6 +6176,7 @@ pub(crate) fn screen_thread_main(
                     }
                 }
                 screen.render(None)?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::CloseTabWithIndex(tab_index) => {
                 screen.close_tab_at_index(tab_index).non_fatal()
@@ -4856,6 +6225,287 @@ pub(crate) fn screen_thread_main(
                 let _ = screen.unblock_input();
                 let _ = screen.render(None);
             },
+            ScreenInstruction::StartRecording(file, whole_session, client_id) => {
+                screen
+                    .start_recording(file, whole_session, client_id)
+                    .non_fatal();
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::StopRecording(whole_session, client_id) => {
+                if whole_session {
+                    screen.stop_session_recording(client_id);
+                } else {
+                    screen.stop_focused_pane_recording(client_id);
+                }
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::TogglePaneLogging(pane_logging, client_id) => {
+                screen.toggle_pane_logging(pane_logging, client_id);
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::ClientTerminalResize(client_id, new_size) => {
+                screen.set_client_size(client_id, new_size);
+                screen.log_and_report_session_state()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::ReopenClosedPane(client_id) => {
+                // input is unblocked once the pane is reopened
+                if !screen.reopen_closed_pane(client_id)? {
+                    screen.unblock_input()?;
+                }
+            },
+            ScreenInstruction::ClosedPaneReopened(
+                pid,
+                hold_for_command,
+                closed_pane,
+                client_id,
+            ) => {
+                screen
+                    .add_reopened_pane(pid, hold_for_command, closed_pane, client_id)
+                    .non_fatal();
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::ReopenClosedTab(client_id) => {
+                let closed_tab = screen.recently_closed.pop_tab();
+                let layout = closed_tab
+                    .as_ref()
+                    .and_then(|closed_tab| closed_tab.layout());
+                match (closed_tab, layout) {
+                    (Some(closed_tab), Some((tiled_panes_layout, floating_panes_layout))) => {
+                        let tab_index = screen.get_new_tab_index();
+                        pending_tab_ids.insert(tab_index);
+                        let swap_layouts = (
+                            screen.default_layout.swap_tiled_layouts.clone(),
+                            screen.default_layout.swap_floating_layouts.clone(),
+                        );
+                        screen.new_tab(
+                            tab_index,
+                            swap_layouts,
+                            Some(closed_tab.name),
+                            Some(client_id),
+                        )?;
+                        screen.move_tab_to_position(tab_index, closed_tab.position);
+                        let should_change_focus_to_new_tab = true;
+                        screen
+                            .bus
+                            .senders
+                            .send_to_plugin(PluginInstruction::NewTab(
+                                None,
+                                None,
+                                Some(tiled_panes_layout),
+                                floating_panes_layout,
+                                tab_index,
+                                should_change_focus_to_new_tab,
+                                client_id,
+                            ))?;
+                    },
+                    (Some(closed_tab), None) => {
+                        log::error!(
+                            "Failed to reopen tab {:?}, its panes cannot be laid out",
+                            closed_tab.name
+                        );
+                        screen.unblock_input()?;
+                    },
+                    (None, _) => {
+                        screen.unblock_input()?;
+                    },
+                }
+            },
+            ScreenInstruction::JoinPane(pane_id, placement, client_id) => {
+                match screen.client_id(client_id) {
+                    Some(client_id) => screen.join_pane(pane_id, placement, client_id).non_fatal(),
+                    None => log::error!("No client to join pane {:?} for", pane_id),
+                }
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::AddRecentlyClosed(closed_item) => {
+                screen.recently_closed.push(closed_item);
+            },
+            ScreenInstruction::SwapPanes(first_pane_id, second_pane_id) => {
+                match screen
+                    .get_tabs_mut()
+                    .values_mut()
+                    .find(|tab| tab.has_pane_with_pid(&first_pane_id))
+                {
+                    Some(tab) => tab.swap_panes(first_pane_id, second_pane_id).non_fatal(),
+                    None => log::error!("Could not find pane with id: {:?}", first_pane_id),
+                }
+                screen.unblock_input()?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::RotatePanes(rotate_direction, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.rotate_tiled_panes(rotate_direction)
+                );
+                screen.unblock_input()?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::MirrorLayout(mirror_direction, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.mirror_tiled_panes(mirror_direction)
+                );
+                screen.unblock_input()?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::EqualizePanes(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.equalize_tiled_panes(),
+                    ?
+                );
+                screen.unblock_input()?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::ResizeTo(size, dimension, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.resize_focused_pane_to(
+                        client_id, size, dimension
+                    ),
+                    ?
+                );
+                screen.unblock_input()?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::ToggleGoldenRatio(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.toggle_golden_ratio()
+                );
+                screen.unblock_input()?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::TogglePaneInSyncGroup(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .toggle_active_pane_in_sync_group(client_id),
+                    ?
+                );
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::ClearSyncGroup(_client_id) => {
+                screen.clear_sync_group();
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::PaneJump(pane_jump_mode, all_tabs, client_id) => {
+                screen.show_pane_jump_labels(pane_jump_mode, all_tabs, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::ToggleScratchpad(name, client_id) => {
+                // input is unblocked once a new scratchpad is opened
+                match screen.toggle_scratchpad(name, client_id) {
+                    Ok(false) => {},
+                    Ok(true) => {
+                        screen.unblock_input()?;
+                        screen.render(None)?;
+                    },
+                    Err(e) => {
+                        Err::<(), _>(e).non_fatal();
+                        screen.unblock_input()?;
+                    },
+                }
+            },
+            ScreenInstruction::ScratchpadSpawned(pid, hold_for_command, name, client_id) => {
+                screen
+                    .add_scratchpad_pane(pid, hold_for_command, name, client_id)
+                    .non_fatal();
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::ScratchpadFailedToSpawn(name) => {
+                screen.pending_scratchpads.remove(&name);
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::GoToTabGroup(group, client_id) => {
+                screen.go_to_tab_group(group, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::SwitchTabInGroupNext(client_id) => {
+                screen.switch_tab_in_group(true, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::SwitchTabInGroupPrev(client_id) => {
+                screen.switch_tab_in_group(false, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::MoveTabToGroup(group, client_id) => {
+                screen.move_active_tab_to_group(group, client_id)?;
+                screen.unblock_input()?;
+                screen.render(None)?;
+            },
+            ScreenInstruction::ArrangeFloatingPanes(arrangement, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.arrange_floating_panes(arrangement),
+                    ?
+                );
+                screen.unblock_input()?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::PipePaneContentsToCli(pane_id, pipe_id, exit_status) => {
+                screen.pipe_pane_contents_to_cli(pane_id, pipe_id, exit_status)?;
+            },
+            ScreenInstruction::ListTabs(json, client_id) => {
+                screen.list_tabs(json, client_id)?;
+            },
+            ScreenInstruction::ListPanes(json, client_id) => {
+                screen.list_panes(json, client_id)?;
+            },
+            ScreenInstruction::DumpScreenForPaneId(file, pane_id, full) => {
+                let all_tabs = screen.get_tabs_mut();
+                for tab in all_tabs.values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.dump_terminal_screen(Some(file), pane_id, full)
+                            .non_fatal();
+                        break;
+                    }
+                }
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CloseTabAtPosition(tab_position) => {
+                match screen.tab_index_at_position(tab_position) {
+                    Some(tab_index) => screen.close_tab_at_index(tab_index).non_fatal(),
+                    None => log::error!("Failed to find tab at position: {}", tab_position),
+                }
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::RenameTabAtPosition(tab_position, new_name) => {
+                match screen.tab_index_at_position(tab_position) {
+                    Some(tab_index) => screen.rename_tab(tab_index, new_name)?,
+                    None => log::error!("Failed to find tab at position: {}", tab_position),
+                }
+                screen.unblock_input()?;
+            },
         }
     }
     Ok(())
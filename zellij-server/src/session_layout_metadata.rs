@@ -3,10 +3,11 @@ use crate::ClientId;
 use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use zellij_utils::common_path::common_path_all;
+use zellij_utils::data::ClientInfo;
 use zellij_utils::pane_size::PaneGeom;
 use zellij_utils::{
     input::command::RunCommand,
-    input::layout::{Layout, Run, RunPlugin, RunPluginOrAlias},
+    input::layout::{Layout, PaneLogging, Run, RunPlugin, RunPluginOrAlias},
     input::plugins::PluginAliases,
     session_serialization::{
         extract_command_and_args, extract_edit_and_line_number, extract_plugin_and_config,
@@ -21,6 +22,7 @@ pub struct SessionLayoutMetadata {
     pub default_shell: Option<PathBuf>,
     pub default_editor: Option<PathBuf>,
     tabs: Vec<TabLayoutMetadata>,
+    pub redact_env: bool,
 }
 
 impl SessionLayoutMetadata {
@@ -104,6 +106,19 @@ impl SessionLayoutMetadata {
         }
         clients_metadata
     }
+    pub fn client_infos(&self, current_client_id: ClientId) -> Vec<ClientInfo> {
+        self.all_clients_metadata()
+            .iter()
+            .map(|(client_id, client_metadata)| {
+                ClientInfo::new(
+                    *client_id,
+                    client_metadata.get_pane_id().into(),
+                    client_metadata.stringify_command(&self.default_editor),
+                    *client_id == current_client_id,
+                )
+            })
+            .collect()
+    }
     pub fn is_dirty(&self) -> bool {
         // here we check to see if the serialized layout would be different than the base one, and
         // thus is "dirty". A layout is considered dirty if one of the following is true:
@@ -173,6 +188,7 @@ impl SessionLayoutMetadata {
         name: String,
         is_focused: bool,
         hide_floating_panes: bool,
+        group: Option<String>,
         tiled_panes: Vec<PaneLayoutMetadata>,
         floating_panes: Vec<PaneLayoutMetadata>,
     ) {
@@ -180,6 +196,7 @@ impl SessionLayoutMetadata {
             name: Some(name),
             is_focused,
             hide_floating_panes,
+            group,
             tiled_panes,
             floating_panes,
         })
@@ -222,6 +239,15 @@ impl SessionLayoutMetadata {
     ) {
         let mut update_cmd_in_pane_metadata = |pane_layout_metadata: &mut PaneLayoutMetadata| {
             if let PaneId::Terminal(id) = pane_layout_metadata.id {
+                if let Some(Run::Command(RunCommand {
+                    remote: Some(_), ..
+                })) = pane_layout_metadata.run
+                {
+                    // the local process is the ssh connection, the remote pane is kept as it was
+                    // declared so that it reconnects when resurrected
+                    terminal_ids_to_commands.remove(&id);
+                    return;
+                }
                 if let Some(command) = terminal_ids_to_commands.remove(&id) {
                     let mut command_line = command.iter();
                     if let Some(command_name) = command_line.next() {
@@ -313,6 +339,7 @@ impl Into<GlobalLayoutManifest> for SessionLayoutMetadata {
             default_layout: self.default_layout,
             default_shell: self.default_shell,
             global_cwd: self.global_cwd,
+            redact_env: self.redact_env,
             tabs: self
                 .tabs
                 .into_iter()
@@ -329,6 +356,7 @@ impl Into<TabLayoutManifest> for TabLayoutMetadata {
             floating_panes: self.floating_panes.into_iter().map(|t| t.into()).collect(),
             is_focused: self.is_focused,
             hide_floating_panes: self.hide_floating_panes,
+            group: self.group,
         }
     }
 }
@@ -343,6 +371,8 @@ impl Into<PaneLayoutManifest> for PaneLayoutMetadata {
             title: self.title,
             is_focused: self.is_focused,
             pane_contents: self.pane_contents,
+            log_to_file: self.log_to_file,
+            env: self.env,
         }
     }
 }
@@ -354,6 +384,7 @@ pub struct TabLayoutMetadata {
     floating_panes: Vec<PaneLayoutMetadata>,
     is_focused: bool,
     hide_floating_panes: bool,
+    group: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -367,6 +398,8 @@ pub struct PaneLayoutMetadata {
     is_focused: bool,
     pane_contents: Option<String>,
     focused_clients: Vec<ClientId>,
+    log_to_file: Option<PaneLogging>,
+    env: BTreeMap<String, String>,
 }
 
 impl PaneLayoutMetadata {
@@ -379,6 +412,8 @@ impl PaneLayoutMetadata {
         is_focused: bool,
         pane_contents: Option<String>,
         focused_clients: Vec<ClientId>,
+        log_to_file: Option<PaneLogging>,
+        env: BTreeMap<String, String>,
     ) -> Self {
         PaneLayoutMetadata {
             id,
@@ -390,6 +425,8 @@ impl PaneLayoutMetadata {
             is_focused,
             pane_contents,
             focused_clients,
+            log_to_file,
+            env,
         }
     }
 }
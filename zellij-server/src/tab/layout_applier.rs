@@ -463,6 +463,10 @@ impl<'a> LayoutApplier<'a> {
             new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
             new_pane.handle_pty_bytes("\n\r".as_bytes().into());
         }
+        if let Some(log_to_file) = &floating_pane_layout.log_to_file {
+            new_pane.start_logging(log_to_file.clone()).non_fatal();
+        }
+        new_pane.set_env(floating_pane_layout.env.clone());
         new_pane.set_borderless(false);
         new_pane.set_content_offset(Offset::frame(1));
         if let Some(held_command) = hold_for_command {
@@ -516,6 +520,10 @@ impl<'a> LayoutApplier<'a> {
             new_pane.handle_pty_bytes(pane_initial_contents.as_bytes().into());
             new_pane.handle_pty_bytes("\n\r".as_bytes().into());
         }
+        if let Some(log_to_file) = &layout.log_to_file {
+            new_pane.start_logging(log_to_file.clone()).non_fatal();
+        }
+        new_pane.set_env(layout.env.clone());
         new_pane.set_borderless(layout.borderless);
         if let Some(exclude_from_sync) = layout.exclude_from_sync {
             new_pane.set_exclude_from_sync(exclude_from_sync);
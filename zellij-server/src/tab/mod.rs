@@ -4,6 +4,7 @@
 mod clipboard;
 mod copy_command;
 mod layout_applier;
+mod render_throttle;
 mod swap_layouts;
 
 use copy_command::CopyCommand;
@@ -11,8 +12,8 @@ use std::env::temp_dir;
 use std::path::PathBuf;
 use uuid::Uuid;
 use zellij_utils::data::{
-    Direction, KeyWithModifier, PaneInfo, PermissionStatus, PermissionType, PluginPermission,
-    ResizeStrategy,
+    Direction, FloatingPanesArrangement, KeyWithModifier, MirrorDirection, PaneInfo,
+    PermissionStatus, PermissionType, PluginPermission, ResizeStrategy, RotateDirection,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -23,8 +24,13 @@ use zellij_utils::{position::Position, serde};
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
 use crate::screen::CopyOptions;
-use crate::ui::{loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams};
+use crate::ui::{
+    loading_indication::LoadingIndication, overlay::pane_jump::PaneJumpPosition,
+    pane_boundaries_frame::FrameParams,
+};
 use layout_applier::LayoutApplier;
+use render_throttle::RenderThrottle;
+pub use render_throttle::RenderThrottleConfig;
 use swap_layouts::SwapLayouts;
 
 use self::clipboard::ClipboardProvider;
@@ -45,7 +51,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::time::Instant;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     str,
 };
 use zellij_utils::{
@@ -53,10 +59,11 @@ use zellij_utils::{
         Event, FloatingPaneCoordinates, InputMode, ModeInfo, Palette, PaletteColor, Style, Styling,
     },
     input::{
+        actions::{JoinPanePlacement, PaneDimension},
         command::TerminalAction,
         layout::{
-            FloatingPaneLayout, Run, RunPluginOrAlias, SwapFloatingLayout, SwapTiledLayout,
-            TiledPaneLayout,
+            FloatingPaneLayout, PaneLogging, Run, RunPluginOrAlias, SplitDirection, SplitSize,
+            SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
         },
         parse_keys,
     },
@@ -152,9 +159,13 @@ pub(crate) struct Tab {
     pub position: usize,
     pub name: String,
     pub prev_name: String,
+    pub group: Option<String>, // the tab group this tab belongs to
     tiled_panes: TiledPanes,
     floating_panes: FloatingPanes,
     suppressed_panes: SuppressedPanes,
+    // the hidden floating panes tucked away in the suppressed panes while a pane is shown on its
+    // own over them (eg. a scratchpad), put back once it leaves the floating panes
+    stashed_floating_panes: Option<(PaneId, Vec<PaneId>)>,
     max_panes: Option<usize>,
     viewport: Rc<RefCell<Viewport>>, // includes all non-UI panes
     display_area: Rc<RefCell<Size>>, // includes all panes (including eg. the status bar and tab bar in the default layout)
@@ -196,6 +207,9 @@ pub(crate) struct Tab {
     arrow_fonts: bool,
     styled_underlines: bool,
     explicitly_disable_kitty_keyboard_protocol: bool,
+    render_throttle: RenderThrottle,
+    golden_ratio: bool,
+    golden_ratio_pane_id: Option<PaneId>, // the pane last resized to the golden ratio
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -528,6 +542,22 @@ pub trait Pane {
     fn toggle_pinned(&mut self) {}
     fn set_pinned(&mut self, _should_be_pinned: bool) {}
     fn reset_logical_position(&mut self) {}
+    fn set_throttled(&mut self, _is_throttled: bool) {} // only relevant to terminal panes
+    fn set_in_sync_group(&mut self, _in_sync_group: bool) {} // only relevant to terminal panes
+    fn in_sync_group(&self) -> bool {
+        false
+    }
+    fn start_logging(&mut self, _pane_logging: PaneLogging) -> Result<()> {
+        Err(anyhow!("only terminal panes can be logged to a file"))
+    }
+    fn stop_logging(&mut self) {}
+    fn log_to_file(&self) -> Option<&PaneLogging> {
+        None
+    }
+    fn set_env(&mut self, _env: BTreeMap<String, String>) {} // only relevant to terminal panes
+    fn env(&self) -> Option<&BTreeMap<String, String>> {
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -591,6 +621,7 @@ impl Tab {
         styled_underlines: bool,
         explicitly_disable_kitty_keyboard_protocol: bool,
         default_editor: Option<PathBuf>,
+        render_throttle_config: RenderThrottleConfig,
     ) -> Self {
         let name = if name.is_empty() {
             format!("Tab #{}", index + 1)
@@ -649,8 +680,10 @@ impl Tab {
             tiled_panes,
             floating_panes,
             suppressed_panes: HashMap::new(),
+            stashed_floating_panes: None,
             name: name.clone(),
             prev_name: name,
+            group: None,
             max_panes,
             viewport,
             display_area,
@@ -687,6 +720,9 @@ impl Tab {
             styled_underlines,
             explicitly_disable_kitty_keyboard_protocol,
             default_editor,
+            render_throttle: RenderThrottle::new(render_throttle_config),
+            golden_ratio: false,
+            golden_ratio_pane_id: None,
         }
     }
 
@@ -699,6 +735,9 @@ impl Tab {
         new_plugin_ids: HashMap<RunPluginOrAlias, Vec<u32>>,
         client_id: ClientId,
     ) -> Result<()> {
+        if layout.tab_group.is_some() {
+            self.group = layout.tab_group.clone();
+        }
         self.swap_layouts
             .set_base_layout((layout.clone(), floating_panes_layout.clone()));
         if let Ok(should_show_floating_panes) = LayoutApplier::new(
@@ -1071,6 +1110,9 @@ impl Tab {
         default_shell: Option<TerminalAction>,
     ) -> Result<()> {
         if self.floating_panes.panes_are_visible() {
+            if let Some((pane_id, _)) = self.stashed_floating_panes {
+                self.restore_floating_panes_stashed_for(pane_id);
+            }
             self.hide_floating_panes();
             self.set_force_render();
         } else {
@@ -1478,6 +1520,7 @@ impl Tab {
                 self.set_should_clear_display_before_rendering();
                 self.tiled_panes.focus_pane(pid, client_id);
                 self.swap_layouts.set_is_tiled_damaged();
+                self.resize_focused_pane_to_golden_ratio();
             }
         } else {
             log::error!("No room to split pane horizontally");
@@ -1538,6 +1581,7 @@ impl Tab {
                 self.set_should_clear_display_before_rendering();
                 self.tiled_panes.focus_pane(pid, client_id);
                 self.swap_layouts.set_is_tiled_damaged();
+                self.resize_focused_pane_to_golden_ratio();
             }
         } else {
             log::error!("No room to split pane vertically");
@@ -1713,6 +1757,9 @@ impl Tab {
     fn process_pty_bytes(&mut self, pid: u32, bytes: VteBytes) -> Result<()> {
         let err_context = || format!("failed to process pty bytes from pid {pid}");
 
+        let pane_is_focused = self.pane_is_focused_by_any_client(PaneId::Terminal(pid));
+        let pane_is_hidden = self.pane_is_hidden(PaneId::Terminal(pid));
+        let mut should_schedule_render = false;
         if let Some(terminal_output) = self
             .tiled_panes
             .get_pane_mut(PaneId::Terminal(pid))
@@ -1733,7 +1780,33 @@ impl Tab {
                 )
                 .with_context(err_context)?;
             }
+            let byte_count = bytes.len();
             terminal_output.handle_pty_bytes(bytes);
+            let had_deferred_render = self.render_throttle.has_deferred_render(pid);
+            if self
+                .render_throttle
+                .should_defer_render(pid, pane_is_focused, Instant::now())
+            {
+                // the bytes were parsed, but we only render them once the render interval
+                // elapsed (see Tab::render)
+                terminal_output.set_should_render(false);
+                should_schedule_render = !had_deferred_render;
+            }
+            terminal_output.set_throttled(self.render_throttle.is_throttled(pid));
+            if pane_is_hidden {
+                if self
+                    .render_throttle
+                    .should_pause_hidden_pane(pid, byte_count)
+                {
+                    self.senders
+                        .send_to_pty(PtyInstruction::PauseReading(pid))
+                        .with_context(err_context)?;
+                }
+            } else if self.render_throttle.pane_is_visible(pid) {
+                self.senders
+                    .send_to_pty(PtyInstruction::ResumeReading(pid))
+                    .with_context(err_context)?;
+            }
             let messages_to_pty = terminal_output.drain_messages_to_pty();
             let clipboard_update = terminal_output.drain_clipboard_update();
             for message in messages_to_pty {
@@ -1745,6 +1818,72 @@ impl Tab {
                     .with_context(err_context)?;
             }
         }
+        if should_schedule_render {
+            self.schedule_render_for_throttled_panes()
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    fn pane_is_focused_by_any_client(&self, pane_id: PaneId) -> bool {
+        let connected_clients: Vec<ClientId> =
+            { self.connected_clients.borrow().iter().copied().collect() };
+        connected_clients
+            .iter()
+            .any(|client_id| self.get_active_pane_id(*client_id) == Some(pane_id))
+    }
+    fn pane_is_hidden(&self, pane_id: PaneId) -> bool {
+        // a pane is hidden if no client is looking at this tab or if it's suppressed
+        self.connected_clients.borrow().is_empty()
+            || !self.has_non_suppressed_pane_with_pid(&pane_id)
+    }
+    fn schedule_render_for_throttled_panes(&self) -> Result<()> {
+        if let Some(interval) = self.render_throttle.interval() {
+            self.senders
+                .send_to_background_jobs(BackgroundJob::RenderThrottledPanes(interval))
+                .context("failed to schedule render for throttled panes")?;
+        }
+        Ok(())
+    }
+    fn remove_pane_from_render_throttle(&mut self, pane_id: PaneId) {
+        if let PaneId::Terminal(terminal_id) = pane_id {
+            if self.render_throttle.remove_pane(terminal_id) {
+                // the pane is moving elsewhere or closing, we should not leave its pty paused
+                self.senders
+                    .send_to_pty(PtyInstruction::ResumeReading(terminal_id))
+                    .context("failed to resume reading from pty")
+                    .non_fatal();
+            }
+        }
+    }
+    fn render_throttled_panes_as_needed(&mut self) -> Result<()> {
+        let (due_renders, should_schedule_render) =
+            self.render_throttle.take_due_renders(Instant::now());
+        for terminal_id in due_renders {
+            let is_throttled = self.render_throttle.is_throttled(terminal_id);
+            if let Some(pane) = self
+                .tiled_panes
+                .get_pane_mut(PaneId::Terminal(terminal_id))
+                .or_else(|| {
+                    self.floating_panes
+                        .get_pane_mut(PaneId::Terminal(terminal_id))
+                })
+            {
+                pane.set_should_render(true);
+                pane.set_throttled(is_throttled);
+            }
+        }
+        if should_schedule_render {
+            self.schedule_render_for_throttled_panes()?;
+        }
+        for terminal_id in self.render_throttle.paused_panes() {
+            if !self.pane_is_hidden(PaneId::Terminal(terminal_id))
+                && self.render_throttle.pane_is_visible(terminal_id)
+            {
+                self.senders
+                    .send_to_pty(PtyInstruction::ResumeReading(terminal_id))
+                    .context("failed to resume reading from pty")?;
+            }
+        }
         Ok(())
     }
 
@@ -2111,6 +2250,45 @@ impl Tab {
     pub fn toggle_sync_panes_is_active(&mut self) {
         self.synchronize_is_active = !self.synchronize_is_active;
     }
+    pub fn toggle_active_pane_in_sync_group(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to toggle sync group for client {client_id}");
+        let active_pane = self
+            .get_active_pane_or_floating_pane_mut(client_id)
+            .with_context(|| format!("no active pane for client {client_id}"))
+            .with_context(err_context)?;
+        match active_pane.pid() {
+            PaneId::Terminal(_) => {
+                let in_sync_group = active_pane.in_sync_group();
+                active_pane.set_in_sync_group(!in_sync_group);
+            },
+            PaneId::Plugin(_) => {
+                log::error!("Only terminal panes can be added to the sync group");
+            },
+        }
+        Ok(())
+    }
+    pub fn active_pane_is_in_sync_group(&self, client_id: ClientId) -> bool {
+        self.get_active_pane(client_id)
+            .map(|pane| pane.in_sync_group())
+            .unwrap_or(false)
+    }
+    pub fn pane_ids_in_sync_group(&self) -> Vec<PaneId> {
+        self.get_static_and_floating_pane_ids()
+            .into_iter()
+            .filter(|pane_id| {
+                self.get_pane_with_id(*pane_id)
+                    .map(|pane| pane.in_sync_group())
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+    pub fn clear_sync_group(&mut self) {
+        for pane_id in self.pane_ids_in_sync_group() {
+            if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
+                pane.set_in_sync_group(false);
+            }
+        }
+    }
     pub fn mark_active_pane_for_rerender(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_mut(client_id) {
             active_pane.set_should_render(true);
@@ -2142,6 +2320,8 @@ impl Tab {
         }
         self.update_active_panes_in_pty_thread()
             .with_context(err_context)?;
+        self.render_throttled_panes_as_needed()
+            .with_context(err_context)?;
 
         let floating_panes_stack = self.floating_panes.stack();
         output.add_clients(
@@ -2389,6 +2569,7 @@ impl Tab {
             return;
         }
         self.tiled_panes.focus_next_pane(client_id);
+        self.resize_focused_pane_to_golden_ratio();
     }
     pub fn focus_previous_pane(&mut self, client_id: ClientId) {
         if !self.has_selectable_panes() {
@@ -2399,12 +2580,14 @@ impl Tab {
             return;
         }
         self.tiled_panes.focus_previous_pane(client_id);
+        self.resize_focused_pane_to_golden_ratio();
     }
     pub fn focus_pane_on_edge(&mut self, direction: Direction, client_id: ClientId) {
         if self.floating_panes.panes_are_visible() {
             self.floating_panes.focus_pane_on_edge(direction, client_id);
         } else if self.has_selectable_panes() && !self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.focus_pane_on_edge(direction, client_id);
+            self.resize_focused_pane_to_golden_ratio();
         }
     }
     // returns a boolean that indicates whether the focus moved
@@ -2426,7 +2609,9 @@ impl Tab {
             if self.tiled_panes.fullscreen_is_active() {
                 return Ok(self.focus_pane_left_fullscreen(client_id));
             }
-            Ok(self.tiled_panes.move_focus_left(client_id))
+            let moved = self.tiled_panes.move_focus_left(client_id);
+            self.resize_focused_pane_to_golden_ratio();
+            Ok(moved)
         }
     }
     pub fn move_focus_down(&mut self, client_id: ClientId) -> Result<bool> {
@@ -2448,7 +2633,9 @@ impl Tab {
                 self.focus_pane_down_fullscreen(client_id);
                 return Ok(true);
             }
-            Ok(self.tiled_panes.move_focus_down(client_id))
+            let moved = self.tiled_panes.move_focus_down(client_id);
+            self.resize_focused_pane_to_golden_ratio();
+            Ok(moved)
         }
     }
     pub fn move_focus_up(&mut self, client_id: ClientId) -> Result<bool> {
@@ -2470,7 +2657,9 @@ impl Tab {
                 self.focus_pane_up_fullscreen(client_id);
                 return Ok(true);
             }
-            Ok(self.tiled_panes.move_focus_up(client_id))
+            let moved = self.tiled_panes.move_focus_up(client_id);
+            self.resize_focused_pane_to_golden_ratio();
+            Ok(moved)
         }
     }
     // returns a boolean that indicates whether the focus moved
@@ -2492,7 +2681,9 @@ impl Tab {
             if self.tiled_panes.fullscreen_is_active() {
                 return Ok(self.focus_pane_right_fullscreen(client_id));
             }
-            Ok(self.tiled_panes.move_focus_right(client_id))
+            let moved = self.tiled_panes.move_focus_right(client_id);
+            self.resize_focused_pane_to_golden_ratio();
+            Ok(moved)
         }
     }
     pub fn move_active_pane(&mut self, client_id: ClientId) {
@@ -2693,6 +2884,39 @@ impl Tab {
             .copied()
             .collect()
     }
+    /// The selectable panes of this tab with their titles and, for the panes that are visible,
+    /// their position on screen - ordered top to bottom and left to right
+    pub fn pane_jump_candidates(&self) -> Vec<(PaneId, String, PaneJumpPosition)> {
+        let floating_panes_are_visible = self.floating_panes.panes_are_visible();
+        let mut candidates: Vec<(PaneId, String, PaneJumpPosition)> = self
+            .tiled_panes
+            .get_panes()
+            .filter(|(_, pane)| pane.selectable())
+            .map(|(pane_id, pane)| {
+                let is_visible = !self.tiled_panes.panes_to_hide_contains(*pane_id);
+                (
+                    *pane_id,
+                    pane.current_title(),
+                    is_visible.then(|| (pane.x(), pane.y())),
+                )
+            })
+            .chain(
+                self.floating_panes
+                    .get_panes()
+                    .filter(|(_, pane)| pane.selectable())
+                    .map(|(pane_id, pane)| {
+                        (
+                            *pane_id,
+                            pane.current_title(),
+                            floating_panes_are_visible.then(|| (pane.x(), pane.y())),
+                        )
+                    }),
+            )
+            .collect();
+        candidates
+            .sort_by_key(|(_, _, position)| (position.is_none(), position.map(|(x, y)| (y, x))));
+        candidates
+    }
     pub fn set_pane_selectable(&mut self, id: PaneId, selectable: bool) {
         if self.is_pending {
             self.pending_instructions
@@ -2725,6 +2949,7 @@ impl Tab {
         );
     }
     pub fn close_pane(&mut self, id: PaneId, ignore_suppressed_panes: bool) {
+        self.remove_pane_from_render_throttle(id);
         // we need to ignore suppressed panes when we toggle a pane to be floating/embedded(tiled)
         // this is because in that case, while we do use this logic, we're not actually closing the
         // pane, we're moving it
@@ -2741,6 +2966,7 @@ impl Tab {
         if self.floating_panes.panes_contain(&id) {
             let _closed_pane = self.floating_panes.remove_pane(id);
             self.floating_panes.move_clients_out_of_pane(id);
+            self.restore_floating_panes_stashed_for(id);
             if !self.floating_panes.has_panes() {
                 self.hide_floating_panes();
             }
@@ -2768,6 +2994,7 @@ impl Tab {
                 // confusing
                 let _ = self.next_swap_layout();
             }
+            self.resize_focused_pane_to_golden_ratio();
         };
         let _ = self.senders.send_to_plugin(PluginInstruction::Update(vec![(
             None,
@@ -2780,6 +3007,7 @@ impl Tab {
         id: PaneId,
         dont_swap_if_suppressed: bool,
     ) -> Option<Box<dyn Pane>> {
+        self.remove_pane_from_render_throttle(id);
         if !dont_swap_if_suppressed && self.suppressed_panes.contains_key(&id) {
             // this is done for the scrollback editor
             return match self.replace_pane_with_suppressed_pane(id) {
@@ -2801,6 +3029,7 @@ impl Tab {
         if self.floating_panes.panes_contain(&id) {
             let mut closed_pane = self.floating_panes.remove_pane(id);
             self.floating_panes.move_clients_out_of_pane(id);
+            self.restore_floating_panes_stashed_for(id);
             if !self.floating_panes.has_panes() {
                 self.hide_floating_panes();
             }
@@ -3750,6 +3979,7 @@ impl Tab {
         if let Some(clicked_pane) = self.get_pane_id_at(point, true).with_context(err_context)? {
             self.tiled_panes.focus_pane(clicked_pane, client_id);
             self.set_pane_active_at(clicked_pane);
+            self.resize_focused_pane_to_golden_ratio();
             if self.floating_panes.panes_are_visible() {
                 self.hide_floating_panes();
                 self.set_force_render();
@@ -4165,7 +4395,10 @@ impl Tab {
         // TODO: should error if pane is not selectable
         self.tiled_panes
             .focus_pane_if_exists(pane_id, client_id)
-            .map(|_| self.hide_floating_panes())
+            .map(|_| {
+                self.hide_floating_panes();
+                self.resize_focused_pane_to_golden_ratio();
+            })
             .or_else(|_| {
                 let focused_floating_pane =
                     self.floating_panes.focus_pane_if_exists(pane_id, client_id);
@@ -4212,6 +4445,69 @@ impl Tab {
                 .insert(pane_id, (is_scrollback_editor, pane));
         }
     }
+    pub fn floating_pane_is_visible(&self, pane_id: &PaneId) -> bool {
+        self.floating_panes.panes_contain(pane_id) && self.are_floating_panes_visible()
+    }
+    /// Shows a pane taken out of this tab or another one as a focused floating pane, placed
+    /// where it was before - the other floating panes stay hidden if they were
+    pub fn show_pane_as_floating(
+        &mut self,
+        mut pane: Box<dyn Pane>,
+        pane_id: PaneId,
+    ) -> Result<()> {
+        let geom = pane.position_and_size();
+        let floating_pane_coordinates = FloatingPaneCoordinates::default()
+            .with_x_fixed(geom.x)
+            .with_y_fixed(geom.y)
+            .with_width_fixed(geom.cols.as_usize())
+            .with_height_fixed(geom.rows.as_usize());
+        pane.set_selectable(true);
+        self.stash_hidden_floating_panes(pane_id);
+        self.show_floating_panes();
+        self.add_floating_pane(pane, pane_id, Some(floating_pane_coordinates), true)
+    }
+    /// Tucks the floating panes of the tab away if they are hidden, so that the pane about to be
+    /// shown as floating is shown on its own - they are put back once it leaves the floating
+    /// panes
+    pub fn stash_hidden_floating_panes(&mut self, pane_id: PaneId) {
+        if self.are_floating_panes_visible() {
+            return;
+        }
+        if let Some((stashed_for, _)) = self.stashed_floating_panes {
+            self.restore_floating_panes_stashed_for(stashed_for);
+        }
+        // pinned panes are shown when the floating panes are hidden as well
+        let panes_to_stash: Vec<PaneId> = self
+            .floating_panes
+            .get_panes()
+            .filter(|(id, pane)| **id != pane_id && !pane.position_and_size().is_pinned)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in &panes_to_stash {
+            if let Some(pane) = self.floating_panes.remove_pane(*id) {
+                self.floating_panes.move_clients_out_of_pane(*id);
+                let is_scrollback_editor = false;
+                self.suppressed_panes
+                    .insert(*id, (is_scrollback_editor, pane));
+            }
+        }
+        if !panes_to_stash.is_empty() {
+            self.stashed_floating_panes = Some((pane_id, panes_to_stash));
+        }
+    }
+    fn restore_floating_panes_stashed_for(&mut self, pane_id: PaneId) {
+        match self.stashed_floating_panes.take() {
+            Some((stashed_for, stashed_panes)) if stashed_for == pane_id => {
+                for id in stashed_panes {
+                    if let Some((_is_scrollback_editor, pane)) = self.suppressed_panes.remove(&id) {
+                        self.floating_panes.add_pane(id, pane);
+                    }
+                }
+                self.hide_floating_panes();
+            },
+            stashed_floating_panes => self.stashed_floating_panes = stashed_floating_panes,
+        }
+    }
     pub fn pane_infos(&self) -> Vec<PaneInfo> {
         let mut pane_info = vec![];
         let mut tiled_pane_info = self.tiled_panes.pane_info();
@@ -4296,8 +4592,327 @@ impl Tab {
                                                       // next layout
             self.next_swap_layout()?;
         }
+        self.resize_focused_pane_to_golden_ratio();
+        Ok(())
+    }
+    /// Adds a pane taken out of another tab and focuses it for this client
+    pub fn join_pane(
+        &mut self,
+        mut pane: Box<dyn Pane>,
+        placement: JoinPanePlacement,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to join pane to tab for client {client_id}");
+        let pane_id = pane.pid();
+        match placement {
+            JoinPanePlacement::Floating => {
+                self.show_floating_panes();
+                self.add_floating_pane(pane, pane_id, None, true)
+                    .with_context(err_context)?;
+            },
+            JoinPanePlacement::Stacked => {
+                self.hide_floating_panes();
+                match self.tiled_panes.get_active_pane_id(client_id) {
+                    Some(root_pane_id) if self.has_room_for_stack(root_pane_id, 2) => {
+                        pane.set_active_at(Instant::now());
+                        self.stack_panes(root_pane_id, vec![pane]);
+                        self.tiled_panes.focus_pane(pane_id, client_id);
+                        self.tiled_panes.expand_pane_in_stack(pane_id);
+                        self.tiled_panes.reapply_pane_frames();
+                    },
+                    _ => {
+                        log::error!("No room to stack pane, adding it as a tiled pane instead");
+                        self.add_tiled_pane(pane, pane_id, Some(client_id))
+                            .with_context(err_context)?;
+                    },
+                }
+            },
+            JoinPanePlacement::Tiled(direction) => {
+                self.hide_floating_panes();
+                if self.tiled_panes.fullscreen_is_active() {
+                    self.tiled_panes.unset_fullscreen();
+                }
+                let can_split = match direction {
+                    Some(Direction::Left) | Some(Direction::Right) => {
+                        self.tiled_panes.can_split_pane_vertically(client_id)
+                    },
+                    Some(Direction::Up) | Some(Direction::Down) => {
+                        self.tiled_panes.can_split_pane_horizontally(client_id)
+                    },
+                    None => false,
+                };
+                match direction {
+                    Some(direction) if can_split => {
+                        pane.set_active_at(Instant::now());
+                        // the pane always ends up to the right of or below the focused pane, so we
+                        // swap them if it should have been the other way around
+                        match direction {
+                            Direction::Left | Direction::Right => {
+                                self.tiled_panes
+                                    .split_pane_vertically(pane_id, pane, client_id);
+                            },
+                            Direction::Up | Direction::Down => {
+                                self.tiled_panes
+                                    .split_pane_horizontally(pane_id, pane, client_id);
+                            },
+                        }
+                        match direction {
+                            Direction::Left => self.tiled_panes.move_pane_left(pane_id),
+                            Direction::Up => self.tiled_panes.move_pane_up(pane_id),
+                            Direction::Right | Direction::Down => {},
+                        }
+                        self.tiled_panes.focus_pane(pane_id, client_id);
+                        self.set_should_clear_display_before_rendering();
+                        self.swap_layouts.set_is_tiled_damaged();
+                    },
+                    _ => {
+                        self.add_tiled_pane(pane, pane_id, Some(client_id))
+                            .with_context(err_context)?;
+                    },
+                }
+            },
+        }
+        self.set_force_render();
+        Ok(())
+    }
+    /// The tiled pane that took up all the space of a closed pane with this geometry, and the
+    /// direction the closed pane was in from it
+    pub fn tiled_pane_covering(&self, closed_pane_geom: &PaneGeom) -> Option<(PaneId, Direction)> {
+        let closed_pane_right = closed_pane_geom.x + closed_pane_geom.cols.as_usize();
+        let closed_pane_bottom = closed_pane_geom.y + closed_pane_geom.rows.as_usize();
+        self.tiled_panes.get_panes().find_map(|(pane_id, pane)| {
+            let geom = pane.position_and_size();
+            let right = geom.x + geom.cols.as_usize();
+            let bottom = geom.y + geom.rows.as_usize();
+            let covers_closed_pane = geom.x <= closed_pane_geom.x
+                && geom.y <= closed_pane_geom.y
+                && right >= closed_pane_right
+                && bottom >= closed_pane_bottom;
+            let direction = if !covers_closed_pane {
+                return None;
+            } else if geom.x < closed_pane_geom.x {
+                Direction::Right
+            } else if right > closed_pane_right {
+                Direction::Left
+            } else if geom.y < closed_pane_geom.y {
+                Direction::Down
+            } else if bottom > closed_pane_bottom {
+                Direction::Up
+            } else {
+                return None;
+            };
+            Some((*pane_id, direction))
+        })
+    }
+    /// Moves a tiled pane of this tab to the given side of another one, splitting it
+    pub fn move_tiled_pane_next_to(
+        &mut self,
+        pane_id: PaneId,
+        other_pane_id: PaneId,
+        direction: Direction,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to move pane {pane_id:?} next to {other_pane_id:?}");
+        if pane_id == other_pane_id
+            || !self.tiled_panes.panes_contain(&pane_id)
+            || !self.tiled_panes.panes_contain(&other_pane_id)
+        {
+            return Ok(());
+        }
+        let pane = self.extract_pane(pane_id, true).with_context(err_context)?;
+        self.tiled_panes.focus_pane(other_pane_id, client_id);
+        self.join_pane(pane, JoinPanePlacement::Tiled(Some(direction)), client_id)
+            .with_context(err_context)
+    }
+    /// Exchanges the positions of two panes of this tab, a tiled and a floating pane also exchange
+    /// layers
+    pub fn swap_panes(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) -> Result<()> {
+        let err_context =
+            || format!("failed to swap panes {first_pane_id:?} and {second_pane_id:?}");
+        if first_pane_id == second_pane_id {
+            return Ok(());
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let is_tiled = |tab: &Tab, pane_id: &PaneId| tab.tiled_panes.panes_contain(pane_id);
+        let is_floating = |tab: &Tab, pane_id: &PaneId| tab.floating_panes.panes_contain(pane_id);
+        if is_tiled(self, &first_pane_id) && is_tiled(self, &second_pane_id) {
+            self.tiled_panes.swap_panes(first_pane_id, second_pane_id);
+            self.swap_layouts.set_is_tiled_damaged();
+        } else if is_floating(self, &first_pane_id) && is_floating(self, &second_pane_id) {
+            self.floating_panes
+                .swap_panes(first_pane_id, second_pane_id);
+            self.swap_layouts.set_is_floating_damaged();
+        } else if is_tiled(self, &first_pane_id) && is_floating(self, &second_pane_id) {
+            self.swap_tiled_and_floating_panes(first_pane_id, second_pane_id)
+                .with_context(err_context)?;
+        } else if is_floating(self, &first_pane_id) && is_tiled(self, &second_pane_id) {
+            self.swap_tiled_and_floating_panes(second_pane_id, first_pane_id)
+                .with_context(err_context)?;
+        } else {
+            return Err(anyhow!("both panes must be in the same tab")).with_context(err_context);
+        }
+        self.set_force_render();
+        Ok(())
+    }
+    fn swap_tiled_and_floating_panes(
+        &mut self,
+        tiled_pane_id: PaneId,
+        floating_pane_id: PaneId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!("failed to swap tiled pane {tiled_pane_id:?} with floating pane {floating_pane_id:?}")
+        };
+        let clients_in_floating_pane: Vec<ClientId> = self
+            .connected_clients
+            .borrow()
+            .iter()
+            .copied()
+            .filter(|client_id| {
+                self.floating_panes.get_active_pane_id(*client_id) == Some(floating_pane_id)
+            })
+            .collect();
+        self.floating_panes
+            .move_clients_out_of_pane(floating_pane_id);
+        let mut floating_pane = self
+            .floating_panes
+            .remove_pane(floating_pane_id)
+            .with_context(err_context)?;
+        let floating_geom = floating_pane.position_and_size();
+        floating_pane.set_active_at(Instant::now());
+        let mut tiled_pane = self
+            .tiled_panes
+            .replace_pane(tiled_pane_id, floating_pane)
+            .with_context(err_context)?;
+        tiled_pane.set_geom(floating_geom);
+        tiled_pane.reset_size_and_position_override();
+        tiled_pane.set_should_render(true);
+        self.floating_panes.add_pane(tiled_pane_id, tiled_pane);
+        for client_id in clients_in_floating_pane {
+            self.floating_panes.focus_pane(tiled_pane_id, client_id);
+        }
+        self.swap_layouts.set_is_tiled_damaged();
+        self.swap_layouts.set_is_floating_damaged();
+        self.floating_panes
+            .set_pane_frames()
+            .with_context(err_context)
+    }
+    pub fn rotate_tiled_panes(&mut self, rotate_direction: RotateDirection) {
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes.rotate_panes(rotate_direction);
+        self.swap_layouts.set_is_tiled_damaged();
+        self.set_force_render();
+    }
+    pub fn mirror_tiled_panes(&mut self, mirror_direction: MirrorDirection) {
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes.mirror_panes(mirror_direction);
+        self.swap_layouts.set_is_tiled_damaged();
+        self.set_force_render();
+    }
+    pub fn arrange_floating_panes(&mut self, arrangement: FloatingPanesArrangement) -> Result<()> {
+        if !self.floating_panes.has_panes() {
+            return Ok(());
+        }
+        self.floating_panes
+            .arrange_panes(arrangement)
+            .with_context(|| format!("failed to arrange floating panes in tab {}", self.index))?;
+        self.swap_layouts.set_is_floating_damaged();
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn equalize_tiled_panes(&mut self) -> Result<()> {
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes
+            .equalize_panes()
+            .with_context(|| format!("failed to equalize panes in tab {}", self.index))?;
+        self.swap_layouts.set_is_tiled_damaged();
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn resize_focused_pane_to(
+        &mut self,
+        client_id: ClientId,
+        size: SplitSize,
+        dimension: PaneDimension,
+    ) -> Result<()> {
+        let err_context = || format!("failed to resize focused pane to {:?}", size);
+        if self.floating_panes.panes_are_visible() {
+            if let Some(pane_id) = self.floating_panes.get_active_pane_id(client_id) {
+                let new_coordinates = match dimension {
+                    PaneDimension::Width => FloatingPaneCoordinates {
+                        width: Some(size),
+                        ..Default::default()
+                    },
+                    PaneDimension::Height => FloatingPaneCoordinates {
+                        height: Some(size),
+                        ..Default::default()
+                    },
+                };
+                self.floating_panes
+                    .change_pane_coordinates(pane_id, new_coordinates)
+                    .with_context(err_context)?;
+                self.swap_layouts.set_is_floating_damaged();
+                self.set_force_render();
+                return Ok(());
+            }
+        }
+        let Some(pane_id) = self.tiled_panes.get_active_pane_id(client_id) else {
+            return Ok(());
+        };
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let direction = match dimension {
+            PaneDimension::Width => SplitDirection::Horizontal,
+            PaneDimension::Height => SplitDirection::Vertical,
+        };
+        let pane_size_changed = self
+            .tiled_panes
+            .resize_pane_to(pane_id, direction, size)
+            .with_context(err_context)?;
+        if pane_size_changed {
+            self.swap_layouts.set_is_tiled_damaged();
+            self.set_force_render();
+        } else {
+            log::error!(
+                "Cannot resize pane {:?}, it has no siblings to take the {:?} from",
+                pane_id,
+                dimension
+            );
+        }
         Ok(())
     }
+    pub fn toggle_golden_ratio(&mut self) {
+        self.golden_ratio = !self.golden_ratio;
+        self.golden_ratio_pane_id = None;
+        self.resize_focused_pane_to_golden_ratio();
+        self.set_force_render();
+    }
+    // called whenever the focus moves to another tiled pane
+    fn resize_focused_pane_to_golden_ratio(&mut self) {
+        if !self.golden_ratio || self.tiled_panes.fullscreen_is_active() {
+            return;
+        }
+        let focused_pane_id = self.tiled_panes.first_active_pane_id();
+        if focused_pane_id.is_none() || focused_pane_id == self.golden_ratio_pane_id {
+            return;
+        }
+        self.golden_ratio_pane_id = focused_pane_id;
+        if let Some(pane_id) = focused_pane_id {
+            self.tiled_panes
+                .resize_pane_to_golden_ratio(pane_id)
+                .non_fatal();
+            self.swap_layouts.set_is_tiled_damaged();
+            self.set_force_render();
+        }
+    }
     pub fn request_plugin_permissions(&mut self, pid: u32, permissions: Option<PluginPermission>) {
         let mut should_focus_pane = false;
         if let Some(plugin_pane) = self
@@ -4466,6 +5081,22 @@ impl Tab {
             self.set_force_render();
         }
     }
+    pub fn toggle_active_pane_logging(
+        &mut self,
+        pane_logging: PaneLogging,
+        client_id: ClientId,
+    ) -> Result<()> {
+        if let Some(pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            if pane.log_to_file().is_some() {
+                pane.stop_logging();
+            } else {
+                pane.start_logging(pane_logging)
+                    .with_context(|| format!("failed to log active pane of client {client_id}"))?;
+            }
+            self.set_force_render();
+        }
+        Ok(())
+    }
     pub fn set_floating_pane_pinned(&mut self, pane_id: PaneId, should_be_pinned: bool) {
         if let Some(pane) = self.get_pane_with_id_mut(pane_id) {
             pane.set_pinned(should_be_pinned);
@@ -4600,6 +5231,9 @@ pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     pane_info.pane_content_columns = pane.get_content_columns();
     pane_info.cursor_coordinates_in_pane = pane.cursor_coordinates();
     pane_info.is_selectable = pane.selectable();
+    pane_info.log_file = pane
+        .log_to_file()
+        .map(|pane_logging| pane_logging.file.display().to_string());
     pane_info.title = pane.current_title();
     pane_info.exited = pane.exited();
     pane_info.exit_status = pane.exit_status();
@@ -0,0 +1,158 @@
+//! Coalesces the renders of panes producing a lot of output while they are not focused, and
+//! keeps track of hidden panes whose pty we stopped reading from.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderThrottleConfig {
+    pub unfocused_pane_render_interval: Option<Duration>,
+    pub hidden_pane_pause_threshold: Option<usize>,
+}
+
+impl RenderThrottleConfig {
+    pub fn new(
+        unfocused_pane_render_interval: Option<u64>,
+        hidden_pane_pause_threshold: Option<usize>,
+    ) -> Self {
+        // 0 means "disabled" for both options
+        RenderThrottleConfig {
+            unfocused_pane_render_interval: unfocused_pane_render_interval
+                .filter(|interval| *interval > 0)
+                .map(Duration::from_millis),
+            hidden_pane_pause_threshold: hidden_pane_pause_threshold
+                .filter(|threshold| *threshold > 0),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RenderThrottle {
+    config: RenderThrottleConfig,
+    last_render_at: HashMap<u32, Instant>, // u32 - terminal_id
+    throttled_panes: HashSet<u32>,
+    panes_with_deferred_renders: HashSet<u32>,
+    bytes_while_hidden: HashMap<u32, usize>,
+    paused_panes: HashSet<u32>,
+}
+
+impl RenderThrottle {
+    pub fn new(config: RenderThrottleConfig) -> Self {
+        RenderThrottle {
+            config,
+            ..Default::default()
+        }
+    }
+    pub fn interval(&self) -> Option<Duration> {
+        self.config.unfocused_pane_render_interval
+    }
+    /// Called after a pane parsed some bytes, returns true if rendering them should be deferred
+    /// to a later render
+    pub fn should_defer_render(
+        &mut self,
+        terminal_id: u32,
+        is_focused: bool,
+        now: Instant,
+    ) -> bool {
+        let interval = match self.config.unfocused_pane_render_interval {
+            Some(interval) if !is_focused => interval,
+            _ => {
+                self.last_render_at.insert(terminal_id, now);
+                self.panes_with_deferred_renders.remove(&terminal_id);
+                self.throttled_panes.remove(&terminal_id);
+                return false;
+            },
+        };
+        match self.last_render_at.get(&terminal_id) {
+            Some(last_render_at) if now.duration_since(*last_render_at) < interval => {
+                self.throttled_panes.insert(terminal_id);
+                self.panes_with_deferred_renders.insert(terminal_id);
+                true
+            },
+            _ => {
+                self.last_render_at.insert(terminal_id, now);
+                false
+            },
+        }
+    }
+    /// Returns the panes whose deferred renders are due, as well as whether another render needs
+    /// to be scheduled (so that panes which stopped producing output can be un-throttled)
+    pub fn take_due_renders(&mut self, now: Instant) -> (Vec<u32>, bool) {
+        let interval = match self.config.unfocused_pane_render_interval {
+            Some(interval) => interval,
+            None => {
+                let due = self.throttled_panes.drain().collect();
+                self.panes_with_deferred_renders.clear();
+                return (due, false);
+            },
+        };
+        let mut due = vec![];
+        let mut should_schedule_render = false;
+        for terminal_id in self.throttled_panes.iter().copied().collect::<Vec<_>>() {
+            let render_is_due = self
+                .last_render_at
+                .get(&terminal_id)
+                .map(|last_render_at| now.duration_since(*last_render_at) >= interval)
+                .unwrap_or(true);
+            if !render_is_due {
+                // a render was already scheduled when this pane's render was first deferred
+                continue;
+            }
+            if self.panes_with_deferred_renders.remove(&terminal_id) {
+                // the pane is still producing output, we render it and keep it throttled
+                self.last_render_at.insert(terminal_id, now);
+                should_schedule_render = true;
+            } else {
+                // no output since the last render, render once more to clear the indication
+                self.throttled_panes.remove(&terminal_id);
+            }
+            due.push(terminal_id);
+        }
+        (due, should_schedule_render)
+    }
+    pub fn has_deferred_render(&self, terminal_id: u32) -> bool {
+        self.panes_with_deferred_renders.contains(&terminal_id)
+    }
+    pub fn is_throttled(&self, terminal_id: u32) -> bool {
+        self.throttled_panes.contains(&terminal_id)
+    }
+    /// Called when a hidden pane parsed some bytes, returns true if we should stop reading from
+    /// its pty
+    pub fn should_pause_hidden_pane(&mut self, terminal_id: u32, byte_count: usize) -> bool {
+        let threshold = match self.config.hidden_pane_pause_threshold {
+            Some(threshold) => threshold,
+            None => return false,
+        };
+        if self.paused_panes.contains(&terminal_id) {
+            return false;
+        }
+        let bytes_while_hidden = self.bytes_while_hidden.entry(terminal_id).or_insert(0);
+        *bytes_while_hidden += byte_count;
+        if *bytes_while_hidden >= threshold {
+            self.paused_panes.insert(terminal_id);
+            true
+        } else {
+            false
+        }
+    }
+    /// Called when a pane is visible, returns true if we should resume reading from its pty
+    pub fn pane_is_visible(&mut self, terminal_id: u32) -> bool {
+        self.bytes_while_hidden.remove(&terminal_id);
+        self.paused_panes.remove(&terminal_id)
+    }
+    pub fn paused_panes(&self) -> Vec<u32> {
+        self.paused_panes.iter().copied().collect()
+    }
+    /// Returns true if the pane's pty was paused
+    pub fn remove_pane(&mut self, terminal_id: u32) -> bool {
+        self.last_render_at.remove(&terminal_id);
+        self.throttled_panes.remove(&terminal_id);
+        self.panes_with_deferred_renders.remove(&terminal_id);
+        self.bytes_while_hidden.remove(&terminal_id);
+        self.paused_panes.remove(&terminal_id)
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/render_throttle_tests.rs"]
+mod render_throttle_tests;
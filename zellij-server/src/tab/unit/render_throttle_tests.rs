@@ -0,0 +1,91 @@
+use super::{RenderThrottle, RenderThrottleConfig};
+use std::time::{Duration, Instant};
+
+fn render_throttle(interval_ms: u64, pause_threshold: usize) -> RenderThrottle {
+    RenderThrottle::new(RenderThrottleConfig::new(
+        Some(interval_ms),
+        Some(pause_threshold),
+    ))
+}
+
+#[test]
+fn zero_means_disabled() {
+    let config = RenderThrottleConfig::new(Some(0), Some(0));
+    assert_eq!(config, RenderThrottleConfig::default());
+}
+
+#[test]
+fn focused_panes_are_never_throttled() {
+    let mut render_throttle = render_throttle(100, 0);
+    let now = Instant::now();
+    assert!(!render_throttle.should_defer_render(1, true, now));
+    assert!(!render_throttle.should_defer_render(1, true, now));
+    assert!(!render_throttle.is_throttled(1));
+}
+
+#[test]
+fn unfocused_pane_renders_are_deferred_within_interval() {
+    let mut render_throttle = render_throttle(100, 0);
+    let now = Instant::now();
+    assert!(
+        !render_throttle.should_defer_render(1, false, now),
+        "first render is not deferred"
+    );
+    assert!(render_throttle.should_defer_render(1, false, now + Duration::from_millis(10)));
+    assert!(render_throttle.is_throttled(1));
+    let (due, should_schedule_render) =
+        render_throttle.take_due_renders(now + Duration::from_millis(50));
+    assert!(
+        due.is_empty(),
+        "render is not due before the interval elapsed"
+    );
+    assert!(!should_schedule_render);
+    assert!(!render_throttle.should_defer_render(1, false, now + Duration::from_millis(150)));
+}
+
+#[test]
+fn deferred_renders_are_flushed_and_throttling_stops_once_output_stops() {
+    let mut render_throttle = render_throttle(100, 0);
+    let now = Instant::now();
+    render_throttle.should_defer_render(1, false, now);
+    render_throttle.should_defer_render(1, false, now + Duration::from_millis(10));
+    let (due, should_schedule_render) =
+        render_throttle.take_due_renders(now + Duration::from_millis(100));
+    assert_eq!(due, vec![1]);
+    assert!(
+        should_schedule_render,
+        "we check again after another interval"
+    );
+    assert!(render_throttle.is_throttled(1));
+    let (due, should_schedule_render) =
+        render_throttle.take_due_renders(now + Duration::from_millis(200));
+    assert_eq!(due, vec![1], "one more render to clear the indication");
+    assert!(!should_schedule_render);
+    assert!(!render_throttle.is_throttled(1));
+}
+
+#[test]
+fn hidden_panes_are_paused_after_threshold() {
+    let mut render_throttle = render_throttle(0, 100);
+    assert!(!render_throttle.should_pause_hidden_pane(1, 60));
+    assert!(render_throttle.should_pause_hidden_pane(1, 60));
+    assert!(
+        !render_throttle.should_pause_hidden_pane(1, 60),
+        "already paused"
+    );
+    assert_eq!(render_throttle.paused_panes(), vec![1]);
+    assert!(render_throttle.pane_is_visible(1));
+    assert!(render_throttle.paused_panes().is_empty());
+    assert!(
+        !render_throttle.should_pause_hidden_pane(1, 60),
+        "byte count was reset"
+    );
+}
+
+#[test]
+fn removing_a_paused_pane_reports_it() {
+    let mut render_throttle = render_throttle(0, 10);
+    render_throttle.should_pause_hidden_pane(1, 20);
+    assert!(render_throttle.remove_pane(1));
+    assert!(!render_throttle.remove_pane(1));
+}
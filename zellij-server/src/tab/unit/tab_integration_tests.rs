@@ -1,4 +1,4 @@
-use super::{Output, Tab};
+use super::{Output, RenderThrottleConfig, Tab};
 use crate::panes::sixel::SixelImageStore;
 use crate::screen::CopyOptions;
 use crate::Arc;
@@ -10,6 +10,7 @@ use crate::{
     thread_bus::ThreadSenders,
     ClientId,
 };
+use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
@@ -124,6 +125,9 @@ impl ServerOsApi for FakeInputOutput {
         self.file_dumps.lock().to_anyhow()?.insert(f, buf);
         Ok(())
     }
+    fn create_file(&mut self, _name: String) -> Result<Box<dyn Write + Send>> {
+        unimplemented!()
+    }
     fn re_run_command_in_terminal(
         &self,
         _terminal_id: u32,
@@ -257,6 +261,7 @@ fn create_new_tab(size: Size, default_mode: ModeInfo) -> Tab {
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -340,6 +345,7 @@ fn create_new_tab_with_swap_layouts(
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     let (
         base_layout,
@@ -424,6 +430,7 @@ fn create_new_tab_with_os_api(
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -494,6 +501,7 @@ fn create_new_tab_with_layout(size: Size, default_mode: ModeInfo, layout: &str)
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     let pane_ids = tab_layout
         .extract_run_instructions()
@@ -578,6 +586,7 @@ fn create_new_tab_with_mock_pty_writer(
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -653,6 +662,7 @@ fn create_new_tab_with_sixel_support(
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
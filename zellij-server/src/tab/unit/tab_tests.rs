@@ -1,4 +1,4 @@
-use super::Tab;
+use super::{RenderThrottleConfig, Tab};
 use crate::panes::sixel::SixelImageStore;
 use crate::screen::CopyOptions;
 use crate::{
@@ -7,9 +7,11 @@ use crate::{
     thread_bus::ThreadSenders,
     ClientId,
 };
+use std::io::Write;
 use std::path::PathBuf;
 use zellij_utils::data::{Direction, Resize, ResizeStrategy};
 use zellij_utils::errors::prelude::*;
+use zellij_utils::input::actions::PaneDimension;
 use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
 use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
@@ -93,6 +95,9 @@ impl ServerOsApi for FakeInputOutput {
     fn write_to_file(&mut self, _buf: String, _name: Option<String>) -> Result<()> {
         unimplemented!()
     }
+    fn create_file(&mut self, _name: String) -> Result<Box<dyn Write + Send>> {
+        unimplemented!()
+    }
     fn re_run_command_in_terminal(
         &self,
         _terminal_id: u32,
@@ -197,6 +202,7 @@ fn create_new_tab(size: Size, stacked_resize: bool) -> Tab {
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -264,6 +270,7 @@ fn create_new_tab_with_layout(size: Size, layout: TiledPaneLayout) -> Tab {
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     let mut new_terminal_ids = vec![];
     for i in 0..layout.extract_run_instructions().len() {
@@ -337,6 +344,7 @@ fn create_new_tab_with_cell_size(
         styled_underlines,
         explicitly_disable_kitty_keyboard_protocol,
         None,
+        RenderThrottleConfig::default(),
     );
     tab.apply_layout(
         TiledPaneLayout::default(),
@@ -14641,3 +14649,128 @@ fn correctly_resize_frameless_panes_on_pane_close() {
     let content_size = (pane.get_content_columns(), pane.get_content_rows());
     assert_eq!(content_size, (cols, rows));
 }
+
+#[test]
+fn equalize_tiled_panes() {
+    let size = Size {
+        cols: 120,
+        rows: 20,
+    };
+    let stacked_resize = true;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.vertical_split(PaneId::Terminal(3), None, 1).unwrap();
+    tab.equalize_tiled_panes().unwrap();
+    for pane_id in [
+        PaneId::Terminal(1),
+        PaneId::Terminal(2),
+        PaneId::Terminal(3),
+    ] {
+        assert_eq!(
+            tab.tiled_panes
+                .panes
+                .get(&pane_id)
+                .unwrap()
+                .position_and_size()
+                .cols
+                .as_usize(),
+            40,
+            "pane {:?} column count",
+            pane_id
+        );
+    }
+}
+
+#[test]
+fn golden_ratio_is_applied_when_the_focus_moves() {
+    let size = Size {
+        cols: 120,
+        rows: 20,
+    };
+    let stacked_resize = true;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.toggle_golden_ratio();
+    let cols = |tab: &Tab, pane_id: PaneId| {
+        tab.tiled_panes
+            .panes
+            .get(&pane_id)
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    assert!(
+        cols(&tab, PaneId::Terminal(2)) > cols(&tab, PaneId::Terminal(1)),
+        "focused pane is the larger one once the golden ratio is toggled"
+    );
+    tab.move_focus_left(1).unwrap();
+    assert!(
+        cols(&tab, PaneId::Terminal(1)) > cols(&tab, PaneId::Terminal(2)),
+        "pane the focus moved to is the larger one, without rendering"
+    );
+}
+
+#[test]
+fn resize_focused_pane_to_percent_of_tab() {
+    let size = Size {
+        cols: 120,
+        rows: 20,
+    };
+    let stacked_resize = true;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    let focused_pane_id = tab.get_active_pane_id(1).unwrap();
+    tab.resize_focused_pane_to(1, SplitSize::Percent(25), PaneDimension::Width)
+        .unwrap();
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&focused_pane_id)
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        30,
+        "focused pane column count"
+    );
+    for pane_id in [PaneId::Terminal(1), PaneId::Terminal(2)] {
+        assert_eq!(
+            tab.tiled_panes
+                .panes
+                .get(&pane_id)
+                .unwrap()
+                .position_and_size()
+                .rows
+                .as_usize(),
+            20,
+            "pane {:?} row count is unchanged",
+            pane_id
+        );
+    }
+}
+
+#[test]
+fn resize_focused_pane_to_column_count() {
+    let size = Size {
+        cols: 120,
+        rows: 20,
+    };
+    let stacked_resize = true;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.horizontal_split(PaneId::Terminal(2), None, 1).unwrap();
+    let focused_pane_id = tab.get_active_pane_id(1).unwrap();
+    tab.resize_focused_pane_to(1, SplitSize::Fixed(5), PaneDimension::Height)
+        .unwrap();
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&focused_pane_id)
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        5,
+        "focused pane row count"
+    );
+}
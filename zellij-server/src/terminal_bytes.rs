@@ -6,6 +6,10 @@ use crate::{
 use async_std::{future::timeout as async_timeout, task};
 use std::{
     os::unix::io::RawFd,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use zellij_utils::{
@@ -14,6 +18,8 @@ use zellij_utils::{
     logging::debug_to_file,
 };
 
+const PAUSED_READING_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 enum ReadResult {
     Ok(usize),
     Timeout,
@@ -40,6 +46,7 @@ pub(crate) struct TerminalBytes {
     minimum_render_send_time: Option<Duration>,
     buffering_pause: Duration,
     last_render: Instant,
+    reading_paused: Arc<AtomicBool>,
 }
 
 impl TerminalBytes {
@@ -49,6 +56,7 @@ impl TerminalBytes {
         os_input: Box<dyn ServerOsApi>,
         debug: bool,
         terminal_id: u32,
+        reading_paused: Arc<AtomicBool>,
     ) -> Self {
         TerminalBytes {
             pid,
@@ -61,6 +69,7 @@ impl TerminalBytes {
             minimum_render_send_time: None,
             buffering_pause: Duration::from_millis(30),
             last_render: Instant::now(),
+            reading_paused,
         }
     }
     pub async fn listen(&mut self) -> Result<()> {
@@ -81,6 +90,12 @@ impl TerminalBytes {
         err_ctx.add_call(ContextType::AsyncTask);
         let mut buf = [0u8; 65536];
         loop {
+            if self.reading_paused.load(Ordering::SeqCst) {
+                // the pane is hidden and has produced a lot of output, we stop reading so that
+                // the process writing to it blocks until the pane is visible again
+                task::sleep(PAUSED_READING_POLL_INTERVAL).await;
+                continue;
+            }
             match self.deadline_read(&mut buf).await {
                 ReadResult::Ok(0) | ReadResult::Err(_) => break, // EOF or error
                 ReadResult::Timeout => {
@@ -5,10 +5,13 @@
 //! prompt's:
 //!
 //! notification's:
+//!
+//! pane jump labels:
 
+pub mod pane_jump;
 pub mod prompt;
 
-use crate::ServerInstruction;
+use crate::{ClientId, ServerInstruction};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::pane_size::Size;
 
@@ -26,6 +29,7 @@ pub trait Overlayable {
 #[derive(Clone, Debug)]
 pub enum OverlayType {
     Prompt(prompt::Prompt),
+    PaneJump(pane_jump::PaneJump),
 }
 
 impl Overlayable for OverlayType {
@@ -34,6 +38,9 @@ impl Overlayable for OverlayType {
             OverlayType::Prompt(prompt) => prompt
                 .generate_overlay(size)
                 .context("failed to generate VTE output from overlay type"),
+            OverlayType::PaneJump(pane_jump) => pane_jump
+                .generate_overlay(size)
+                .context("failed to generate VTE output from overlay type"),
         }
     }
 }
@@ -65,11 +72,20 @@ impl Overlay {
     pub fn prompt_confirm(self) -> Option<Box<ServerInstruction>> {
         match self.overlay_type {
             OverlayType::Prompt(p) => p.confirm(),
+            OverlayType::PaneJump(_) => None,
         }
     }
     pub fn prompt_deny(self) -> Option<Box<ServerInstruction>> {
         match self.overlay_type {
             OverlayType::Prompt(p) => p.deny(),
+            OverlayType::PaneJump(_) => None,
+        }
+    }
+    /// The client this overlay is drawn for, if it is only drawn for one of them
+    pub fn client_id(&self) -> Option<ClientId> {
+        match &self.overlay_type {
+            OverlayType::Prompt(_) => None,
+            OverlayType::PaneJump(pane_jump) => Some(pane_jump.client_id),
         }
     }
 }
@@ -0,0 +1,138 @@
+use zellij_utils::data::{BareKey, KeyWithModifier};
+use zellij_utils::input::actions::PaneJumpMode;
+use zellij_utils::pane_size::Size;
+
+use super::{Overlay, Overlayable};
+use crate::{panes::PaneId, ClientId};
+use zellij_utils::errors::prelude::*;
+
+use std::fmt::Write;
+
+// home row first, so that the labels of the first few panes are the easiest to type
+const LABELS: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+// (x, y) of a pane if it is visible to the client
+pub type PaneJumpPosition = Option<(usize, usize)>;
+
+#[derive(Clone, Debug)]
+pub struct PaneJumpTarget {
+    pub label: char,
+    pub tab_position: usize,
+    pub pane_id: PaneId,
+    pub title: String,
+    pub position: PaneJumpPosition,
+}
+
+/// Labels on the panes a client can jump to, until the client types one of them
+#[derive(Clone, Debug)]
+pub struct PaneJump {
+    pub client_id: ClientId,
+    pub mode: PaneJumpMode,
+    targets: Vec<PaneJumpTarget>,
+}
+
+impl PaneJump {
+    /// Expects the candidates as (tab position, pane id, pane title, position on screen), panes
+    /// are labeled in this order until we run out of labels
+    pub fn new(
+        client_id: ClientId,
+        mode: PaneJumpMode,
+        candidates: Vec<(usize, PaneId, String, PaneJumpPosition)>,
+    ) -> Self {
+        let targets = LABELS
+            .chars()
+            .zip(candidates)
+            .map(
+                |(label, (tab_position, pane_id, title, position))| PaneJumpTarget {
+                    label,
+                    tab_position,
+                    pane_id,
+                    title,
+                    position,
+                },
+            )
+            .collect();
+        PaneJump {
+            client_id,
+            mode,
+            targets,
+        }
+    }
+    pub fn target_with_label(&self, label: char) -> Option<&PaneJumpTarget> {
+        self.targets.iter().find(|target| target.label == label)
+    }
+    /// The label typed by the user, if the input is a single character without modifiers
+    pub fn label_from_input(
+        key_with_modifier: &Option<KeyWithModifier>,
+        raw_bytes: &[u8],
+    ) -> Option<char> {
+        match key_with_modifier {
+            Some(key_with_modifier) if key_with_modifier.key_modifiers.is_empty() => {
+                match key_with_modifier.bare_key {
+                    BareKey::Char(c) => Some(c),
+                    _ => None,
+                }
+            },
+            Some(_) => None,
+            None => {
+                let input = std::str::from_utf8(raw_bytes).ok()?;
+                let mut chars = input.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if !c.is_control() => Some(c),
+                    _ => None,
+                }
+            },
+        }
+    }
+}
+
+impl Overlayable for PaneJump {
+    fn generate_overlay(&self, size: Size) -> Result<String> {
+        let err_context = || "failed to generate VTE output from pane jump";
+        let mut output = String::new();
+        output.push_str("\u{1b}7"); // save cursor position
+        for target in &self.targets {
+            if let Some((x, y)) = target.position {
+                if x + 4 > size.cols || y >= size.rows {
+                    continue;
+                }
+                write!(
+                    &mut output,
+                    "\u{1b}[{};{}H\u{1b}[1;7m {} \u{1b}[m",
+                    y + 1,
+                    x + 2,
+                    target.label,
+                )
+                .with_context(err_context)?;
+            }
+        }
+        // panes in other tabs are listed on the bottom line of the screen
+        let mut other_tabs_line = String::new();
+        for target in self.targets.iter().filter(|t| t.position.is_none()) {
+            write!(
+                &mut other_tabs_line,
+                " [{}] {}: {}",
+                target.label,
+                target.tab_position + 1,
+                target.title
+            )
+            .with_context(err_context)?;
+        }
+        if !other_tabs_line.is_empty() && size.rows > 0 {
+            other_tabs_line = other_tabs_line.chars().take(size.cols).collect();
+            Overlay::pad_cols(&mut other_tabs_line, size.cols);
+            write!(
+                &mut output,
+                "\u{1b}[{};1H\u{1b}[48;5;238m{}\u{1b}[m",
+                size.rows, other_tabs_line,
+            )
+            .with_context(err_context)?;
+        }
+        output.push_str("\u{1b}8"); // restore cursor position
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/pane_jump_tests.rs"]
+mod pane_jump_tests;
@@ -0,0 +1,64 @@
+use super::*;
+use std::collections::BTreeSet;
+use zellij_utils::data::KeyModifier;
+
+fn pane_jump() -> PaneJump {
+    PaneJump::new(
+        1,
+        PaneJumpMode::Focus,
+        vec![
+            (0, PaneId::Terminal(1), "fish".into(), Some((0, 0))),
+            (0, PaneId::Terminal(2), "vim".into(), Some((60, 0))),
+            (1, PaneId::Terminal(3), "htop".into(), None),
+        ],
+    )
+}
+
+#[test]
+fn panes_are_labeled_in_order() {
+    let pane_jump = pane_jump();
+    assert_eq!(
+        pane_jump.target_with_label('a').map(|t| t.pane_id),
+        Some(PaneId::Terminal(1))
+    );
+    assert_eq!(
+        pane_jump.target_with_label('s').map(|t| t.pane_id),
+        Some(PaneId::Terminal(2))
+    );
+    assert_eq!(
+        pane_jump.target_with_label('d').map(|t| t.pane_id),
+        Some(PaneId::Terminal(3))
+    );
+    assert!(pane_jump.target_with_label('f').is_none());
+}
+
+#[test]
+fn labels_are_drawn_on_visible_panes_and_listed_for_other_tabs() {
+    let output = pane_jump()
+        .generate_overlay(Size {
+            cols: 120,
+            rows: 20,
+        })
+        .unwrap();
+    assert!(output.contains("\u{1b}[1;2H\u{1b}[1;7m a "));
+    assert!(output.contains("\u{1b}[1;62H\u{1b}[1;7m s "));
+    assert!(output.contains("\u{1b}[20;1H\u{1b}[48;5;238m [d] 2: htop"));
+}
+
+#[test]
+fn label_is_read_from_a_single_character() {
+    let key = KeyWithModifier {
+        bare_key: BareKey::Char('s'),
+        key_modifiers: BTreeSet::new(),
+    };
+    assert_eq!(PaneJump::label_from_input(&Some(key), &[]), Some('s'));
+    let mut key_modifiers = BTreeSet::new();
+    key_modifiers.insert(KeyModifier::Ctrl);
+    let key_with_ctrl = KeyWithModifier {
+        bare_key: BareKey::Char('s'),
+        key_modifiers,
+    };
+    assert_eq!(PaneJump::label_from_input(&Some(key_with_ctrl), &[]), None);
+    assert_eq!(PaneJump::label_from_input(&None, b"d"), Some('d'));
+    assert_eq!(PaneJump::label_from_input(&None, &[27]), None);
+}
@@ -82,6 +82,8 @@ pub struct PaneFrame {
     should_draw_pane_frames: bool,
     is_pinned: bool,
     is_floating: bool,
+    is_throttled: bool,
+    is_synced: bool,
 }
 
 impl PaneFrame {
@@ -108,12 +110,22 @@ impl PaneFrame {
             should_draw_pane_frames: frame_params.should_draw_pane_frames,
             is_pinned: false,
             is_floating: frame_params.pane_is_floating,
+            is_throttled: false,
+            is_synced: false,
         }
     }
     pub fn is_pinned(mut self, is_pinned: bool) -> Self {
         self.is_pinned = is_pinned;
         self
     }
+    pub fn is_throttled(mut self, is_throttled: bool) -> Self {
+        self.is_throttled = is_throttled;
+        self
+    }
+    pub fn is_synced(mut self, is_synced: bool) -> Self {
+        self.is_synced = is_synced;
+        self
+    }
     pub fn add_exit_status(&mut self, exit_status: Option<i32>) {
         self.exit_status = match exit_status {
             Some(exit_status) => Some(ExitStatus::Code(exit_status)),
@@ -159,6 +171,73 @@ impl PaneFrame {
         max_length: usize,
     ) -> Option<(Vec<TerminalCharacter>, usize)> {
         // string and length because of color
+        let synced_indication = if self.is_synced {
+            self.render_synced_indication(max_length)
+        } else {
+            None
+        };
+        let space_for_other_indications = synced_indication
+            .as_ref()
+            .map(|(_, length)| max_length.saturating_sub(*length + 1))
+            .unwrap_or(max_length);
+        let other_indications =
+            self.render_throttle_scroll_and_pin_indications(space_for_other_indications);
+        match (synced_indication, other_indications) {
+            (
+                Some((mut synced_indication, synced_indication_len)),
+                Some((mut other_indications, other_indications_len)),
+            ) => {
+                let mut characters: Vec<_> = synced_indication.drain(..).collect();
+                let mut separator = foreground_color(&format!("|"), self.color);
+                characters.append(&mut separator);
+                characters.append(&mut other_indications);
+                Some((
+                    characters,
+                    synced_indication_len + other_indications_len + 1,
+                ))
+            },
+            (Some(synced_indication), None) => Some(synced_indication),
+            (None, Some(other_indications)) => Some(other_indications),
+            _ => None,
+        }
+    }
+    fn render_throttle_scroll_and_pin_indications(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
+        let throttled_indication = if self.is_throttled {
+            self.render_throttled_indication(max_length)
+        } else {
+            None
+        };
+        let space_for_other_indications = throttled_indication
+            .as_ref()
+            .map(|(_, length)| max_length.saturating_sub(*length + 1))
+            .unwrap_or(max_length);
+        let other_indications = self.render_scroll_and_pin_indications(space_for_other_indications);
+        match (throttled_indication, other_indications) {
+            (
+                Some((mut throttled_indication, throttled_indication_len)),
+                Some((mut other_indications, other_indications_len)),
+            ) => {
+                let mut characters: Vec<_> = throttled_indication.drain(..).collect();
+                let mut separator = foreground_color(&format!("|"), self.color);
+                characters.append(&mut separator);
+                characters.append(&mut other_indications);
+                Some((
+                    characters,
+                    throttled_indication_len + other_indications_len + 1,
+                ))
+            },
+            (Some(throttled_indication), None) => Some(throttled_indication),
+            (None, Some(other_indications)) => Some(other_indications),
+            _ => None,
+        }
+    }
+    fn render_scroll_and_pin_indications(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
         let has_scroll = self.scroll_position.0 > 0 || self.scroll_position.1 > 0;
         if has_scroll {
             let pin_indication = if self.is_floating {
@@ -221,6 +300,50 @@ impl PaneFrame {
             None
         }
     }
+    fn render_synced_indication(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
+        let full_indication = " SYNC ";
+        let short_indication = " S ";
+        let full_indication_len = full_indication.chars().count();
+        let short_indication_len = short_indication.chars().count();
+        if full_indication_len <= max_length {
+            Some((
+                foreground_color(full_indication, self.color),
+                full_indication_len,
+            ))
+        } else if short_indication_len <= max_length {
+            Some((
+                foreground_color(short_indication, self.color),
+                short_indication_len,
+            ))
+        } else {
+            None
+        }
+    }
+    fn render_throttled_indication(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
+        let full_indication = " THROTTLED ";
+        let short_indication = " T ";
+        let full_indication_len = full_indication.chars().count();
+        let short_indication_len = short_indication.chars().count();
+        if full_indication_len <= max_length {
+            Some((
+                foreground_color(full_indication, self.color),
+                full_indication_len,
+            ))
+        } else if short_indication_len <= max_length {
+            Some((
+                foreground_color(short_indication, self.color),
+                short_indication_len,
+            ))
+        } else {
+            None
+        }
+    }
     fn render_pinned_indication(
         &self,
         max_length: usize,
@@ -0,0 +1,133 @@
+use super::{run_hooks, run_hooks_with_session_kill_timeout};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+use zellij_utils::input::hooks::{HookContext, HookEvent, Hooks};
+
+// hooks other than on_session_kill run in the background, so we wait for them to be done
+const HOOK_DONE_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn hooks(event: HookEvent, command: String) -> Hooks {
+    let mut hooks = BTreeMap::new();
+    hooks.insert(event, vec![command]);
+    Hooks { hooks }
+}
+
+// a hook that writes its environment to this file
+fn dump_env_to(file: &Path) -> String {
+    format!("env > {0}.partial && mv {0}.partial {0}", file.display())
+}
+
+fn env_dumped_to(file: &Path) -> BTreeMap<String, String> {
+    let start = Instant::now();
+    while !file.exists() {
+        assert!(
+            start.elapsed() < HOOK_DONE_TIMEOUT,
+            "hook did not run in {:?}",
+            HOOK_DONE_TIMEOUT
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    std::fs::read_to_string(file)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .filter(|(name, _)| name.starts_with("ZELLIJ_HOOK_"))
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect()
+}
+
+#[test]
+fn tab_create_hook_runs_with_its_context_in_its_env() {
+    let dir = tempdir().unwrap();
+    let env_file = dir.path().join("env");
+    let hooks = hooks(HookEvent::TabCreate, dump_env_to(&env_file));
+    run_hooks(
+        &hooks,
+        HookEvent::TabCreate,
+        HookContext {
+            session_name: Some("my-session".to_owned()),
+            client_id: Some(1),
+            tab_index: Some(2),
+            tab_name: Some("logs".to_owned()),
+            ..Default::default()
+        },
+    );
+    let env = env_dumped_to(&env_file);
+    assert_eq!(env["ZELLIJ_HOOK_EVENT"], "on_tab_create");
+    assert_eq!(env["ZELLIJ_HOOK_SESSION_NAME"], "my-session");
+    assert_eq!(env["ZELLIJ_HOOK_CLIENT_ID"], "1");
+    assert_eq!(env["ZELLIJ_HOOK_TAB_INDEX"], "2");
+    assert_eq!(env["ZELLIJ_HOOK_TAB_NAME"], "logs");
+    assert!(
+        !env.contains_key("ZELLIJ_HOOK_PANE_ID"),
+        "no pane for a tab event"
+    );
+}
+
+#[test]
+fn pane_exit_hook_runs_with_the_pane_and_its_exit_code_in_its_env() {
+    let dir = tempdir().unwrap();
+    let env_file = dir.path().join("env");
+    let hooks = hooks(HookEvent::PaneExit, dump_env_to(&env_file));
+    // what the pty thread sends the server when a command pane exits with a non-zero status
+    run_hooks(
+        &hooks,
+        HookEvent::PaneExit,
+        HookContext {
+            session_name: Some("my-session".to_owned()),
+            pane_id: Some(3),
+            exit_code: Some(2),
+            ..Default::default()
+        },
+    );
+    let env = env_dumped_to(&env_file);
+    assert_eq!(env["ZELLIJ_HOOK_EVENT"], "on_pane_exit");
+    assert_eq!(env["ZELLIJ_HOOK_PANE_ID"], "3");
+    assert_eq!(env["ZELLIJ_HOOK_EXIT_CODE"], "2");
+}
+
+#[test]
+fn session_kill_hooks_are_waited_for() {
+    let dir = tempdir().unwrap();
+    let env_file = dir.path().join("env");
+    let hooks = hooks(
+        HookEvent::SessionKill,
+        format!("sleep 0.2 && {}", dump_env_to(&env_file)),
+    );
+    run_hooks(
+        &hooks,
+        HookEvent::SessionKill,
+        HookContext {
+            session_name: Some("my-session".to_owned()),
+            ..Default::default()
+        },
+    );
+    assert!(
+        env_file.exists(),
+        "session is only torn down once its on_session_kill hooks are done"
+    );
+}
+
+#[test]
+fn session_kill_hooks_are_killed_after_the_timeout() {
+    let dir = tempdir().unwrap();
+    let done_file = dir.path().join("done");
+    let hooks = hooks(
+        HookEvent::SessionKill,
+        format!("sleep 30 && touch {}", done_file.display()),
+    );
+    let start = Instant::now();
+    run_hooks_with_session_kill_timeout(
+        &hooks,
+        HookEvent::SessionKill,
+        HookContext::default(),
+        Duration::from_millis(200),
+    );
+    assert!(
+        start.elapsed() < Duration::from_secs(10),
+        "hung hook does not hold up the session for longer than the timeout"
+    );
+    assert!(!done_file.exists(), "hung hook was killed");
+}
@@ -0,0 +1,456 @@
+use super::{pty_thread_main, ClientTabIndexOrPaneId, Pty, PtyInstruction};
+use crate::os_input_output::{async_trait, AsyncReader, Pid, ServerOsApi};
+use crate::panes::PaneId;
+use crate::screen::ScreenInstruction;
+use crate::thread_bus::Bus;
+use crate::{ClientId, ServerInstruction};
+use std::collections::HashMap;
+use std::io::Write;
+use std::os::unix::io::RawFd;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use zellij_utils::channels::{self, ChannelWithContext, Receiver, SenderWithContext};
+use zellij_utils::data::Palette;
+use zellij_utils::errors::{prelude::*, ErrorContext};
+use zellij_utils::input::command::{RemoteHost, RunCommand, TerminalAction};
+use zellij_utils::input::hooks::{HookContext, HookEvent};
+use zellij_utils::input::layout::{Layout, Run, TiledPaneLayout};
+use zellij_utils::interprocess::local_socket::LocalSocketStream;
+use zellij_utils::ipc::{ClientToServerMsg, IpcReceiverWithContext, ServerToClientMsg};
+
+type QuitCb = Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>;
+
+// a pty that outputs these bytes and then ends
+struct FakePty {
+    output: Vec<u8>,
+}
+
+#[async_trait]
+impl AsyncReader for FakePty {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, std::io::Error> {
+        let n_bytes = self.output.len().min(buf.len());
+        buf[..n_bytes].copy_from_slice(&self.output[..n_bytes]);
+        self.output.drain(..n_bytes);
+        Ok(n_bytes)
+    }
+}
+
+#[derive(Clone, Default)]
+struct FakeInputOutput {
+    next_terminal_id: Arc<Mutex<u32>>,
+    spawned_terminals: Arc<Mutex<Vec<(u32, TerminalAction)>>>,
+    quit_cbs: Arc<Mutex<HashMap<u32, QuitCb>>>,
+    pane_output: Vec<u8>,
+}
+
+impl FakeInputOutput {
+    fn next_terminal_id(&self) -> u32 {
+        let mut next_terminal_id = self.next_terminal_id.lock().unwrap();
+        *next_terminal_id += 1;
+        *next_terminal_id
+    }
+    // simulates the command of this terminal exiting
+    fn exit_terminal(&self, terminal_id: u32, exit_status: Option<i32>) {
+        let quit_cbs = self.quit_cbs.lock().unwrap();
+        let quit_cb = quit_cbs.get(&terminal_id).unwrap();
+        quit_cb(
+            PaneId::Terminal(terminal_id),
+            exit_status,
+            RunCommand::default(),
+        );
+    }
+    fn spawned_terminals(&self) -> Vec<(u32, TerminalAction)> {
+        self.spawned_terminals.lock().unwrap().clone()
+    }
+}
+
+impl ServerOsApi for FakeInputOutput {
+    fn set_terminal_size_using_terminal_id(
+        &self,
+        _terminal_id: u32,
+        _cols: u16,
+        _rows: u16,
+        _width_in_pixels: Option<u16>,
+        _height_in_pixels: Option<u16>,
+    ) -> Result<()> {
+        Ok(())
+    }
+    fn spawn_terminal(
+        &self,
+        terminal_action: TerminalAction,
+        quit_cb: QuitCb,
+        _default_editor: Option<PathBuf>,
+    ) -> Result<(u32, RawFd, RawFd)> {
+        let terminal_id = self.next_terminal_id();
+        self.spawned_terminals
+            .lock()
+            .unwrap()
+            .push((terminal_id, terminal_action));
+        self.quit_cbs.lock().unwrap().insert(terminal_id, quit_cb);
+        Ok((terminal_id, terminal_id as RawFd, terminal_id as RawFd))
+    }
+    fn reserve_terminal_id(&self) -> Result<u32> {
+        Ok(self.next_terminal_id())
+    }
+    fn read_from_tty_stdout(&self, _fd: RawFd, _buf: &mut [u8]) -> Result<usize> {
+        Ok(0)
+    }
+    fn async_file_reader(&self, _fd: RawFd) -> Box<dyn AsyncReader> {
+        Box::new(FakePty {
+            output: self.pane_output.clone(),
+        })
+    }
+    fn write_to_tty_stdin(&self, _terminal_id: u32, buf: &[u8]) -> Result<usize> {
+        Ok(buf.len())
+    }
+    fn tcdrain(&self, _terminal_id: u32) -> Result<()> {
+        Ok(())
+    }
+    fn kill(&self, _pid: Pid) -> Result<()> {
+        Ok(())
+    }
+    fn force_kill(&self, _pid: Pid) -> Result<()> {
+        Ok(())
+    }
+    fn box_clone(&self) -> Box<dyn ServerOsApi> {
+        Box::new((*self).clone())
+    }
+    fn send_to_client(&self, _client_id: ClientId, _msg: ServerToClientMsg) -> Result<()> {
+        unimplemented!()
+    }
+    fn new_client(
+        &mut self,
+        _client_id: ClientId,
+        _stream: LocalSocketStream,
+    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
+        unimplemented!()
+    }
+    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
+        unimplemented!()
+    }
+    fn load_palette(&self) -> Palette {
+        unimplemented!()
+    }
+    fn get_cwd(&self, _pid: Pid) -> Option<PathBuf> {
+        None
+    }
+    fn write_to_file(&mut self, _buf: String, _file: Option<String>) -> Result<()> {
+        unimplemented!()
+    }
+    fn create_file(&mut self, _file: String) -> Result<Box<dyn Write + Send>> {
+        unimplemented!()
+    }
+    fn re_run_command_in_terminal(
+        &self,
+        terminal_id: u32,
+        run_command: RunCommand,
+        quit_cb: QuitCb,
+    ) -> Result<(RawFd, RawFd)> {
+        self.spawned_terminals
+            .lock()
+            .unwrap()
+            .push((terminal_id, TerminalAction::RunCommand(run_command)));
+        self.quit_cbs.lock().unwrap().insert(terminal_id, quit_cb);
+        Ok((terminal_id as RawFd, terminal_id as RawFd))
+    }
+    fn clear_terminal_id(&self, _terminal_id: u32) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct MockPty {
+    pty: Pty,
+    os_input: FakeInputOutput,
+    to_pty: SenderWithContext<PtyInstruction>,
+    screen_receiver: Receiver<(ScreenInstruction, ErrorContext)>,
+    server_receiver: Receiver<(ServerInstruction, ErrorContext)>,
+}
+
+impl MockPty {
+    fn new(default_shell: Option<PathBuf>) -> Self {
+        MockPty::with_pane_output(default_shell, vec![])
+    }
+    fn with_pane_output(default_shell: Option<PathBuf>, pane_output: Vec<u8>) -> Self {
+        let (to_screen, screen_receiver): ChannelWithContext<ScreenInstruction> =
+            channels::unbounded();
+        let to_screen = SenderWithContext::new(to_screen);
+        let (to_server, server_receiver): ChannelWithContext<ServerInstruction> =
+            channels::unbounded();
+        let to_server = SenderWithContext::new(to_server);
+        let (to_pty, pty_receiver): ChannelWithContext<PtyInstruction> = channels::unbounded();
+        let to_pty = SenderWithContext::new(to_pty);
+        let os_input = FakeInputOutput {
+            pane_output,
+            ..Default::default()
+        };
+        let bus: Bus<PtyInstruction> = Bus::new(
+            vec![pty_receiver],
+            Some(&to_screen),
+            None,
+            None,
+            Some(&to_server),
+            None,
+            None,
+            Some(Box::new(os_input.clone())),
+        )
+        .should_silently_fail();
+        MockPty {
+            pty: Pty::new(bus, false, None, None, default_shell),
+            os_input,
+            to_pty,
+            screen_receiver,
+            server_receiver,
+        }
+    }
+    // handles these instructions the way the pty thread does
+    fn handle_instructions(self, instructions: Vec<PtyInstruction>) -> FakeInputOutput {
+        for instruction in instructions {
+            self.to_pty.send(instruction).unwrap();
+        }
+        self.to_pty.send(PtyInstruction::Exit).unwrap();
+        pty_thread_main(self.pty, Box::new(Layout::default())).unwrap();
+        self.os_input
+    }
+    fn pane_exit_hooks(&self) -> Vec<HookContext> {
+        self.server_receiver
+            .try_iter()
+            .filter_map(|(instruction, _)| match instruction {
+                ServerInstruction::RunHook(HookEvent::PaneExit, hook_context) => Some(hook_context),
+                _ => None,
+            })
+            .collect()
+    }
+    fn command_exits(&self) -> Vec<(PaneId, Option<i32>)> {
+        self.screen_receiver
+            .try_iter()
+            .filter_map(|(instruction, _)| match instruction {
+                ScreenInstruction::CommandExited(pane_id, exit_status) => {
+                    Some((pane_id, exit_status))
+                },
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn command_pane_layout(command: &str) -> TiledPaneLayout {
+    TiledPaneLayout {
+        run: Some(Run::Command(RunCommand {
+            command: PathBuf::from(command),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn layout_command_pane_exits_are_reported_to_screen() {
+    let client_id = 1;
+    let mut mock_pty = MockPty::new(None);
+    mock_pty
+        .pty
+        .spawn_terminals_for_layout(
+            None,
+            command_pane_layout("make"),
+            vec![],
+            None,
+            HashMap::new(),
+            0,
+            true,
+            client_id,
+        )
+        .unwrap();
+    let spawned_terminals = mock_pty.os_input.spawned_terminals();
+    assert_eq!(
+        spawned_terminals.len(),
+        1,
+        "one pane spawned for the layout"
+    );
+    let terminal_id = spawned_terminals[0].0;
+    mock_pty.os_input.exit_terminal(terminal_id, Some(3));
+    assert_eq!(
+        mock_pty.command_exits(),
+        vec![(PaneId::Terminal(terminal_id), Some(3))],
+        "exit status of the layout command reported"
+    );
+}
+
+#[test]
+fn rerun_command_pane_exits_are_reported_to_screen() {
+    let client_id = 1;
+    let mut mock_pty = MockPty::new(None);
+    mock_pty
+        .pty
+        .spawn_terminals_for_layout(
+            None,
+            command_pane_layout("make"),
+            vec![],
+            None,
+            HashMap::new(),
+            0,
+            true,
+            client_id,
+        )
+        .unwrap();
+    let terminal_id = mock_pty.os_input.spawned_terminals()[0].0;
+    mock_pty.os_input.exit_terminal(terminal_id, Some(0));
+    mock_pty
+        .pty
+        .rerun_command_in_pane(
+            PaneId::Terminal(terminal_id),
+            RunCommand {
+                command: PathBuf::from("make"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    mock_pty.os_input.exit_terminal(terminal_id, Some(2));
+    assert_eq!(
+        mock_pty.command_exits(),
+        vec![
+            (PaneId::Terminal(terminal_id), Some(0)),
+            (PaneId::Terminal(terminal_id), Some(2))
+        ],
+        "exit status of the rerun command reported"
+    );
+}
+
+#[test]
+fn new_panes_opened_from_a_remote_pane_open_on_its_host_with_a_configured_default_shell() {
+    let client_id = 1;
+    let default_shell = PathBuf::from("zsh");
+    let remote = RemoteHost::new("me@build-box");
+    let mut mock_pty = MockPty::new(Some(default_shell.clone()));
+    let remote_pane_layout = TiledPaneLayout {
+        run: Some(Run::Command(RunCommand {
+            command: PathBuf::from("htop"),
+            remote: Some(remote.clone()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    mock_pty
+        .pty
+        .spawn_terminals_for_layout(
+            None,
+            remote_pane_layout,
+            vec![],
+            None,
+            HashMap::new(),
+            0,
+            true,
+            client_id,
+        )
+        .unwrap();
+    let remote_terminal_id = mock_pty.os_input.spawned_terminals()[0].0;
+    mock_pty
+        .pty
+        .set_active_pane(Some(PaneId::Terminal(remote_terminal_id)), client_id);
+    // this is what a new pane action is sent with when the default shell is configured
+    let configured_default_shell = TerminalAction::RunCommand(RunCommand {
+        command: default_shell,
+        ..Default::default()
+    });
+    let local_command = TerminalAction::RunCommand(RunCommand {
+        command: PathBuf::from("top"),
+        ..Default::default()
+    });
+    let os_input = mock_pty.handle_instructions(vec![
+        PtyInstruction::SpawnTerminalVertically(Some(configured_default_shell), None, client_id),
+        PtyInstruction::SpawnTerminalVertically(Some(local_command), None, client_id),
+        PtyInstruction::SpawnTerminal(
+            None,
+            None,
+            None,
+            None,
+            false,
+            ClientTabIndexOrPaneId::ClientId(client_id),
+        ),
+    ]);
+    let remotes: Vec<Option<RemoteHost>> = os_input
+        .spawned_terminals()
+        .into_iter()
+        .skip(1)
+        .map(|(_, terminal_action)| match terminal_action {
+            TerminalAction::RunCommand(run_command) => run_command.remote,
+            TerminalAction::OpenFile(..) => None,
+        })
+        .collect();
+    assert_eq!(
+        remotes,
+        vec![Some(remote.clone()), None, Some(remote)],
+        "the default shell opens on the host of the focused pane, other commands open locally"
+    );
+}
+
+#[test]
+fn popup_contents_are_piped_after_the_last_bytes_of_its_command() {
+    let mut mock_pty = MockPty::with_pane_output(None, b"all of the output".to_vec());
+    let popup_command = TerminalAction::RunCommand(RunCommand {
+        command: PathBuf::from("make"),
+        output_pipe_id: Some("popup-output".to_owned()),
+        ..Default::default()
+    });
+    let (terminal_id, _starts_held) = mock_pty
+        .pty
+        .spawn_terminal(Some(popup_command), ClientTabIndexOrPaneId::ClientId(1))
+        .unwrap();
+    mock_pty.os_input.exit_terminal(terminal_id, Some(0));
+    let screen_instructions: Vec<String> = mock_pty
+        .screen_receiver
+        .try_iter()
+        .filter_map(|(instruction, _)| match instruction {
+            ScreenInstruction::PtyBytes(_, bytes) => {
+                Some(format!("bytes: {}", String::from_utf8_lossy(&bytes)))
+            },
+            ScreenInstruction::PipePaneContentsToCli(_, pipe_id, _) => {
+                Some(format!("pipe contents to: {}", pipe_id))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        screen_instructions,
+        vec![
+            "bytes: all of the output".to_owned(),
+            "pipe contents to: popup-output".to_owned()
+        ],
+        "pane contents piped once all of the output of the command reached the screen"
+    );
+}
+
+#[test]
+fn failed_command_pane_exits_run_the_pane_exit_hook() {
+    let client_id = 1;
+    let mut mock_pty = MockPty::new(None);
+    mock_pty
+        .pty
+        .spawn_terminals_for_layout(
+            None,
+            command_pane_layout("make"),
+            vec![],
+            None,
+            HashMap::new(),
+            0,
+            true,
+            client_id,
+        )
+        .unwrap();
+    let terminal_id = mock_pty.os_input.spawned_terminals()[0].0;
+    mock_pty.os_input.exit_terminal(terminal_id, Some(0));
+    assert_eq!(
+        mock_pty.pane_exit_hooks(),
+        vec![],
+        "no hook for a successful exit"
+    );
+    mock_pty.os_input.exit_terminal(terminal_id, Some(2));
+    assert_eq!(
+        mock_pty.pane_exit_hooks(),
+        vec![HookContext {
+            pane_id: Some(terminal_id),
+            exit_code: Some(2),
+            ..Default::default()
+        }],
+        "hook run with the pane and its exit code"
+    );
+}
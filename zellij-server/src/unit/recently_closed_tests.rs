@@ -0,0 +1,167 @@
+use super::*;
+use crate::panes::PaneId;
+use crate::session_layout_metadata::PaneLayoutMetadata;
+use std::collections::{BTreeMap, HashMap};
+use zellij_utils::input::layout::Layout;
+use zellij_utils::pane_size::PaneGeom;
+
+fn closed_pane(tab_index: usize) -> ClosedItem {
+    ClosedItem::Pane(ClosedPane {
+        tab_index,
+        is_floating: false,
+        pane: PaneLayoutManifest::default(),
+    })
+}
+
+fn closed_tab(name: &str) -> ClosedItem {
+    ClosedItem::Tab(ClosedTab {
+        position: 0,
+        name: name.to_owned(),
+        tab: TabLayoutManifest::default(),
+    })
+}
+
+fn pane_layout_metadata(terminal_id: u32) -> PaneLayoutMetadata {
+    PaneLayoutMetadata::new(
+        PaneId::Terminal(terminal_id),
+        PaneGeom::default(),
+        false,
+        None,
+        None,
+        true,
+        Some("scrollback".to_owned()),
+        vec![],
+        None,
+        BTreeMap::new(),
+    )
+}
+
+#[test]
+fn most_recently_closed_panes_are_reopened_first() {
+    let mut recently_closed = RecentlyClosed::new(DEFAULT_CLOSED_PANE_RETENTION);
+    recently_closed.push(closed_pane(1));
+    recently_closed.push(closed_pane(2));
+    recently_closed.push(closed_tab("my tab"));
+    assert_eq!(recently_closed.pop_pane().map(|p| p.tab_index), Some(2));
+    assert_eq!(recently_closed.pop_pane().map(|p| p.tab_index), Some(1));
+    assert!(recently_closed.pop_pane().is_none());
+    assert_eq!(
+        recently_closed.pop_tab().map(|t| t.name),
+        Some("my tab".to_owned())
+    );
+    assert!(recently_closed.pop_tab().is_none());
+}
+
+#[test]
+fn closed_panes_expire_after_the_retention_time() {
+    let mut recently_closed = RecentlyClosed::new(Duration::from_secs(60));
+    let now = Instant::now();
+    let long_ago = now.checked_sub(Duration::from_secs(120)).unwrap();
+    recently_closed.push_closed_at(closed_pane(1), long_ago);
+    recently_closed.push_closed_at(closed_tab("old tab"), long_ago);
+    recently_closed.push_closed_at(closed_pane(2), now);
+    assert_eq!(
+        recently_closed.pop_pane_at(now).map(|p| p.tab_index),
+        Some(2)
+    );
+    assert!(
+        recently_closed.pop_pane_at(now).is_none(),
+        "expired pane was dropped"
+    );
+    assert!(
+        recently_closed.pop_tab_at(now).is_none(),
+        "expired tab was dropped"
+    );
+}
+
+#[test]
+fn nothing_is_kept_when_retention_is_zero() {
+    let mut recently_closed = RecentlyClosed::new(Duration::ZERO);
+    assert!(!recently_closed.is_enabled());
+    recently_closed.push(closed_pane(1));
+    recently_closed.push(closed_tab("my tab"));
+    assert!(recently_closed.pop_pane().is_none());
+    assert!(recently_closed.pop_tab().is_none());
+}
+
+#[test]
+fn only_so_many_closed_panes_are_kept() {
+    let mut recently_closed = RecentlyClosed::new(DEFAULT_CLOSED_PANE_RETENTION);
+    for tab_index in 0..MAX_RECENTLY_CLOSED + 5 {
+        recently_closed.push(closed_pane(tab_index));
+    }
+    let mut reopened = vec![];
+    while let Some(closed_pane) = recently_closed.pop_pane() {
+        reopened.push(closed_pane.tab_index);
+    }
+    assert_eq!(reopened.len(), MAX_RECENTLY_CLOSED);
+    assert_eq!(reopened.last(), Some(&5), "oldest panes were dropped");
+}
+
+#[test]
+fn closed_panes_keep_their_whole_cwd() {
+    let mut session_layout_metadata = SessionLayoutMetadata::new(Box::new(Layout::default()));
+    session_layout_metadata.add_tab(
+        "my tab".to_owned(),
+        true,
+        false,
+        None,
+        vec![pane_layout_metadata(1), pane_layout_metadata(2)],
+        vec![],
+    );
+    session_layout_metadata.update_terminal_cwds(HashMap::from([
+        (1, PathBuf::from("/home/user/project")),
+        (2, PathBuf::from("/home/user")),
+    ]));
+    let closed_tab =
+        match ClosedItem::new(session_layout_metadata, ClosedFrom::Tab { tab_position: 3 }) {
+            Some(ClosedItem::Tab(closed_tab)) => closed_tab,
+            other => panic!("expected a closed tab, got {:?}", other),
+        };
+    assert_eq!(closed_tab.name, "my tab");
+    assert_eq!(closed_tab.position, 3);
+    let cwds: Vec<Option<PathBuf>> = closed_tab
+        .tab
+        .tiled_panes
+        .iter()
+        .map(|pane| pane.cwd.clone())
+        .collect();
+    assert_eq!(
+        cwds,
+        vec![
+            Some(PathBuf::from("/home/user/project")),
+            Some(PathBuf::from("/home/user")),
+        ]
+    );
+}
+
+#[test]
+fn closed_shell_panes_are_reopened_in_their_cwd() {
+    let mut session_layout_metadata = SessionLayoutMetadata::new(Box::new(Layout::default()));
+    session_layout_metadata.add_tab(
+        "my tab".to_owned(),
+        true,
+        false,
+        None,
+        vec![],
+        vec![pane_layout_metadata(1)],
+    );
+    session_layout_metadata
+        .update_terminal_cwds(HashMap::from([(1, PathBuf::from("/home/user/project"))]));
+    let closed_pane =
+        match ClosedItem::new(session_layout_metadata, ClosedFrom::Pane { tab_index: 2 }) {
+            Some(ClosedItem::Pane(closed_pane)) => closed_pane,
+            other => panic!("expected a closed pane, got {:?}", other),
+        };
+    assert_eq!(closed_pane.tab_index, 2);
+    assert!(closed_pane.is_floating);
+    assert_eq!(
+        closed_pane.run(),
+        Some(Run::Cwd(PathBuf::from("/home/user/project")))
+    );
+    assert_eq!(
+        closed_pane.pane.pane_contents,
+        Some("scrollback".to_owned())
+    );
+    assert!(closed_pane.floating_pane_coordinates().is_some());
+}
@@ -1,5 +1,7 @@
 use super::{screen_thread_main, CopyOptions, Screen, ScreenInstruction};
 use crate::panes::PaneId;
+use crate::recently_closed::{ClosedPane, DEFAULT_CLOSED_PANE_RETENTION};
+use crate::tab::{RenderThrottleConfig, Tab};
 use crate::{
     channels::SenderWithContext,
     os_input_output::{AsyncReader, Pid, ServerOsApi},
@@ -8,20 +10,29 @@ use crate::{
     ClientId, ServerInstruction, SessionMetaData, ThreadSenders,
 };
 use insta::assert_snapshot;
+use std::io::Write;
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
-use zellij_utils::data::{Event, Resize, Style};
+use zellij_utils::data::{
+    Event, FloatingPanesArrangement, MirrorDirection, PaneManifest, Resize, RotateDirection, Style,
+    TabInfo,
+};
 use zellij_utils::errors::{prelude::*, ErrorContext};
-use zellij_utils::input::actions::Action;
+use zellij_utils::input::actions::{Action, JoinPanePlacement, PaneJumpMode};
 use zellij_utils::input::command::{RunCommand, TerminalAction};
 use zellij_utils::input::config::Config;
+use zellij_utils::input::hooks::Hooks;
 use zellij_utils::input::layout::{
     FloatingPaneLayout, Layout, PluginAlias, PluginUserConfiguration, Run, RunPlugin,
     RunPluginLocation, RunPluginOrAlias, SplitDirection, SplitSize, TiledPaneLayout,
 };
+use zellij_utils::input::mouse::MouseEvent;
 use zellij_utils::input::options::Options;
+use zellij_utils::input::scratchpad::Scratchpads;
 use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::position::Position;
+use zellij_utils::session_serialization::PaneLayoutManifest;
 
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
@@ -114,9 +125,26 @@ fn send_cli_action_to_server(
     session_metadata: &SessionMetaData,
     cli_action: CliAction,
     client_id: ClientId,
+) {
+    send_targeted_cli_action_to_server(session_metadata, cli_action, None, None, client_id)
+}
+
+fn send_targeted_cli_action_to_server(
+    session_metadata: &SessionMetaData,
+    cli_action: CliAction,
+    pane_id: Option<String>,
+    tab_index: Option<usize>,
+    client_id: ClientId,
 ) {
     let get_current_dir = || PathBuf::from(".");
-    let actions = Action::actions_from_cli(cli_action, Box::new(get_current_dir), None).unwrap();
+    let actions = Action::actions_from_cli_with_target(
+        cli_action,
+        pane_id,
+        tab_index,
+        Box::new(get_current_dir),
+        None,
+    )
+    .unwrap();
     let senders = session_metadata.senders.clone();
     let capabilities = PluginCapabilities::default();
     let client_attributes = ClientAttributes::default();
@@ -156,6 +184,26 @@ struct FakeInputOutput {
     server_to_client_messages: Arc<Mutex<HashMap<ClientId, Vec<ServerToClientMsg>>>>,
 }
 
+struct FakeFile {
+    filename: String,
+    fake_filesystem: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl Write for FakeFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.fake_filesystem
+            .lock()
+            .unwrap()
+            .entry(self.filename.clone())
+            .or_default()
+            .push_str(&String::from_utf8_lossy(buf));
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 impl ServerOsApi for FakeInputOutput {
     fn set_terminal_size_using_terminal_id(
         &self,
@@ -231,6 +279,16 @@ impl ServerOsApi for FakeInputOutput {
         }
         Ok(())
     }
+    fn create_file(&mut self, filename: String) -> Result<Box<dyn Write + Send>> {
+        self.fake_filesystem
+            .lock()
+            .unwrap()
+            .insert(filename.clone(), String::new());
+        Ok(Box::new(FakeFile {
+            filename,
+            fake_filesystem: self.fake_filesystem.clone(),
+        }))
+    }
     fn re_run_command_in_terminal(
         &self,
         _terminal_id: u32,
@@ -265,6 +323,7 @@ fn create_new_screen(size: Size) -> Screen {
     let session_serialization = true;
     let serialize_pane_viewport = false;
     let scrollback_lines_to_serialize = None;
+    let redact_serialized_env = false;
     let layout_dir = None;
 
     let debug = false;
@@ -288,12 +347,17 @@ fn create_new_screen(size: Size) -> Screen {
         session_serialization,
         serialize_pane_viewport,
         scrollback_lines_to_serialize,
+        redact_serialized_env,
         styled_underlines,
         arrow_fonts,
         layout_dir,
         explicitly_disable_kitty_keyboard_protocol,
         stacked_resize,
         None,
+        RenderThrottleConfig::default(),
+        DEFAULT_CLOSED_PANE_RETENTION,
+        Scratchpads::default(),
+        Hooks::default(),
     );
     screen
 }
@@ -544,6 +608,7 @@ impl MockScreen {
             session_configuration: self.session_metadata.session_configuration.clone(),
             layout,
             current_input_modes: self.session_metadata.current_input_modes.clone(),
+            hooks: self.session_metadata.hooks.clone(),
         }
     }
 }
@@ -602,6 +667,7 @@ impl MockScreen {
             layout,
             session_configuration: Default::default(),
             current_input_modes: HashMap::new(),
+            hooks: Default::default(),
         };
 
         let os_input = FakeInputOutput::default();
@@ -1310,199 +1376,1099 @@ fn attach_after_first_tab_closed() {
 }
 
 #[test]
-fn open_new_floating_pane_with_custom_coordinates() {
+fn tabs_are_sized_to_the_clients_viewing_them() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
+    let small_size = Size { cols: 80, rows: 10 };
     let mut screen = create_new_screen(size);
+    screen.session_is_mirrored = false;
 
     new_tab(&mut screen, 1, 0);
-    let active_tab = screen.get_active_tab_mut(1).unwrap();
-    let should_float = Some(true);
-    active_tab
-        .new_pane(
-            PaneId::Terminal(2),
-            None,
-            should_float,
-            None,
-            Some(FloatingPaneCoordinates {
-                x: Some(SplitSize::Percent(10)),
-                y: Some(SplitSize::Fixed(5)),
-                width: Some(SplitSize::Percent(1)),
-                height: Some(SplitSize::Fixed(2)),
-                pinned: None,
-            }),
-            false,
-            Some(1),
-        )
-        .unwrap();
-    let active_pane = active_tab.get_active_pane(1).unwrap();
-    assert_eq!(active_pane.x(), 12, "x coordinates set properly");
-    assert_eq!(active_pane.y(), 5, "y coordinates set properly");
-    assert_eq!(active_pane.rows(), 2, "rows set properly");
-    assert_eq!(active_pane.cols(), 1, "columns set properly");
+    new_tab(&mut screen, 2, 1);
+    screen.add_client(2).expect("TEST");
+    screen.switch_tab_prev(None, true, 2).expect("TEST");
+    screen.set_client_size(1, size);
+    screen.set_client_size(2, small_size);
+    screen.resize_to_screen(small_size).expect("TEST");
+    screen.render(None).expect("TEST");
+
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().get_display_area(),
+        size,
+        "the tab of the bigger client is not shrunk"
+    );
+    assert_eq!(
+        screen.get_active_tab(2).unwrap().get_display_area(),
+        small_size,
+        "the tab of the smaller client is sized to it"
+    );
+
+    screen.switch_tab_next(None, true, 2).expect("TEST");
+    screen.render(None).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().get_display_area(),
+        small_size,
+        "a tab viewed by both clients is sized to the smallest of them"
+    );
 }
 
 #[test]
-fn open_new_floating_pane_with_custom_coordinates_exceeding_viewport() {
+fn tabs_are_sized_to_the_smallest_client_when_session_is_mirrored() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
+    let small_size = Size { cols: 80, rows: 10 };
     let mut screen = create_new_screen(size);
 
     new_tab(&mut screen, 1, 0);
-    let active_tab = screen.get_active_tab_mut(1).unwrap();
-    let should_float = Some(true);
-    active_tab
-        .new_pane(
-            PaneId::Terminal(2),
-            None,
-            should_float,
-            None,
-            Some(FloatingPaneCoordinates {
-                x: Some(SplitSize::Fixed(122)),
-                y: Some(SplitSize::Fixed(21)),
-                width: Some(SplitSize::Fixed(10)),
-                height: Some(SplitSize::Fixed(10)),
-                pinned: None,
-            }),
-            false,
-            Some(1),
+    new_tab(&mut screen, 2, 1);
+    screen.add_client(2).expect("TEST");
+    screen.set_client_size(1, size);
+    screen.set_client_size(2, small_size);
+    screen.resize_to_screen(small_size).expect("TEST");
+    screen.render(None).expect("TEST");
+
+    for tab in screen.tabs.values() {
+        assert_eq!(tab.get_display_area(), small_size);
+    }
+}
+
+#[test]
+fn join_pane_from_another_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .vertical_split(PaneId::Terminal(3), None, client_id)
+        .unwrap();
+    new_tab(&mut screen, 2, 1);
+
+    screen
+        .join_pane(
+            PaneId::Terminal(3),
+            JoinPanePlacement::Tiled(Some(Direction::Left)),
+            client_id,
         )
         .unwrap();
-    let active_pane = active_tab.get_active_pane(1).unwrap();
-    assert_eq!(active_pane.x(), 111, "x coordinates set properly");
-    assert_eq!(active_pane.y(), 10, "y coordinates set properly");
-    assert_eq!(active_pane.rows(), 10, "rows set properly");
-    assert_eq!(active_pane.cols(), 10, "columns set properly");
-}
 
-// Following are tests for sending CLI actions
-// these tests are only partially relevant to Screen
-// and are included here for two reasons:
-// 1. The best way to "integration test" these is combining the "screen_thread_main" and
-//    "route_action" functions and mocking everything around them
-// 2. These inadvertently also test many parts of Screen that are not tested elsewhere
+    let source_tab = screen.tabs.get(&0).unwrap();
+    assert!(
+        !source_tab.has_pane_with_pid(&PaneId::Terminal(3)),
+        "pane was taken out of its tab"
+    );
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(
+        active_tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(3)),
+        "joined pane is focused"
+    );
+    let joined_pane_x = active_tab
+        .get_pane_with_id(PaneId::Terminal(3))
+        .unwrap()
+        .x();
+    let existing_pane_x = active_tab
+        .get_pane_with_id(PaneId::Terminal(2))
+        .unwrap()
+        .x();
+    assert!(
+        joined_pane_x < existing_pane_x,
+        "joined pane was placed to the left of the focused pane"
+    );
+}
 
 #[test]
-pub fn send_cli_write_chars_action_to_screen() {
+fn join_pane_from_another_tab_as_floating_pane() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
-    let client_id = 10; // fake client id should not appear in the screen's state
-    let mut mock_screen = MockScreen::new(size);
-    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
-    let session_metadata = mock_screen.clone_session_metadata();
-    let screen_thread = mock_screen.run(None, vec![]);
-    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
-    let pty_writer_thread = log_actions_in_thread!(
-        received_pty_instructions,
-        PtyWriteInstruction::Exit,
-        pty_writer_receiver
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+
+    screen
+        .join_pane(PaneId::Terminal(1), JoinPanePlacement::Floating, client_id)
+        .unwrap();
+
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert!(active_tab.are_floating_panes_visible());
+    assert_eq!(
+        active_tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "joined pane is focused"
     );
-    let cli_action = CliAction::WriteChars {
-        chars: "input from the cli".into(),
-    };
-    send_cli_action_to_server(&session_metadata, cli_action, client_id);
-    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
-    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
-    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+    screen.render(None).unwrap();
+    assert_eq!(screen.tabs.len(), 1, "emptied tab was closed");
 }
 
 #[test]
-pub fn send_cli_write_action_to_screen() {
+fn reopened_tiled_pane_is_put_back_where_it_was() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
-    let client_id = 10; // fake client id should not appear in the screen's state
-    let mut mock_screen = MockScreen::new(size);
-    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
-    let session_metadata = mock_screen.clone_session_metadata();
-    let screen_thread = mock_screen.run(None, vec![]);
-    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
-    let pty_writer_thread = log_actions_in_thread!(
-        received_pty_instructions,
-        PtyWriteInstruction::Exit,
-        pty_writer_receiver
-    );
-    let cli_action = CliAction::Write {
-        bytes: vec![102, 111, 111],
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let tab = screen.get_active_tab_mut(client_id).unwrap();
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    let closed_pane_geom = tab
+        .get_pane_with_id(PaneId::Terminal(1))
+        .unwrap()
+        .position_and_size();
+    tab.close_pane(PaneId::Terminal(1), false);
+    let closed_pane = ClosedPane {
+        tab_index: 0,
+        is_floating: false,
+        pane: PaneLayoutManifest {
+            geom: closed_pane_geom,
+            ..Default::default()
+        },
     };
-    send_cli_action_to_server(&session_metadata, cli_action, client_id);
-    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
-    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
-    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+
+    screen
+        .add_reopened_pane(PaneId::Terminal(3), None, closed_pane, client_id)
+        .unwrap();
+
+    let tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(
+        tab.get_pane_with_id(PaneId::Terminal(3))
+            .unwrap()
+            .position_and_size()
+            .x,
+        closed_pane_geom.x,
+        "pane reopened to the left of the pane that took up its space"
+    );
 }
 
 #[test]
-pub fn send_cli_resize_action_to_screen() {
-    let size = Size { cols: 80, rows: 20 };
-    let client_id = 10; // fake client id should not appear in the screen's state
-    let mut initial_layout = TiledPaneLayout::default();
-    initial_layout.children_split_direction = SplitDirection::Vertical;
-    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
-    let mut mock_screen = MockScreen::new(size);
-    let session_metadata = mock_screen.clone_session_metadata();
-    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
-    let received_server_instructions = Arc::new(Mutex::new(vec![]));
-    let server_receiver = mock_screen.server_receiver.take().unwrap();
-    let pty_writer_thread = log_actions_in_thread!(
-        received_server_instructions,
-        ServerInstruction::KillSession,
-        server_receiver
-    );
-    let resize_cli_action = CliAction::Resize {
-        resize: Resize::Increase,
-        direction: Some(Direction::Left),
+fn swap_two_tiled_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
     };
-    send_cli_action_to_server(&session_metadata, resize_cli_action, client_id);
-    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
-    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
-        received_server_instructions.lock().unwrap().iter(),
-        size,
-    );
-    let snapshot_count = snapshots.len();
-    for (_cursor_coordinates, snapshot) in snapshots {
-        assert_snapshot!(format!("{}", snapshot));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let tab = screen.get_active_tab_mut(client_id).unwrap();
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    let geom = |tab: &Tab, pane_id| tab.get_pane_with_id(pane_id).unwrap().position_and_size();
+    let first_geom = geom(tab, PaneId::Terminal(1));
+    let second_geom = geom(tab, PaneId::Terminal(2));
+
+    tab.swap_panes(PaneId::Terminal(1), PaneId::Terminal(2))
+        .unwrap();
+
+    assert_eq!(geom(tab, PaneId::Terminal(1)), second_geom);
+    assert_eq!(geom(tab, PaneId::Terminal(2)), first_geom);
 }
 
 #[test]
-pub fn send_cli_focus_next_pane_action() {
-    let size = Size { cols: 80, rows: 20 };
-    let client_id = 10; // fake client id should not appear in the screen's state
-    let mut initial_layout = TiledPaneLayout::default();
-    initial_layout.children_split_direction = SplitDirection::Vertical;
-    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
-    let mut mock_screen = MockScreen::new(size);
-    let session_metadata = mock_screen.clone_session_metadata();
-    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
-    let received_server_instructions = Arc::new(Mutex::new(vec![]));
-    let server_receiver = mock_screen.server_receiver.take().unwrap();
-    let server_instruction = log_actions_in_thread!(
-        received_server_instructions,
-        ServerInstruction::KillSession,
-        server_receiver
-    );
-    let focus_next_pane_action = CliAction::FocusNextPane;
-    send_cli_action_to_server(&session_metadata, focus_next_pane_action, client_id);
-    std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_instruction, screen_thread]);
-    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
-        received_server_instructions.lock().unwrap().iter(),
-        size,
+fn swap_tiled_and_floating_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let tab = screen.get_active_tab_mut(client_id).unwrap();
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.new_pane(
+        PaneId::Terminal(3),
+        None,
+        Some(true),
+        None,
+        None,
+        false,
+        Some(client_id),
+    )
+    .unwrap();
+    let geom = |tab: &Tab, pane_id| tab.get_pane_with_id(pane_id).unwrap().position_and_size();
+    let tiled_geom = geom(tab, PaneId::Terminal(1));
+    let floating_geom = geom(tab, PaneId::Terminal(3));
+
+    tab.swap_panes(PaneId::Terminal(1), PaneId::Terminal(3))
+        .unwrap();
+
+    assert_eq!(geom(tab, PaneId::Terminal(3)), tiled_geom);
+    assert_eq!(geom(tab, PaneId::Terminal(1)), floating_geom);
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "focus followed the floating position"
     );
-    let snapshot_count = snapshots.len();
-    for (cursor_coordinates, _snapshot) in snapshots {
-        // here we assert he cursor_coordinates to let us know if we switched the pane focus
-        assert_snapshot!(format!("{:?}", cursor_coordinates));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
+    assert_eq!(tab.get_visible_selectable_floating_panes_count(), 1);
+}
+
+#[test]
+fn rotate_tiled_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let tab = screen.get_active_tab_mut(client_id).unwrap();
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, client_id)
+        .unwrap();
+    // 1 on the left, 2 on the top right and 3 on the bottom right
+    let geom = |tab: &Tab, pane_id| tab.get_pane_with_id(pane_id).unwrap().position_and_size();
+    let left = geom(tab, PaneId::Terminal(1));
+    let top_right = geom(tab, PaneId::Terminal(2));
+    let bottom_right = geom(tab, PaneId::Terminal(3));
+
+    tab.rotate_tiled_panes(RotateDirection::Clockwise);
+    assert_eq!(geom(tab, PaneId::Terminal(1)), top_right);
+    assert_eq!(geom(tab, PaneId::Terminal(2)), bottom_right);
+    assert_eq!(geom(tab, PaneId::Terminal(3)), left);
+
+    tab.rotate_tiled_panes(RotateDirection::CounterClockwise);
+    assert_eq!(geom(tab, PaneId::Terminal(1)), left);
+    assert_eq!(geom(tab, PaneId::Terminal(2)), top_right);
+    assert_eq!(geom(tab, PaneId::Terminal(3)), bottom_right);
+}
+
+#[test]
+fn mirror_tiled_layout() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let tab = screen.get_active_tab_mut(client_id).unwrap();
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, client_id)
+        .unwrap();
+    let geom = |tab: &Tab, pane_id| tab.get_pane_with_id(pane_id).unwrap().position_and_size();
+
+    tab.mirror_tiled_panes(MirrorDirection::Horizontal);
+    let (left, top_right) = (
+        geom(tab, PaneId::Terminal(1)),
+        geom(tab, PaneId::Terminal(2)),
+    );
+    assert_eq!(top_right.x, 0, "right panes moved to the left");
+    assert_eq!(
+        left.x,
+        top_right.cols.as_usize(),
+        "left pane moved to the right"
+    );
+
+    tab.mirror_tiled_panes(MirrorDirection::Vertical);
+    let (top, bottom) = (
+        geom(tab, PaneId::Terminal(2)),
+        geom(tab, PaneId::Terminal(3)),
+    );
+    assert_eq!(bottom.y, 0, "bottom pane moved to the top");
+    assert_eq!(
+        top.y,
+        bottom.rows.as_usize(),
+        "top pane moved to the bottom"
+    );
+}
+
+#[test]
+fn arrange_floating_panes_in_grid_and_cascade() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let tab = screen.get_active_tab_mut(client_id).unwrap();
+    for terminal_id in 2..=5 {
+        tab.new_pane(
+            PaneId::Terminal(terminal_id),
+            None,
+            Some(true),
+            None,
+            None,
+            false,
+            Some(client_id),
+        )
+        .unwrap();
+    }
+    let position = |tab: &Tab, terminal_id| {
+        let geom = tab
+            .get_pane_with_id(PaneId::Terminal(terminal_id))
+            .unwrap()
+            .position_and_size();
+        (geom.x, geom.y, geom.cols.as_usize(), geom.rows.as_usize())
+    };
+
+    tab.arrange_floating_panes(FloatingPanesArrangement::Grid)
+        .unwrap();
+    assert_eq!(position(tab, 2), (0, 0, 60, 10));
+    assert_eq!(position(tab, 3), (60, 0, 61, 10));
+    assert_eq!(position(tab, 4), (0, 10, 60, 10));
+    assert_eq!(position(tab, 5), (60, 10, 61, 10));
+
+    tab.arrange_floating_panes(FloatingPanesArrangement::Cascade)
+        .unwrap();
+    assert_eq!(position(tab, 2), (0, 0, 80, 13));
+    assert_eq!(position(tab, 3), (4, 2, 80, 13));
+    assert_eq!(position(tab, 4), (8, 4, 80, 13));
+    assert_eq!(position(tab, 5), (12, 6, 80, 13));
+}
+
+#[test]
+fn floating_pane_snaps_when_dropped_with_the_mouse() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let tab = screen.get_active_tab_mut(client_id).unwrap();
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        Some(true),
+        None,
+        None,
+        false,
+        Some(client_id),
+    )
+    .unwrap();
+    let position = |tab: &Tab| {
+        let geom = tab
+            .get_pane_with_id(PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size();
+        (geom.x, geom.y, geom.cols.as_usize(), geom.rows.as_usize())
+    };
+    let drag_pane = |tab: &mut Tab, from: Position, to: Position| {
+        tab.handle_mouse_event(&MouseEvent::new_left_press_event(from), client_id)
+            .unwrap();
+        tab.handle_mouse_event(&MouseEvent::new_left_motion_event(to), client_id)
+            .unwrap();
+        tab.handle_mouse_event(&MouseEvent::new_left_release_event(to), client_id)
+            .unwrap();
+    };
+    assert_eq!(position(tab), (30, 5, 60, 10));
+
+    // dropped close to the left edge of the viewport
+    drag_pane(tab, Position::new(5, 40), Position::new(7, 11));
+    assert_eq!(position(tab), (0, 7, 60, 10), "snapped to the left edge");
+
+    // dropped on the right edge of the viewport
+    drag_pane(tab, Position::new(7, 10), Position::new(7, 120));
+    assert_eq!(position(tab), (60, 0, 61, 20), "fills the right half");
+
+    // dropped on the top left corner of the viewport
+    drag_pane(tab, Position::new(0, 70), Position::new(0, 0));
+    assert_eq!(position(tab), (0, 0, 60, 10), "fills the top left quarter");
+
+    // clicking on the frame without moving the pane does not snap it
+    drag_pane(tab, Position::new(0, 30), Position::new(0, 30));
+    assert_eq!(position(tab), (0, 0, 60, 10), "pane did not move");
+}
+
+#[test]
+fn sync_group_spans_tabs() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .toggle_active_pane_in_sync_group(client_id)
+        .unwrap();
+    new_tab(&mut screen, 3, 1);
+    assert!(
+        !screen.active_pane_is_in_sync_group(client_id),
+        "pane in new tab is not synced"
+    );
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .toggle_active_pane_in_sync_group(client_id)
+        .unwrap();
+    assert!(screen.active_pane_is_in_sync_group(client_id));
+    let mut synced_pane_ids: Vec<PaneId> = screen
+        .tabs
+        .values()
+        .flat_map(|tab| tab.pane_ids_in_sync_group())
+        .collect();
+    synced_pane_ids.sort();
+    assert_eq!(
+        synced_pane_ids,
+        vec![PaneId::Terminal(2), PaneId::Terminal(3)],
+        "focused panes of both tabs are synced"
+    );
+
+    screen.clear_sync_group();
+    assert!(
+        screen
+            .tabs
+            .values()
+            .all(|tab| tab.pane_ids_in_sync_group().is_empty()),
+        "sync group was cleared"
+    );
+}
+
+#[test]
+fn pane_jump_focuses_pane_by_label() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    new_tab(&mut screen, 3, 1);
+
+    screen
+        .show_pane_jump_labels(PaneJumpMode::Focus, true, client_id)
+        .unwrap();
+    assert!(screen.pane_jump_is_active(client_id));
+    // panes 1 and 2 of the first tab are labeled after pane 3 of the focused tab
+    screen
+        .jump_to_pane_with_label(Some('d'), client_id)
+        .unwrap();
+
+    assert!(
+        !screen.pane_jump_is_active(client_id),
+        "labels were removed"
+    );
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(active_tab.position, 0, "switched to the tab of the pane");
+    assert_eq!(
+        active_tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(2))
+    );
+}
+
+#[test]
+fn pane_jump_is_dismissed_by_any_other_key() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    screen
+        .show_pane_jump_labels(PaneJumpMode::Focus, false, client_id)
+        .unwrap();
+    screen.jump_to_pane_with_label(None, client_id).unwrap();
+    assert!(!screen.pane_jump_is_active(client_id));
+}
+
+#[test]
+fn switch_tabs_within_tab_group() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    for (tab_index, group) in [Some("backend"), None, Some("backend"), Some("frontend")]
+        .into_iter()
+        .enumerate()
+    {
+        new_tab(&mut screen, tab_index as u32 + 1, tab_index);
+        screen.tabs.get_mut(&tab_index).unwrap().group = group.map(|g| g.to_owned());
+    }
+
+    screen
+        .go_to_tab_group("backend".to_owned(), client_id)
+        .unwrap();
+    assert_eq!(
+        screen.get_active_tab(client_id).unwrap().position,
+        2,
+        "went to the tab of the group that was focused last"
+    );
+    screen.switch_tab_in_group(true, client_id).unwrap();
+    assert_eq!(
+        screen.get_active_tab(client_id).unwrap().position,
+        0,
+        "wrapped around within the group"
+    );
+    screen.switch_tab_in_group(true, client_id).unwrap();
+    assert_eq!(
+        screen.get_active_tab(client_id).unwrap().position,
+        2,
+        "skipped the tab outside of the group"
+    );
+    screen.switch_tab_in_group(false, client_id).unwrap();
+    assert_eq!(screen.get_active_tab(client_id).unwrap().position, 0);
+
+    screen
+        .go_to_tab_group("frontend".to_owned(), client_id)
+        .unwrap();
+    assert_eq!(screen.get_active_tab(client_id).unwrap().position, 3);
+    screen
+        .go_to_tab_group("backend".to_owned(), client_id)
+        .unwrap();
+    assert_eq!(screen.get_active_tab(client_id).unwrap().position, 0);
+
+    screen.move_active_tab_to_group(None, client_id).unwrap();
+    assert_eq!(screen.get_active_tab(client_id).unwrap().group, None);
+    screen.switch_tab_in_group(true, client_id).unwrap();
+    assert_eq!(
+        screen.get_active_tab(client_id).unwrap().position,
+        1,
+        "tabs without a group are cycled through together"
+    );
+}
+
+#[test]
+fn scratchpad_is_hidden_and_follows_the_client_to_other_tabs() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let scratchpad_pane_id = PaneId::Terminal(2);
+    screen
+        .add_scratchpad_pane(scratchpad_pane_id, None, "notes".to_owned(), client_id)
+        .unwrap();
+    assert!(screen
+        .get_active_tab(client_id)
+        .unwrap()
+        .floating_pane_is_visible(&scratchpad_pane_id));
+
+    assert!(screen
+        .toggle_scratchpad("notes".to_owned(), client_id)
+        .unwrap());
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert!(
+        !active_tab.floating_pane_is_visible(&scratchpad_pane_id),
+        "scratchpad was hidden"
+    );
+    assert!(
+        active_tab.has_pane_with_pid(&scratchpad_pane_id),
+        "scratchpad was suppressed rather than closed"
+    );
+
+    new_tab(&mut screen, 3, 1);
+    assert!(screen
+        .toggle_scratchpad("notes".to_owned(), client_id)
+        .unwrap());
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(active_tab.position, 1);
+    assert!(
+        active_tab.floating_pane_is_visible(&scratchpad_pane_id),
+        "scratchpad was brought over to the tab of the client"
+    );
+    assert_eq!(
+        active_tab.get_active_pane_id(client_id),
+        Some(scratchpad_pane_id)
+    );
+}
+
+#[test]
+fn scratchpad_is_shown_without_the_hidden_floating_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    let floating_pane_id = PaneId::Terminal(2);
+    let active_tab = screen.get_active_tab_mut(client_id).unwrap();
+    active_tab
+        .new_pane(
+            floating_pane_id,
+            None,
+            Some(true),
+            None,
+            None,
+            false,
+            Some(client_id),
+        )
+        .unwrap();
+    active_tab
+        .toggle_floating_panes(Some(client_id), None)
+        .unwrap();
+    let scratchpad_pane_id = PaneId::Terminal(3);
+    screen
+        .add_scratchpad_pane(scratchpad_pane_id, None, "notes".to_owned(), client_id)
+        .unwrap();
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert!(active_tab.floating_pane_is_visible(&scratchpad_pane_id));
+    assert!(
+        !active_tab.floating_pane_is_visible(&floating_pane_id),
+        "hidden floating pane was not shown along with the scratchpad"
+    );
+
+    assert!(screen
+        .toggle_scratchpad("notes".to_owned(), client_id)
+        .unwrap());
+    let active_tab = screen.get_active_tab_mut(client_id).unwrap();
+    assert!(!active_tab.are_floating_panes_visible());
+    active_tab
+        .toggle_floating_panes(Some(client_id), None)
+        .unwrap();
+    assert!(
+        active_tab.floating_pane_is_visible(&floating_pane_id),
+        "floating pane was put back once the scratchpad was hidden"
+    );
+    assert!(!active_tab.floating_pane_is_visible(&scratchpad_pane_id));
+}
+
+#[test]
+fn scratchpad_is_opened_once_when_toggled_again_while_opening() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    screen
+        .scratchpads
+        .scratchpads
+        .insert("notes".to_owned(), Default::default());
+    assert!(
+        !screen
+            .toggle_scratchpad("notes".to_owned(), client_id)
+            .unwrap(),
+        "scratchpad is being opened"
+    );
+    assert!(
+        screen
+            .toggle_scratchpad("notes".to_owned(), client_id)
+            .unwrap(),
+        "scratchpad is not opened again while it is being opened"
+    );
+    screen
+        .add_scratchpad_pane(PaneId::Terminal(2), None, "notes".to_owned(), client_id)
+        .unwrap();
+    assert!(screen.pending_scratchpads.is_empty());
+}
+
+#[test]
+fn open_new_floating_pane_with_custom_coordinates() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    let active_tab = screen.get_active_tab_mut(1).unwrap();
+    let should_float = Some(true);
+    active_tab
+        .new_pane(
+            PaneId::Terminal(2),
+            None,
+            should_float,
+            None,
+            Some(FloatingPaneCoordinates {
+                x: Some(SplitSize::Percent(10)),
+                y: Some(SplitSize::Fixed(5)),
+                width: Some(SplitSize::Percent(1)),
+                height: Some(SplitSize::Fixed(2)),
+                pinned: None,
+            }),
+            false,
+            Some(1),
+        )
+        .unwrap();
+    let active_pane = active_tab.get_active_pane(1).unwrap();
+    assert_eq!(active_pane.x(), 12, "x coordinates set properly");
+    assert_eq!(active_pane.y(), 5, "y coordinates set properly");
+    assert_eq!(active_pane.rows(), 2, "rows set properly");
+    assert_eq!(active_pane.cols(), 1, "columns set properly");
+}
+
+#[test]
+fn open_new_floating_pane_with_custom_coordinates_exceeding_viewport() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    let active_tab = screen.get_active_tab_mut(1).unwrap();
+    let should_float = Some(true);
+    active_tab
+        .new_pane(
+            PaneId::Terminal(2),
+            None,
+            should_float,
+            None,
+            Some(FloatingPaneCoordinates {
+                x: Some(SplitSize::Fixed(122)),
+                y: Some(SplitSize::Fixed(21)),
+                width: Some(SplitSize::Fixed(10)),
+                height: Some(SplitSize::Fixed(10)),
+                pinned: None,
+            }),
+            false,
+            Some(1),
+        )
+        .unwrap();
+    let active_pane = active_tab.get_active_pane(1).unwrap();
+    assert_eq!(active_pane.x(), 111, "x coordinates set properly");
+    assert_eq!(active_pane.y(), 10, "y coordinates set properly");
+    assert_eq!(active_pane.rows(), 10, "rows set properly");
+    assert_eq!(active_pane.cols(), 10, "columns set properly");
+}
+
+// Following are tests for sending CLI actions
+// these tests are only partially relevant to Screen
+// and are included here for two reasons:
+// 1. The best way to "integration test" these is combining the "screen_thread_main" and
+//    "route_action" functions and mocking everything around them
+// 2. These inadvertently also test many parts of Screen that are not tested elsewhere
+
+#[test]
+pub fn send_cli_write_chars_action_to_screen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_action = CliAction::WriteChars {
+        chars: "input from the cli".into(),
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_write_action_to_screen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_action = CliAction::Write {
+        bytes: vec![102, 111, 111],
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_send_keys_action_to_screen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_action = CliAction::SendKeys {
+        keys: vec![
+            "Ctrl c".to_owned(),
+            "Enter".to_owned(),
+            "Alt Left".to_owned(),
+        ],
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let written_bytes: Vec<Vec<u8>> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, _terminal_id) => Some(bytes.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_bytes,
+        vec![vec![3], vec![13], b"\x1b[1;3D".to_vec()],
+        "keys encoded as a terminal without the kitty keyboard protocol would send them"
+    );
+}
+
+#[test]
+pub fn cli_send_keys_action_with_a_key_that_cannot_be_sent_is_an_error() {
+    let cli_action = CliAction::SendKeys {
+        keys: vec!["Enter".to_owned(), "CapsLock".to_owned()],
+    };
+    let actions = Action::actions_from_cli(cli_action, Box::new(PathBuf::new), None);
+    assert_eq!(
+        actions,
+        Err("Key \"CapsLock\" cannot be sent to a terminal".to_owned()),
+        "keys that are serialized to nothing are reported rather than silently dropped"
+    );
+}
+
+#[test]
+pub fn send_cli_wait_for_signal_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let wait_for_action = CliAction::WaitFor {
+        name: Some("build-done".to_owned()),
+        signal: false,
+        pane_exit: None,
+        pane_close: None,
+        pattern: None,
+        in_pane: None,
+        timeout: None,
+    };
+    send_cli_action_to_server(&session_metadata, wait_for_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let unblocked_before_signal = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|instruction| matches!(instruction, ServerInstruction::UnblockCliPipeInput(..)));
+    let signal_action = CliAction::WaitFor {
+        name: Some("build-done".to_owned()),
+        signal: true,
+        pane_exit: None,
+        pane_close: None,
+        pattern: None,
+        in_pane: None,
+        timeout: None,
+    };
+    send_cli_action_to_server(&session_metadata, signal_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let exit_statuses: Vec<(String, i32)> = received_server_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::CliPipeExitStatus(pipe_id, exit_status) => {
+                Some((pipe_id.clone(), *exit_status))
+            },
+            _ => None,
+        })
+        .collect();
+    let unblocked_pipes: Vec<String> = received_server_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::UnblockCliPipeInput(pipe_id) => Some(pipe_id.clone()),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        !unblocked_before_signal,
+        "waiting client not unblocked before the signal"
+    );
+    assert_eq!(exit_statuses.len(), 1, "one waiting client");
+    assert_eq!(exit_statuses[0].1, 0, "waiting client exits successfully");
+    assert_eq!(
+        unblocked_pipes,
+        vec![exit_statuses[0].0.clone()],
+        "waiting client unblocked after the signal"
+    );
+}
+
+#[test]
+pub fn wait_for_signal_is_forgotten_when_the_waiting_client_exits() {
+    let size = Size { cols: 80, rows: 20 };
+    let timed_out_client_id = 10;
+    let signaling_client_id = 11;
+    let waiting_client_id = 12;
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let wait_for_action = CliAction::WaitFor {
+        name: Some("build-done".to_owned()),
+        signal: false,
+        pane_exit: None,
+        pane_close: None,
+        pattern: None,
+        in_pane: None,
+        timeout: None,
+    };
+    let signal_action = CliAction::WaitFor {
+        name: Some("build-done".to_owned()),
+        signal: true,
+        pane_exit: None,
+        pane_close: None,
+        pattern: None,
+        in_pane: None,
+        timeout: None,
+    };
+    let unblocked_pipe_count = || {
+        received_server_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|instruction| matches!(instruction, ServerInstruction::UnblockCliPipeInput(..)))
+            .count()
+    };
+    send_cli_action_to_server(
+        &session_metadata,
+        wait_for_action.clone(),
+        timed_out_client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RemoveClient(timed_out_client_id));
+    send_cli_action_to_server(&session_metadata, signal_action, signaling_client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let unblocked_by_signal = unblocked_pipe_count();
+    send_cli_action_to_server(&session_metadata, wait_for_action, waiting_client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let unblocked_after_wait = unblocked_pipe_count();
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    assert_eq!(
+        unblocked_by_signal, 0,
+        "pipe of the client that exited not unblocked by the signal"
+    );
+    assert_eq!(
+        unblocked_after_wait, 1,
+        "signal kept for the next client waiting for it"
+    );
+}
+
+#[test]
+pub fn send_cli_resize_action_to_screen() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let pty_writer_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let resize_cli_action = CliAction::Resize {
+        resize: Some(Resize::Increase),
+        direction: Some(Direction::Left),
+        to: None,
+        height: false,
+    };
+    send_cli_action_to_server(&session_metadata, resize_cli_action, client_id);
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let snapshot_count = snapshots.len();
+    for (_cursor_coordinates, snapshot) in snapshots {
+        assert_snapshot!(format!("{}", snapshot));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
+}
+
+#[test]
+pub fn send_cli_focus_next_pane_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let focus_next_pane_action = CliAction::FocusNextPane;
+    send_cli_action_to_server(&session_metadata, focus_next_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let snapshot_count = snapshots.len();
+    for (cursor_coordinates, _snapshot) in snapshots {
+        // here we assert he cursor_coordinates to let us know if we switched the pane focus
+        assert_snapshot!(format!("{:?}", cursor_coordinates));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
 }
 
 #[test]
@@ -1590,26 +2556,164 @@ pub fn send_cli_move_focus_or_tab_pane_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let move_focus_action = CliAction::MoveFocusOrTab {
-        direction: Direction::Right,
+    let move_focus_action = CliAction::MoveFocusOrTab {
+        direction: Direction::Right,
+    };
+    send_cli_action_to_server(&session_metadata, move_focus_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let snapshot_count = snapshots.len();
+    for (cursor_coordinates, _snapshot) in snapshots {
+        // here we assert he cursor_coordinates to let us know if we switched the pane focus
+        assert_snapshot!(format!("{:?}", cursor_coordinates));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
+}
+
+#[test]
+pub fn send_cli_move_pane_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let cli_action = CliAction::MovePane {
+        direction: Some(Direction::Right),
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let snapshot_count = snapshots.len();
+    for (_cursor_coordinates, snapshot) in snapshots {
+        assert_snapshot!(format!("{}", snapshot));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
+}
+
+#[test]
+pub fn send_cli_dump_screen_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let cli_action = CliAction::DumpScreen {
+        path: PathBuf::from("/tmp/foo"),
+        full: true,
+    };
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "fill pane up with something".as_bytes().to_vec(),
+    ));
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    assert_snapshot!(format!(
+        "{:?}",
+        *mock_screen.os_input.fake_filesystem.lock().unwrap()
+    ));
+}
+
+#[test]
+pub fn send_cli_record_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let start_recording = CliAction::Record {
+        path: Some(PathBuf::from("/tmp/foo.cast")),
+        whole_session: false,
+        stop: false,
     };
-    send_cli_action_to_server(&session_metadata, move_focus_action, client_id);
+    let stop_recording = CliAction::Record {
+        path: None,
+        whole_session: false,
+        stop: true,
+    };
+    send_cli_action_to_server(&session_metadata, start_recording, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_instruction, screen_thread]);
-    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
-        received_server_instructions.lock().unwrap().iter(),
-        size,
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "recorded output".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(&session_metadata, stop_recording, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "output after recording stopped".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let recording = mock_screen
+        .os_input
+        .fake_filesystem
+        .lock()
+        .unwrap()
+        .get("/tmp/foo.cast")
+        .cloned()
+        .unwrap();
+    let (header, events) = zellij_utils::asciicast::parse_asciicast(&recording).unwrap();
+    let recorded_output: Vec<String> = events
+        .into_iter()
+        .filter_map(|event| match event {
+            zellij_utils::asciicast::AsciicastEvent::Output(_, output) => Some(output),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        header.width > 0 && header.width <= 40,
+        "recording has the pane's size rather than the screen's"
     );
-    let snapshot_count = snapshots.len();
-    for (cursor_coordinates, _snapshot) in snapshots {
-        // here we assert he cursor_coordinates to let us know if we switched the pane focus
-        assert_snapshot!(format!("{:?}", cursor_coordinates));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
+    assert_eq!(
+        recorded_output.len(),
+        2,
+        "initial contents and pane output recorded"
+    );
+    assert_eq!(recorded_output[1], "recorded output");
 }
 
 #[test]
-pub fn send_cli_move_pane_action() {
+pub fn recording_of_a_closed_pane_is_written_out() {
     let size = Size { cols: 80, rows: 20 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
@@ -1620,30 +2724,44 @@ pub fn send_cli_move_pane_action() {
     let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
     let received_server_instructions = Arc::new(Mutex::new(vec![]));
     let server_receiver = mock_screen.server_receiver.take().unwrap();
-    let server_instruction = log_actions_in_thread!(
+    let server_thread = log_actions_in_thread!(
         received_server_instructions,
         ServerInstruction::KillSession,
         server_receiver
     );
-    let cli_action = CliAction::MovePane {
-        direction: Some(Direction::Right),
+    let start_recording = CliAction::Record {
+        path: Some(PathBuf::from("/tmp/foo.cast")),
+        whole_session: false,
+        stop: false,
     };
-    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    send_cli_action_to_server(&session_metadata, start_recording, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_instruction, screen_thread]);
-    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
-        received_server_instructions.lock().unwrap().iter(),
-        size,
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "recorded output".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(&session_metadata, CliAction::ClosePane, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    // read before the screen is torn down, which would write out the recording regardless
+    let recording = mock_screen
+        .os_input
+        .fake_filesystem
+        .lock()
+        .unwrap()
+        .get("/tmp/foo.cast")
+        .cloned()
+        .unwrap();
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    assert!(
+        recording.contains("recorded output"),
+        "recording written out when its pane is closed: {:?}",
+        recording
     );
-    let snapshot_count = snapshots.len();
-    for (_cursor_coordinates, snapshot) in snapshots {
-        assert_snapshot!(format!("{}", snapshot));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
 }
 
 #[test]
-pub fn send_cli_dump_screen_action() {
+pub fn send_cli_toggle_pane_logging_action() {
     let size = Size { cols: 80, rows: 20 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
@@ -1659,21 +2777,34 @@ pub fn send_cli_dump_screen_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let cli_action = CliAction::DumpScreen {
-        path: PathBuf::from("/tmp/foo"),
-        full: true,
+    let log_dir = tempfile::tempdir().unwrap();
+    let log_file = log_dir.path().join("pane.log");
+    let toggle_pane_logging = CliAction::TogglePaneLogging {
+        path: log_file.clone(),
+        strip_ansi: true,
+        timestamps: false,
+        max_size: None,
     };
+    send_cli_action_to_server(&session_metadata, toggle_pane_logging.clone(), client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
     let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
         0,
-        "fill pane up with something".as_bytes().to_vec(),
+        "\u{1b}[31mlogged output\u{1b}[m\n".as_bytes().to_vec(),
     ));
-    send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_thread, screen_thread]);
-    assert_snapshot!(format!(
-        "{:?}",
-        *mock_screen.os_input.fake_filesystem.lock().unwrap()
+    send_cli_action_to_server(&session_metadata, toggle_pane_logging, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "output after logging stopped".as_bytes().to_vec(),
     ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    assert_eq!(
+        std::fs::read_to_string(&log_file).unwrap(),
+        "logged output\n",
+        "only the output sent while logging was on is logged, without ANSI codes"
+    );
 }
 
 #[test]
@@ -2235,6 +3366,8 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         width: None,
         height: None,
         pinned: None,
+        popup: false,
+        print_output: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2279,6 +3412,8 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         width: None,
         height: None,
         pinned: None,
+        popup: false,
+        print_output: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2323,6 +3458,8 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         width: None,
         height: None,
         pinned: None,
+        popup: false,
+        print_output: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2378,6 +3515,54 @@ pub fn send_cli_new_pane_action_with_floating_pane_and_coordinates() {
         width: Some("20%".to_owned()),
         height: None,
         pinned: None,
+        popup: false,
+        print_output: false,
+    };
+    send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_new_pane_action_as_popup() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec!["fzf".into()],
+        plugin: None,
+        cwd: Some("/some/folder".into()),
+        floating: false,
+        in_place: false,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        configuration: None,
+        skip_plugin_cache: false,
+        x: None,
+        y: None,
+        width: None,
+        height: None,
+        pinned: None,
+        popup: true,
+        print_output: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2385,6 +3570,50 @@ pub fn send_cli_new_pane_action_with_floating_pane_and_coordinates() {
     assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
 }
 
+#[test]
+pub fn failed_popup_command_sends_its_exit_status_to_its_pipe() {
+    let size = Size { cols: 80, rows: 20 };
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "error: no such file".as_bytes().to_vec(),
+    ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::PipePaneContentsToCli(
+            PaneId::Terminal(0),
+            "popup-output".to_owned(),
+            Some(2),
+        ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let popup_pipe_messages: Vec<String> = received_server_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::CliPipeOutput(_, output) => Some(format!("output: {}", output)),
+            ServerInstruction::CliPipeExitStatus(_, exit_status) => {
+                Some(format!("exit status: {}", exit_status))
+            },
+            ServerInstruction::UnblockCliPipeInput(_) => Some("unblocked".to_owned()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        popup_pipe_messages,
+        vec!["exit status: 2".to_owned(), "unblocked".to_owned()],
+        "output of the failed command is held on screen and its exit status sent to the popup client"
+    );
+}
+
 #[test]
 pub fn send_cli_edit_action_with_default_parameters() {
     let size = Size {
@@ -2994,7 +4223,7 @@ pub fn send_cli_query_tab_names_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let query_tab_names = CliAction::QueryTabNames;
+    let query_tab_names = CliAction::QueryTabNames { json: false };
     send_cli_action_to_server(&session_metadata, query_tab_names, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
     mock_screen.teardown(vec![server_thread, screen_thread]);
@@ -3010,6 +4239,183 @@ pub fn send_cli_query_tab_names_action() {
     assert_snapshot!(format!("{:#?}", log_tab_names_instruction));
 }
 
+#[test]
+pub fn send_cli_list_tabs_and_panes_actions_as_json() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::ListTabs { json: true },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::ListPanes { json: true },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_lines: Vec<String> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _client_id) => Some(lines.join("\n")),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(logged_lines.len(), 2, "both queries were answered");
+    let tab_infos: Vec<TabInfo> = serde_json::from_str(&logged_lines[0]).unwrap();
+    assert_eq!(
+        tab_infos
+            .iter()
+            .map(|tab_info| tab_info.name.clone())
+            .collect::<Vec<_>>(),
+        vec!["Tab #1".to_owned(), "Tab #2".to_owned()],
+        "tabs listed in order"
+    );
+    assert_eq!(
+        tab_infos.iter().filter(|tab_info| tab_info.active).count(),
+        1,
+        "exactly one tab is active"
+    );
+    let pane_manifest: PaneManifest = serde_json::from_str(&logged_lines[1]).unwrap();
+    assert_eq!(pane_manifest.panes.len(), 2, "panes listed for each tab");
+}
+
+#[test]
+pub fn cli_action_tab_index_starts_at_one() {
+    let actions = Action::actions_from_cli_with_target(
+        CliAction::CloseTab,
+        None,
+        Some(0),
+        Box::new(PathBuf::new),
+        None,
+    );
+    assert!(actions.is_err(), "there is no tab with index 0");
+    let actions = Action::actions_from_cli_with_target(
+        CliAction::CloseTab,
+        None,
+        Some(2),
+        Box::new(PathBuf::new),
+        None,
+    );
+    assert_eq!(actions, Ok(vec![Action::CloseTabAtPosition(1)]));
+}
+
+#[test]
+pub fn send_cli_actions_to_a_pane_and_a_tab_that_are_not_focused() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        CliAction::RenamePane {
+            name: "background-pane".into(),
+        },
+        Some("terminal_1".to_owned()),
+        None,
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_targeted_cli_action_to_server(
+        &session_metadata,
+        CliAction::RenameTab {
+            name: "background-tab".into(),
+        },
+        None,
+        Some(1), // the first tab, like go-to-tab
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::ListTabs { json: true },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::ListPanes { json: true },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_lines: Vec<String> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _client_id) => Some(lines.join("\n")),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(logged_lines.len(), 2, "both queries were answered");
+    let tab_infos: Vec<TabInfo> = serde_json::from_str(&logged_lines[0]).unwrap();
+    let renamed_tab = tab_infos.iter().find(|tab_info| tab_info.position == 0);
+    assert_eq!(
+        renamed_tab.map(|tab_info| tab_info.name.clone()),
+        Some("background-tab".to_owned()),
+        "first tab renamed"
+    );
+    assert!(
+        !renamed_tab.map(|tab_info| tab_info.active).unwrap_or(true),
+        "renamed tab did not get focus"
+    );
+    let pane_manifest: PaneManifest = serde_json::from_str(&logged_lines[1]).unwrap();
+    let renamed_pane = pane_manifest
+        .panes
+        .values()
+        .flatten()
+        .find(|pane_info| !pane_info.is_plugin && pane_info.id == 1);
+    assert_eq!(
+        renamed_pane.map(|pane_info| pane_info.title.clone()),
+        Some("background-pane".to_owned()),
+        "pane with id 1 renamed"
+    );
+    assert!(
+        !renamed_pane
+            .map(|pane_info| pane_info.is_focused)
+            .unwrap_or(true),
+        "renamed pane did not get focus"
+    );
+    let unblock_count = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|instruction| matches!(instruction, ServerInstruction::UnblockInputThread))
+        .count();
+    assert!(
+        unblock_count >= 2,
+        "cli client unblocked after each targeted action"
+    );
+}
+
 #[test]
 pub fn send_cli_launch_or_focus_plugin_action() {
     let size = Size {
@@ -0,0 +1,152 @@
+//! CLI clients blocked on `zellij action wait-for`, each waiting on its own cli pipe until its
+//! condition is met.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::panes::PaneId;
+use crate::ClientId;
+use zellij_utils::regex::Regex;
+
+// the command of the pane or the pane itself is gone without the condition having been met
+const CONDITION_NOT_MET_EXIT_STATUS: i32 = 1;
+
+/// A met condition: the cli pipe to unblock and the exit status to report to its client
+pub type MetCondition = (String, i32);
+
+// the cli pipe to unblock once the condition is met and the client waiting on it
+type Waiter = (String, ClientId);
+
+#[derive(Debug, Default)]
+pub struct CliWaiters {
+    signals: HashMap<String, Vec<Waiter>>, // signal name -> waiters
+    // signals sent while nobody was waiting for them, the next wait for them returns at once
+    pending_signals: HashSet<String>,
+    pane_exits: Vec<(PaneId, Waiter)>,
+    pane_closes: Vec<(PaneId, Waiter)>,
+    patterns: Vec<(PaneId, Regex, Waiter)>,
+}
+
+impl CliWaiters {
+    pub fn wait_for_signal(
+        &mut self,
+        name: String,
+        pipe_id: String,
+        client_id: ClientId,
+    ) -> Option<MetCondition> {
+        if self.pending_signals.remove(&name) {
+            Some((pipe_id, 0))
+        } else {
+            self.signals
+                .entry(name)
+                .or_default()
+                .push((pipe_id, client_id));
+            None
+        }
+    }
+    pub fn signal(&mut self, name: &str) -> Vec<MetCondition> {
+        match self.signals.remove(name) {
+            Some(waiters) => waiters
+                .into_iter()
+                .map(|(pipe_id, _)| (pipe_id, 0))
+                .collect(),
+            None => {
+                self.pending_signals.insert(name.to_owned());
+                vec![]
+            },
+        }
+    }
+    pub fn wait_for_pane_exit(&mut self, pane_id: PaneId, pipe_id: String, client_id: ClientId) {
+        self.pane_exits.push((pane_id, (pipe_id, client_id)));
+    }
+    pub fn wait_for_pane_close(&mut self, pane_id: PaneId, pipe_id: String, client_id: ClientId) {
+        self.pane_closes.push((pane_id, (pipe_id, client_id)));
+    }
+    pub fn wait_for_pattern(
+        &mut self,
+        pane_id: PaneId,
+        pattern: Regex,
+        pipe_id: String,
+        client_id: ClientId,
+    ) {
+        self.patterns.push((pane_id, pattern, (pipe_id, client_id)));
+    }
+    /// Forgets everything this client was waiting for, eg. because it timed out, so that its
+    /// pipe is not the one unblocked by a later signal
+    pub fn client_exited(&mut self, client_id: ClientId) {
+        let is_other_client = |(_, waiting_client_id): &Waiter| *waiting_client_id != client_id;
+        for waiters in self.signals.values_mut() {
+            waiters.retain(is_other_client);
+        }
+        self.signals.retain(|_, waiters| !waiters.is_empty());
+        self.pane_exits
+            .retain(|(_, waiter)| is_other_client(waiter));
+        self.pane_closes
+            .retain(|(_, waiter)| is_other_client(waiter));
+        self.patterns
+            .retain(|(_, _, waiter)| is_other_client(waiter));
+    }
+    pub fn pane_exited(&mut self, pane_id: PaneId, exit_status: Option<i32>) -> Vec<MetCondition> {
+        // a command killed by a signal has no exit status
+        let exit_status = exit_status.unwrap_or(CONDITION_NOT_MET_EXIT_STATUS);
+        let (exited, waiting) = self
+            .pane_exits
+            .drain(..)
+            .partition(|(waited_for_pane_id, _)| *waited_for_pane_id == pane_id);
+        self.pane_exits = waiting;
+        exited
+            .into_iter()
+            .map(|(_, (pipe_id, _))| (pipe_id, exit_status))
+            .collect()
+    }
+    pub fn is_waiting_for_pattern_in(&self, pane_id: PaneId) -> bool {
+        self.patterns
+            .iter()
+            .any(|(waited_for_pane_id, _, _)| *waited_for_pane_id == pane_id)
+    }
+    pub fn pattern_appeared(&mut self, pane_id: PaneId, pane_contents: &str) -> Vec<MetCondition> {
+        let (appeared, waiting) =
+            self.patterns
+                .drain(..)
+                .partition(|(waited_for_pane_id, pattern, _)| {
+                    *waited_for_pane_id == pane_id && pattern.is_match(pane_contents)
+                });
+        self.patterns = waiting;
+        appeared
+            .into_iter()
+            .map(|(_, _, (pipe_id, _))| (pipe_id, 0))
+            .collect()
+    }
+    pub fn is_waiting_for_panes(&self) -> bool {
+        !self.pane_exits.is_empty() || !self.pane_closes.is_empty() || !self.patterns.is_empty()
+    }
+    /// Unblocks everything waiting on panes that no longer exist, successfully only if it was
+    /// waiting for the pane to close
+    pub fn panes_closed(&mut self, pane_exists: impl Fn(PaneId) -> bool) -> Vec<MetCondition> {
+        let mut met_conditions = vec![];
+        self.pane_closes.retain(|(pane_id, (pipe_id, _))| {
+            if pane_exists(*pane_id) {
+                true
+            } else {
+                met_conditions.push((pipe_id.clone(), 0));
+                false
+            }
+        });
+        self.pane_exits.retain(|(pane_id, (pipe_id, _))| {
+            if pane_exists(*pane_id) {
+                true
+            } else {
+                met_conditions.push((pipe_id.clone(), CONDITION_NOT_MET_EXIT_STATUS));
+                false
+            }
+        });
+        self.patterns.retain(|(pane_id, _, (pipe_id, _))| {
+            if pane_exists(*pane_id) {
+                true
+            } else {
+                met_conditions.push((pipe_id.clone(), CONDITION_NOT_MET_EXIT_STATUS));
+                false
+            }
+        });
+        met_conditions
+    }
+}
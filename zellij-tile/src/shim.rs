@@ -1262,6 +1262,47 @@ pub fn change_floating_panes_coordinates(
     unsafe { host_run_plugin_command() };
 }
 
+/// Moves the pane with the specified id from its tab to the focused tab, as a floating pane if `should_float` is true
+pub fn join_pane_to_focused_tab(pane_id: PaneId, should_float: bool) {
+    let plugin_command = PluginCommand::JoinPaneToFocusedTab(pane_id, should_float);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Exchanges the positions of two panes of the same tab
+pub fn swap_panes(first_pane_id: PaneId, second_pane_id: PaneId) {
+    let plugin_command = PluginCommand::SwapPanes(first_pane_id, second_pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Moves every tiled pane of the focused tab to the position of its neighbour around the center of the layout
+pub fn rotate_panes(rotate_direction: RotateDirection) {
+    let plugin_command = PluginCommand::RotatePanes(rotate_direction);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Open a command pane as a centered popup that closes by itself when the command exits
+/// successfully and stays open (showing its exit status) otherwise.
+pub fn open_command_pane_popup(command_to_run: CommandToRun, context: BTreeMap<String, String>) {
+    let plugin_command = PluginCommand::OpenCommandPanePopup(command_to_run, context);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Flips the tiled layout of the focused tab
+pub fn mirror_layout(mirror_direction: MirrorDirection) {
+    let plugin_command = PluginCommand::MirrorLayout(mirror_direction);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Utility Functions
 
 #[allow(unused)]
@@ -293,6 +293,8 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[prost(bool, tag = "7")]
     pub hold_on_start: bool,
+    #[prost(bool, tag = "8")]
+    pub close_on_success: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -390,6 +390,8 @@ pub struct PaneInfo {
     pub plugin_url: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "22")]
     pub is_selectable: bool,
+    #[prost(string, optional, tag = "23")]
+    pub log_file: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -422,6 +424,8 @@ pub struct TabInfo {
     pub display_area_rows: u32,
     #[prost(uint32, tag = "14")]
     pub display_area_columns: u32,
+    #[prost(string, optional, tag = "15")]
+    pub group: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -208,6 +208,16 @@ pub mod plugin_command {
         OpenFileFloatingNearPluginPayload(super::OpenFileFloatingNearPluginPayload),
         #[prost(message, tag = "101")]
         OpenFileInPlaceOfPluginPayload(super::OpenFileInPlaceOfPluginPayload),
+        #[prost(message, tag = "102")]
+        JoinPaneToFocusedTabPayload(super::JoinPaneToFocusedTabPayload),
+        #[prost(message, tag = "103")]
+        SwapPanesPayload(super::SwapPanesPayload),
+        #[prost(message, tag = "104")]
+        RotatePanesPayload(super::RotatePanesPayload),
+        #[prost(message, tag = "105")]
+        MirrorLayoutPayload(super::MirrorLayoutPayload),
+        #[prost(message, tag = "106")]
+        OpenCommandPanePopupPayload(super::OpenCommandPanePayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -318,6 +328,34 @@ pub struct SetFloatingPanePinnedPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct JoinPaneToFocusedTabPayload {
+    #[prost(message, optional, tag = "1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(bool, tag = "2")]
+    pub should_float: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SwapPanesPayload {
+    #[prost(message, optional, tag = "1")]
+    pub first_pane_id: ::core::option::Option<PaneId>,
+    #[prost(message, optional, tag = "2")]
+    pub second_pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RotatePanesPayload {
+    #[prost(bool, tag = "1")]
+    pub counter_clockwise: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MirrorLayoutPayload {
+    #[prost(bool, tag = "1")]
+    pub vertically: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ChangeHostFolderPayload {
     #[prost(string, tag = "1")]
     pub new_host_folder: ::prost::alloc::string::String,
@@ -885,6 +923,11 @@ pub enum CommandName {
     OpenFileNearPlugin = 124,
     OpenFileFloatingNearPlugin = 125,
     OpenFileInPlaceOfPlugin = 126,
+    JoinPaneToFocusedTab = 127,
+    SwapPanes = 128,
+    RotatePanes = 129,
+    MirrorLayout = 130,
+    OpenCommandPanePopup = 131,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1030,6 +1073,11 @@ impl CommandName {
             CommandName::OpenFileNearPlugin => "OpenFileNearPlugin",
             CommandName::OpenFileFloatingNearPlugin => "OpenFileFloatingNearPlugin",
             CommandName::OpenFileInPlaceOfPlugin => "OpenFileInPlaceOfPlugin",
+            CommandName::JoinPaneToFocusedTab => "JoinPaneToFocusedTab",
+            CommandName::SwapPanes => "SwapPanes",
+            CommandName::RotatePanes => "RotatePanes",
+            CommandName::MirrorLayout => "MirrorLayout",
+            CommandName::OpenCommandPanePopup => "OpenCommandPanePopup",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1172,6 +1220,11 @@ impl CommandName {
             "OpenFileNearPlugin" => Some(Self::OpenFileNearPlugin),
             "OpenFileFloatingNearPlugin" => Some(Self::OpenFileFloatingNearPlugin),
             "OpenFileInPlaceOfPlugin" => Some(Self::OpenFileInPlaceOfPlugin),
+            "JoinPaneToFocusedTab" => Some(Self::JoinPaneToFocusedTab),
+            "SwapPanes" => Some(Self::SwapPanes),
+            "RotatePanes" => Some(Self::RotatePanes),
+            "MirrorLayout" => Some(Self::MirrorLayout),
+            "OpenCommandPanePopup" => Some(Self::OpenCommandPanePopup),
             _ => None,
         }
     }
@@ -0,0 +1,227 @@
+//! Reading and writing of [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/)
+//! recordings.
+//!
+//! A recording is a header line followed by one event per line, each of them a JSON document.
+//! The [`AsciicastRecorder`] only formats these lines, writing them to disk is left to the caller.
+
+use crate::errors::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const ASCIICAST_VERSION: usize = 2;
+const OUTPUT_EVENT_CODE: &str = "o";
+const RESIZE_EVENT_CODE: &str = "r";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsciicastHeader {
+    pub version: usize,
+    pub width: usize,
+    pub height: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsciicastEvent {
+    Output(Duration, String),
+    Resize(Duration, usize, usize), // width, height
+}
+
+impl AsciicastEvent {
+    pub fn time(&self) -> Duration {
+        match self {
+            AsciicastEvent::Output(time, _) | AsciicastEvent::Resize(time, ..) => *time,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AsciicastRecorder {
+    started_at: Instant,
+    // bytes of a UTF-8 sequence that was cut in the middle, we keep them until the rest of it
+    // arrives because events can only contain valid strings
+    incomplete_utf8_sequence: Vec<u8>,
+}
+
+impl AsciicastRecorder {
+    /// Starts a new recording, returns the recorder along with the header line of the file
+    pub fn new(width: usize, height: usize, title: Option<String>) -> Result<(Self, String)> {
+        let header = AsciicastHeader {
+            version: ASCIICAST_VERSION,
+            width,
+            height,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            title,
+        };
+        let header_line =
+            serde_json::to_string(&header).context("failed to serialize asciicast header")? + "\n";
+        let recorder = AsciicastRecorder {
+            started_at: Instant::now(),
+            incomplete_utf8_sequence: vec![],
+        };
+        Ok((recorder, header_line))
+    }
+    /// Returns the line to append to the recording for these output bytes, if any
+    pub fn output(&mut self, bytes: &[u8]) -> Option<String> {
+        self.output_at(self.started_at.elapsed(), bytes)
+    }
+    /// Returns the line to append to the recording for this resize event
+    pub fn resize(&self, width: usize, height: usize) -> String {
+        self.resize_at(self.started_at.elapsed(), width, height)
+    }
+    fn output_at(&mut self, time: Duration, bytes: &[u8]) -> Option<String> {
+        let data = self.take_valid_utf8(bytes);
+        if data.is_empty() {
+            None
+        } else {
+            Some(event_line(time, OUTPUT_EVENT_CODE, &data))
+        }
+    }
+    fn resize_at(&self, time: Duration, width: usize, height: usize) -> String {
+        event_line(time, RESIZE_EVENT_CODE, &format!("{}x{}", width, height))
+    }
+    fn take_valid_utf8(&mut self, bytes: &[u8]) -> String {
+        self.incomplete_utf8_sequence.extend_from_slice(bytes);
+        let mut valid_utf8 = String::new();
+        loop {
+            match std::str::from_utf8(&self.incomplete_utf8_sequence) {
+                Ok(all_valid) => {
+                    valid_utf8.push_str(all_valid);
+                    self.incomplete_utf8_sequence.clear();
+                    break;
+                },
+                Err(e) => {
+                    let valid_up_to = e.valid_up_to();
+                    valid_utf8.push_str(&String::from_utf8_lossy(
+                        &self.incomplete_utf8_sequence[..valid_up_to],
+                    ));
+                    match e.error_len() {
+                        Some(invalid_len) => {
+                            valid_utf8.push(char::REPLACEMENT_CHARACTER);
+                            self.incomplete_utf8_sequence
+                                .drain(..valid_up_to + invalid_len);
+                        },
+                        None => {
+                            // the sequence is cut at the end, wait for the rest of it
+                            self.incomplete_utf8_sequence.drain(..valid_up_to);
+                            break;
+                        },
+                    }
+                },
+            }
+        }
+        valid_utf8
+    }
+}
+
+fn event_line(time: Duration, code: &str, data: &str) -> String {
+    // asciinema itself records with microsecond precision
+    let seconds = (time.as_secs_f64() * 1_000_000.0).round() / 1_000_000.0;
+    // serializing a tuple of a float and two strings cannot fail
+    serde_json::to_string(&(seconds, code, data)).unwrap_or_default() + "\n"
+}
+
+/// Parses an asciicast v2 recording, ignoring events other than output and resize
+pub fn parse_asciicast(recording: &str) -> Result<(AsciicastHeader, Vec<AsciicastEvent>)> {
+    let mut lines = recording.lines().filter(|l| !l.trim().is_empty());
+    let header_line = lines.next().ok_or_else(|| anyhow!("recording is empty"))?;
+    let header: AsciicastHeader =
+        serde_json::from_str(header_line).context("failed to parse asciicast header")?;
+    if header.version != ASCIICAST_VERSION {
+        return Err(anyhow!(
+            "unsupported asciicast version {}, only version {} is supported",
+            header.version,
+            ASCIICAST_VERSION
+        ));
+    }
+    let mut events = vec![];
+    for (index, line) in lines.enumerate() {
+        let (seconds, code, data): (f64, String, String) = serde_json::from_str(line)
+            .with_context(|| format!("failed to parse event number {}", index + 1))?;
+        let time = Duration::try_from_secs_f64(seconds)
+            .with_context(|| format!("invalid time for event number {}", index + 1))?;
+        match code.as_str() {
+            OUTPUT_EVENT_CODE => events.push(AsciicastEvent::Output(time, data)),
+            RESIZE_EVENT_CODE => {
+                if let Some((width, height)) = data.split_once('x') {
+                    if let (Ok(width), Ok(height)) = (width.parse(), height.parse()) {
+                        events.push(AsciicastEvent::Resize(time, width, height));
+                    }
+                }
+            },
+            _ => {}, // input and marker events are not relevant for playback
+        }
+    }
+    Ok((header, events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_events_are_formatted_as_v2_lines() {
+        let (mut recorder, _header) = AsciicastRecorder::new(80, 24, None).unwrap();
+        let line = recorder
+            .output_at(Duration::from_millis(1500), b"hello\r\n")
+            .unwrap();
+        assert_eq!(line, "[1.5,\"o\",\"hello\\r\\n\"]\n");
+        let line = recorder.resize_at(Duration::from_secs(2), 100, 30);
+        assert_eq!(line, "[2.0,\"r\",\"100x30\"]\n");
+    }
+
+    #[test]
+    fn utf8_sequences_split_across_reads_are_kept_whole() {
+        let (mut recorder, _header) = AsciicastRecorder::new(80, 24, None).unwrap();
+        let bytes = "a€".as_bytes();
+        let first_line = recorder.output_at(Duration::from_secs(1), &bytes[..2]);
+        let second_line = recorder.output_at(Duration::from_secs(1), &bytes[2..3]);
+        let third_line = recorder.output_at(Duration::from_secs(1), &bytes[3..]);
+        assert_eq!(first_line, Some("[1.0,\"o\",\"a\"]\n".to_owned()));
+        assert_eq!(second_line, None);
+        assert_eq!(third_line, Some("[1.0,\"o\",\"€\"]\n".to_owned()));
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced() {
+        let (mut recorder, _header) = AsciicastRecorder::new(80, 24, None).unwrap();
+        let line = recorder.output_at(Duration::from_secs(0), &[b'a', 0xff, b'b']);
+        assert_eq!(line, Some("[0.0,\"o\",\"a\u{fffd}b\"]\n".to_owned()));
+    }
+
+    #[test]
+    fn recordings_can_be_parsed_back() {
+        let (mut recorder, header_line) =
+            AsciicastRecorder::new(80, 24, Some("my recording".to_owned())).unwrap();
+        let mut recording = header_line;
+        recording.push_str(
+            &recorder
+                .output_at(Duration::from_millis(10), b"\x1b[31mred")
+                .unwrap(),
+        );
+        recording.push_str(&recorder.resize_at(Duration::from_millis(20), 120, 40));
+        recording.push_str("[0.03, \"i\", \"q\"]\n");
+        let (header, events) = parse_asciicast(&recording).unwrap();
+        assert_eq!(header.width, 80);
+        assert_eq!(header.height, 24);
+        assert_eq!(header.title, Some("my recording".to_owned()));
+        assert_eq!(
+            events,
+            vec![
+                AsciicastEvent::Output(Duration::from_millis(10), "\x1b[31mred".to_owned()),
+                AsciicastEvent::Resize(Duration::from_millis(20), 120, 40),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_versions_are_rejected() {
+        let recording = "{\"version\": 1, \"width\": 80, \"height\": 24}\n";
+        assert!(parse_asciicast(recording).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::data::{Direction, InputMode, Resize};
+use crate::data::{Direction, FloatingPanesArrangement, InputMode, MirrorDirection, Resize};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
@@ -117,6 +117,16 @@ pub enum Sessions {
         /// List the sessions in reverse order (default is ascending order)
         #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
         reverse: bool,
+
+        /// Print the sessions as a JSON list, including the tabs and panes of running sessions
+        #[clap(
+            long,
+            value_parser,
+            takes_value(false),
+            default_value("false"),
+            conflicts_with("short")
+        )]
+        json: bool,
     },
     /// List existing plugin aliases
     #[clap(visible_alias = "la")]
@@ -189,8 +199,18 @@ pub enum Sessions {
 
     /// Send actions to a specific session
     #[clap(visible_alias = "ac")]
-    #[clap(subcommand)]
-    Action(CliAction),
+    Action {
+        /// Send the action to this pane rather than to the focused one (eg. terminal_1,
+        /// plugin_1 or a bare integer for a terminal pane)
+        #[clap(long, value_parser)]
+        pane_id: Option<String>,
+        /// Send the action to the tab at this position rather than to the focused one, starting
+        /// at 1 like go-to-tab
+        #[clap(long, value_parser, conflicts_with("pane_id"))]
+        tab_index: Option<usize>,
+        #[clap(subcommand)]
+        action: CliAction,
+    },
     /// Run a command in a new pane
     #[clap(visible_alias = "r")]
     Run {
@@ -249,6 +269,29 @@ pub enum Sessions {
         /// Whether to pin a floating pane so that it is always on top
         #[clap(long, requires("floating"))]
         pinned: Option<bool>,
+        /// Open the new pane as a popup: a centered floating pane that closes by itself when its
+        /// command succeeds and stays open when it fails
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("floating"),
+            conflicts_with("in_place"),
+            conflicts_with("direction")
+        )]
+        popup: bool,
+        /// Wait for the popup to close and print what its command left on the screen to STDOUT
+        /// (eg. the selection of fzf)
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires("popup"),
+            requires("command")
+        )]
+        print_output: bool,
     },
     /// Load a plugin
     #[clap(visible_alias = "p")]
@@ -343,6 +386,23 @@ pub enum Sessions {
         #[clap(long, requires("floating"))]
         pinned: Option<bool>,
     },
+    /// Replay an asciicast (v2) recording, eg. one made with `zellij action record`
+    ///
+    /// Inside a session the recording is played in a new read-only floating pane, otherwise it
+    /// is played in the current terminal
+    Replay {
+        /// The recording to replay
+        #[clap(value_parser)]
+        file: PathBuf,
+
+        /// Play the recording in the current terminal even if inside a session
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        here: bool,
+
+        /// Playback speed, eg. 2 to play the recording twice as fast
+        #[clap(long, value_parser, default_value("1.0"))]
+        speed: f64,
+    },
     ConvertConfig {
         old_config_file: PathBuf,
     },
@@ -401,10 +461,28 @@ pub enum CliAction {
     WriteChars {
         chars: String,
     },
+    /// Send keys to the terminal, encoded the way the pane expects them.
+    ///
+    /// Example: zellij action send-keys "Ctrl c" "Alt Left" Enter
+    SendKeys {
+        #[clap(required(true))]
+        keys: Vec<String>,
+    },
     /// [increase|decrease] the focused panes area at the [left|down|up|right] border.
+    ///
+    /// Example: zellij action resize --to 40%
     Resize {
-        resize: Resize,
+        #[clap(required_unless_present("to"))]
+        resize: Option<Resize>,
+        #[clap(conflicts_with("to"))]
         direction: Option<Direction>,
+        /// Set the width of the focused pane to a bare integer number of columns (eg. 80) or a
+        /// percentage of the tab (eg. 40%)
+        #[clap(long, value_parser, conflicts_with("resize"))]
+        to: Option<String>,
+        /// Together with --to, set the height of the focused pane rather than its width
+        #[clap(long, value_parser, requires("to"), takes_value(false))]
+        height: bool,
     },
     /// Change focus to the next pane
     FocusNextPane,
@@ -437,7 +515,11 @@ pub enum CliAction {
         full: bool,
     },
     /// Dump current layout to stdout
-    DumpLayout,
+    DumpLayout {
+        /// Print the tabs and panes of the session as JSON rather than as a KDL layout
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
     /// Open the pane scrollback in your default editor
     EditScrollback,
     /// Scroll up in the focused pane
@@ -538,6 +620,29 @@ pub enum CliAction {
         /// Whether to pin a floating pane so that it is always on top
         #[clap(long, requires("floating"))]
         pinned: Option<bool>,
+        /// Open the new pane as a popup: a centered floating pane that closes by itself when its
+        /// command succeeds and stays open when it fails
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("floating"),
+            conflicts_with("in_place"),
+            conflicts_with("direction")
+        )]
+        popup: bool,
+        /// Wait for the popup to close and print what its command left on the screen to STDOUT
+        /// (eg. the selection of fzf)
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires("popup"),
+            requires("command")
+        )]
+        print_output: bool,
     },
     /// Open the specified file in a new zellij pane with your default EDITOR
     Edit {
@@ -650,7 +755,11 @@ pub enum CliAction {
     PreviousSwapLayout,
     NextSwapLayout,
     /// Query all tab names
-    QueryTabNames,
+    QueryTabNames {
+        /// Print the tab names as a JSON list
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
     StartOrReloadPlugin {
         url: String,
         #[clap(short, long, value_parser)]
@@ -758,7 +867,12 @@ tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://e
         #[clap(short('t'), long, value_parser, display_order(10))]
         plugin_title: Option<String>,
     },
-    ListClients,
+    /// List the clients connected to this session, their focused pane and its running command
+    ListClients {
+        /// Print the clients as a JSON list
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
     TogglePanePinned,
     /// Stack pane ids
     /// Ids are a space separated list of pane ids.
@@ -792,4 +906,192 @@ tail -f /tmp/my-live-logfile | zellij action pipe --name logs --plugin https://e
         #[clap(long)]
         pinned: Option<bool>,
     },
+    /// Record the focused pane to an asciicast (v2) file, replay it with `zellij replay`
+    ///
+    /// Example: zellij action record /tmp/my-pane.cast
+    Record {
+        /// The file to record to (will be overwritten if it exists)
+        #[clap(value_parser, required_unless_present("stop"))]
+        path: Option<PathBuf>,
+        /// Record the whole session as it is displayed to this client rather than the focused
+        /// pane
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        whole_session: bool,
+        /// Stop the ongoing recording
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("path")
+        )]
+        stop: bool,
+    },
+    /// Log the output of the focused pane to a file, or stop logging it if it already is
+    ///
+    /// Example: zellij action toggle-pane-logging /tmp/my-pane.log --strip-ansi
+    TogglePaneLogging {
+        /// The file to log to (output is appended to it if it exists)
+        #[clap(value_parser)]
+        path: PathBuf,
+        /// Strip ANSI escape codes (colors, cursor movement, etc.) from the logged output
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        strip_ansi: bool,
+        /// Prefix every logged line with the time it was written
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        timestamps: bool,
+        /// Rotate the log file (to <PATH>.1, <PATH>.2, etc.) once it reaches this size in bytes
+        #[clap(long, value_parser)]
+        max_size: Option<u64>,
+    },
+    /// Reopen the most recently closed pane, with its command, cwd and scrollback
+    ReopenClosedPane,
+    /// Reopen the most recently closed tab, with all of its panes
+    ReopenClosedTab,
+    /// Move a pane from another tab into the focused tab, keeping its process and scrollback
+    ///
+    /// Example: zellij action join-pane terminal_3 --direction right
+    JoinPane {
+        /// The pane_id of the pane, eg. terminal_1, plugin_2 or 3 (equivalent to terminal_3)
+        #[clap(value_parser)]
+        pane_id: String,
+        /// Split the focused pane in this direction to make room for the pane
+        #[clap(short, long, value_parser, conflicts_with("floating"))]
+        direction: Option<Direction>,
+        /// Add the pane as a floating pane
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        floating: bool,
+        /// Stack the pane on top of the focused pane
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("floating"),
+            conflicts_with("direction")
+        )]
+        stacked: bool,
+    },
+    /// Exchange the positions of two panes of the same tab, a tiled and a floating pane also
+    /// exchange layers
+    ///
+    /// Example: zellij action swap-panes terminal_1 terminal_2
+    SwapPanes {
+        /// The pane_id of the first pane, eg. terminal_1, plugin_2 or 3 (equivalent to terminal_3)
+        #[clap(value_parser)]
+        pane_id: String,
+        /// The pane_id of the second pane
+        #[clap(value_parser)]
+        other_pane_id: String,
+    },
+    /// Move every tiled pane of the focused tab to the position of its neighbour around the
+    /// center of the layout
+    RotatePanes {
+        /// Rotate the panes counter-clockwise rather than clockwise
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        counter_clockwise: bool,
+    },
+    /// Flip the tiled layout of the focused tab
+    ///
+    /// Example: zellij action mirror-layout horizontal
+    MirrorLayout {
+        /// "horizontal" swaps the left and right of the layout, "vertical" its top and bottom
+        #[clap(value_parser)]
+        direction: MirrorDirection,
+    },
+    /// Give the tiled panes of the focused tab that share a row or a column equal sizes
+    EqualizePanes,
+    /// Keep the focused tiled pane larger than its neighbours, following the golden ratio
+    ToggleGoldenRatio,
+    /// Add the focused pane to the sync group or remove it from there, input to any pane of the
+    /// group is sent to all of them, across tabs
+    TogglePaneInSyncGroup,
+    /// Remove all panes from the sync group
+    ClearSyncGroup,
+    /// Show a label on every visible pane and focus the pane whose label is typed next
+    PaneJump {
+        /// Swap the focused pane with the picked pane instead of focusing it (or move it there if
+        /// the picked pane is in another tab)
+        #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
+        swap: bool,
+        /// Also label the panes of the other tabs, listed at the bottom of the screen
+        #[clap(short, long, value_parser, takes_value(false), default_value("false"))]
+        all_tabs: bool,
+    },
+    /// Show or hide a scratchpad declared in the config, opening it on first use
+    ToggleScratchpad {
+        /// The name of the scratchpad, eg. "notes" for `scratchpad "notes" command="nvim notes.md"`
+        name: String,
+    },
+    /// Go to the tab of this group that was focused last, or to its first tab
+    GoToTabGroup {
+        name: String,
+    },
+    /// Go to the next tab in the group of the focused tab (tabs without a group are cycled
+    /// through together)
+    GoToNextTabInGroup,
+    /// Go to the previous tab in the group of the focused tab (tabs without a group are cycled
+    /// through together)
+    GoToPreviousTabInGroup,
+    /// Move the focused tab to a group, or out of its group if no name is given
+    MoveTabToGroup {
+        name: Option<String>,
+    },
+    /// Lay out all floating panes of the focused tab
+    ///
+    /// Example: zellij action arrange-floating-panes cascade
+    ArrangeFloatingPanes {
+        /// "grid" tiles the floating panes side by side, "cascade" stacks them diagonally
+        #[clap(value_parser)]
+        arrangement: FloatingPanesArrangement,
+    },
+    /// List the tabs of this session, as seen by the client running this command
+    ListTabs {
+        /// Print the tabs as JSON, in the same form plugins receive them
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
+    /// List the panes of all tabs in this session
+    ListPanes {
+        /// Print the panes as JSON (indexed by tab position), in the same form plugins receive
+        /// them
+        #[clap(long, value_parser, takes_value(false), default_value("false"))]
+        json: bool,
+    },
+    /// Block until a signal is sent by another `wait-for --signal`, the command of a pane exits,
+    /// a pane closes or a pattern appears in a pane.
+    ///
+    /// Example: zellij action wait-for --pane-exit terminal_3 --timeout 60
+    WaitFor {
+        /// The name of the signal to wait for, or to send with --signal
+        #[clap(required_unless_present_any(&["pane_exit", "pane_close", "pattern"]))]
+        name: Option<String>,
+        /// Send the signal, unblocking everyone waiting for it (or the next one to wait for it)
+        #[clap(
+            short('S'),
+            long,
+            value_parser,
+            takes_value(false),
+            default_value("false"),
+            requires("name")
+        )]
+        signal: bool,
+        /// Wait for the command of this pane to exit, and exit with its exit status
+        #[clap(long, value_parser, conflicts_with_all(&["name", "pane_close", "pattern"]))]
+        pane_exit: Option<String>,
+        /// Wait for this pane to close
+        #[clap(long, value_parser, conflicts_with_all(&["name", "pattern"]))]
+        pane_close: Option<String>,
+        /// Wait for this regular expression to match the visible contents of the pane given with
+        /// --in-pane
+        #[clap(long, value_parser, requires("in_pane"), conflicts_with("name"))]
+        pattern: Option<String>,
+        /// The pane to look for the --pattern in
+        #[clap(long, value_parser, requires("pattern"))]
+        in_pane: Option<String>,
+        /// Give up after this many seconds, exiting with status 124
+        #[clap(long, value_parser)]
+        timeout: Option<u64>,
+    },
 }
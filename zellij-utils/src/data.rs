@@ -559,6 +559,15 @@ impl KeyWithModifier {
             .encode(modifiers, key_code_encode_modes, true)
             .ok()
     }
+    /// Serializes the key the way a terminal without the kitty keyboard protocol would send it,
+    /// or with the protocol if it can't be expressed otherwise (eg. with several modifiers).
+    /// The bool is true in the latter case. Panes re-encode the key when they need to.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn serialize_for_write(&self) -> Option<(Vec<u8>, bool)> {
+        self.serialize_non_kitty()
+            .map(|s| (s.into_bytes(), false))
+            .or_else(|| self.serialize_kitty().map(|s| (s.into_bytes(), true)))
+    }
     pub fn has_no_modifiers(&self) -> bool {
         self.key_modifiers.is_empty()
     }
@@ -665,6 +674,73 @@ impl FromStr for Resize {
     }
 }
 
+/// Which way tiled panes move around the center of their layout when rotated
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum RotateDirection {
+    Clockwise,
+    CounterClockwise,
+}
+
+impl FromStr for RotateDirection {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Clockwise" | "clockwise" => Ok(RotateDirection::Clockwise),
+            "CounterClockwise" | "counterclockwise" | "counter-clockwise" => {
+                Ok(RotateDirection::CounterClockwise)
+            },
+            _ => Err(format!(
+                "Failed to parse RotateDirection. Unknown RotateDirection: {}",
+                s
+            )),
+        }
+    }
+}
+
+/// How a tiled layout is flipped: `Horizontal` swaps its left and right sides, `Vertical` its top
+/// and bottom
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum MirrorDirection {
+    Horizontal,
+    Vertical,
+}
+
+impl FromStr for MirrorDirection {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Horizontal" | "horizontal" => Ok(MirrorDirection::Horizontal),
+            "Vertical" | "vertical" => Ok(MirrorDirection::Vertical),
+            _ => Err(format!(
+                "Failed to parse MirrorDirection. Unknown MirrorDirection: {}",
+                s
+            )),
+        }
+    }
+}
+
+/// How the floating panes of a tab are laid out when arranged: `Grid` tiles them side by side
+/// across the whole viewport, `Cascade` stacks them diagonally so that each of their titles shows
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum FloatingPanesArrangement {
+    Grid,
+    Cascade,
+}
+
+impl FromStr for FloatingPanesArrangement {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Grid" | "grid" => Ok(FloatingPanesArrangement::Grid),
+            "Cascade" | "cascade" => Ok(FloatingPanesArrangement::Cascade),
+            _ => Err(format!(
+                "Failed to parse FloatingPanesArrangement. Unknown FloatingPanesArrangement: {}",
+                s
+            )),
+        }
+    }
+}
+
 /// Container type that fully describes resize operations.
 ///
 /// This is best thought of as follows:
@@ -1663,6 +1739,8 @@ pub struct TabInfo {
     /// Column count in the display area (including all panes, will typically be larger than the
     /// viewport)
     pub display_area_columns: usize,
+    /// The name of the tab group this tab belongs to, if any
+    pub group: Option<String>,
 }
 
 /// The `PaneManifest` contains a dictionary of panes, indexed by the tab position (0 indexed).
@@ -1728,6 +1806,8 @@ pub struct PaneInfo {
     /// Unselectable panes are often used for UI elements that do not have direct user interaction
     /// (eg. the default `status-bar` or `tab-bar`).
     pub is_selectable: bool,
+    /// The file this pane's output is being logged to, if any
+    pub log_file: Option<String>,
 }
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ClientInfo {
@@ -2043,6 +2123,8 @@ impl PipeMessage {
     }
 }
 
+const POPUP_SIZE_PERCENT: usize = 80;
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Default)]
 pub struct FloatingPaneCoordinates {
     pub x: Option<SplitSize>,
@@ -2076,6 +2158,17 @@ impl FloatingPaneCoordinates {
             })
         }
     }
+    /// A popup is centered on the screen and covers most of it
+    pub fn popup() -> Self {
+        let margin = (100 - POPUP_SIZE_PERCENT) / 2;
+        FloatingPaneCoordinates {
+            x: Some(SplitSize::Percent(margin)),
+            y: Some(SplitSize::Percent(margin)),
+            width: Some(SplitSize::Percent(POPUP_SIZE_PERCENT)),
+            height: Some(SplitSize::Percent(POPUP_SIZE_PERCENT)),
+            pinned: None,
+        }
+    }
     pub fn with_x_fixed(mut self, x: usize) -> Self {
         self.x = Some(SplitSize::Fixed(x));
         self
@@ -2301,4 +2394,9 @@ pub enum PluginCommand {
     OpenFileNearPlugin(FileToOpen, Context),
     OpenFileFloatingNearPlugin(FileToOpen, Option<FloatingPaneCoordinates>, Context),
     OpenFileInPlaceOfPlugin(FileToOpen, Context),
+    JoinPaneToFocusedTab(PaneId, bool), // bool -> should float
+    SwapPanes(PaneId, PaneId),
+    RotatePanes(RotateDirection),
+    MirrorLayout(MirrorDirection),
+    OpenCommandPanePopup(CommandToRun, Context),
 }
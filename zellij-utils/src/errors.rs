@@ -375,6 +375,42 @@ pub enum ScreenContext {
     SetFloatingPanePinned,
     StackPanes,
     ChangeFloatingPanesCoordinates,
+    StartRecording,
+    StopRecording,
+    TogglePaneLogging,
+    ClientTerminalResize,
+    ReopenClosedPane,
+    ReopenClosedTab,
+    AddRecentlyClosed,
+    ClosedPaneReopened,
+    JoinPane,
+    SwapPanes,
+    RotatePanes,
+    MirrorLayout,
+    EqualizePanes,
+    ResizeTo,
+    ToggleGoldenRatio,
+    TogglePaneInSyncGroup,
+    ClearSyncGroup,
+    PaneJump,
+    ToggleScratchpad,
+    ScratchpadSpawned,
+    ScratchpadFailedToSpawn,
+    GoToTabGroup,
+    SwitchTabInGroupNext,
+    SwitchTabInGroupPrev,
+    MoveTabToGroup,
+    ArrangeFloatingPanes,
+    PipePaneContentsToCli,
+    ListTabs,
+    ListPanes,
+    DumpScreenForPaneId,
+    CloseTabAtPosition,
+    RenameTabAtPosition,
+    WriteKeyToPaneId,
+    WaitFor,
+    SendWaitForSignal,
+    CommandExited,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
@@ -399,6 +435,11 @@ pub enum PtyContext {
     ListClientsMetadata,
     Reconfigure,
     ListClientsToPlugin,
+    PauseReading,
+    ResumeReading,
+    FillClosedPanesMetadata,
+    ReopenClosedPane,
+    SpawnScratchpad,
     Exit,
 }
 
@@ -460,6 +501,7 @@ pub enum ClientContext {
     SetSynchronisedOutput,
     UnblockCliPipeInput,
     CliPipeOutput,
+    CliPipeExitStatus,
     QueryTerminalSize,
     WriteConfigToDisk,
 }
@@ -483,6 +525,7 @@ pub enum ServerContext {
     SwitchSession,
     UnblockCliPipeInput,
     CliPipeOutput,
+    CliPipeExitStatus,
     AssociatePipeWithClient,
     DisconnectAllClientsExcept,
     ChangeMode,
@@ -491,6 +534,7 @@ pub enum ServerContext {
     ConfigWrittenToDisk,
     FailedToWriteConfigToDisk,
     RebindKeys,
+    RunHook,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -513,6 +557,7 @@ pub enum BackgroundJobContext {
     RunCommand,
     WebRequest,
     ReportPluginList,
+    RenderThrottledPanes,
     Exit,
 }
 
@@ -2,12 +2,12 @@
 
 use super::command::{OpenFilePayload, RunCommandAction};
 use super::layout::{
-    FloatingPaneLayout, Layout, PluginAlias, RunPlugin, RunPluginLocation, RunPluginOrAlias,
-    SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
+    FloatingPaneLayout, Layout, PaneLogging, PluginAlias, RunPlugin, RunPluginLocation,
+    RunPluginOrAlias, SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
 };
 use crate::cli::CliAction;
-use crate::data::{Direction, KeyWithModifier, PaneId, Resize};
-use crate::data::{FloatingPaneCoordinates, InputMode};
+use crate::data::{Direction, KeyWithModifier, MirrorDirection, PaneId, Resize, RotateDirection};
+use crate::data::{FloatingPaneCoordinates, FloatingPanesArrangement, InputMode};
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::mouse::{MouseEvent, MouseEventType};
@@ -93,6 +93,81 @@ impl FromStr for SearchOption {
     }
 }
 
+/// Where a pane joined from another tab is placed in the focused tab
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum JoinPanePlacement {
+    /// Split the focused pane in this direction, or wherever there is room if none is given
+    Tiled(Option<Direction>),
+    Floating,
+    /// Stack the pane on top of the focused pane
+    Stacked,
+}
+
+impl Default for JoinPanePlacement {
+    fn default() -> Self {
+        JoinPanePlacement::Tiled(None)
+    }
+}
+
+/// Which size of the focused pane `Action::ResizeTo` sets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PaneDimension {
+    #[default]
+    Width,
+    Height,
+}
+
+impl FromStr for PaneDimension {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Width" | "width" => Ok(PaneDimension::Width),
+            "Height" | "height" => Ok(PaneDimension::Height),
+            _ => Err(format!(
+                "Failed to parse PaneDimension. Unknown PaneDimension: {}",
+                s
+            )),
+        }
+    }
+}
+
+/// What happens to the pane picked from the labels shown by `Action::PaneJump`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PaneJumpMode {
+    /// Focus the picked pane
+    #[default]
+    Focus,
+    /// Swap the focused pane with the picked pane, or move it to the tab of the picked pane
+    Swap,
+}
+
+impl FromStr for PaneJumpMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Focus" | "focus" => Ok(PaneJumpMode::Focus),
+            "Swap" | "swap" => Ok(PaneJumpMode::Swap),
+            _ => Err(format!(
+                "Failed to parse PaneJumpMode. Unknown PaneJumpMode: {}",
+                s
+            )),
+        }
+    }
+}
+
+/// What `zellij action wait-for` blocks on
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum WaitForCondition {
+    /// A signal with this name, sent with `zellij action wait-for --signal`
+    Signal(String),
+    /// The command of the pane exiting
+    PaneExit(PaneId),
+    /// The pane closing
+    PaneClose(PaneId),
+    /// A regular expression matching the visible contents of the pane
+    Pattern(String, PaneId),
+}
+
 // As these actions are bound to the default config, please
 // do take care when refactoring - or renaming.
 // They might need to be adjusted in the default config
@@ -127,8 +202,8 @@ pub enum Action {
     ClearScreen,
     /// Dumps the screen to a file
     DumpScreen(String, bool),
-    /// Dumps
-    DumpLayout,
+    /// Dumps the layout of the session to the cli client (as JSON if true)
+    DumpLayout(bool),
     /// Scroll up in focus pane.
     EditScrollback,
     ScrollUp,
@@ -237,8 +312,8 @@ pub enum Action {
     ToggleMouseMode,
     PreviousSwapLayout,
     NextSwapLayout,
-    /// Query all tab names
-    QueryTabNames,
+    /// Query all tab names (as JSON if true)
+    QueryTabNames(bool),
     /// Open a new tiled (embedded, non-floating) plugin pane
     NewTiledPluginPane(RunPluginOrAlias, Option<String>, bool, Option<PathBuf>), // String is an optional name, bool is
     // skip_cache, Option<PathBuf> is cwd
@@ -292,10 +367,93 @@ pub enum Action {
         cwd: Option<PathBuf>,
         pane_title: Option<String>,
     },
-    ListClients,
+    /// List the clients connected to the session (as JSON if true)
+    ListClients(bool),
     TogglePanePinned,
     StackPanes(Vec<PaneId>),
     ChangeFloatingPaneCoordinates(PaneId, FloatingPaneCoordinates),
+    /// Record the focused pane (or the whole session if true) to an asciicast file
+    StartRecording(PathBuf, bool),
+    /// Stop recording the focused pane (or the whole session if true)
+    StopRecording(bool),
+    /// Start logging the output of the focused pane to a file, or stop if it is already logged
+    TogglePaneLogging(PaneLogging),
+    /// Reopen the most recently closed pane, with its command, cwd and scrollback
+    ReopenClosedPane,
+    /// Reopen the most recently closed tab, with all of its panes
+    ReopenClosedTab,
+    /// Move a pane from another tab into the focused tab
+    JoinPane(PaneId, JoinPanePlacement),
+    /// Exchange the positions of two panes of the same tab
+    SwapPanes(PaneId, PaneId),
+    /// Move every tiled pane to the position of its neighbour around the center of the layout
+    RotatePanes(RotateDirection),
+    /// Flip the tiled layout, swapping its left and right or its top and bottom
+    MirrorLayout(MirrorDirection),
+    /// Give the tiled panes that share a row or a column equal sizes
+    EqualizePanes,
+    /// Set the width or height of the focused pane to a number of columns/rows or to a percentage
+    /// of the tab
+    ResizeTo(SplitSize, PaneDimension),
+    /// Keep the focused tiled pane larger than its neighbours, following the golden ratio
+    ToggleGoldenRatio,
+    /// Add the focused pane to the sync group or remove it from there, input to any pane of the
+    /// group is sent to all of them, across tabs
+    TogglePaneInSyncGroup,
+    /// Remove all panes from the sync group
+    ClearSyncGroup,
+    /// Show a label on every visible pane and pick the pane whose label is typed next, the bool
+    /// also labels the panes of the other tabs
+    PaneJump(PaneJumpMode, bool),
+    /// Show or hide the scratchpad with this name from the config, opening it on first use
+    ToggleScratchpad(String),
+    /// Go to the tab of this group that was focused last, or to its first tab
+    GoToTabGroup(String),
+    /// Go to the next tab in the group of the focused tab
+    GoToNextTabInGroup,
+    /// Go to the previous tab in the group of the focused tab
+    GoToPreviousTabInGroup,
+    /// Move the focused tab to this group, or out of its group if there is none
+    MoveTabToGroup(Option<String>),
+    /// Lay out all floating panes of the focused tab in a grid or a cascade
+    ArrangeFloatingPanes(FloatingPanesArrangement),
+    /// List the tabs of the session (as JSON if true)
+    ListTabs(bool),
+    /// List the panes of the session (as JSON if true)
+    ListPanes(bool),
+    WriteToPaneId(Vec<u8>, PaneId),
+    ResizePaneWithId(Resize, Option<Direction>, PaneId),
+    MovePaneWithPaneId(Option<Direction>, PaneId),
+    ClearScreenForPaneId(PaneId),
+    /// Dump the pane to a file, with its full scrollback if true
+    DumpScreenForPaneId(String, bool, PaneId),
+    EditScrollbackForPaneId(PaneId),
+    ScrollUpInPaneId(PaneId),
+    ScrollDownInPaneId(PaneId),
+    ScrollToTopInPaneId(PaneId),
+    ScrollToBottomInPaneId(PaneId),
+    PageScrollUpInPaneId(PaneId),
+    PageScrollDownInPaneId(PaneId),
+    TogglePaneIdFullscreen(PaneId),
+    TogglePaneEmbedOrFloatingForPaneId(PaneId),
+    ClosePaneWithId(PaneId),
+    RenamePaneWithId(PaneId, Vec<u8>),
+    /// Send keys to the focused pane, encoded according to the mode of the pane
+    SendKeys(Vec<KeyWithModifier>),
+    SendKeysToPaneId(Vec<KeyWithModifier>, PaneId),
+    /// Block the cli client until the condition is met, then unblock its pipe - the timeout (in
+    /// seconds) is kept by the cli client
+    WaitFor {
+        pipe_id: String,
+        condition: WaitForCondition,
+        timeout: Option<u64>,
+    },
+    /// Unblock the cli clients waiting for this signal
+    SendWaitForSignal(String),
+    /// Close the tab at this position, whether it is focused or not
+    CloseTabAtPosition(usize),
+    /// Rename the tab at this position, whether it is focused or not
+    RenameTabAtPosition(usize, Vec<u8>),
 }
 
 impl Action {
@@ -317,7 +475,28 @@ impl Action {
         match cli_action {
             CliAction::Write { bytes } => Ok(vec![Action::Write(None, bytes, false)]),
             CliAction::WriteChars { chars } => Ok(vec![Action::WriteChars(chars)]),
-            CliAction::Resize { resize, direction } => Ok(vec![Action::Resize(resize, direction)]),
+            CliAction::SendKeys { keys } => Ok(vec![Action::SendKeys(parse_keys(keys)?)]),
+            CliAction::Resize {
+                resize,
+                direction,
+                to,
+                height,
+            } => match to {
+                Some(to) => {
+                    let size = SplitSize::from_str(&to)
+                        .map_err(|e| format!("Failed to parse size \"{}\": {}", to, e))?;
+                    let dimension = if height {
+                        PaneDimension::Height
+                    } else {
+                        PaneDimension::Width
+                    };
+                    Ok(vec![Action::ResizeTo(size, dimension)])
+                },
+                None => Ok(vec![Action::Resize(
+                    resize.unwrap_or(Resize::Increase),
+                    direction,
+                )]),
+            },
             CliAction::FocusNextPane => Ok(vec![Action::FocusNextPane]),
             CliAction::FocusPreviousPane => Ok(vec![Action::FocusPreviousPane]),
             CliAction::MoveFocus { direction } => Ok(vec![Action::MoveFocus(direction)]),
@@ -330,7 +509,7 @@ impl Action {
                 path.as_os_str().to_string_lossy().into(),
                 full,
             )]),
-            CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
+            CliAction::DumpLayout { json } => Ok(vec![Action::DumpLayout(json)]),
             CliAction::EditScrollback => Ok(vec![Action::EditScrollback]),
             CliAction::ScrollUp => Ok(vec![Action::ScrollUp]),
             CliAction::ScrollDown => Ok(vec![Action::ScrollDown]),
@@ -360,6 +539,8 @@ impl Action {
                 width,
                 height,
                 pinned,
+                popup,
+                print_output,
             } => {
                 let current_dir = get_current_dir();
                 // cwd should only be specified in a plugin alias if it was explicitly given to us,
@@ -389,7 +570,15 @@ impl Action {
                             RunPluginOrAlias::Alias(plugin_alias)
                         },
                     };
-                    if floating {
+                    if popup {
+                        Ok(vec![Action::NewFloatingPluginPane(
+                            plugin,
+                            name,
+                            skip_plugin_cache,
+                            cwd,
+                            Some(FloatingPaneCoordinates::popup()),
+                        )])
+                    } else if floating {
                         Ok(vec![Action::NewFloatingPluginPane(
                             plugin,
                             name,
@@ -424,6 +613,12 @@ impl Action {
                     let (command, args) = (PathBuf::from(command.remove(0)), command);
                     let hold_on_start = start_suspended;
                     let hold_on_close = !close_on_exit;
+                    let close_on_success = popup;
+                    let output_pipe_id = if print_output {
+                        Some(Uuid::new_v4().to_string())
+                    } else {
+                        None
+                    };
                     let run_command_action = RunCommandAction {
                         command,
                         args,
@@ -431,9 +626,17 @@ impl Action {
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        close_on_success,
+                        output_pipe_id,
                         ..Default::default()
                     };
-                    if floating {
+                    if popup {
+                        Ok(vec![Action::NewFloatingPane(
+                            Some(run_command_action),
+                            name,
+                            Some(FloatingPaneCoordinates::popup()),
+                        )])
+                    } else if floating {
                         Ok(vec![Action::NewFloatingPane(
                             Some(run_command_action),
                             name,
@@ -449,7 +652,13 @@ impl Action {
                         )])
                     }
                 } else {
-                    if floating {
+                    if popup {
+                        Ok(vec![Action::NewFloatingPane(
+                            None,
+                            name,
+                            Some(FloatingPaneCoordinates::popup()),
+                        )])
+                    } else if floating {
                         Ok(vec![Action::NewFloatingPane(
                             None,
                             name,
@@ -643,7 +852,7 @@ impl Action {
             },
             CliAction::PreviousSwapLayout => Ok(vec![Action::PreviousSwapLayout]),
             CliAction::NextSwapLayout => Ok(vec![Action::NextSwapLayout]),
-            CliAction::QueryTabNames => Ok(vec![Action::QueryTabNames]),
+            CliAction::QueryTabNames { json } => Ok(vec![Action::QueryTabNames(json)]),
             CliAction::StartOrReloadPlugin { url, configuration } => {
                 let current_dir = get_current_dir();
                 let run_plugin_or_alias = RunPluginOrAlias::from_url(
@@ -735,7 +944,7 @@ impl Action {
                     skip_cache,
                 }])
             },
-            CliAction::ListClients => Ok(vec![Action::ListClients]),
+            CliAction::ListClients { json } => Ok(vec![Action::ListClients(json)]),
             CliAction::TogglePanePinned => Ok(vec![Action::TogglePanePinned]),
             CliAction::StackPanes { pane_ids } => {
                 let mut malformed_ids = vec![];
@@ -787,6 +996,232 @@ impl Action {
                     }
                 }
             },
+            CliAction::Record {
+                path,
+                whole_session,
+                stop,
+            } => {
+                if stop {
+                    return Ok(vec![Action::StopRecording(whole_session)]);
+                }
+                match path {
+                    Some(path) => {
+                        // the server does not share our working directory
+                        let path = if path.is_relative() {
+                            get_current_dir().join(path)
+                        } else {
+                            path
+                        };
+                        Ok(vec![Action::StartRecording(path, whole_session)])
+                    },
+                    None => Err("Please specify a file to record to".to_owned()),
+                }
+            },
+            CliAction::TogglePaneLogging {
+                path,
+                strip_ansi,
+                timestamps,
+                max_size,
+            } => {
+                // the server does not share our working directory
+                let path = if path.is_relative() {
+                    get_current_dir().join(path)
+                } else {
+                    path
+                };
+                Ok(vec![Action::TogglePaneLogging(PaneLogging {
+                    file: path,
+                    strip_ansi,
+                    timestamps,
+                    max_size,
+                })])
+            },
+            CliAction::ReopenClosedPane => Ok(vec![Action::ReopenClosedPane]),
+            CliAction::ReopenClosedTab => Ok(vec![Action::ReopenClosedTab]),
+            CliAction::JoinPane {
+                pane_id,
+                direction,
+                floating,
+                stacked,
+            } => {
+                let parsed_pane_id = PaneId::from_str(&pane_id).map_err(|_| {
+                    format!(
+                        "Malformed pane id: {}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)",
+                        pane_id
+                    )
+                })?;
+                let placement = if floating {
+                    JoinPanePlacement::Floating
+                } else if stacked {
+                    JoinPanePlacement::Stacked
+                } else {
+                    JoinPanePlacement::Tiled(direction)
+                };
+                Ok(vec![Action::JoinPane(parsed_pane_id, placement)])
+            },
+            CliAction::SwapPanes {
+                pane_id,
+                other_pane_id,
+            } => {
+                let parse_pane_id = |pane_id: &str| {
+                    PaneId::from_str(pane_id).map_err(|_| {
+                        format!(
+                            "Malformed pane id: {}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)",
+                            pane_id
+                        )
+                    })
+                };
+                Ok(vec![Action::SwapPanes(
+                    parse_pane_id(&pane_id)?,
+                    parse_pane_id(&other_pane_id)?,
+                )])
+            },
+            CliAction::RotatePanes { counter_clockwise } => {
+                let rotate_direction = if counter_clockwise {
+                    RotateDirection::CounterClockwise
+                } else {
+                    RotateDirection::Clockwise
+                };
+                Ok(vec![Action::RotatePanes(rotate_direction)])
+            },
+            CliAction::MirrorLayout { direction } => Ok(vec![Action::MirrorLayout(direction)]),
+            CliAction::EqualizePanes => Ok(vec![Action::EqualizePanes]),
+            CliAction::ToggleGoldenRatio => Ok(vec![Action::ToggleGoldenRatio]),
+            CliAction::TogglePaneInSyncGroup => Ok(vec![Action::TogglePaneInSyncGroup]),
+            CliAction::ClearSyncGroup => Ok(vec![Action::ClearSyncGroup]),
+            CliAction::PaneJump { swap, all_tabs } => {
+                let pane_jump_mode = if swap {
+                    PaneJumpMode::Swap
+                } else {
+                    PaneJumpMode::Focus
+                };
+                Ok(vec![Action::PaneJump(pane_jump_mode, all_tabs)])
+            },
+            CliAction::ToggleScratchpad { name } => Ok(vec![Action::ToggleScratchpad(name)]),
+            CliAction::GoToTabGroup { name } => Ok(vec![Action::GoToTabGroup(name)]),
+            CliAction::GoToNextTabInGroup => Ok(vec![Action::GoToNextTabInGroup]),
+            CliAction::GoToPreviousTabInGroup => Ok(vec![Action::GoToPreviousTabInGroup]),
+            CliAction::MoveTabToGroup { name } => Ok(vec![Action::MoveTabToGroup(name)]),
+            CliAction::ArrangeFloatingPanes { arrangement } => {
+                Ok(vec![Action::ArrangeFloatingPanes(arrangement)])
+            },
+            CliAction::ListTabs { json } => Ok(vec![Action::ListTabs(json)]),
+            CliAction::ListPanes { json } => Ok(vec![Action::ListPanes(json)]),
+            CliAction::WaitFor {
+                name,
+                signal,
+                pane_exit,
+                pane_close,
+                pattern,
+                in_pane,
+                timeout,
+            } => {
+                let condition = match (name, pane_exit, pane_close, pattern.zip(in_pane)) {
+                    (Some(name), ..) if signal => {
+                        return Ok(vec![Action::SendWaitForSignal(name)]);
+                    },
+                    (Some(name), ..) => WaitForCondition::Signal(name),
+                    (_, Some(pane_id), ..) => WaitForCondition::PaneExit(parse_pane_id(&pane_id)?),
+                    (_, _, Some(pane_id), _) => {
+                        WaitForCondition::PaneClose(parse_pane_id(&pane_id)?)
+                    },
+                    (_, _, _, Some((pattern, pane_id))) => {
+                        WaitForCondition::Pattern(pattern, parse_pane_id(&pane_id)?)
+                    },
+                    _ => return Err(String::from("Nothing to wait for")),
+                };
+                Ok(vec![Action::WaitFor {
+                    pipe_id: Uuid::new_v4().to_string(),
+                    condition,
+                    timeout,
+                }])
+            },
+        }
+    }
+    /// Like [`Action::actions_from_cli`], but sends the action to the given pane or to the tab at
+    /// the given position rather than to the focused one
+    pub fn actions_from_cli_with_target(
+        cli_action: CliAction,
+        pane_id: Option<String>,
+        tab_index: Option<usize>,
+        get_current_dir: Box<dyn Fn() -> PathBuf>,
+        config: Option<Config>,
+    ) -> Result<Vec<Action>, String> {
+        match (pane_id, tab_index) {
+            (Some(pane_id), _) => {
+                Action::actions_from_cli_in_pane(cli_action, parse_pane_id(&pane_id)?)
+            },
+            (None, Some(tab_index)) => match tab_index.checked_sub(1) {
+                Some(tab_position) => Action::actions_from_cli_in_tab(cli_action, tab_position),
+                None => Err(String::from("--tab-index starts at 1")),
+            },
+            (None, None) => Action::actions_from_cli(cli_action, get_current_dir, config),
+        }
+    }
+    fn actions_from_cli_in_pane(
+        cli_action: CliAction,
+        pane_id: PaneId,
+    ) -> Result<Vec<Action>, String> {
+        match cli_action {
+            CliAction::Write { bytes } => Ok(vec![Action::WriteToPaneId(bytes, pane_id)]),
+            CliAction::WriteChars { chars } => {
+                Ok(vec![Action::WriteToPaneId(chars.into_bytes(), pane_id)])
+            },
+            CliAction::SendKeys { keys } => {
+                Ok(vec![Action::SendKeysToPaneId(parse_keys(keys)?, pane_id)])
+            },
+            CliAction::Resize {
+                resize,
+                direction,
+                to: None,
+                ..
+            } => Ok(vec![Action::ResizePaneWithId(
+                resize.unwrap_or(Resize::Increase),
+                direction,
+                pane_id,
+            )]),
+            CliAction::MovePane { direction } => {
+                Ok(vec![Action::MovePaneWithPaneId(direction, pane_id)])
+            },
+            CliAction::Clear => Ok(vec![Action::ClearScreenForPaneId(pane_id)]),
+            CliAction::DumpScreen { path, full } => Ok(vec![Action::DumpScreenForPaneId(
+                path.as_os_str().to_string_lossy().into(),
+                full,
+                pane_id,
+            )]),
+            CliAction::EditScrollback => Ok(vec![Action::EditScrollbackForPaneId(pane_id)]),
+            CliAction::ScrollUp => Ok(vec![Action::ScrollUpInPaneId(pane_id)]),
+            CliAction::ScrollDown => Ok(vec![Action::ScrollDownInPaneId(pane_id)]),
+            CliAction::ScrollToTop => Ok(vec![Action::ScrollToTopInPaneId(pane_id)]),
+            CliAction::ScrollToBottom => Ok(vec![Action::ScrollToBottomInPaneId(pane_id)]),
+            CliAction::PageScrollUp => Ok(vec![Action::PageScrollUpInPaneId(pane_id)]),
+            CliAction::PageScrollDown => Ok(vec![Action::PageScrollDownInPaneId(pane_id)]),
+            CliAction::ToggleFullscreen => Ok(vec![Action::TogglePaneIdFullscreen(pane_id)]),
+            CliAction::TogglePaneEmbedOrFloating => {
+                Ok(vec![Action::TogglePaneEmbedOrFloatingForPaneId(pane_id)])
+            },
+            CliAction::ClosePane => Ok(vec![Action::ClosePaneWithId(pane_id)]),
+            CliAction::RenamePane { name } => {
+                Ok(vec![Action::RenamePaneWithId(pane_id, name.into_bytes())])
+            },
+            _ => Err(String::from(
+                "This action can not be sent to a specific pane, try it without --pane-id",
+            )),
+        }
+    }
+    fn actions_from_cli_in_tab(
+        cli_action: CliAction,
+        tab_position: usize,
+    ) -> Result<Vec<Action>, String> {
+        match cli_action {
+            CliAction::CloseTab => Ok(vec![Action::CloseTabAtPosition(tab_position)]),
+            CliAction::RenameTab { name } => Ok(vec![Action::RenameTabAtPosition(
+                tab_position,
+                name.into_bytes(),
+            )]),
+            _ => Err(String::from(
+                "This action can not be sent to a specific tab, try it without --tab-index",
+            )),
         }
     }
     pub fn launches_plugin(&self, plugin_url: &str) -> bool {
@@ -810,6 +1245,33 @@ impl Action {
     }
 }
 
+fn parse_pane_id(pane_id: &str) -> Result<PaneId, String> {
+    PaneId::from_str(pane_id).map_err(|_| {
+        format!(
+            "Malformed pane id: {}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)",
+            pane_id
+        )
+    })
+}
+
+fn parse_keys(keys: Vec<String>) -> Result<Vec<KeyWithModifier>, String> {
+    keys.iter()
+        .map(|key| {
+            let parsed_key = KeyWithModifier::from_str(key)
+                .map_err(|e| format!("Failed to parse key \"{}\": {}", key, e))?;
+            #[cfg(not(target_family = "wasm"))]
+            if parsed_key
+                .serialize_for_write()
+                .map(|(raw_bytes, _)| raw_bytes.is_empty())
+                .unwrap_or(true)
+            {
+                return Err(format!("Key \"{}\" cannot be sent to a terminal", key));
+            }
+            Ok(parsed_key)
+        })
+        .collect()
+}
+
 impl From<OnForceClose> for Action {
     fn from(ofc: OnForceClose) -> Action {
         match ofc {
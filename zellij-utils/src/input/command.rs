@@ -1,7 +1,8 @@
 //! Trigger a command
 use crate::data::{Direction, OriginatingPlugin};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub enum TerminalAction {
@@ -20,6 +21,17 @@ impl TerminalAction {
             },
         }
     }
+    /// Sets environment variables for the spawned process, on top of the ones it already has
+    pub fn add_env(&mut self, env: &BTreeMap<String, String>) {
+        match self {
+            TerminalAction::OpenFile(open_file_payload) => {
+                open_file_payload.env.extend(env.clone());
+            },
+            TerminalAction::RunCommand(run_command) => {
+                run_command.env.extend(env.clone());
+            },
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -28,6 +40,8 @@ pub struct OpenFilePayload {
     pub line_number: Option<usize>,
     pub cwd: Option<PathBuf>,
     pub originating_plugin: Option<OriginatingPlugin>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 impl OpenFilePayload {
@@ -37,6 +51,7 @@ impl OpenFilePayload {
             line_number,
             cwd,
             originating_plugin: None,
+            env: BTreeMap::new(),
         }
     }
     pub fn with_originating_plugin(mut self, originating_plugin: OriginatingPlugin) -> Self {
@@ -58,7 +73,16 @@ pub struct RunCommand {
     #[serde(default)]
     pub hold_on_start: bool,
     #[serde(default)]
+    pub close_on_success: bool, // overrides hold_on_close when the command exits with status 0
+    #[serde(default)]
+    pub output_pipe_id: Option<String>, // cli pipe receiving the pane contents once it exits
+    #[serde(default)]
     pub originating_plugin: Option<OriginatingPlugin>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>, // set on top of the environment the command inherits
+    #[serde(default)]
+    pub remote: Option<RemoteHost>, // if Some, the command runs on this host, an empty command
+                                    // being its login shell
 }
 
 impl std::fmt::Display for RunCommand {
@@ -73,7 +97,106 @@ impl std::fmt::Display for RunCommand {
             command.push(' ');
             command.push_str(arg);
         }
-        write!(f, "{}", command)
+        match &self.remote {
+            Some(remote) if command.is_empty() => write!(f, "{}", remote.destination()),
+            Some(remote) => write!(f, "{}: {}", remote.destination(), command),
+            None => write!(f, "{}", command),
+        }
+    }
+}
+
+/// A host the command of a pane runs on, connected to with ssh (or a compatible command)
+#[derive(Clone, Debug, Deserialize, Default, Serialize, PartialEq, Eq)]
+pub struct RemoteHost {
+    pub host: String,
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<PathBuf>, // on the remote host
+    #[serde(default)]
+    pub connect_with: Option<PathBuf>, // the ssh compatible command to connect with, ssh if None
+}
+
+impl RemoteHost {
+    /// Parses a `host` or `user@host` destination
+    pub fn new(destination: &str) -> Self {
+        match destination.rsplit_once('@') {
+            Some((user, host)) => RemoteHost {
+                host: host.to_owned(),
+                user: Some(user.to_owned()),
+                ..Default::default()
+            },
+            None => RemoteHost {
+                host: destination.to_owned(),
+                ..Default::default()
+            },
+        }
+    }
+    pub fn destination(&self) -> String {
+        match &self.user {
+            Some(user) => format!("{}@{}", user, self.host),
+            None => self.host.clone(),
+        }
+    }
+    /// The local command connecting to the host and running `command` there, or the login shell
+    /// of the host if `command` is empty
+    pub fn connect_command(&self, command: &Path, args: &[String]) -> (PathBuf, Vec<String>) {
+        let connect_with = self
+            .connect_with
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("ssh"));
+        let mut connect_args = vec!["-t".to_owned()];
+        if let Some(user) = &self.user {
+            connect_args.push("-l".to_owned());
+            connect_args.push(user.clone());
+        }
+        // so that the host is never taken for an option of the connecting command
+        connect_args.push("--".to_owned());
+        connect_args.push(self.host.clone());
+        let remote_command = if command.as_os_str().is_empty() {
+            None
+        } else {
+            let mut remote_command = shell_quote(&command.to_string_lossy());
+            for arg in args {
+                remote_command.push(' ');
+                remote_command.push_str(&shell_quote(arg));
+            }
+            Some(remote_command)
+        };
+        match (&self.cwd, remote_command) {
+            (Some(cwd), Some(remote_command)) => {
+                connect_args.push(format!(
+                    "cd {} && {}",
+                    quote_remote_path(cwd),
+                    remote_command
+                ));
+            },
+            (Some(cwd), None) => {
+                connect_args.push(format!(
+                    "cd {} && exec \"$SHELL\" -l",
+                    quote_remote_path(cwd)
+                ));
+            },
+            (None, Some(remote_command)) => connect_args.push(remote_command),
+            (None, None) => {},
+        }
+        (connect_with, connect_args)
+    }
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+// the home folder is expanded by the remote shell, so it is left out of the quotes
+fn quote_remote_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if path == "~" {
+        path.to_string()
+    } else if let Some(path_in_home) = path.strip_prefix("~/") {
+        format!("~/{}", shell_quote(path_in_home))
+    } else {
+        shell_quote(&path)
     }
 }
 
@@ -93,6 +216,10 @@ pub struct RunCommandAction {
     #[serde(default)]
     pub hold_on_start: bool,
     #[serde(default)]
+    pub close_on_success: bool, // overrides hold_on_close when the command exits with status 0
+    #[serde(default)]
+    pub output_pipe_id: Option<String>, // cli pipe receiving the pane contents once it exits
+    #[serde(default)]
     pub originating_plugin: Option<OriginatingPlugin>,
 }
 
@@ -104,7 +231,11 @@ impl From<RunCommandAction> for RunCommand {
             cwd: action.cwd,
             hold_on_close: action.hold_on_close,
             hold_on_start: action.hold_on_start,
+            close_on_success: action.close_on_success,
+            output_pipe_id: action.output_pipe_id,
             originating_plugin: action.originating_plugin,
+            env: BTreeMap::new(),
+            remote: None,
         }
     }
 }
@@ -118,6 +249,8 @@ impl From<RunCommand> for RunCommandAction {
             direction: None,
             hold_on_close: run_command.hold_on_close,
             hold_on_start: run_command.hold_on_start,
+            close_on_success: run_command.close_on_success,
+            output_pipe_id: run_command.output_pipe_id,
             originating_plugin: run_command.originating_plugin,
         }
     }
@@ -134,4 +267,19 @@ impl RunCommand {
         self.cwd = Some(cwd);
         self
     }
+    /// The login shell of the given host
+    pub fn remote_shell(remote: RemoteHost) -> Self {
+        RunCommand {
+            remote: Some(remote),
+            ..Default::default()
+        }
+    }
+    /// The command and arguments to spawn locally, connecting to the remote host first if there
+    /// is one
+    pub fn local_command_and_args(&self) -> (PathBuf, Vec<String>) {
+        match &self.remote {
+            Some(remote) => remote.connect_command(&self.command, &self.args),
+            None => (self.command.clone(), self.args.clone()),
+        }
+    }
 }
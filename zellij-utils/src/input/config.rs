@@ -1,24 +1,29 @@
 use crate::data::Styling;
+use kdl::{KdlDocument, KdlNode};
 use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use std::convert::TryFrom;
 
+use super::hooks::Hooks;
 use super::keybinds::Keybinds;
 use super::layout::RunPluginOrAlias;
 use super::options::Options;
 use super::plugins::{PluginAliases, PluginsConfigError};
+use super::scratchpad::Scratchpads;
 use super::theme::{Themes, UiConfig};
 use crate::cli::{CliArgs, Command};
 use crate::envs::EnvironmentVariables;
 use crate::{home, setup};
 
 const DEFAULT_CONFIG_FILE_NAME: &str = "config.kdl";
+pub(crate) const INCLUDE_NODE_NAME: &str = "include";
 
 type ConfigResult = Result<Config, ConfigError>;
 
@@ -32,6 +37,8 @@ pub struct Config {
     pub ui: UiConfig,
     pub env: EnvironmentVariables,
     pub background_plugins: HashSet<RunPluginOrAlias>,
+    pub scratchpads: Scratchpads,
+    pub hooks: Hooks,
 }
 
 #[derive(Error, Debug)]
@@ -183,13 +190,27 @@ impl Config {
             Err(e) => Err(e),
         }
     }
+    /// Loads the config file layered over the default config, if any: the files it includes are
+    /// merged over the default config in the order they are included, and the config file itself
+    /// is then parsed over them so that it overrides (and can unbind keys of) all of them
     pub fn from_path(path: &PathBuf, default_config: Option<Config>) -> ConfigResult {
+        Config::from_path_included_from(path, default_config, &mut vec![])
+    }
+    fn from_path_included_from(
+        path: &PathBuf,
+        default_config: Option<Config>,
+        including_files: &mut Vec<PathBuf>,
+    ) -> ConfigResult {
         match File::open(path) {
             Ok(mut file) => {
                 let mut kdl_config = String::new();
                 file.read_to_string(&mut kdl_config)
                     .map_err(|e| ConfigError::IoPath(e, path.to_path_buf()))?;
-                match Config::from_kdl(&kdl_config, default_config) {
+                including_files.push(fs::canonicalize(path).unwrap_or_else(|_| path.clone()));
+                let base_config =
+                    Config::load_includes(path, &kdl_config, default_config, including_files);
+                including_files.pop();
+                match Config::from_kdl(&kdl_config, base_config?) {
                     Ok(config) => Ok(config),
                     Err(ConfigError::KdlDeserializationError(kdl_error)) => {
                         let error_message = match kdl_error.kind {
@@ -228,6 +249,106 @@ impl Config {
             Err(e) => Err(ConfigError::IoPath(e, path.into())),
         }
     }
+    /// Loads each file included in this config file over the given config and the files included
+    /// before it (the files matching a glob in alphabetical order), so that they can eg. clear or
+    /// unbind the keybindings of the ones they are layered over
+    fn load_includes(
+        path: &Path,
+        kdl_config: &str,
+        mut config: Option<Config>,
+        including_files: &mut Vec<PathBuf>,
+    ) -> Result<Option<Config>, ConfigError> {
+        let kdl_document: KdlDocument = match kdl_config.parse() {
+            Ok(kdl_document) => kdl_document,
+            // this will be reported when parsing the config itself
+            Err(_) => return Ok(config),
+        };
+        let include_error = |error_message: String, include_node: &KdlNode| {
+            ConfigError::KdlError(KdlError {
+                error_message,
+                src: Some(NamedSource::new(
+                    path.as_os_str().to_string_lossy(),
+                    kdl_config.to_owned(),
+                )),
+                offset: Some(include_node.span().offset()),
+                len: Some(include_node.span().len()),
+                help_message: None,
+            })
+        };
+        for include_node in kdl_document
+            .nodes()
+            .iter()
+            .filter(|n| n.name().value() == INCLUDE_NODE_NAME)
+        {
+            let include = include_node
+                .entries()
+                .first()
+                .and_then(|e| e.value().as_string())
+                .ok_or_else(|| {
+                    include_error(
+                        "include must have a path, eg. include \"shared.kdl\"".into(),
+                        include_node,
+                    )
+                })?;
+            let included_files = resolve_include(&include_path(path, include))
+                .map_err(|e| include_error(e, include_node))?;
+            for included_file in included_files {
+                let canonical_path =
+                    fs::canonicalize(&included_file).unwrap_or_else(|_| included_file.clone());
+                if including_files.contains(&canonical_path) {
+                    return Err(include_error(
+                        format!("{} includes itself", included_file.display()),
+                        include_node,
+                    ));
+                }
+                config = Some(Config::from_path_included_from(
+                    &included_file,
+                    config,
+                    including_files,
+                )?);
+            }
+        }
+        Ok(config)
+    }
+    /// The config file and everything it includes, recursively - with globs left as they are so
+    /// that files created later which match them are included as well
+    pub fn config_file_and_includes(path: &Path) -> Vec<PathBuf> {
+        let mut config_file_and_includes = vec![path.to_path_buf()];
+        let mut files_to_read = vec![path.to_path_buf()];
+        let mut read_files = HashSet::new();
+        while let Some(file) = files_to_read.pop() {
+            if !read_files.insert(file.clone()) {
+                continue;
+            }
+            let kdl_document: Option<KdlDocument> = fs::read_to_string(&file)
+                .ok()
+                .and_then(|kdl_config| kdl_config.parse().ok());
+            let includes = kdl_document
+                .iter()
+                .flat_map(|d| d.nodes())
+                .filter(|n| n.name().value() == INCLUDE_NODE_NAME)
+                .filter_map(|n| n.entries().first().and_then(|e| e.value().as_string()))
+                .map(|include| include_path(&file, include))
+                .collect::<Vec<_>>();
+            for include in includes {
+                files_to_read.append(&mut resolve_include(&include).unwrap_or_default());
+                config_file_and_includes.push(include);
+            }
+        }
+        config_file_and_includes
+    }
+    /// Whether the path is one of (or matches a glob in) the result of
+    /// [`Config::config_file_and_includes`]
+    pub fn is_config_file_or_include(config_file_and_includes: &[PathBuf], path: &Path) -> bool {
+        config_file_and_includes.iter().any(|include| {
+            include == path
+                || (include.parent() == path.parent()
+                    && file_name_glob(include)
+                        .zip(path.file_name())
+                        .map(|(glob, file_name)| glob.is_match(&file_name.to_string_lossy()))
+                        .unwrap_or(false))
+        })
+    }
     pub fn merge(&mut self, other: Config) -> Result<(), ConfigError> {
         self.options = self.options.merge(other.options);
         self.keybinds.merge(other.keybinds.clone());
@@ -235,6 +356,9 @@ impl Config {
         self.plugins.merge(other.plugins);
         self.ui = self.ui.merge(other.ui);
         self.env = self.env.merge(other.env);
+        self.scratchpads.merge(other.scratchpads);
+        self.hooks.merge(other.hooks);
+        self.background_plugins.extend(other.background_plugins);
         Ok(())
     }
     pub fn config_file_path(opts: &CliArgs) -> Option<PathBuf> {
@@ -253,6 +377,17 @@ impl Config {
                 None
             })
             .and_then(|parsed_config| {
+                // the config is written as one file, which would leave the files it includes
+                // out of it
+                if let Some(config_file_path) = Config::config_file_path(opts) {
+                    if Config::config_file_and_includes(&config_file_path).len() > 1 {
+                        log::error!(
+                            "Not writing over {} as it includes other config files",
+                            config_file_path.display()
+                        );
+                        return Err(Some(config_file_path));
+                    }
+                }
                 let backed_up_file_name = Config::backup_current_config(&opts)?;
                 let config_file_path = Config::config_file_path(&opts).ok_or_else(|| {
                     log::error!("Config file path not found");
@@ -404,12 +539,67 @@ impl Config {
     }
 }
 
+// includes are relative to the folder of the file including them
+fn include_path(including_file: &Path, include: &str) -> PathBuf {
+    including_file
+        .parent()
+        .map(|folder| folder.join(include))
+        .unwrap_or_else(|| PathBuf::from(include))
+}
+
+/// `*` and `?` in the file name of an include match any characters and any one character
+/// respectively
+fn file_name_glob(include: &Path) -> Option<Regex> {
+    let file_name = include.file_name()?.to_string_lossy();
+    if !file_name.contains(['*', '?']) {
+        return None;
+    }
+    let glob: String = file_name
+        .chars()
+        .map(|c| match c {
+            '*' => String::from(".*"),
+            '?' => String::from("."),
+            c => regex::escape(&c.to_string()),
+        })
+        .collect();
+    Regex::new(&format!("^{}$", glob)).ok()
+}
+
+/// The files an include refers to, those matching its glob in alphabetical order if it has one
+pub(crate) fn resolve_include(include: &Path) -> Result<Vec<PathBuf>, String> {
+    match (file_name_glob(include), include.parent()) {
+        (Some(glob), Some(folder)) => {
+            let mut included_files: Vec<PathBuf> = fs::read_dir(folder)
+                .map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .file_name()
+                            .map(|file_name| glob.is_match(&file_name.to_string_lossy()))
+                            .unwrap_or(false)
+                })
+                .collect();
+            included_files.sort();
+            Ok(included_files)
+        },
+        _ if include.is_file() => Ok(vec![include.to_path_buf()]),
+        _ => Err(format!("Included file {} not found", include.display())),
+    }
+}
+
 #[cfg(test)]
 mod config_test {
     use super::*;
-    use crate::data::{InputMode, Palette, PaletteColor, PluginTag, StyleDeclaration, Styling};
+    use crate::data::{
+        BareKey, FloatingPaneCoordinates, InputMode, KeyWithModifier, Palette, PaletteColor,
+        PluginTag, StyleDeclaration, Styling,
+    };
+    use crate::input::command::RunCommand;
+    use crate::input::hooks::HookEvent;
     use crate::input::layout::{RunPlugin, RunPluginLocation};
     use crate::input::options::{Clipboard, OnForceClose};
+    use crate::input::scratchpad::Scratchpad;
     use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
     use std::collections::{BTreeMap, HashMap};
     use std::io::Write;
@@ -1157,4 +1347,315 @@ mod config_test {
             "Env variables defined in config"
         );
     }
+
+    #[test]
+    fn can_define_scratchpads_in_config_file() {
+        let config_contents = r#"
+            scratchpad "notes" command="nvim 'my notes.md'" cwd="/tmp" width="80%" height=20
+            scratchpad "shell"
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        let notes = config.scratchpads.get("notes").unwrap();
+        assert_eq!(
+            notes.run,
+            Some(RunCommand {
+                command: PathBuf::from("nvim"),
+                args: vec!["my notes.md".to_owned()],
+                cwd: Some(PathBuf::from("/tmp")),
+                ..Default::default()
+            }),
+            "Scratchpad command split into command and args"
+        );
+        assert_eq!(
+            notes.coordinates,
+            FloatingPaneCoordinates::new(None, None, Some("80%".into()), Some("20".into()), None),
+            "Scratchpad coordinates"
+        );
+        assert_eq!(
+            config.scratchpads.get("shell"),
+            Some(&Scratchpad::default()),
+            "Scratchpad without a command opens the default shell"
+        );
+        let serialized = Config::from_kdl(&config.to_string(false), None).unwrap();
+        assert_eq!(
+            serialized.scratchpads, config.scratchpads,
+            "Scratchpads survive serialization"
+        );
+    }
+
+    #[test]
+    fn can_define_hooks_in_config_file() {
+        let config_contents = r#"
+            hooks {
+                on_session_create "echo created"
+                on_pane_exit "notify-send \"pane $ZELLIJ_HOOK_PANE_ID exited\""
+                on_pane_exit "logger pane exited"
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        assert_eq!(
+            config.hooks.get(HookEvent::SessionCreate),
+            &["echo created".to_owned()],
+            "Hook command"
+        );
+        assert_eq!(
+            config.hooks.get(HookEvent::PaneExit),
+            &[
+                "notify-send \"pane $ZELLIJ_HOOK_PANE_ID exited\"".to_owned(),
+                "logger pane exited".to_owned()
+            ],
+            "Repeated hooks run in the order they were declared"
+        );
+        assert!(
+            config.hooks.get(HookEvent::TabClose).is_empty(),
+            "Undeclared hook has no commands"
+        );
+        let serialized = Config::from_kdl(&config.to_string(false), None).unwrap();
+        assert_eq!(
+            serialized.hooks, config.hooks,
+            "Hooks survive serialization"
+        );
+        assert!(
+            Config::from_kdl("hooks { on_session_start \"echo\"; }", None).is_err(),
+            "Unknown hook is an error"
+        );
+    }
+
+    #[test]
+    fn config_file_is_layered_over_its_includes() {
+        let tmp = tempdir().unwrap();
+        fs::create_dir(tmp.path().join("conf.d")).unwrap();
+        File::create(tmp.path().join("shared.kdl"))
+            .unwrap()
+            .write_all(b"scroll_buffer_size 100\ndefault_shell \"fish\"\n")
+            .unwrap();
+        File::create(tmp.path().join("conf.d/a.kdl"))
+            .unwrap()
+            .write_all(b"pane_frames false\nmouse_mode false\n")
+            .unwrap();
+        File::create(tmp.path().join("conf.d/b.kdl"))
+            .unwrap()
+            .write_all(b"mouse_mode true\n")
+            .unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        File::create(&config_file_path)
+            .unwrap()
+            .write_all(b"include \"shared.kdl\"\ninclude \"conf.d/*.kdl\"\ndefault_shell \"zsh\"\n")
+            .unwrap();
+        let config = Config::from_path(&config_file_path, None).unwrap();
+        assert_eq!(
+            config.options.scroll_buffer_size,
+            Some(100),
+            "Option from an included file"
+        );
+        assert_eq!(
+            config.options.default_shell,
+            Some(PathBuf::from("zsh")),
+            "Config file overrides its includes"
+        );
+        assert_eq!(
+            config.options.pane_frames,
+            Some(false),
+            "Option from a glob include"
+        );
+        assert_eq!(
+            config.options.mouse_mode,
+            Some(true),
+            "Glob includes are layered in alphabetical order"
+        );
+        let config_file_and_includes = Config::config_file_and_includes(&config_file_path);
+        assert!(
+            Config::is_config_file_or_include(
+                &config_file_and_includes,
+                &tmp.path().join("shared.kdl")
+            ),
+            "Included file is watched"
+        );
+        assert!(
+            Config::is_config_file_or_include(
+                &config_file_and_includes,
+                &tmp.path().join("conf.d/c.kdl")
+            ),
+            "New file matching a glob include is watched"
+        );
+        assert!(
+            !Config::is_config_file_or_include(
+                &config_file_and_includes,
+                &tmp.path().join("layouts.kdl")
+            ),
+            "Other files are not watched"
+        );
+    }
+
+    #[test]
+    fn included_files_are_merged_over_the_default_config() {
+        let tmp = tempdir().unwrap();
+        File::create(tmp.path().join("keys.kdl"))
+            .unwrap()
+            .write_all(
+                b"keybinds {\n    normal {\n        bind \"Ctrl y\" { NewPane; }\n    }\n}\n",
+            )
+            .unwrap();
+        File::create(tmp.path().join("hooks.kdl"))
+            .unwrap()
+            .write_all(b"hooks {\n    on_tab_create \"echo tab\"\n}\n")
+            .unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        File::create(&config_file_path)
+            .unwrap()
+            .write_all(b"include \"keys.kdl\"\ninclude \"hooks.kdl\"\nkeybinds {\n    normal {\n        bind \"Ctrl z\" { NewTab; }\n    }\n}\n")
+            .unwrap();
+        let default_config = Config::from_default_assets().unwrap();
+        let config = Config::from_path(&config_file_path, Some(default_config)).unwrap();
+        let ctrl = |c| KeyWithModifier::new(BareKey::Char(c)).with_ctrl_modifier();
+        assert!(
+            config
+                .keybinds
+                .get_actions_for_key_in_mode(&InputMode::Normal, &ctrl('y'))
+                .is_some(),
+            "Keybinding from an included file"
+        );
+        assert!(
+            config
+                .keybinds
+                .get_actions_for_key_in_mode(&InputMode::Normal, &ctrl('z'))
+                .is_some(),
+            "Keybinding from the config file"
+        );
+        assert!(
+            config
+                .keybinds
+                .get_actions_for_key_in_mode(&InputMode::Locked, &ctrl('g'))
+                .is_some(),
+            "Default keybinding"
+        );
+        assert_eq!(
+            config.hooks.get(HookEvent::TabCreate),
+            &["echo tab".to_owned()],
+            "Hooks from an included file that does not declare keybindings"
+        );
+    }
+
+    #[test]
+    fn included_files_are_parsed_over_the_config_they_are_layered_on() {
+        let tmp = tempdir().unwrap();
+        File::create(tmp.path().join("keys.kdl"))
+            .unwrap()
+            .write_all(b"keybinds clear-defaults=true {\n    normal {\n        bind \"Ctrl y\" { NewPane; }\n    }\n}\n")
+            .unwrap();
+        File::create(tmp.path().join("unbind.kdl"))
+            .unwrap()
+            .write_all(b"keybinds {\n    unbind \"Ctrl y\"\n    normal {\n        bind \"Ctrl u\" { NewTab; }\n    }\n}\n")
+            .unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        File::create(&config_file_path)
+            .unwrap()
+            .write_all(b"include \"keys.kdl\"\ninclude \"unbind.kdl\"\n")
+            .unwrap();
+        let default_config = Config::from_default_assets().unwrap();
+        let config = Config::from_path(&config_file_path, Some(default_config)).unwrap();
+        let ctrl = |c| KeyWithModifier::new(BareKey::Char(c)).with_ctrl_modifier();
+        assert!(
+            config
+                .keybinds
+                .get_actions_for_key_in_mode(&InputMode::Locked, &ctrl('g'))
+                .is_none(),
+            "Default keybinding cleared by an included file"
+        );
+        assert!(
+            config
+                .keybinds
+                .get_actions_for_key_in_mode(&InputMode::Normal, &ctrl('y'))
+                .is_none(),
+            "Keybinding of an included file unbound by a later one"
+        );
+        assert!(
+            config
+                .keybinds
+                .get_actions_for_key_in_mode(&InputMode::Normal, &ctrl('u'))
+                .is_some(),
+            "Keybinding from the last included file"
+        );
+    }
+
+    #[test]
+    fn config_with_includes_is_not_written_over() {
+        let tmp = tempdir().unwrap();
+        File::create(tmp.path().join("shared.kdl"))
+            .unwrap()
+            .write_all(b"pane_frames false\n")
+            .unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        let kdl_config = "include \"shared.kdl\"\n";
+        File::create(&config_file_path)
+            .unwrap()
+            .write_all(kdl_config.as_bytes())
+            .unwrap();
+        let opts = CliArgs {
+            config: Some(config_file_path.clone()),
+            ..Default::default()
+        };
+        let config = Config::from_path(&config_file_path, None).unwrap();
+        assert_eq!(
+            Config::write_config_to_disk(config.to_string(false), &opts),
+            Err(Some(config_file_path.clone()))
+        );
+        assert_eq!(
+            fs::read_to_string(&config_file_path).unwrap(),
+            kdl_config,
+            "Config file left as it is"
+        );
+    }
+
+    #[test]
+    fn errors_in_included_files_point_into_them() {
+        let tmp = tempdir().unwrap();
+        let included_file_path = tmp.path().join("shared.kdl");
+        File::create(&included_file_path)
+            .unwrap()
+            .write_all(
+                b"keybinds {\n    normal {\n        bind \"Ctrl g\" { NotAnAction; }\n    }\n}\n",
+            )
+            .unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        File::create(&config_file_path)
+            .unwrap()
+            .write_all(b"include \"shared.kdl\"\n")
+            .unwrap();
+        match Config::from_path(&config_file_path, None) {
+            Err(ConfigError::KdlError(kdl_error)) => {
+                let span: miette::SourceSpan =
+                    (kdl_error.offset.unwrap_or(0), kdl_error.len.unwrap_or(0)).into();
+                let error_source = kdl_error
+                    .source_code()
+                    .and_then(|source_code| source_code.read_span(&span, 0, 0).ok())
+                    .and_then(|span_contents| span_contents.name().map(|name| name.to_owned()));
+                assert_eq!(
+                    error_source,
+                    Some(included_file_path.to_string_lossy().to_string()),
+                    "Error source is the included file"
+                );
+            },
+            other => panic!("expected a kdl error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn include_cycles_are_errors() {
+        let tmp = tempdir().unwrap();
+        File::create(tmp.path().join("shared.kdl"))
+            .unwrap()
+            .write_all(format!("include \"{}\"\n", DEFAULT_CONFIG_FILE_NAME).as_bytes())
+            .unwrap();
+        let config_file_path = tmp.path().join(DEFAULT_CONFIG_FILE_NAME);
+        File::create(&config_file_path)
+            .unwrap()
+            .write_all(b"include \"shared.kdl\"\n")
+            .unwrap();
+        assert!(Config::from_path(&config_file_path, None).is_err());
+        assert!(
+            Config::from_path(&tmp.path().join("missing.kdl"), None).is_err(),
+            "Missing config file"
+        );
+    }
 }
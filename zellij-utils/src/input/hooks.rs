@@ -0,0 +1,93 @@
+//! Commands run on session lifecycle events, declared in the `hooks` block of the config, eg.
+//! `hooks { on_pane_exit "notify-send \"pane $ZELLIJ_HOOK_PANE_ID exited\""; }`
+//!
+//! Hooks run in the background, except for `on_session_kill`: the session (killed or left by its
+//! last client) is only torn down once those are done, and the server waits up to 5 seconds for
+//! them before killing them. The server does not handle other instructions meanwhile.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum HookEvent {
+    SessionCreate,
+    SessionAttach,
+    SessionDetach,
+    TabCreate,
+    TabClose,
+    PaneExit,    // only when the pane exited with a non-zero status
+    SessionKill, // waited for (up to 5 seconds) before the session is torn down
+}
+
+impl HookEvent {
+    /// The name of the event in the `hooks` block
+    pub fn kdl_name(&self) -> &'static str {
+        match self {
+            HookEvent::SessionCreate => "on_session_create",
+            HookEvent::SessionAttach => "on_session_attach",
+            HookEvent::SessionDetach => "on_session_detach",
+            HookEvent::TabCreate => "on_tab_create",
+            HookEvent::TabClose => "on_tab_close",
+            HookEvent::PaneExit => "on_pane_exit",
+            HookEvent::SessionKill => "on_session_kill",
+        }
+    }
+    pub fn from_kdl_name(name: &str) -> Option<Self> {
+        HookEvent::iter().find(|event| event.kdl_name() == name)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Hooks {
+    // shell command lines, run with `sh -c` in the order they were declared
+    pub hooks: BTreeMap<HookEvent, Vec<String>>,
+}
+
+impl Hooks {
+    pub fn get(&self, event: HookEvent) -> &[String] {
+        self.hooks
+            .get(&event)
+            .map(|commands| commands.as_slice())
+            .unwrap_or(&[])
+    }
+    pub fn merge(&mut self, other: Hooks) {
+        self.hooks.extend(other.hooks);
+    }
+}
+
+/// What triggered a hook, passed to its commands as `ZELLIJ_HOOK_*` env variables
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct HookContext {
+    pub session_name: Option<String>,
+    pub client_id: Option<u16>,
+    pub tab_index: Option<usize>,
+    pub tab_name: Option<String>,
+    pub pane_id: Option<u32>,
+    pub exit_code: Option<i32>,
+}
+
+impl HookContext {
+    pub fn env_variables(&self, event: HookEvent) -> BTreeMap<String, String> {
+        let mut env_variables = BTreeMap::new();
+        env_variables.insert("ZELLIJ_HOOK_EVENT".to_owned(), event.kdl_name().to_owned());
+        let mut insert = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                env_variables.insert(format!("ZELLIJ_HOOK_{}", name), value);
+            }
+        };
+        insert("SESSION_NAME", self.session_name.clone());
+        insert("CLIENT_ID", self.client_id.map(|c| c.to_string()));
+        insert("TAB_INDEX", self.tab_index.map(|t| t.to_string()));
+        insert("TAB_NAME", self.tab_name.clone());
+        insert("PANE_ID", self.pane_id.map(|p| p.to_string()));
+        insert("EXIT_CODE", self.exit_code.map(|e| e.to_string()));
+        env_variables
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/hooks_test.rs"]
+mod hooks_test;
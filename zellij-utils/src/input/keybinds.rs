@@ -5,6 +5,50 @@ use crate::data::{BareKey, InputMode, KeyWithModifier, KeybindsVec};
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
+
+/// Keys commonly used by programs running in the terminal, and what they use them for
+const TERMINAL_APP_KEYS: &[(&str, &str)] = &[
+    ("Ctrl a", "shells: go to the start of the line"),
+    ("Ctrl b", "tmux: prefix, vim: page up"),
+    ("Ctrl d", "shells: exit, vim: half a page down"),
+    ("Ctrl e", "shells: go to the end of the line"),
+    ("Ctrl f", "shells: move forward a character, vim: page down"),
+    ("Ctrl g", "emacs: cancel, nano: help"),
+    ("Ctrl h", "terminals: backspace, vim: move left"),
+    ("Ctrl n", "shells: next command, vim: completion"),
+    (
+        "Ctrl o",
+        "vim: jump back, nano: write out, emacs: open line",
+    ),
+    ("Ctrl p", "shells: previous command, vim: completion"),
+    ("Ctrl q", "terminals: resume output"),
+    ("Ctrl r", "shells: search history, vim: redo"),
+    (
+        "Ctrl s",
+        "emacs: search, nano: save, terminals: pause output",
+    ),
+    ("Ctrl t", "fzf: find files, shells: transpose characters"),
+    (
+        "Ctrl u",
+        "shells: delete to the start of the line, vim: half a page up",
+    ),
+    ("Ctrl w", "shells: delete a word, vim: window commands"),
+    ("Ctrl x", "emacs: prefix, nano: exit"),
+    ("Alt b", "shells: move back a word"),
+    ("Alt f", "shells: move forward a word"),
+];
+
+/// A keybinding in effect once the defaults, `clear-defaults` and the shared blocks have all
+/// been applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedKeybinding {
+    pub mode: InputMode,
+    pub key: String,
+    pub actions: Vec<String>,
+    /// What programs running in the terminal use the key for, if it never reaches them
+    pub shadows: Option<String>,
+}
 
 /// Used in the config struct
 #[derive(Clone, PartialEq, Deserialize, Serialize, Default)]
@@ -99,6 +143,50 @@ impl Keybinds {
         }
         ret
     }
+    /// The keybindings of all modes (or just the one given) sorted by mode and key, keys bound in
+    /// the mode the terminal is used in being the ones kept from the programs running in it
+    pub fn resolved_keybindings(
+        &self,
+        mode: Option<InputMode>,
+        default_input_mode: InputMode,
+    ) -> Vec<ResolvedKeybinding> {
+        let sorted_keybindings: BTreeMap<InputMode, BTreeMap<&KeyWithModifier, &Vec<Action>>> =
+            self.0
+                .iter()
+                .filter(|(input_mode, _)| mode.map(|m| m == **input_mode).unwrap_or(true))
+                .map(|(input_mode, keybindings)| (*input_mode, keybindings.iter().collect()))
+                .collect();
+        let mut resolved_keybindings = vec![];
+        for (input_mode, keybindings) in sorted_keybindings {
+            for (key, actions) in keybindings {
+                let shadows = if input_mode == default_input_mode {
+                    TERMINAL_APP_KEYS
+                        .iter()
+                        .find(|(app_key, _)| {
+                            KeyWithModifier::from_str(app_key)
+                                .map(|app_key| &app_key == key)
+                                .unwrap_or(false)
+                        })
+                        .map(|(_, used_for)| used_for.to_string())
+                } else {
+                    None
+                };
+                resolved_keybindings.push(ResolvedKeybinding {
+                    mode: input_mode,
+                    key: key.to_string(),
+                    actions: actions
+                        .iter()
+                        .map(|action| match action.to_kdl() {
+                            Some(kdl_action) => kdl_action.to_string().trim().to_owned(),
+                            None => format!("{:?}", action),
+                        })
+                        .collect(),
+                    shadows,
+                });
+            }
+        }
+        resolved_keybindings
+    }
     pub fn merge(&mut self, mut other: Keybinds) {
         for (other_input_mode, mut other_input_mode_keybinds) in other.0.drain() {
             let input_mode_keybinds = self
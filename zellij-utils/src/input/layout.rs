@@ -250,12 +250,19 @@ impl Run {
         match (base, other) {
             (Some(Run::Command(base_run_command)), Some(Run::Command(other_run_command))) => {
                 let mut merged = other_run_command.clone();
+                if merged.command.as_os_str().is_empty() {
+                    // a remote pane running the command of its template on its own host
+                    merged.command = base_run_command.command.clone();
+                }
                 if merged.cwd.is_none() && base_run_command.cwd.is_some() {
                     merged.cwd = base_run_command.cwd.clone();
                 }
                 if merged.args.is_empty() && !base_run_command.args.is_empty() {
                     merged.args = base_run_command.args.clone();
                 }
+                if merged.remote.is_none() {
+                    merged.remote = base_run_command.remote.clone();
+                }
                 Some(Run::Command(merged))
             },
             (Some(Run::Command(base_run_command)), Some(Run::Cwd(other_cwd))) => {
@@ -740,6 +747,27 @@ impl FromStr for PercentOrFixed {
     }
 }
 
+/// Tees the output of a terminal pane to a file
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct PaneLogging {
+    pub file: PathBuf,
+    /// Remove ANSI escape sequences (colors, cursor movements, etc.) from the logged output
+    pub strip_ansi: bool,
+    /// Prefix each logged line with the time it was written at
+    pub timestamps: bool,
+    /// Rotate the file once it grows beyond this many bytes
+    pub max_size: Option<u64>,
+}
+
+impl PaneLogging {
+    pub fn new(file: PathBuf) -> Self {
+        PaneLogging {
+            file,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub struct FloatingPaneLayout {
     pub name: Option<String>,
@@ -753,6 +781,8 @@ pub struct FloatingPaneLayout {
     pub already_running: bool,
     pub pane_initial_contents: Option<String>,
     pub logical_position: Option<usize>,
+    pub log_to_file: Option<PaneLogging>,
+    pub env: BTreeMap<String, String>,
 }
 
 impl FloatingPaneLayout {
@@ -769,6 +799,8 @@ impl FloatingPaneLayout {
             already_running: false,
             pane_initial_contents: None,
             logical_position: None,
+            log_to_file: None,
+            env: BTreeMap::new(),
         }
     }
     pub fn add_cwd_to_layout(&mut self, cwd: &PathBuf) {
@@ -779,6 +811,14 @@ impl FloatingPaneLayout {
             },
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        // variables set on the pane itself take precedence
+        for (name, value) in env {
+            self.env
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
     pub fn add_start_suspended(&mut self, start_suspended: Option<bool>) {
         if let Some(run) = self.run.as_mut() {
             run.add_start_suspended(start_suspended);
@@ -792,6 +832,8 @@ impl From<&TiledPaneLayout> for FloatingPaneLayout {
             name: pane_layout.name.clone(),
             run: pane_layout.run.clone(),
             focus: pane_layout.focus,
+            log_to_file: pane_layout.log_to_file.clone(),
+            env: pane_layout.env.clone(),
             ..Default::default()
         }
     }
@@ -813,6 +855,9 @@ pub struct TiledPaneLayout {
     pub run_instructions_to_ignore: Vec<Option<Run>>,
     pub hide_floating_panes: bool, // only relevant if this is the base layout
     pub pane_initial_contents: Option<String>,
+    pub log_to_file: Option<PaneLogging>,
+    pub tab_group: Option<String>, // only relevant if this is the base layout of a tab
+    pub env: BTreeMap<String, String>,
 }
 
 impl TiledPaneLayout {
@@ -932,21 +977,27 @@ impl TiledPaneLayout {
         Ok(layouts)
     }
     pub fn extract_run_instructions(&self) -> Vec<Option<Run>> {
+        self.extract_run_instructions_and_env()
+            .into_iter()
+            .map(|(run_instruction, _env)| run_instruction)
+            .collect()
+    }
+    pub fn extract_run_instructions_and_env(&self) -> Vec<(Option<Run>, BTreeMap<String, String>)> {
         // the order of these run instructions is significant and needs to be the same
         // as the order of the "flattened" layout panes received from eg. position_panes_in_space
         let mut run_instructions = vec![];
         if self.children.is_empty() {
-            run_instructions.push(self.run.clone());
+            run_instructions.push((self.run.clone(), self.env.clone()));
         }
         for child in &self.children {
-            let mut child_run_instructions = child.extract_run_instructions();
+            let mut child_run_instructions = child.extract_run_instructions_and_env();
             run_instructions.append(&mut child_run_instructions);
         }
         let mut successfully_ignored = 0;
         for instruction_to_ignore in &self.run_instructions_to_ignore {
             if let Some(position) = run_instructions
                 .iter()
-                .position(|i| i == instruction_to_ignore)
+                .position(|(i, _env)| i == instruction_to_ignore)
             {
                 run_instructions.remove(position);
                 successfully_ignored += 1;
@@ -962,7 +1013,7 @@ impl TiledPaneLayout {
                 .len()
                 .saturating_sub(successfully_ignored)
             {
-                if let Some(position) = run_instructions.iter().position(|i| {
+                if let Some(position) = run_instructions.iter().position(|(i, _env)| {
                     match i {
                         // this is because a bare CWD instruction should be overidden by a terminal
                         // in run_instructions_to_ignore (for cases where the cwd for example comes
@@ -997,6 +1048,17 @@ impl TiledPaneLayout {
             child.add_cwd_to_layout(cwd);
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        // variables set closer to the pane take precedence
+        for (name, value) in env {
+            self.env
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+        for child in self.children.iter_mut() {
+            child.add_env_to_layout(env);
+        }
+    }
     pub fn populate_plugin_aliases_in_layout(&mut self, plugin_aliases: &PluginAliases) {
         match self.run.as_mut() {
             Some(run) => run.populate_run_plugin_if_needed(plugin_aliases),
@@ -1594,6 +1656,20 @@ impl Layout {
             }
         }
     }
+    pub fn add_env_to_layout(&mut self, env: &BTreeMap<String, String>) {
+        for (_, tiled_pane_layout, floating_panes) in self.tabs.iter_mut() {
+            tiled_pane_layout.add_env_to_layout(env);
+            for floating_pane in floating_panes {
+                floating_pane.add_env_to_layout(env);
+            }
+        }
+        if let Some((tiled_pane_layout, floating_panes)) = self.template.as_mut() {
+            tiled_pane_layout.add_env_to_layout(env);
+            for floating_pane in floating_panes {
+                floating_pane.add_env_to_layout(env);
+            }
+        }
+    }
     pub fn pane_count(&self) -> usize {
         let mut pane_count = 0;
         if let Some((tiled_pane_layout, floating_panes)) = self.template.as_ref() {
@@ -1,12 +1,14 @@
 pub mod actions;
 pub mod command;
 pub mod config;
+pub mod hooks;
 pub mod keybinds;
 pub mod layout;
 pub mod mouse;
 pub mod options;
 pub mod permission;
 pub mod plugins;
+pub mod scratchpad;
 pub mod theme;
 
 #[cfg(not(target_family = "wasm"))]
@@ -179,6 +179,40 @@ pub struct Options {
     #[clap(long, value_parser)]
     #[serde(default)]
     pub show_release_notes: Option<bool>,
+
+    /// Minimum time (in milliseconds) between renders of panes that are not focused, output is
+    /// still parsed in between renders
+    /// default is 0 (no throttling)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub unfocused_pane_render_interval: Option<u64>,
+
+    /// Stop reading from the pty of a hidden pane (eg. in a background tab) once it has produced
+    /// this many bytes, reading resumes once the pane is visible again
+    /// default is 0 (never stop reading)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub hidden_pane_pause_threshold: Option<usize>,
+
+    /// How long (in seconds) closed panes and tabs can be reopened for, along with their
+    /// scrollback
+    /// default is 300 (5 minutes), 0 disables reopening closed panes and tabs
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub closed_pane_retention: Option<u64>,
+
+    /// Whether the values of the env variables set in layouts are left out when serializing
+    /// sessions, they are then taken from the environment the session is resurrected in
+    /// default is false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub redact_serialized_env: Option<bool>,
+
+    /// The ssh compatible command panes with a remote host connect with
+    /// default is ssh
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub ssh_command: Option<PathBuf>,
 }
 
 #[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
@@ -260,6 +294,15 @@ impl Options {
         let stacked_resize = other.stacked_resize.or(self.stacked_resize);
         let show_startup_tips = other.show_startup_tips.or(self.show_startup_tips);
         let show_release_notes = other.show_release_notes.or(self.show_release_notes);
+        let unfocused_pane_render_interval = other
+            .unfocused_pane_render_interval
+            .or(self.unfocused_pane_render_interval);
+        let hidden_pane_pause_threshold = other
+            .hidden_pane_pause_threshold
+            .or(self.hidden_pane_pause_threshold);
+        let closed_pane_retention = other.closed_pane_retention.or(self.closed_pane_retention);
+        let redact_serialized_env = other.redact_serialized_env.or(self.redact_serialized_env);
+        let ssh_command = other.ssh_command.or_else(|| self.ssh_command.clone());
 
         Options {
             simplified_ui,
@@ -292,6 +335,11 @@ impl Options {
             stacked_resize,
             show_startup_tips,
             show_release_notes,
+            unfocused_pane_render_interval,
+            hidden_pane_pause_threshold,
+            closed_pane_retention,
+            redact_serialized_env,
+            ssh_command,
         }
     }
 
@@ -353,6 +401,15 @@ impl Options {
         let stacked_resize = other.stacked_resize.or(self.stacked_resize);
         let show_startup_tips = other.show_startup_tips.or(self.show_startup_tips);
         let show_release_notes = other.show_release_notes.or(self.show_release_notes);
+        let unfocused_pane_render_interval = other
+            .unfocused_pane_render_interval
+            .or(self.unfocused_pane_render_interval);
+        let hidden_pane_pause_threshold = other
+            .hidden_pane_pause_threshold
+            .or(self.hidden_pane_pause_threshold);
+        let closed_pane_retention = other.closed_pane_retention.or(self.closed_pane_retention);
+        let redact_serialized_env = other.redact_serialized_env.or(self.redact_serialized_env);
+        let ssh_command = other.ssh_command.or_else(|| self.ssh_command.clone());
 
         Options {
             simplified_ui,
@@ -385,6 +442,11 @@ impl Options {
             stacked_resize,
             show_startup_tips,
             show_release_notes,
+            unfocused_pane_render_interval,
+            hidden_pane_pause_threshold,
+            closed_pane_retention,
+            redact_serialized_env,
+            ssh_command,
         }
     }
 
@@ -453,6 +515,11 @@ impl From<CliOptions> for Options {
             stacked_resize: opts.stacked_resize,
             show_release_notes: opts.show_release_notes,
             show_startup_tips: opts.show_startup_tips,
+            unfocused_pane_render_interval: opts.unfocused_pane_render_interval,
+            hidden_pane_pause_threshold: opts.hidden_pane_pause_threshold,
+            closed_pane_retention: opts.closed_pane_retention,
+            redact_serialized_env: opts.redact_serialized_env,
+            ssh_command: opts.ssh_command,
             ..Default::default()
         }
     }
@@ -0,0 +1,28 @@
+//! Named floating panes declared in the config, that can be toggled from any tab of the session.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::command::RunCommand;
+use crate::data::FloatingPaneCoordinates;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scratchpads {
+    pub scratchpads: BTreeMap<String, Scratchpad>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scratchpad {
+    /// The command to run in the scratchpad, the default shell if none is given
+    pub run: Option<RunCommand>,
+    /// Where the scratchpad is placed when it is first opened
+    pub coordinates: Option<FloatingPaneCoordinates>,
+}
+
+impl Scratchpads {
+    pub fn get(&self, name: &str) -> Option<&Scratchpad> {
+        self.scratchpads.get(name)
+    }
+    pub fn merge(&mut self, other: Scratchpads) {
+        self.scratchpads.extend(other.scratchpads);
+    }
+}
@@ -0,0 +1,61 @@
+use super::super::hooks::*;
+
+#[test]
+fn env_variables_include_the_event_and_its_context() {
+    let context = HookContext {
+        session_name: Some("my-session".to_owned()),
+        client_id: Some(2),
+        tab_index: Some(1),
+        tab_name: Some("build".to_owned()),
+        pane_id: Some(7),
+        exit_code: Some(130),
+    };
+    let env_variables: Vec<(String, String)> = context
+        .env_variables(HookEvent::PaneExit)
+        .into_iter()
+        .collect();
+    let expected: Vec<(String, String)> = vec![
+        ("ZELLIJ_HOOK_CLIENT_ID", "2"),
+        ("ZELLIJ_HOOK_EVENT", "on_pane_exit"),
+        ("ZELLIJ_HOOK_EXIT_CODE", "130"),
+        ("ZELLIJ_HOOK_PANE_ID", "7"),
+        ("ZELLIJ_HOOK_SESSION_NAME", "my-session"),
+        ("ZELLIJ_HOOK_TAB_INDEX", "1"),
+        ("ZELLIJ_HOOK_TAB_NAME", "build"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_owned(), value.to_owned()))
+    .collect();
+    assert_eq!(env_variables, expected);
+}
+
+#[test]
+fn env_variables_leave_out_what_the_event_does_not_have() {
+    let context = HookContext {
+        session_name: Some("my-session".to_owned()),
+        ..Default::default()
+    };
+    let env_variables = context.env_variables(HookEvent::SessionKill);
+    assert_eq!(
+        env_variables.keys().collect::<Vec<_>>(),
+        vec!["ZELLIJ_HOOK_EVENT", "ZELLIJ_HOOK_SESSION_NAME"],
+        "no variables for the client, tab or pane"
+    );
+    assert_eq!(env_variables["ZELLIJ_HOOK_EVENT"], "on_session_kill");
+}
+
+#[test]
+fn events_round_trip_through_their_kdl_names() {
+    for event in [
+        HookEvent::SessionCreate,
+        HookEvent::SessionAttach,
+        HookEvent::SessionDetach,
+        HookEvent::TabCreate,
+        HookEvent::TabClose,
+        HookEvent::PaneExit,
+        HookEvent::SessionKill,
+    ] {
+        assert_eq!(HookEvent::from_kdl_name(event.kdl_name()), Some(event));
+    }
+    assert_eq!(HookEvent::from_kdl_name("on_something_else"), None);
+}
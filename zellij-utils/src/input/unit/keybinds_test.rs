@@ -1,6 +1,7 @@
 use super::super::actions::*;
 use super::super::keybinds::*;
-use crate::data::{BareKey, Direction, KeyWithModifier};
+use crate::data::{BareKey, Direction, FloatingPaneCoordinates, KeyWithModifier};
+use crate::input::command::RunCommandAction;
 use crate::input::config::Config;
 use insta::assert_snapshot;
 use strum::IntoEnumIterator;
@@ -554,3 +555,89 @@ fn error_received_on_unknown_key_instruction() {
     let config_error = Config::from_kdl(config_contents, None).unwrap_err();
     assert_snapshot!(format!("{:?}", config_error));
 }
+
+#[test]
+fn can_define_popup_run_action() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Alt f" { Run "fzf" { popup true; }; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let alt_f_normal_mode_action = config.keybinds.get_actions_for_key_in_mode(
+        &InputMode::Normal,
+        &KeyWithModifier::new(BareKey::Char('f')).with_alt_modifier(),
+    );
+    let run_command_action = RunCommandAction {
+        command: "fzf".into(),
+        hold_on_close: true,
+        close_on_success: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        alt_f_normal_mode_action,
+        Some(&vec![Action::NewFloatingPane(
+            Some(run_command_action),
+            None,
+            Some(FloatingPaneCoordinates::popup())
+        )]),
+        "Popup defined as a floating pane that closes on success"
+    );
+}
+
+#[test]
+fn resolved_keybindings_flag_keys_shadowed_in_the_default_mode() {
+    let config_contents = r#"
+        keybinds clear-defaults=true {
+            normal {
+                bind "Ctrl o" { SwitchToMode "Session"; }
+                bind "Alt n" { NewPane; }
+            }
+            pane {
+                bind "Ctrl o" { SwitchToMode "Normal"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let all_keybindings = config
+        .keybinds
+        .resolved_keybindings(None, InputMode::Normal);
+    let normal_mode_keys: Vec<&str> = all_keybindings
+        .iter()
+        .filter(|keybinding| keybinding.mode == InputMode::Normal)
+        .map(|keybinding| keybinding.key.as_str())
+        .collect();
+    assert_eq!(
+        normal_mode_keys,
+        vec!["Alt n", "Ctrl o"],
+        "Keybindings sorted by key"
+    );
+    let pane_mode_keybindings = config
+        .keybinds
+        .resolved_keybindings(Some(InputMode::Pane), InputMode::Normal);
+    assert_eq!(
+        pane_mode_keybindings,
+        vec![ResolvedKeybinding {
+            mode: InputMode::Pane,
+            key: "Ctrl o".to_owned(),
+            actions: vec![r#"SwitchToMode "normal""#.to_owned()],
+            shadows: None,
+        }],
+        "Only the requested mode listed, keys outside the default mode not flagged"
+    );
+    let ctrl_o_in_normal_mode = all_keybindings
+        .iter()
+        .find(|keybinding| keybinding.mode == InputMode::Normal && keybinding.key == "Ctrl o")
+        .unwrap();
+    assert!(
+        ctrl_o_in_normal_mode.shadows.is_some(),
+        "Ctrl o flagged in the default mode"
+    );
+    let alt_n_in_normal_mode = all_keybindings
+        .iter()
+        .find(|keybinding| keybinding.mode == InputMode::Normal && keybinding.key == "Alt n")
+        .unwrap();
+    assert!(alt_n_in_normal_mode.shadows.is_none(), "Alt n not flagged");
+}
@@ -1,3 +1,4 @@
+use super::super::command::RemoteHost;
 use super::super::layout::*;
 use insta::assert_snapshot;
 
@@ -203,6 +204,46 @@ fn layout_with_hidden_floating_panes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_tab_groups() {
+    let kdl_layout = r#"
+        layout {
+            tab_group "backend" {
+                tab name="api"
+                tab name="db"
+            }
+            tab name="notes"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let tab_groups: Vec<(Option<String>, Option<String>)> = layout
+        .tabs
+        .iter()
+        .map(|(tab_name, tab_layout, _)| (tab_name.clone(), tab_layout.tab_group.clone()))
+        .collect();
+    assert_eq!(
+        tab_groups,
+        vec![
+            (Some("api".to_owned()), Some("backend".to_owned())),
+            (Some("db".to_owned()), Some("backend".to_owned())),
+            (Some("notes".to_owned()), None),
+        ]
+    );
+}
+
+#[test]
+fn tab_group_can_only_contain_tabs() {
+    let kdl_layout = r#"
+        layout {
+            tab_group "backend" {
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided for a pane in a tab group");
+}
+
 #[test]
 fn layout_with_floating_panes_template() {
     let kdl_layout = r#"
@@ -2242,3 +2283,307 @@ fn env_var_missing() {
     let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
     assert!(layout.is_err(), "invalid env var lookup should fail");
 }
+
+#[test]
+fn layout_with_panes_logging_to_file() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="logged" log_to_file="/tmp/template.log"
+            pane log_to_file="/tmp/pane.log"
+            pane {
+                log_to_file "/tmp/stripped.log" strip_ansi=true timestamps=true max_size=1024
+            }
+            logged
+            floating_panes {
+                pane log_to_file="/tmp/floating.log"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_panes, floating_panes) = layout.template.unwrap();
+    assert_eq!(
+        tiled_panes.children[0].log_to_file,
+        Some(PaneLogging::new(PathBuf::from("/tmp/pane.log")))
+    );
+    assert_eq!(
+        tiled_panes.children[1].log_to_file,
+        Some(PaneLogging {
+            file: PathBuf::from("/tmp/stripped.log"),
+            strip_ansi: true,
+            timestamps: true,
+            max_size: Some(1024),
+        })
+    );
+    assert_eq!(
+        tiled_panes.children[2].log_to_file,
+        Some(PaneLogging::new(PathBuf::from("/tmp/template.log")))
+    );
+    assert_eq!(
+        floating_panes[0].log_to_file,
+        Some(PaneLogging::new(PathBuf::from("/tmp/floating.log")))
+    );
+}
+
+#[test]
+fn cannot_define_unknown_log_to_file_property() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                log_to_file "/tmp/pane.log" rotate=true
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "error provided for unknown log_to_file property"
+    );
+}
+
+#[test]
+fn layout_with_env_variables() {
+    let kdl_layout = r#"
+        layout {
+            env {
+                LEVEL "layout"
+                FROM_LAYOUT "1"
+            }
+            tab {
+                env {
+                    LEVEL "tab"
+                    FROM_TAB 2
+                }
+                pane {
+                    env {
+                        LEVEL "pane"
+                    }
+                }
+                pane
+                floating_panes {
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (_tab_name, tiled_panes, floating_panes) = &layout.tabs[0];
+    let env = |vars: &[(&str, &str)]| -> BTreeMap<String, String> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    };
+    assert_eq!(
+        tiled_panes.extract_run_instructions_and_env(),
+        vec![
+            (
+                None,
+                env(&[("LEVEL", "pane"), ("FROM_LAYOUT", "1"), ("FROM_TAB", "2")])
+            ),
+            (
+                None,
+                env(&[("LEVEL", "tab"), ("FROM_LAYOUT", "1"), ("FROM_TAB", "2")])
+            ),
+        ]
+    );
+    assert_eq!(
+        floating_panes[0].env,
+        env(&[("LEVEL", "tab"), ("FROM_LAYOUT", "1"), ("FROM_TAB", "2")])
+    );
+}
+
+#[test]
+fn layout_with_env_file() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        layout_dir.path().join(".env"),
+        "# comment\nexport FROM_FILE=\"file\"\nOVERRIDDEN=file\n",
+    )
+    .unwrap();
+    let kdl_layout = r#"
+        layout {
+            pane env_file=".env" {
+                env {
+                    OVERRIDDEN "block"
+                }
+            }
+        }
+    "#;
+    let layout_file_name = layout_dir.path().join("layout.kdl");
+    let layout = Layout::from_kdl(
+        kdl_layout,
+        Some(layout_file_name.display().to_string()),
+        None,
+        None,
+    )
+    .unwrap();
+    let (tiled_panes, _floating_panes) = layout.template.unwrap();
+    assert_eq!(
+        tiled_panes.children[0].env,
+        BTreeMap::from([
+            ("FROM_FILE".to_owned(), "file".to_owned()),
+            ("OVERRIDDEN".to_owned(), "block".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn cannot_define_env_variable_with_invalid_value() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    RUST_LOG true
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "error provided for env variable that is not a string or a number"
+    );
+}
+
+#[test]
+fn layout_with_remote_panes() {
+    let kdl_layout = r#"
+        layout {
+            pane command="htop" remote="deploy@web1"
+            pane {
+                remote "db1" user="admin" cwd="~/app"
+            }
+            floating_panes {
+                pane remote="web2" close_on_exit=true
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_panes, floating_panes) = layout.template.unwrap();
+    assert_eq!(
+        tiled_panes.children[0].run,
+        Some(Run::Command(RunCommand {
+            command: PathBuf::from("htop"),
+            hold_on_close: true,
+            remote: Some(RemoteHost::new("deploy@web1")),
+            ..Default::default()
+        }))
+    );
+    assert_eq!(
+        tiled_panes.children[1].run,
+        Some(Run::Command(RunCommand {
+            hold_on_close: true,
+            remote: Some(RemoteHost {
+                host: "db1".to_owned(),
+                user: Some("admin".to_owned()),
+                cwd: Some(PathBuf::from("~/app")),
+                connect_with: None,
+            }),
+            ..Default::default()
+        })),
+        "a remote pane with no command runs the login shell of its host"
+    );
+    assert_eq!(
+        floating_panes[0].run,
+        Some(Run::Command(RunCommand {
+            remote: Some(RemoteHost::new("web2")),
+            ..Default::default()
+        }))
+    );
+}
+
+#[test]
+fn remote_pane_connects_with_ssh() {
+    let kdl_layout = r#"
+        layout {
+            pane command="tail" remote="deploy@web1" {
+                args "-f" "it's.log"
+            }
+            pane {
+                remote "db1" cwd="~/my app"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_panes, _floating_panes) = layout.template.unwrap();
+    let local_command_and_args = |run: &Option<Run>| match run {
+        Some(Run::Command(run_command)) => run_command.local_command_and_args(),
+        _ => panic!("remote pane should run a command"),
+    };
+    assert_eq!(
+        local_command_and_args(&tiled_panes.children[0].run),
+        (
+            PathBuf::from("ssh"),
+            vec![
+                "-t".to_owned(),
+                "-l".to_owned(),
+                "deploy".to_owned(),
+                "--".to_owned(),
+                "web1".to_owned(),
+                "'tail' '-f' 'it'\\''s.log'".to_owned(),
+            ]
+        )
+    );
+    assert_eq!(
+        local_command_and_args(&tiled_panes.children[1].run),
+        (
+            PathBuf::from("ssh"),
+            vec![
+                "-t".to_owned(),
+                "--".to_owned(),
+                "db1".to_owned(),
+                "cd ~/'my app' && exec \"$SHELL\" -l".to_owned(),
+            ]
+        )
+    );
+}
+
+#[test]
+fn remote_host_and_user_cannot_be_ssh_options() {
+    let kdl_layout = r#"
+        layout {
+            pane remote="-oProxyCommand=touch_pwned"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "error provided for a host starting with a dash"
+    );
+    let kdl_layout = r#"
+        layout {
+            pane {
+                remote "web1" user="-oProxyCommand=touch_pwned"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "error provided for a user starting with a dash"
+    );
+}
+
+#[test]
+fn cannot_edit_file_on_remote_host() {
+    let kdl_layout = r#"
+        layout {
+            pane edit="README.md" remote="web1"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided for remote edit pane");
+}
+
+#[test]
+fn cannot_define_remote_pane_with_empty_host() {
+    let kdl_layout = r#"
+        layout {
+            pane remote="deploy@"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "error provided for remote pane without a host"
+    );
+}
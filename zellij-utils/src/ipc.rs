@@ -108,8 +108,9 @@ pub enum ServerToClientMsg {
     Log(Vec<String>),
     LogError(Vec<String>),
     SwitchSession(ConnectToSession),
-    UnblockCliPipeInput(String),   // String -> pipe name
-    CliPipeOutput(String, String), // String -> pipe name, String -> Output
+    UnblockCliPipeInput(String),    // String -> pipe name
+    CliPipeOutput(String, String),  // String -> pipe name, String -> Output
+    CliPipeExitStatus(String, i32), // String -> pipe name, i32 -> exit status
     QueryTerminalSize,
     WriteConfigToDisk { config: String },
 }
@@ -1,10 +1,10 @@
 use crate::input::{
-    command::RunCommand,
+    command::{RemoteHost, RunCommand},
     config::ConfigError,
     layout::{
-        FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, PluginUserConfiguration, Run,
-        RunPluginOrAlias, SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        FloatingPaneLayout, Layout, LayoutConstraint, PaneLogging, PercentOrFixed,
+        PluginUserConfiguration, Run, RunPluginOrAlias, SplitDirection, SplitSize,
+        SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
 };
 
@@ -73,6 +73,7 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "plugin"
             || word == "children"
             || word == "tab"
+            || word == "tab_group"
             || word == "args"
             || word == "close_on_exit"
             || word == "start_suspended"
@@ -86,6 +87,8 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "swap_floating_layout"
             || word == "hide_floating_panes"
             || word == "contents_file"
+            || word == "log_to_file"
+            || word == "remote"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -106,6 +109,10 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
             || property_name == "contents_file"
+            || property_name == "log_to_file"
+            || property_name == "env"
+            || property_name == "env_file"
+            || property_name == "remote"
     }
     fn is_a_valid_floating_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -124,6 +131,10 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "height"
             || property_name == "pinned"
             || property_name == "contents_file"
+            || property_name == "log_to_file"
+            || property_name == "env"
+            || property_name == "env_file"
+            || property_name == "remote"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
@@ -136,6 +147,8 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "min_panes"
             || property_name == "exact_panes"
             || property_name == "hide_floating_panes"
+            || property_name == "env"
+            || property_name == "env_file"
     }
     pub fn is_a_reserved_plugin_property(property_name: &str) -> bool {
         property_name == "location"
@@ -387,6 +400,165 @@ impl<'a> KdlLayoutParser<'a> {
         }
         Ok(PluginUserConfiguration::new(configuration))
     }
+    fn parse_log_to_file(&self, pane_node: &KdlNode) -> Result<Option<PaneLogging>, ConfigError> {
+        // either a `log_to_file "/path"` property or a child node that can also hold the
+        // logging options, eg. `log_to_file "/path" strip_ansi=true timestamps=true`
+        let file = match self.parse_path(pane_node, "log_to_file")? {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        let mut pane_logging = PaneLogging::new(file);
+        if let Some(log_to_file_node) = kdl_get_child!(pane_node, "log_to_file") {
+            for entry in log_to_file_node.entries() {
+                match entry.name().map(|n| n.value()) {
+                    Some("strip_ansi") => {
+                        pane_logging.strip_ansi = entry.value().as_bool().ok_or_else(|| {
+                            kdl_parsing_error!("strip_ansi should be true or false".into(), entry)
+                        })?;
+                    },
+                    Some("timestamps") => {
+                        pane_logging.timestamps = entry.value().as_bool().ok_or_else(|| {
+                            kdl_parsing_error!("timestamps should be true or false".into(), entry)
+                        })?;
+                    },
+                    Some("max_size") => {
+                        let max_size = entry
+                            .value()
+                            .as_i64()
+                            .filter(|max_size| *max_size > 0)
+                            .ok_or_else(|| {
+                                kdl_parsing_error!(
+                                    "max_size should be a positive number of bytes".into(),
+                                    entry
+                                )
+                            })?;
+                        pane_logging.max_size = Some(max_size as u64);
+                    },
+                    Some(unknown_property) => {
+                        return Err(kdl_parsing_error!(
+                            format!("Unknown log_to_file property: {}", unknown_property),
+                            entry
+                        ));
+                    },
+                    None => {}, // the file itself
+                }
+            }
+        }
+        Ok(Some(pane_logging))
+    }
+    fn parse_remote(&self, pane_node: &KdlNode) -> Result<Option<RemoteHost>, ConfigError> {
+        // either a `remote "user@host"` property or a child node that can also hold the
+        // remote options, eg. `remote "host" user="deploy" cwd="~/project"`
+        let destination =
+            match kdl_get_string_property_or_child_value_with_error!(pane_node, "remote") {
+                Some(destination) => destination,
+                None => return Ok(None),
+            };
+        let mut remote = RemoteHost::new(destination);
+        if let Some(remote_node) = kdl_get_child!(pane_node, "remote") {
+            for entry in remote_node.entries() {
+                match entry.name().map(|n| n.value()) {
+                    Some("user") => {
+                        let user = entry.value().as_string().ok_or_else(|| {
+                            kdl_parsing_error!("user should be a string".into(), entry)
+                        })?;
+                        remote.user = Some(user.to_owned());
+                    },
+                    Some("cwd") => {
+                        // this folder is on the remote host, so it is not expanded here
+                        let cwd = entry.value().as_string().ok_or_else(|| {
+                            kdl_parsing_error!("cwd should be a string".into(), entry)
+                        })?;
+                        remote.cwd = Some(PathBuf::from(cwd));
+                    },
+                    Some(unknown_property) => {
+                        return Err(kdl_parsing_error!(
+                            format!("Unknown remote property: {}", unknown_property),
+                            entry
+                        ));
+                    },
+                    None => {}, // the destination itself
+                }
+            }
+        }
+        if remote.host.is_empty() {
+            return Err(ConfigError::new_layout_kdl_error(
+                "remote should name a host, eg. remote \"user@host\"".into(),
+                pane_node.span().offset(),
+                pane_node.span().len(),
+            ));
+        }
+        // these are passed to ssh, where they would be taken for options
+        if remote.host.starts_with('-') || remote.user.iter().any(|user| user.starts_with('-')) {
+            return Err(ConfigError::new_layout_kdl_error(
+                "remote host and user cannot start with a dash".into(),
+                pane_node.span().offset(),
+                pane_node.span().len(),
+            ));
+        }
+        Ok(Some(remote))
+    }
+    fn parse_env(&self, kdl_node: &KdlNode) -> Result<BTreeMap<String, String>, ConfigError> {
+        // variables can be read from a dotenv file (`env_file ".env"`, relative to the layout)
+        // and set in an `env` block, the latter taking precedence, eg:
+        // env {
+        //     RUST_LOG "debug"
+        //     AWS_PROFILE // bare names are taken from the environment the layout is loaded in
+        // }
+        let mut env = BTreeMap::new();
+        if let Some(env_file) = self.parse_path(kdl_node, "env_file")? {
+            let env_file = match self.file_name.as_ref().and_then(|f| f.parent()) {
+                Some(layout_folder) if env_file.is_relative() => layout_folder.join(env_file),
+                _ => env_file,
+            };
+            let env_file_contents = std::fs::read_to_string(&env_file).map_err(|e| {
+                let env_file_entry = kdl_get_property_or_child!(kdl_node, "env_file");
+                let (offset, len) = env_file_entry
+                    .map(|e| (e.span().offset(), e.span().len()))
+                    .unwrap_or((kdl_node.span().offset(), kdl_node.span().len()));
+                ConfigError::new_layout_kdl_error(
+                    format!("Failed to read env_file {}: {}", env_file.display(), e),
+                    offset,
+                    len,
+                )
+            })?;
+            env.extend(parse_env_file(&env_file_contents));
+        }
+        if let Some(env_block) = kdl_get_child!(kdl_node, "env") {
+            if !env_block.entries().is_empty() {
+                return Err(kdl_parsing_error!(
+                    "env variables should be placed in the child braces, eg. env { RUST_LOG \"debug\"; }".into(),
+                    env_block
+                ));
+            }
+            for env_var in kdl_children_nodes!(env_block).unwrap_or_default() {
+                let env_var_name = kdl_name!(env_var);
+                let env_var_value = kdl_first_entry_as_string!(env_var)
+                    .map(|s| s.to_string())
+                    .or_else(|| kdl_first_entry_as_i64!(env_var).map(|i| i.to_string()));
+                match env_var_value {
+                    Some(env_var_value) => {
+                        env.insert(env_var_name.to_owned(), env_var_value);
+                    },
+                    None if env_var.entries().is_empty() => {
+                        if let Ok(env_var_value) = std::env::var(env_var_name) {
+                            env.insert(env_var_name.to_owned(), env_var_value);
+                        }
+                    },
+                    None => {
+                        return Err(kdl_parsing_error!(
+                            format!(
+                                "The value of env variable {} should be a string or a number",
+                                env_var_name
+                            ),
+                            env_var
+                        ));
+                    },
+                }
+            }
+        }
+        Ok(env)
+    }
     fn parse_args(&self, pane_node: &KdlNode) -> Result<Option<Vec<String>>, ConfigError> {
         match kdl_get_child!(pane_node, "args") {
             Some(kdl_args) => {
@@ -437,9 +609,11 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "close_on_exit");
         let start_suspended =
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "start_suspended");
+        let remote = self.parse_remote(pane_node)?;
         if !is_template {
             self.assert_no_bare_attributes_in_pane_node(
                 &command,
+                &remote,
                 &args,
                 &close_on_exit,
                 &start_suspended,
@@ -448,7 +622,23 @@ impl<'a> KdlLayoutParser<'a> {
         }
         let hold_on_close = close_on_exit.map(|c| !c).unwrap_or(true);
         let hold_on_start = start_suspended.map(|c| c).unwrap_or(false);
+        if let (Some(_edit), Some(_remote)) = (&edit, &remote) {
+            return Err(ConfigError::new_layout_kdl_error(
+                "cannot edit a file on a remote host, use a command instead".into(),
+                pane_node.span().offset(),
+                pane_node.span().len(),
+            ));
+        }
         match (command, edit, cwd) {
+            (None, None, cwd) if remote.is_some() => Ok(Some(Run::Command(RunCommand {
+                // the login shell of the remote host
+                command: PathBuf::new(),
+                cwd,
+                hold_on_close,
+                hold_on_start,
+                remote,
+                ..Default::default()
+            }))),
             (None, None, Some(cwd)) => Ok(Some(Run::Cwd(cwd))),
             (Some(command), None, cwd) => Ok(Some(Run::Command(RunCommand {
                 command,
@@ -456,6 +646,7 @@ impl<'a> KdlLayoutParser<'a> {
                 cwd,
                 hold_on_close,
                 hold_on_start,
+                remote,
                 ..Default::default()
             }))),
             (None, Some(edit), Some(cwd)) => {
@@ -534,6 +725,8 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
+        let log_to_file = self.parse_log_to_file(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
         let split_size = self.parse_split_size(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
@@ -572,7 +765,7 @@ impl<'a> KdlLayoutParser<'a> {
                     std::fs::read_to_string(parent_folder.join(contents_file)).ok()
                 })
         });
-        Ok(TiledPaneLayout {
+        let mut pane_layout = TiledPaneLayout {
             borderless: borderless.unwrap_or_default(),
             focus,
             name,
@@ -585,8 +778,11 @@ impl<'a> KdlLayoutParser<'a> {
             children_are_stacked,
             is_expanded_in_stack,
             pane_initial_contents,
+            log_to_file,
             ..Default::default()
-        })
+        };
+        pane_layout.add_env_to_layout(&env);
+        Ok(pane_layout)
     }
     fn parse_floating_pane_node(
         &self,
@@ -604,6 +800,8 @@ impl<'a> KdlLayoutParser<'a> {
             .map(|name| name.to_string());
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
+        let log_to_file = self.parse_log_to_file(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
         self.assert_no_mixed_children_and_properties(kdl_node)?;
         let pane_initial_contents = contents_file.and_then(|contents_file| {
             self.file_name
@@ -623,6 +821,8 @@ impl<'a> KdlLayoutParser<'a> {
             focus,
             pinned,
             pane_initial_contents,
+            log_to_file,
+            env,
             ..Default::default()
         })
     }
@@ -719,6 +919,8 @@ impl<'a> KdlLayoutParser<'a> {
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 let exclude_from_sync =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
+                let log_to_file = self.parse_log_to_file(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
 
                 let external_children_index = if should_mark_external_children_index {
                     self.populate_external_children_index(kdl_node)?
@@ -758,6 +960,9 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(exclude_from_sync) = exclude_from_sync {
                     pane_template.exclude_from_sync = Some(exclude_from_sync);
                 }
+                if let Some(log_to_file) = log_to_file {
+                    pane_template.log_to_file = Some(log_to_file);
+                }
                 if let Some(split_size) = split_size {
                     pane_template.split_size = Some(split_size);
                 }
@@ -776,6 +981,10 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(is_expanded_in_stack) = is_expanded_in_stack {
                     pane_template.is_expanded_in_stack = is_expanded_in_stack;
                 }
+                // the consuming pane's variables override those of the template
+                pane_template.env.extend(env);
+                let pane_env = pane_template.env.clone();
+                pane_template.add_env_to_layout(&pane_env);
                 pane_template.external_children_index = external_children_index;
                 Ok(pane_template)
             },
@@ -822,6 +1031,8 @@ impl<'a> KdlLayoutParser<'a> {
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let log_to_file = self.parse_log_to_file(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
@@ -844,6 +1055,10 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                if let Some(log_to_file) = log_to_file {
+                    pane_template.log_to_file = Some(log_to_file);
+                }
+                pane_template.env.extend(env);
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
@@ -876,6 +1091,8 @@ impl<'a> KdlLayoutParser<'a> {
                 let start_suspended =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "start_suspended");
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
+                let log_to_file = self.parse_log_to_file(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 self.assert_no_bare_attributes_in_pane_node_with_template(
                     &run,
                     &pane_template.run,
@@ -898,6 +1115,10 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(name) = name {
                     pane_template.name = Some(name);
                 }
+                if let Some(log_to_file) = log_to_file {
+                    pane_template.log_to_file = Some(log_to_file);
+                }
+                pane_template.env.extend(env);
                 let height = self.parse_percent_or_fixed(kdl_node, "height", false)?;
                 let width = self.parse_percent_or_fixed(kdl_node, "width", false)?;
                 let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
@@ -1070,6 +1291,8 @@ impl<'a> KdlLayoutParser<'a> {
         self.assert_legal_template_name(&template_name, kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let log_to_file = self.parse_log_to_file(kdl_node)?;
+        let env = self.parse_env(kdl_node)?;
 
         let is_floating = self.differentiate_pane_and_floating_pane_template(&kdl_node)?;
         let can_be_either_floating_or_tiled =
@@ -1082,6 +1305,8 @@ impl<'a> KdlLayoutParser<'a> {
                     PaneOrFloatingPane::Either(TiledPaneLayout {
                         focus,
                         run,
+                        log_to_file,
+                        env,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1106,6 +1331,8 @@ impl<'a> KdlLayoutParser<'a> {
                         x,
                         y,
                         pinned,
+                        log_to_file,
+                        env,
                         ..Default::default()
                     }),
                     kdl_node.clone(),
@@ -1131,23 +1358,23 @@ impl<'a> KdlLayoutParser<'a> {
                 None => (None, vec![]),
             };
             self.assert_no_mixed_children_and_properties(kdl_node)?;
+            let mut pane_template = TiledPaneLayout {
+                borderless: borderless.unwrap_or_default(),
+                focus,
+                split_size,
+                run,
+                children_split_direction,
+                external_children_index,
+                children: pane_parts,
+                children_are_stacked,
+                is_expanded_in_stack,
+                log_to_file,
+                ..Default::default()
+            };
+            pane_template.add_env_to_layout(&env);
             self.pane_templates.insert(
                 template_name,
-                (
-                    PaneOrFloatingPane::Pane(TiledPaneLayout {
-                        borderless: borderless.unwrap_or_default(),
-                        focus,
-                        split_size,
-                        run,
-                        children_split_direction,
-                        external_children_index,
-                        children: pane_parts,
-                        children_are_stacked,
-                        is_expanded_in_stack,
-                        ..Default::default()
-                    }),
-                    kdl_node.clone(),
-                ),
+                (PaneOrFloatingPane::Pane(pane_template), kdl_node.clone()),
             );
         }
 
@@ -1170,6 +1397,7 @@ impl<'a> KdlLayoutParser<'a> {
         let tab_name =
             kdl_get_string_property_or_child_value!(kdl_node, "name").map(|s| s.to_string());
         let tab_cwd = self.parse_path(kdl_node, "cwd")?;
+        let tab_env = self.parse_env(kdl_node)?;
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
         let hide_floating_panes =
             kdl_get_bool_property_or_child_value!(kdl_node, "hide_floating_panes").unwrap_or(false);
@@ -1198,6 +1426,10 @@ impl<'a> KdlLayoutParser<'a> {
                 floating_pane.add_cwd_to_layout(&cwd_prefix);
             }
         }
+        pane_layout.add_env_to_layout(&tab_env);
+        for floating_pane in child_floating_panes.iter_mut() {
+            floating_pane.add_env_to_layout(&tab_env);
+        }
         Ok((is_focused, tab_name, pane_layout, child_floating_panes))
     }
     fn parse_child_pane_nodes_for_tab(
@@ -1222,6 +1454,8 @@ impl<'a> KdlLayoutParser<'a> {
                 )?);
             } else if kdl_name!(child) == "floating_panes" {
                 self.populate_floating_pane_children(child, child_floating_panes)?;
+            } else if kdl_name!(child) == "env" || kdl_name!(child) == "env_file" {
+                // parsed along with the tab
             } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                 return Err(ConfigError::new_layout_kdl_error(
                     format!("Tab property '{}' must be placed on the tab title line and not in the child braces", kdl_name!(child)),
@@ -1352,12 +1586,13 @@ impl<'a> KdlLayoutParser<'a> {
     fn assert_no_bare_attributes_in_pane_node(
         &self,
         command: &Option<PathBuf>,
+        remote: &Option<RemoteHost>,
         args: &Option<Vec<String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
     ) -> Result<(), ConfigError> {
-        if command.is_none() {
+        if command.is_none() && remote.is_none() {
             if close_on_exit.is_some() {
                 return Err(ConfigError::new_layout_kdl_error(
                     "close_on_exit can only be set if a command was specified".into(),
@@ -1372,13 +1607,13 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_node.span().len(),
                 ));
             }
-            if args.is_some() {
-                return Err(ConfigError::new_layout_kdl_error(
-                    "args can only be set if a command was specified".into(),
-                    pane_node.span().offset(),
-                    pane_node.span().len(),
-                ));
-            }
+        }
+        if command.is_none() && args.is_some() {
+            return Err(ConfigError::new_layout_kdl_error(
+                "args can only be set if a command was specified".into(),
+                pane_node.span().offset(),
+                pane_node.span().len(),
+            ));
         }
         Ok(())
     }
@@ -1572,6 +1807,7 @@ impl<'a> KdlLayoutParser<'a> {
         let tab_name =
             kdl_get_string_property_or_child_value!(kdl_node, "name").map(|s| s.to_string());
         let tab_cwd = self.parse_path(kdl_node, "cwd")?;
+        let tab_env = self.parse_env(kdl_node)?;
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         match kdl_children_nodes!(kdl_node) {
@@ -1607,6 +1843,13 @@ impl<'a> KdlLayoutParser<'a> {
                 floating_pane.add_cwd_to_layout(&cwd_prefix);
             }
         }
+        // the tab's variables override those of the template
+        tab_layout.env.extend(tab_env);
+        let tab_env = tab_layout.env.clone();
+        tab_layout.add_env_to_layout(&tab_env);
+        for floating_pane in tab_template_floating_panes.iter_mut() {
+            floating_pane.add_env_to_layout(&tab_env);
+        }
         tab_layout.external_children_index = None;
         Ok((
             is_focused,
@@ -1701,6 +1944,9 @@ impl<'a> KdlLayoutParser<'a> {
                 } else if kdl_name!(child) == "floating_panes" {
                     children_index_offset += 1;
                     self.populate_floating_pane_children(child, &mut tab_floating_children)?;
+                } else if kdl_name!(child) == "env" || kdl_name!(child) == "env_file" {
+                    // parsed along with the tab_template
+                    children_index_offset += 1;
                 } else if self.is_a_valid_tab_property(kdl_name!(child)) {
                     return Err(ConfigError::new_layout_kdl_error(
                         format!("Tab property '{}' must be placed on the tab_template title line and not in the child braces", kdl_name!(child)),
@@ -1716,15 +1962,18 @@ impl<'a> KdlLayoutParser<'a> {
                 }
             }
         }
-        Ok((
-            TiledPaneLayout {
-                children_split_direction,
-                children: tab_children,
-                external_children_index,
-                ..Default::default()
-            },
-            tab_floating_children,
-        ))
+        let env = self.parse_env(kdl_node)?;
+        let mut tab_layout = TiledPaneLayout {
+            children_split_direction,
+            children: tab_children,
+            external_children_index,
+            ..Default::default()
+        };
+        tab_layout.add_env_to_layout(&env);
+        for floating_pane in tab_floating_children.iter_mut() {
+            floating_pane.add_env_to_layout(&env);
+        }
+        Ok((tab_layout, tab_floating_children))
     }
     fn default_template(&self) -> Result<Option<TiledPaneLayout>, ConfigError> {
         match &self.default_tab_template {
@@ -1736,6 +1985,8 @@ impl<'a> KdlLayoutParser<'a> {
                         .insert(children_index, TiledPaneLayout::default())
                 }
                 template.external_children_index = None;
+                let template_env = template.env.clone();
+                template.add_env_to_layout(&template_env);
                 Ok(Some(template))
             },
             None => Ok(None),
@@ -2235,6 +2486,8 @@ impl<'a> KdlLayoutParser<'a> {
                     child_tabs.push(self.parse_tab_node(child)?);
                 },
             }
+        } else if child_name == "tab_group" {
+            self.populate_tab_group(child, child_tabs, child_panes, child_floating_panes)?;
         } else if let Some((tab_template, tab_template_floating_panes, tab_template_kdl_node)) =
             self.tab_templates.get(child_name).cloned()
         {
@@ -2274,6 +2527,8 @@ impl<'a> KdlLayoutParser<'a> {
                 pane_template.add_cwd_to_layout(&cwd_prefix);
             }
             child_panes.push(pane_template);
+        } else if child_name == "env" || child_name == "env_file" {
+            // parsed along with the layout node
         } else if !self.is_a_reserved_word(child_name) {
             return Err(ConfigError::new_layout_kdl_error(
                 format!("Unknown layout node: '{}'", child_name),
@@ -2283,6 +2538,44 @@ impl<'a> KdlLayoutParser<'a> {
         }
         Ok(())
     }
+    fn populate_tab_group(
+        &mut self,
+        tab_group: &KdlNode,
+        child_tabs: &mut Vec<(
+            bool,
+            Option<String>,
+            TiledPaneLayout,
+            Vec<FloatingPaneLayout>,
+        )>,
+        child_panes: &mut Vec<TiledPaneLayout>,
+        child_floating_panes: &mut Vec<FloatingPaneLayout>,
+    ) -> Result<(), ConfigError> {
+        let group_name = kdl_first_entry_as_string!(tab_group)
+            .map(|s| s.to_owned())
+            .ok_or(ConfigError::new_layout_kdl_error(
+                "tab_group must have a name, eg. tab_group \"backend\"".into(),
+                tab_group.span().offset(),
+                tab_group.span().len(),
+            ))?;
+        let first_tab_in_group = child_tabs.len();
+        for child in kdl_children_nodes!(tab_group).unwrap_or_default() {
+            let child_name = kdl_name!(child);
+            if child_name != "tab" && !self.tab_templates.contains_key(child_name) {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!("tab_group can only contain tabs, found: {}", child_name),
+                    child.span().offset(),
+                    child.span().len(),
+                ));
+            }
+            self.populate_layout_child(child, child_tabs, child_panes, child_floating_panes)?;
+        }
+        for (_is_focused, _tab_name, tab_layout, _floating_panes) in
+            child_tabs.iter_mut().skip(first_tab_in_group)
+        {
+            tab_layout.tab_group = Some(group_name.clone());
+        }
+        Ok(())
+    }
     fn populate_floating_pane_children(
         &self,
         child: &KdlNode,
@@ -2407,6 +2700,7 @@ impl<'a> KdlLayoutParser<'a> {
         let mut child_floating_panes = vec![];
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
+        let global_env = self.parse_env(layout_node)?;
         if let Some(children) = kdl_children_nodes!(layout_node) {
             self.populate_global_cwd(layout_node)?;
             self.populate_pane_templates(children, &kdl_layout)?;
@@ -2422,7 +2716,7 @@ impl<'a> KdlLayoutParser<'a> {
                 )?;
             }
         }
-        if !child_tabs.is_empty() {
+        let mut layout = if !child_tabs.is_empty() {
             let has_more_than_one_focused_tab = child_tabs
                 .iter()
                 .filter(|(is_focused, _, _, _)| *is_focused)
@@ -2466,6 +2760,33 @@ impl<'a> KdlLayoutParser<'a> {
                 swap_tiled_layouts,
                 swap_floating_layouts,
             )
+        }?;
+        layout.add_env_to_layout(&global_env);
+        Ok(layout)
+    }
+}
+
+// the `KEY=value` lines of a dotenv file, ignoring blank lines and comments
+fn parse_env_file(contents: &str) -> BTreeMap<String, String> {
+    let mut env = BTreeMap::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        if let Some((name, value)) = line.split_once('=') {
+            let value = value.trim();
+            let value = if value.len() >= 2
+                && ((value.starts_with('"') && value.ends_with('"'))
+                    || (value.starts_with('\'') && value.ends_with('\'')))
+            {
+                &value[1..value.len() - 1]
+            } else {
+                value
+            };
+            env.insert(name.trim().to_owned(), value.to_owned());
         }
     }
+    env
 }
@@ -1,12 +1,14 @@
 mod kdl_layout_parser;
 use crate::data::{
-    BareKey, Direction, FloatingPaneCoordinates, InputMode, KeyWithModifier, LayoutInfo,
-    MultiplayerColors, Palette, PaletteColor, PaneInfo, PaneManifest, PermissionType, Resize,
-    SessionInfo, StyleDeclaration, Styling, TabInfo, DEFAULT_STYLES,
+    BareKey, Direction, FloatingPaneCoordinates, FloatingPanesArrangement, InputMode,
+    KeyWithModifier, LayoutInfo, MirrorDirection, MultiplayerColors, Palette, PaletteColor,
+    PaneInfo, PaneManifest, PermissionType, Resize, RotateDirection, SessionInfo, StyleDeclaration,
+    Styling, TabInfo, DEFAULT_STYLES,
 };
 use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
-use crate::input::config::{Config, ConfigError, KdlError};
+use crate::input::config::{Config, ConfigError, KdlError, INCLUDE_NODE_NAME};
+use crate::input::hooks::{HookEvent, Hooks};
 use crate::input::keybinds::Keybinds;
 use crate::input::layout::{
     Layout, PluginUserConfiguration, RunPlugin, RunPluginOrAlias, SplitSize,
@@ -14,6 +16,7 @@ use crate::input::layout::{
 use crate::input::options::{Clipboard, OnForceClose, Options};
 use crate::input::permission::{GrantedPermission, PermissionCache};
 use crate::input::plugins::PluginAliases;
+use crate::input::scratchpad::{Scratchpad, Scratchpads};
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
 use kdl_layout_parser::KdlLayoutParser;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -27,8 +30,8 @@ use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use crate::input::actions::{Action, SearchDirection, SearchOption};
-use crate::input::command::RunCommandAction;
+use crate::input::actions::{Action, PaneDimension, PaneJumpMode, SearchDirection, SearchOption};
+use crate::input::command::{RunCommand, RunCommandAction};
 
 #[macro_export]
 macro_rules! parse_kdl_action_arguments {
@@ -75,6 +78,15 @@ macro_rules! parse_kdl_action_arguments {
                 "PreviousSwapLayout" => Ok(Action::PreviousSwapLayout),
                 "NextSwapLayout" => Ok(Action::NextSwapLayout),
                 "Clear" => Ok(Action::ClearScreen),
+                "ReopenClosedPane" => Ok(Action::ReopenClosedPane),
+                "ReopenClosedTab" => Ok(Action::ReopenClosedTab),
+                "EqualizePanes" => Ok(Action::EqualizePanes),
+                "ToggleGoldenRatio" => Ok(Action::ToggleGoldenRatio),
+                "TogglePaneInSyncGroup" => Ok(Action::TogglePaneInSyncGroup),
+                "ClearSyncGroup" => Ok(Action::ClearSyncGroup),
+                "GoToNextTabInGroup" => Ok(Action::GoToNextTabInGroup),
+                "GoToPreviousTabInGroup" => Ok(Action::GoToPreviousTabInGroup),
+                "MoveTabToGroup" => Ok(Action::MoveTabToGroup(None)),
                 _ => Err(ConfigError::new_kdl_error(
                     format!("Unsupported action: {:?}", $action_name),
                     $action_node.span().offset(),
@@ -528,7 +540,7 @@ impl Action {
             },
             "MovePaneBackwards" => Ok(Action::MovePaneBackwards),
             "DumpScreen" => Ok(Action::DumpScreen(string, false)),
-            "DumpLayout" => Ok(Action::DumpLayout),
+            "DumpLayout" => Ok(Action::DumpLayout(false)),
             "NewPane" => {
                 if string.is_empty() {
                     return Ok(Action::NewPane(None, None, false));
@@ -565,6 +577,97 @@ impl Action {
                 Ok(Action::Search(search_direction))
             },
             "RenameSession" => Ok(Action::RenameSession(string)),
+            "RotatePanes" => {
+                if string.is_empty() {
+                    return Ok(Action::RotatePanes(RotateDirection::Clockwise));
+                }
+                let rotate_direction =
+                    RotateDirection::from_str(string.as_str()).map_err(|_| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid rotate direction: '{}'", string),
+                            action_node.span().offset(),
+                            action_node.span().len(),
+                        )
+                    })?;
+                Ok(Action::RotatePanes(rotate_direction))
+            },
+            "MirrorLayout" => {
+                let mirror_direction =
+                    MirrorDirection::from_str(string.as_str()).map_err(|_| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid mirror direction: '{}'", string),
+                            action_node.span().offset(),
+                            action_node.span().len(),
+                        )
+                    })?;
+                Ok(Action::MirrorLayout(mirror_direction))
+            },
+            "ResizeTo" => {
+                let mut size: Option<SplitSize> = None;
+                let mut dimension = PaneDimension::default();
+                for word in string.to_ascii_lowercase().split_whitespace() {
+                    match SplitSize::from_str(word) {
+                        Ok(value) => size = Some(value),
+                        Err(_) => match PaneDimension::from_str(word) {
+                            Ok(value) => dimension = value,
+                            Err(_) => {
+                                return Err(ConfigError::new_kdl_error(
+                                    format!(
+                                        "failed to read either of size or dimension from '{}'",
+                                        word
+                                    ),
+                                    action_node.span().offset(),
+                                    action_node.span().len(),
+                                ))
+                            },
+                        },
+                    }
+                }
+                let size = size.ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        "ResizeTo requires a size, eg. \"40%\" or \"80\"".into(),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::ResizeTo(size, dimension))
+            },
+            "PaneJump" => {
+                let mut pane_jump_mode = PaneJumpMode::default();
+                let mut all_tabs = false;
+                for word in string.to_ascii_lowercase().split_whitespace() {
+                    match word {
+                        "all_tabs" => all_tabs = true,
+                        _ => {
+                            pane_jump_mode = PaneJumpMode::from_str(word).map_err(|_| {
+                                ConfigError::new_kdl_error(
+                                    format!(
+                                        "Invalid PaneJump argument: '{}', expected focus, swap or all_tabs",
+                                        word
+                                    ),
+                                    action_node.span().offset(),
+                                    action_node.span().len(),
+                                )
+                            })?
+                        },
+                    }
+                }
+                Ok(Action::PaneJump(pane_jump_mode, all_tabs))
+            },
+            "ToggleScratchpad" => Ok(Action::ToggleScratchpad(string)),
+            "GoToTabGroup" => Ok(Action::GoToTabGroup(string)),
+            "MoveTabToGroup" => Ok(Action::MoveTabToGroup(Some(string))),
+            "ArrangeFloatingPanes" => {
+                let arrangement =
+                    FloatingPanesArrangement::from_str(string.as_str()).map_err(|_| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid floating panes arrangement: '{}'", string),
+                            action_node.span().offset(),
+                            action_node.span().len(),
+                        )
+                    })?;
+                Ok(Action::ArrangeFloatingPanes(arrangement))
+            },
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name),
                 action_node.span().offset(),
@@ -655,7 +758,7 @@ impl Action {
                 node.push(file.clone());
                 Some(node)
             },
-            Action::DumpLayout => Some(KdlNode::new("DumpLayout")),
+            Action::DumpLayout(_json) => Some(KdlNode::new("DumpLayout")),
             Action::EditScrollback => Some(KdlNode::new("EditScrollback")),
             Action::ScrollUp => Some(KdlNode::new("ScrollUp")),
             Action::ScrollDown => Some(KdlNode::new("ScrollDown")),
@@ -811,6 +914,11 @@ impl Action {
                         hoc_node.push(KdlValue::Bool(false));
                         node_children.nodes_mut().push(hoc_node);
                     }
+                    if run_command_action.close_on_success {
+                        let mut cos_node = KdlNode::new("close_on_success");
+                        cos_node.push(KdlValue::Bool(true));
+                        node_children.nodes_mut().push(cos_node);
+                    }
                 }
                 if let Some(floating_pane_coordinates) = floating_pane_coordinates {
                     if let Some(x) = floating_pane_coordinates.x {
@@ -1103,6 +1211,84 @@ impl Action {
                 Some(node)
             },
             Action::TogglePanePinned => Some(KdlNode::new("TogglePanePinned")),
+            Action::ReopenClosedPane => Some(KdlNode::new("ReopenClosedPane")),
+            Action::ReopenClosedTab => Some(KdlNode::new("ReopenClosedTab")),
+            Action::EqualizePanes => Some(KdlNode::new("EqualizePanes")),
+            Action::ToggleGoldenRatio => Some(KdlNode::new("ToggleGoldenRatio")),
+            Action::TogglePaneInSyncGroup => Some(KdlNode::new("TogglePaneInSyncGroup")),
+            Action::ClearSyncGroup => Some(KdlNode::new("ClearSyncGroup")),
+            Action::PaneJump(pane_jump_mode, all_tabs) => {
+                let mut node = KdlNode::new("PaneJump");
+                let mut arguments = vec![];
+                if *pane_jump_mode == PaneJumpMode::Swap {
+                    arguments.push("swap");
+                }
+                if *all_tabs {
+                    arguments.push("all_tabs");
+                }
+                if !arguments.is_empty() {
+                    node.push(arguments.join(" "));
+                }
+                Some(node)
+            },
+            Action::ToggleScratchpad(name) => {
+                let mut node = KdlNode::new("ToggleScratchpad");
+                node.push(name.clone());
+                Some(node)
+            },
+            Action::GoToTabGroup(name) => {
+                let mut node = KdlNode::new("GoToTabGroup");
+                node.push(name.clone());
+                Some(node)
+            },
+            Action::GoToNextTabInGroup => Some(KdlNode::new("GoToNextTabInGroup")),
+            Action::GoToPreviousTabInGroup => Some(KdlNode::new("GoToPreviousTabInGroup")),
+            Action::MoveTabToGroup(name) => {
+                let mut node = KdlNode::new("MoveTabToGroup");
+                if let Some(name) = name {
+                    node.push(name.clone());
+                }
+                Some(node)
+            },
+            Action::ArrangeFloatingPanes(arrangement) => {
+                let mut node = KdlNode::new("ArrangeFloatingPanes");
+                let arrangement = match arrangement {
+                    FloatingPanesArrangement::Grid => "grid",
+                    FloatingPanesArrangement::Cascade => "cascade",
+                };
+                node.push(arrangement);
+                Some(node)
+            },
+            Action::ResizeTo(size, dimension) => {
+                let mut node = KdlNode::new("ResizeTo");
+                let size = match size {
+                    SplitSize::Percent(percent) => format!("{}%", percent),
+                    SplitSize::Fixed(fixed) => format!("{}", fixed),
+                };
+                match dimension {
+                    PaneDimension::Width => node.push(size),
+                    PaneDimension::Height => node.push(format!("{} height", size)),
+                }
+                Some(node)
+            },
+            Action::RotatePanes(rotate_direction) => {
+                let mut node = KdlNode::new("RotatePanes");
+                let rotate_direction = match rotate_direction {
+                    RotateDirection::Clockwise => "clockwise",
+                    RotateDirection::CounterClockwise => "counterclockwise",
+                };
+                node.push(rotate_direction);
+                Some(node)
+            },
+            Action::MirrorLayout(mirror_direction) => {
+                let mut node = KdlNode::new("MirrorLayout");
+                let mirror_direction = match mirror_direction {
+                    MirrorDirection::Horizontal => "horizontal",
+                    MirrorDirection::Vertical => "vertical",
+                };
+                node.push(mirror_direction);
+                Some(node)
+            },
             _ => None,
         }
     }
@@ -1553,6 +1739,12 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 let in_place = command_metadata
                     .and_then(|c_m| kdl_child_bool_value_for_entry(c_m, "in_place"))
                     .unwrap_or(false);
+                let popup = command_metadata
+                    .and_then(|c_m| kdl_child_bool_value_for_entry(c_m, "popup"))
+                    .unwrap_or(false);
+                let close_on_success = command_metadata
+                    .and_then(|c_m| kdl_child_bool_value_for_entry(c_m, "close_on_success"))
+                    .unwrap_or(popup);
                 let run_command_action = RunCommandAction {
                     command: PathBuf::from(command),
                     args,
@@ -1560,6 +1752,7 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     direction,
                     hold_on_close,
                     hold_on_start,
+                    close_on_success,
                     ..Default::default()
                 };
                 let x = command_metadata
@@ -1576,7 +1769,15 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     .map(|s| s.to_owned());
                 let pinned =
                     command_metadata.and_then(|c_m| kdl_child_bool_value_for_entry(c_m, "pinned"));
-                if floating {
+                if popup {
+                    let coordinates = FloatingPaneCoordinates::new(x, y, width, height, pinned)
+                        .unwrap_or_else(FloatingPaneCoordinates::popup);
+                    Ok(Action::NewFloatingPane(
+                        Some(run_command_action),
+                        name,
+                        Some(coordinates),
+                    ))
+                } else if floating {
                     Ok(Action::NewFloatingPane(
                         Some(run_command_action),
                         name,
@@ -1798,6 +1999,77 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 })
             },
             "TogglePanePinned" => Ok(Action::TogglePanePinned),
+            "ReopenClosedPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ReopenClosedTab" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "RotatePanes" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "MirrorLayout" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "ResizeTo" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "EqualizePanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ToggleGoldenRatio" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePaneInSyncGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ClearSyncGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "PaneJump" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "ToggleScratchpad" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "GoToTabGroup" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "GoToNextTabInGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "GoToPreviousTabInGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "MoveTabToGroup" => {
+                // without a group name, the tab is moved out of its group
+                if action_arguments.is_empty() {
+                    parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+                } else {
+                    parse_kdl_action_char_or_string_arguments!(
+                        action_name,
+                        action_arguments,
+                        kdl_action
+                    )
+                }
+            },
+            "ArrangeFloatingPanes" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name).into(),
                 kdl_action.span().offset(),
@@ -2299,6 +2571,20 @@ impl Options {
         let show_release_notes =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "show_release_notes")
                 .map(|(v, _)| v);
+        let unfocused_pane_render_interval =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "unfocused_pane_render_interval")
+                .map(|(v, _)| v as u64);
+        let hidden_pane_pause_threshold =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "hidden_pane_pause_threshold")
+                .map(|(v, _)| v as usize);
+        let closed_pane_retention =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "closed_pane_retention")
+                .map(|(v, _)| v as u64);
+        let redact_serialized_env =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "redact_serialized_env")
+                .map(|(v, _)| v);
+        let ssh_command = kdl_property_first_arg_as_string_or_error!(kdl_options, "ssh_command")
+            .map(|(string, _entry)| PathBuf::from(string));
         Ok(Options {
             simplified_ui,
             theme,
@@ -2330,6 +2616,11 @@ impl Options {
             stacked_resize,
             show_startup_tips,
             show_release_notes,
+            unfocused_pane_render_interval,
+            hidden_pane_pause_threshold,
+            closed_pane_retention,
+            redact_serialized_env,
+            ssh_command,
         })
     }
     pub fn from_string(stringified_keybindings: &String) -> Result<Self, ConfigError> {
@@ -3207,6 +3498,151 @@ impl Options {
             None
         }
     }
+    fn unfocused_pane_render_interval_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Minimum time in milliseconds between renders of panes that are not focused",
+            "// (output is still processed, only drawing it to the screen is throttled)",
+            "// Default: 0 (no throttling)",
+            "// ",
+        );
+
+        let create_node = |node_value: u64| -> KdlNode {
+            let mut node = KdlNode::new("unfocused_pane_render_interval");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(unfocused_pane_render_interval) = self.unfocused_pane_render_interval {
+            let mut node = create_node(unfocused_pane_render_interval);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(100);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn hidden_pane_pause_threshold_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Stop reading output from hidden panes (eg. in background tabs) once they produced this many",
+            "// bytes, reading resumes when the pane is visible again",
+            "// Default: 0 (never stop reading)",
+            "// ",
+        );
+
+        let create_node = |node_value: usize| -> KdlNode {
+            let mut node = KdlNode::new("hidden_pane_pause_threshold");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(hidden_pane_pause_threshold) = self.hidden_pane_pause_threshold {
+            let mut node = create_node(hidden_pane_pause_threshold);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(10485760);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn closed_pane_retention_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}",
+            " ",
+            "// How long (in seconds) closed panes and tabs can be reopened for, along with their scrollback",
+            "// Default: 300 (0 disables reopening closed panes and tabs)",
+            "// ",
+        );
+
+        let create_node = |node_value: u64| -> KdlNode {
+            let mut node = KdlNode::new("closed_pane_retention");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(closed_pane_retention) = self.closed_pane_retention {
+            let mut node = create_node(closed_pane_retention);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(600);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn ssh_command_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}",
+            " ",
+            "// The ssh compatible command panes with a remote host connect with",
+            "// Default: ssh",
+            "// ",
+        );
+
+        let create_node = |node_value: &str| -> KdlNode {
+            let mut node = KdlNode::new("ssh_command");
+            node.push(node_value.to_owned());
+            node
+        };
+        if let Some(ssh_command) = &self.ssh_command {
+            let mut node = create_node(&ssh_command.display().to_string());
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node("/usr/bin/ssh");
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn redact_serialized_env_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            " ",
+            "// Whether the values of the env variables set in layouts are left out when serializing",
+            "// sessions, they are then taken from the environment the session is resurrected in",
+            "// Options:",
+            "//   - true",
+            "//   - false (default)",
+            "// ",
+        );
+
+        let create_node = |node_value: bool| -> KdlNode {
+            let mut node = KdlNode::new("redact_serialized_env");
+            node.push(KdlValue::Bool(node_value));
+            node
+        };
+        if let Some(redact_serialized_env) = self.redact_serialized_env {
+            let mut node = create_node(redact_serialized_env);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(true);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
     pub fn to_kdl(&self, add_comments: bool) -> Vec<KdlNode> {
         let mut nodes = vec![];
         if let Some(simplified_ui_node) = self.simplified_ui_to_kdl(add_comments) {
@@ -3303,6 +3739,25 @@ impl Options {
         if let Some(show_release_notes) = self.show_release_notes_to_kdl(add_comments) {
             nodes.push(show_release_notes);
         }
+        if let Some(unfocused_pane_render_interval) =
+            self.unfocused_pane_render_interval_to_kdl(add_comments)
+        {
+            nodes.push(unfocused_pane_render_interval);
+        }
+        if let Some(hidden_pane_pause_threshold) =
+            self.hidden_pane_pause_threshold_to_kdl(add_comments)
+        {
+            nodes.push(hidden_pane_pause_threshold);
+        }
+        if let Some(closed_pane_retention) = self.closed_pane_retention_to_kdl(add_comments) {
+            nodes.push(closed_pane_retention);
+        }
+        if let Some(redact_serialized_env) = self.redact_serialized_env_to_kdl(add_comments) {
+            nodes.push(redact_serialized_env);
+        }
+        if let Some(ssh_command) = self.ssh_command_to_kdl(add_comments) {
+            nodes.push(ssh_command);
+        }
         nodes
     }
 }
@@ -3374,6 +3829,169 @@ fn kdl_layout_error(kdl_error: kdl::KdlError, file_name: String, raw_layout: &st
     ConfigError::KdlError(kdl_error)
 }
 
+impl Scratchpads {
+    pub fn from_kdl(kdl_config: &KdlDocument) -> Result<Self, ConfigError> {
+        let mut scratchpads = Scratchpads::default();
+        for kdl_scratchpad in kdl_config
+            .nodes()
+            .iter()
+            .filter(|n| kdl_name!(n) == "scratchpad")
+        {
+            let name = kdl_first_entry_as_string!(kdl_scratchpad).ok_or(kdl_parsing_error!(
+                "scratchpad must have a name, eg. scratchpad \"notes\"".into(),
+                kdl_scratchpad
+            ))?;
+            let cwd = kdl_get_string_property_or_child_value!(kdl_scratchpad, "cwd")
+                .map(|cwd| PathBuf::from(expand_scratchpad_word(cwd)));
+            let run = match kdl_get_string_property_or_child_value!(kdl_scratchpad, "command") {
+                Some(command) => {
+                    let words = shell_words::split(command).map_err(|e| {
+                        kdl_parsing_error!(
+                            format!(
+                                "failed to parse the command of scratchpad {:?}: {}",
+                                name, e
+                            ),
+                            kdl_scratchpad
+                        )
+                    })?;
+                    let mut words = words.iter().map(|word| expand_scratchpad_word(word));
+                    let command = words.next().ok_or(kdl_parsing_error!(
+                        format!("scratchpad {:?} has an empty command", name),
+                        kdl_scratchpad
+                    ))?;
+                    Some(RunCommand {
+                        command: PathBuf::from(command),
+                        args: words.collect(),
+                        cwd: cwd.clone(),
+                        ..Default::default()
+                    })
+                },
+                None => cwd.map(|cwd| RunCommand {
+                    cwd: Some(cwd),
+                    ..Default::default()
+                }),
+            };
+            let coordinate = |coordinate_name: &str| {
+                kdl_get_string_property_or_child_value!(kdl_scratchpad, coordinate_name)
+                    .map(|c| c.to_owned())
+                    .or_else(|| {
+                        kdl_get_int_property_or_child_value!(kdl_scratchpad, coordinate_name)
+                            .map(|c| c.to_string())
+                    })
+            };
+            let coordinates = FloatingPaneCoordinates::new(
+                coordinate("x"),
+                coordinate("y"),
+                coordinate("width"),
+                coordinate("height"),
+                kdl_get_bool_property_or_child_value!(kdl_scratchpad, "pinned"),
+            );
+            scratchpads
+                .scratchpads
+                .insert(name.to_owned(), Scratchpad { run, coordinates });
+        }
+        Ok(scratchpads)
+    }
+    pub fn to_kdl(&self) -> Vec<KdlNode> {
+        let mut nodes = vec![];
+        for (name, scratchpad) in &self.scratchpads {
+            let mut node = KdlNode::new("scratchpad");
+            node.push(name.to_owned());
+            if let Some(run) = &scratchpad.run {
+                if !run.command.as_os_str().is_empty() {
+                    let mut command = vec![run.command.display().to_string()];
+                    command.extend(run.args.iter().cloned());
+                    node.insert("command", shell_words::join(command));
+                }
+                if let Some(cwd) = &run.cwd {
+                    node.insert("cwd", cwd.display().to_string());
+                }
+            }
+            if let Some(coordinates) = &scratchpad.coordinates {
+                let sizes = [
+                    ("x", coordinates.x),
+                    ("y", coordinates.y),
+                    ("width", coordinates.width),
+                    ("height", coordinates.height),
+                ];
+                for (coordinate_name, size) in sizes {
+                    match size {
+                        Some(SplitSize::Percent(percent)) => {
+                            node.insert(coordinate_name, format!("{}%", percent));
+                        },
+                        Some(SplitSize::Fixed(fixed)) => {
+                            node.insert(coordinate_name, fixed as i64);
+                        },
+                        None => {},
+                    }
+                }
+                if let Some(pinned) = coordinates.pinned {
+                    node.insert("pinned", pinned);
+                }
+            }
+            nodes.push(node);
+        }
+        nodes
+    }
+}
+
+fn expand_scratchpad_word(word: &str) -> String {
+    shellexpand::full(word)
+        .map(|expanded| expanded.into_owned())
+        .unwrap_or_else(|_| word.to_owned())
+}
+
+impl Hooks {
+    pub fn from_kdl(kdl_hooks: &KdlNode) -> Result<Self, ConfigError> {
+        let mut hooks = Hooks::default();
+        for kdl_hook in kdl_children_nodes_or_error!(kdl_hooks, "empty hooks block") {
+            let event = HookEvent::from_kdl_name(kdl_name!(kdl_hook)).ok_or_else(|| {
+                let event_names: Vec<&str> = HookEvent::iter().map(|e| e.kdl_name()).collect();
+                kdl_parsing_error!(
+                    format!(
+                        "Unknown hook: {}, expected one of: {}",
+                        kdl_name!(kdl_hook),
+                        event_names.join(", ")
+                    ),
+                    kdl_hook
+                )
+            })?;
+            let command = kdl_first_entry_as_string!(kdl_hook).ok_or_else(|| {
+                kdl_parsing_error!(
+                    format!(
+                        "{} should be a command, eg. {} \"notify-send done\"",
+                        event.kdl_name(),
+                        event.kdl_name()
+                    ),
+                    kdl_hook
+                )
+            })?;
+            hooks
+                .hooks
+                .entry(event)
+                .or_insert_with(Vec::new)
+                .push(command.to_owned());
+        }
+        Ok(hooks)
+    }
+    pub fn to_kdl(&self) -> Option<KdlNode> {
+        if self.hooks.is_empty() {
+            return None;
+        }
+        let mut hooks = KdlNode::new("hooks");
+        let mut kdl_hooks = KdlDocument::new();
+        for (event, commands) in &self.hooks {
+            for command in commands {
+                let mut kdl_hook = KdlNode::new(event.kdl_name());
+                kdl_hook.push(command.to_owned());
+                kdl_hooks.nodes_mut().push(kdl_hook);
+            }
+        }
+        hooks.set_children(kdl_hooks);
+        Some(hooks)
+    }
+}
+
 impl EnvironmentVariables {
     pub fn from_kdl(kdl_env_variables: &KdlNode) -> Result<Self, ConfigError> {
         let mut env: HashMap<String, String> = HashMap::new();
@@ -3758,6 +4376,19 @@ impl BareKey {
     }
 }
 
+/// The top level nodes of a config file which are not options, in the order they are loaded
+pub const CONFIG_NODE_NAMES: &[&str] = &[
+    "keybinds",
+    "themes",
+    "plugins",
+    "load_plugins",
+    "ui",
+    "env",
+    "scratchpad",
+    "hooks",
+    INCLUDE_NODE_NAME,
+];
+
 impl Config {
     pub fn from_kdl(kdl_config: &str, base_config: Option<Config>) -> Result<Config, ConfigError> {
         let mut config = base_config.unwrap_or_else(|| Config::default());
@@ -3768,29 +4399,47 @@ impl Config {
 
         // TODO: handle cases where we have more than one of these blocks (eg. two "keybinds")
         // this should give an informative parsing error
-        if let Some(kdl_keybinds) = kdl_config.get("keybinds") {
-            config.keybinds = Keybinds::from_kdl(&kdl_keybinds, config.keybinds, &config.options)?;
-        }
-        if let Some(kdl_themes) = kdl_config.get("themes") {
-            let sourced_from_external_file = false;
-            let config_themes = Themes::from_kdl(kdl_themes, sourced_from_external_file)?;
-            config.themes = config.themes.merge(config_themes);
-        }
-        if let Some(kdl_plugin_aliases) = kdl_config.get("plugins") {
-            let config_plugins = PluginAliases::from_kdl(kdl_plugin_aliases)?;
-            config.plugins.merge(config_plugins);
-        }
-        if let Some(kdl_load_plugins) = kdl_config.get("load_plugins") {
-            let load_plugins = load_plugins_from_kdl(kdl_load_plugins)?;
-            config.background_plugins = load_plugins;
-        }
-        if let Some(kdl_ui_config) = kdl_config.get("ui") {
-            let config_ui = UiConfig::from_kdl(&kdl_ui_config)?;
-            config.ui = config.ui.merge(config_ui);
-        }
-        if let Some(env_config) = kdl_config.get("env") {
-            let config_env = EnvironmentVariables::from_kdl(&env_config)?;
-            config.env = config.env.merge(config_env);
+        for node_name in CONFIG_NODE_NAMES {
+            let kdl_node = match kdl_config.get(node_name) {
+                Some(kdl_node) => kdl_node,
+                None => continue,
+            };
+            match *node_name {
+                "keybinds" => {
+                    config.keybinds =
+                        Keybinds::from_kdl(kdl_node, config.keybinds, &config.options)?;
+                },
+                "themes" => {
+                    let sourced_from_external_file = false;
+                    let config_themes = Themes::from_kdl(kdl_node, sourced_from_external_file)?;
+                    config.themes = config.themes.merge(config_themes);
+                },
+                "plugins" => {
+                    let config_plugins = PluginAliases::from_kdl(kdl_node)?;
+                    config.plugins.merge(config_plugins);
+                },
+                "load_plugins" => {
+                    config.background_plugins = load_plugins_from_kdl(kdl_node)?;
+                },
+                "ui" => {
+                    let config_ui = UiConfig::from_kdl(kdl_node)?;
+                    config.ui = config.ui.merge(config_ui);
+                },
+                "env" => {
+                    let config_env = EnvironmentVariables::from_kdl(kdl_node)?;
+                    config.env = config.env.merge(config_env);
+                },
+                "scratchpad" => {
+                    // there is one of these nodes for each scratchpad
+                    let config_scratchpads = Scratchpads::from_kdl(&kdl_config)?;
+                    config.scratchpads.merge(config_scratchpads);
+                },
+                "hooks" => {
+                    let config_hooks = Hooks::from_kdl(kdl_node)?;
+                    config.hooks.merge(config_hooks);
+                },
+                _ => {}, // included files are loaded when reading the config file
+            }
         }
         Ok(config)
     }
@@ -3819,6 +4468,12 @@ impl Config {
             document.nodes_mut().push(env);
         }
 
+        document.nodes_mut().append(&mut self.scratchpads.to_kdl());
+
+        if let Some(hooks) = self.hooks.to_kdl() {
+            document.nodes_mut().push(hooks);
+        }
+
         document
             .nodes_mut()
             .append(&mut self.options.to_kdl(add_comments));
@@ -4594,6 +5249,7 @@ impl TabInfo {
         let display_area_rows = optional_int_node!("display_area_rows", usize).unwrap_or(0);
         let display_area_columns = optional_int_node!("display_area_columns", usize).unwrap_or(0);
         let is_swap_layout_dirty = bool_node!("is_swap_layout_dirty");
+        let group = optional_string_node!("group");
         Ok(TabInfo {
             position,
             name,
@@ -4609,6 +5265,7 @@ impl TabInfo {
             viewport_columns,
             display_area_rows,
             display_area_columns,
+            group,
         })
     }
     pub fn encode_to_kdl(&self) -> KdlDocument {
@@ -4676,6 +5333,12 @@ impl TabInfo {
         is_swap_layout_dirty.push(self.is_swap_layout_dirty);
         kdl_doucment.nodes_mut().push(is_swap_layout_dirty);
 
+        if let Some(group) = self.group.as_ref() {
+            let mut group_node = KdlNode::new("group");
+            group_node.push(group.to_string());
+            kdl_doucment.nodes_mut().push(group_node);
+        }
+
         kdl_doucment
     }
 }
@@ -4803,6 +5466,7 @@ impl PaneInfo {
         let terminal_command = optional_string_node!("terminal_command");
         let plugin_url = optional_string_node!("plugin_url");
         let is_selectable = bool_node!("is_selectable");
+        let log_file = optional_string_node!("log_file");
 
         let pane_info = PaneInfo {
             id,
@@ -4827,6 +5491,7 @@ impl PaneInfo {
             terminal_command,
             plugin_url,
             is_selectable,
+            log_file,
         };
         Ok((tab_position, pane_info))
     }
@@ -4887,6 +5552,9 @@ impl PaneInfo {
             string_node!("plugin_url", plugin_url.to_string());
         }
         bool_node!("is_selectable", self.is_selectable);
+        if let Some(log_file) = &self.log_file {
+            string_node!("log_file", log_file.to_string());
+        }
         kdl_doucment
     }
 }
@@ -4974,6 +5642,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            log_file: Some("/tmp/pane.log".to_owned()),
         },
         PaneInfo {
             id: 1,
@@ -4998,6 +5667,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            log_file: None,
         },
     ];
     let mut panes = HashMap::new();
@@ -5020,6 +5690,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 viewport_columns: 10,
                 display_area_rows: 10,
                 display_area_columns: 10,
+                group: None,
             },
             TabInfo {
                 position: 1,
@@ -5036,6 +5707,7 @@ fn serialize_and_deserialize_session_info_with_data() {
                 viewport_columns: 10,
                 display_area_rows: 10,
                 display_area_columns: 10,
+                group: None,
             },
         ],
         panes: PaneManifest { panes },
@@ -6,6 +6,7 @@ pub mod errors;
 pub mod home;
 pub mod input;
 pub mod kdl;
+pub mod lint;
 pub mod pane_size;
 pub mod plugin_api;
 pub mod position;
@@ -15,6 +16,8 @@ pub mod shared;
 
 // The following modules can't be used when targeting wasm
 #[cfg(not(target_family = "wasm"))]
+pub mod asciicast;
+#[cfg(not(target_family = "wasm"))]
 pub mod channels; // Requires async_std
 #[cfg(not(target_family = "wasm"))]
 pub mod common_path;
@@ -28,8 +31,8 @@ pub mod logging; // Requires log4rs
 #[cfg(not(target_family = "wasm"))]
 pub use ::{
     anyhow, async_channel, async_std, clap, humantime, interprocess, isahc, lazy_static, libc,
-    miette, nix, notify_debouncer_full, regex, serde, signal_hook, tempfile, termwiz, url, uuid,
-    vte,
+    miette, nix, notify_debouncer_full, regex, serde, serde_json, signal_hook, tempfile, termwiz,
+    url, uuid, vte,
 };
 
 pub use ::prost;
@@ -0,0 +1,783 @@
+//! `zellij setup --lint`: reports every problem found in the configuration, the layouts and the
+//! themes, rather than only the first one that prevents zellij from loading them.
+use crate::data::{InputMode, KeyWithModifier};
+use crate::input::{
+    actions::Action,
+    config::{resolve_include, Config, ConfigError, KdlError},
+    keybinds::Keybinds,
+    layout::{Layout, RunPluginLocation},
+    options::Options,
+    theme::Themes,
+};
+use crate::kdl::CONFIG_NODE_NAMES;
+use kdl::{KdlDocument, KdlNode};
+use miette::{Diagnostic, LabeledSpan, NamedSource, Severity, SourceCode};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::{env, fs};
+use strum::IntoEnumIterator;
+use thiserror::Error;
+
+const SHARED_KEYBINDS_NODE_NAMES: &[&str] = &["shared", "shared_except", "shared_among"];
+
+#[derive(Error, Debug)]
+#[error("{message}")]
+pub struct LintDiagnostic {
+    pub message: String,
+    pub severity: Severity,
+    pub src: Option<NamedSource>,
+    pub offset: Option<usize>,
+    pub len: Option<usize>,
+    pub help_message: Option<String>,
+}
+
+impl Diagnostic for LintDiagnostic {
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        match self.src.as_ref() {
+            Some(src) => Some(src),
+            None => None,
+        }
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match &self.help_message {
+            Some(help_message) => Some(Box::new(help_message)),
+            None => None,
+        }
+    }
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        if let (Some(offset), Some(len)) = (self.offset, self.len) {
+            let label = LabeledSpan::new(None, offset, len);
+            Some(Box::new(std::iter::once(label)))
+        } else {
+            None
+        }
+    }
+}
+
+impl LintDiagnostic {
+    fn from_config_error(error: ConfigError, file: &LintedFile) -> Self {
+        match error {
+            ConfigError::KdlError(KdlError {
+                error_message,
+                src,
+                offset,
+                len,
+                help_message,
+            }) => LintDiagnostic {
+                message: error_message,
+                severity: Severity::Error,
+                src: src.or_else(|| Some(file.named_source())),
+                offset,
+                len,
+                help_message,
+            },
+            ConfigError::KdlDeserializationError(kdl_error) => LintDiagnostic {
+                message: String::from(kdl_error.help.unwrap_or("Failed to parse KDL")),
+                severity: Severity::Error,
+                src: Some(file.named_source()),
+                offset: Some(kdl_error.span.offset()),
+                len: Some(kdl_error.span.len()),
+                help_message: None,
+            },
+            error => LintDiagnostic {
+                message: format!("{}: {}", file.name, error),
+                severity: Severity::Error,
+                src: None,
+                offset: None,
+                len: None,
+                help_message: None,
+            },
+        }
+    }
+    // to tell apart a problem we found ourselves from the same one found when loading the file
+    fn key(&self) -> (Option<String>, Option<usize>, bool) {
+        let src_name = self
+            .src
+            .as_ref()
+            .and_then(|src| src.read_span(&(0, 0).into(), 0, 0).ok())
+            .and_then(|contents| contents.name().map(|name| name.to_owned()));
+        (src_name, self.offset, self.severity == Severity::Error)
+    }
+}
+
+#[derive(Clone)]
+struct LintedFile {
+    name: String,
+    contents: String,
+}
+
+impl LintedFile {
+    fn read(path: &Path) -> std::io::Result<Self> {
+        Ok(LintedFile {
+            name: path.display().to_string(),
+            contents: fs::read_to_string(path)?,
+        })
+    }
+    fn named_source(&self) -> NamedSource {
+        NamedSource::new(self.name.clone(), self.contents.clone())
+    }
+    fn diagnostic(
+        &self,
+        severity: Severity,
+        message: String,
+        node: &KdlNode,
+        help_message: Option<String>,
+    ) -> LintDiagnostic {
+        LintDiagnostic {
+            message,
+            severity,
+            src: Some(self.named_source()),
+            offset: Some(node.span().offset()),
+            len: Some(node.span().len()),
+            help_message,
+        }
+    }
+    fn parse(&self, diagnostics: &mut Vec<LintDiagnostic>) -> Option<KdlDocument> {
+        match self.contents.parse() {
+            Ok(document) => Some(document),
+            Err(e) => {
+                diagnostics.push(LintDiagnostic::from_config_error(
+                    ConfigError::KdlDeserializationError(e),
+                    self,
+                ));
+                None
+            },
+        }
+    }
+}
+
+/// The problems found in the config file (and the files it includes), the layouts in the layout
+/// folder and the themes in the theme folder, errors first
+pub fn lint(
+    config_file: Option<&Path>,
+    layout_dir: Option<&Path>,
+    theme_dir: Option<&Path>,
+) -> Vec<LintDiagnostic> {
+    let mut diagnostics = vec![];
+    if let Some(config_file) = config_file {
+        lint_config(config_file, &mut diagnostics);
+    }
+    for layout_file in kdl_files_in(layout_dir) {
+        lint_layout(&layout_file, &mut diagnostics);
+    }
+    for theme_file in kdl_files_in(theme_dir) {
+        if let Err(e) = Themes::from_path(theme_file.clone()) {
+            let file = LintedFile {
+                name: theme_file.display().to_string(),
+                contents: String::new(),
+            };
+            diagnostics.push(LintDiagnostic::from_config_error(e, &file));
+        }
+    }
+    let mut reported = HashSet::new();
+    diagnostics.retain(|diagnostic| reported.insert(diagnostic.key()));
+    diagnostics.sort_by_key(|diagnostic| diagnostic.severity != Severity::Error);
+    diagnostics
+}
+
+fn kdl_files_in(folder: Option<&Path>) -> Vec<PathBuf> {
+    let mut kdl_files: Vec<PathBuf> = folder
+        .and_then(|folder| fs::read_dir(folder).ok())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.is_file() && path.extension().map(|e| e == "kdl").unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    kdl_files.sort();
+    kdl_files
+}
+
+fn lint_config(config_file: &Path, diagnostics: &mut Vec<LintDiagnostic>) {
+    let mut mode_blocks = vec![];
+    for include in Config::config_file_and_includes(config_file) {
+        for file in resolve_include(&include).unwrap_or_default() {
+            let file = match LintedFile::read(&file) {
+                Ok(file) => file,
+                Err(e) => {
+                    diagnostics.push(LintDiagnostic {
+                        message: format!("Failed to read {}: {}", file.display(), e),
+                        severity: Severity::Error,
+                        src: None,
+                        offset: None,
+                        len: None,
+                        help_message: None,
+                    });
+                    continue;
+                },
+            };
+            if let Some(document) = file.parse(diagnostics) {
+                lint_config_nodes(&file, &document, diagnostics);
+                if let Some(keybinds) = document.get("keybinds") {
+                    mode_blocks.append(&mut lint_keybinds(&file, keybinds, &document, diagnostics));
+                }
+                lint_commands_and_plugins(&file, document.nodes(), None, diagnostics);
+            }
+        }
+    }
+    // problems which only show when everything is put together
+    let default_config = Config::from_default_assets().ok();
+    match Config::from_path(&config_file.to_path_buf(), default_config) {
+        Ok(config) => {
+            let base_mode = config.options.default_mode.unwrap_or(InputMode::Normal);
+            let reachable_modes = reachable_modes(&config.keybinds, base_mode);
+            for (file, mode, mode_block) in mode_blocks {
+                if !reachable_modes.contains(&mode) {
+                    diagnostics.push(LintDiagnostic {
+                        message: format!(
+                            "No keybinding switches to {:?} mode from the {:?} mode",
+                            mode, base_mode
+                        ),
+                        severity: Severity::Warning,
+                        src: Some(file.named_source()),
+                        offset: Some(mode_block.0),
+                        len: Some(mode_block.1),
+                        help_message: Some(format!(
+                            "Bind a key to SwitchToMode \"{:?}\", unless a plugin switches to it",
+                            mode
+                        )),
+                    });
+                }
+            }
+        },
+        Err(e) => {
+            let file = LintedFile::read(config_file).unwrap_or_else(|_| LintedFile {
+                name: config_file.display().to_string(),
+                contents: String::new(),
+            });
+            diagnostics.push(LintDiagnostic::from_config_error(e, &file));
+        },
+    }
+}
+
+fn lint_config_nodes(
+    file: &LintedFile,
+    document: &KdlDocument,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let option_names = option_names();
+    let known_names: Vec<&str> = option_names
+        .iter()
+        .map(|name| name.as_str())
+        .chain(CONFIG_NODE_NAMES.iter().copied())
+        .collect();
+    for node in document.nodes() {
+        let name = node.name().value();
+        if !known_names.contains(&name) {
+            diagnostics.push(file.diagnostic(
+                Severity::Warning,
+                format!("Unknown config node: '{}', it is ignored", name),
+                node,
+                closest_name(name, &known_names).map(|name| format!("Did you mean '{}'?", name)),
+            ));
+        }
+    }
+}
+
+// the fields of Options are the names of the options in the config
+fn option_names() -> Vec<String> {
+    match serde_json::to_value(Options::default()) {
+        Ok(serde_json::Value::Object(options)) => options.keys().cloned().collect(),
+        _ => vec![],
+    }
+}
+
+/// Reports every invalid mode, key and action and every keybinding shadowed by a later one in
+/// the same file, returning the modes that have their own block in this file
+fn lint_keybinds(
+    file: &LintedFile,
+    keybinds: &KdlNode,
+    document: &KdlDocument,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) -> Vec<(LintedFile, InputMode, (usize, usize))> {
+    let config_options = Options::from_kdl(document).unwrap_or_default();
+    let blocks = keybinds.children().map(|c| c.nodes()).unwrap_or_default();
+    let mut mode_blocks = vec![];
+    // keybindings in shared blocks are bound before those in mode blocks, as when loading them
+    let (shared_blocks, blocks): (Vec<&KdlNode>, Vec<&KdlNode>) = blocks
+        .iter()
+        .partition(|block| SHARED_KEYBINDS_NODE_NAMES.contains(&block.name().value()));
+    // whether a keybinding is in a shared block, as those are meant to be overridden in the
+    // blocks of the modes they are shared among
+    let mut bound_keys: HashMap<(InputMode, KeyWithModifier), (&KdlNode, bool)> = HashMap::new();
+    let mut shadowed_keybindings: BTreeMap<
+        (usize, usize),
+        (&KdlNode, KeyWithModifier, Vec<InputMode>),
+    > = BTreeMap::new();
+    for block in shared_blocks.into_iter().chain(blocks.into_iter()) {
+        let block_name = block.name().value();
+        let is_shared_block = SHARED_KEYBINDS_NODE_NAMES.contains(&block_name);
+        let modes: Vec<InputMode> = if is_shared_block {
+            let mut listed_modes = vec![];
+            for mode_name in block.entries().iter().filter_map(|e| e.value().as_string()) {
+                match InputMode::from_str(mode_name) {
+                    Ok(mode) => listed_modes.push(mode),
+                    Err(_) => diagnostics.push(file.diagnostic(
+                        Severity::Error,
+                        format!("Invalid mode: '{}'", mode_name),
+                        block,
+                        None,
+                    )),
+                }
+            }
+            InputMode::iter()
+                .filter(|mode| (block_name == "shared_among") == listed_modes.contains(mode))
+                .collect()
+        } else if block_name == "unbind" {
+            continue;
+        } else {
+            match InputMode::from_str(block_name) {
+                Ok(mode) => {
+                    let span = (block.name().span().offset(), block.name().span().len());
+                    mode_blocks.push((file.clone(), mode, span));
+                    vec![mode]
+                },
+                Err(_) => {
+                    diagnostics.push(file.diagnostic(
+                        Severity::Error,
+                        format!("Invalid mode: '{}'", block_name),
+                        block,
+                        None,
+                    ));
+                    continue;
+                },
+            }
+        };
+        for key_block in block.children().map(|c| c.nodes()).unwrap_or_default() {
+            match key_block.name().value() {
+                "bind" => {},
+                "unbind" => continue,
+                instruction => {
+                    diagnostics.push(file.diagnostic(
+                        Severity::Error,
+                        format!("Unknown keybind instruction: '{}'", instruction),
+                        key_block,
+                        Some("Keybindings are either bind or unbind".into()),
+                    ));
+                    continue;
+                },
+            }
+            for action in key_block.children().map(|c| c.nodes()).unwrap_or_default() {
+                if let Err(e) = Action::try_from((action, &config_options)) {
+                    diagnostics.push(LintDiagnostic::from_config_error(e, file));
+                }
+            }
+            for key in key_block
+                .entries()
+                .iter()
+                .filter_map(|e| e.value().as_string())
+            {
+                let key = match KeyWithModifier::from_str(key) {
+                    Ok(key) => key,
+                    Err(_) => {
+                        diagnostics.push(file.diagnostic(
+                            Severity::Error,
+                            format!("Invalid key: '{}'", key),
+                            key_block,
+                            None,
+                        ));
+                        continue;
+                    },
+                };
+                for mode in &modes {
+                    let shadowed =
+                        bound_keys.insert((*mode, key.clone()), (key_block, is_shared_block));
+                    if let Some((shadowed, shadowed_is_shared)) = shadowed {
+                        if shadowed_is_shared && !is_shared_block {
+                            continue;
+                        }
+                        shadowed_keybindings
+                            .entry((shadowed.span().offset(), shadowed.span().len()))
+                            .or_insert_with(|| (shadowed, key.clone(), vec![]))
+                            .2
+                            .push(*mode);
+                    }
+                }
+            }
+        }
+    }
+    for (_, (shadowed, key, modes)) in shadowed_keybindings {
+        let modes: Vec<String> = modes.iter().map(|m| format!("{:?}", m)).collect();
+        diagnostics.push(file.diagnostic(
+            Severity::Warning,
+            format!(
+                "'{}' is bound again in {} mode, overriding this keybinding",
+                key,
+                modes.join(", ")
+            ),
+            shadowed,
+            None,
+        ));
+    }
+    mode_blocks
+}
+
+fn reachable_modes(keybinds: &Keybinds, base_mode: InputMode) -> HashSet<InputMode> {
+    let mut reachable_modes = HashSet::from([base_mode]);
+    let mut modes_to_visit = vec![base_mode];
+    while let Some(mode) = modes_to_visit.pop() {
+        let actions = keybinds
+            .0
+            .get(&mode)
+            .into_iter()
+            .flat_map(|k| k.values().flatten());
+        for action in actions {
+            if let Action::SwitchToMode(next_mode) | Action::SwitchModeForAllClients(next_mode) =
+                action
+            {
+                if reachable_modes.insert(*next_mode) {
+                    modes_to_visit.push(*next_mode);
+                }
+            }
+        }
+    }
+    reachable_modes
+}
+
+fn lint_layout(layout_file: &Path, diagnostics: &mut Vec<LintDiagnostic>) {
+    if layout_file.to_string_lossy().ends_with(".swap.kdl") {
+        // these are linted with the layout they belong to
+        return;
+    }
+    let (path_to_layout, raw_layout, swap_layout) = match Layout::stringified_from_path(layout_file)
+    {
+        Ok(stringified_layout) => stringified_layout,
+        Err(e) => {
+            let file = LintedFile {
+                name: layout_file.display().to_string(),
+                contents: String::new(),
+            };
+            diagnostics.push(LintDiagnostic::from_config_error(e, &file));
+            return;
+        },
+    };
+    let file = LintedFile {
+        name: path_to_layout.clone(),
+        contents: raw_layout,
+    };
+    let swap_file = swap_layout.map(|(name, contents)| LintedFile { name, contents });
+    let mut documents = vec![];
+    for file in std::iter::once(&file).chain(swap_file.iter()) {
+        match file.parse(diagnostics) {
+            Some(document) => documents.push((file, document)),
+            // loading the layout would only report the same syntax error
+            None => return,
+        }
+    }
+    if let Err(e) = Layout::from_kdl(
+        &file.contents,
+        Some(path_to_layout),
+        swap_file
+            .as_ref()
+            .map(|f| (f.name.as_str(), f.contents.as_str())),
+        None,
+    ) {
+        diagnostics.push(LintDiagnostic::from_config_error(e, &file));
+    }
+    // layouts can also configure zellij
+    if let Err(e) = Config::from_kdl(&file.contents, None) {
+        diagnostics.push(LintDiagnostic::from_config_error(e, &file));
+    }
+    for (file, document) in documents {
+        lint_commands_and_plugins(file, document.nodes(), None, diagnostics);
+    }
+}
+
+/// Reports commands that are not in the `PATH` and plugin files that do not exist
+fn lint_commands_and_plugins(
+    file: &LintedFile,
+    nodes: &[KdlNode],
+    parent: Option<&str>,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let first_argument = |node: &KdlNode| {
+        node.entries()
+            .iter()
+            .find(|e| e.name().is_none())
+            .and_then(|e| e.value().as_string())
+            .map(|a| a.to_owned())
+    };
+    for node in nodes {
+        let name = node.name().value();
+        let first_word = |c: String| c.split_whitespace().next().map(|c| c.to_owned());
+        let command = match (parent, name) {
+            // scratchpad commands include their arguments
+            (Some("scratchpad"), "command") => first_argument(node).and_then(first_word),
+            (_, "command" | "Run") => first_argument(node),
+            (None, "default_shell" | "scrollback_editor") => first_argument(node),
+            (None, "copy_command") => first_argument(node).and_then(first_word),
+            (_, "scratchpad") => node
+                .get("command")
+                .and_then(|e| e.value().as_string())
+                .and_then(|c| first_word(c.to_owned())),
+            _ => node
+                .get("command")
+                .and_then(|e| e.value().as_string())
+                .map(|c| c.to_owned()),
+        };
+        if let Some(command) = command {
+            if !command_exists(&command) {
+                diagnostics.push(file.diagnostic(
+                    Severity::Warning,
+                    format!("Command not found: '{}'", command),
+                    node,
+                    Some("Is it in the PATH zellij runs with?".into()),
+                ));
+            }
+        }
+        let locations = std::iter::once(name)
+            .chain(node.entries().iter().filter_map(|e| e.value().as_string()));
+        for location in locations.filter(|l| l.starts_with("file:")) {
+            if let Ok(RunPluginLocation::File(path)) = RunPluginLocation::parse(location, None) {
+                // relative to the folder zellij is started in
+                if path.is_absolute() && !path.exists() {
+                    diagnostics.push(file.diagnostic(
+                        Severity::Error,
+                        format!("Plugin file not found: {}", path.display()),
+                        node,
+                        None,
+                    ));
+                }
+            }
+        }
+        if let Some(children) = node.children() {
+            lint_commands_and_plugins(file, children.nodes(), Some(name), diagnostics);
+        }
+    }
+}
+
+// relative paths are relative to the cwd of the pane, so only bare commands and absolute paths are
+// checked
+fn command_exists(command: &str) -> bool {
+    if command.contains('$') {
+        return true;
+    }
+    let command = shellexpand::tilde(command);
+    let command = Path::new(command.as_ref());
+    if command.is_absolute() {
+        command.exists()
+    } else if command.components().count() > 1 {
+        true
+    } else {
+        env::var_os("PATH")
+            .map(|paths| env::split_paths(&paths).any(|folder| folder.join(command).is_file()))
+            .unwrap_or(false)
+    }
+}
+
+fn closest_name<'a>(name: &str, known_names: &[&'a str]) -> Option<&'a str> {
+    known_names
+        .iter()
+        .map(|known_name| (edit_distance(name, known_name), *known_name))
+        .filter(|(distance, _)| *distance <= 2)
+        .min()
+        .map(|(_, known_name)| known_name)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + if a_char == *b_char { 0 } else { 1 };
+            row.push(substitution.min(previous_row[j + 1] + 1).min(row[j] + 1));
+        }
+        previous_row = row;
+    }
+    previous_row[b.len()]
+}
+
+#[cfg(test)]
+mod lint_test {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn lint_messages(diagnostics: &[LintDiagnostic], severity: Severity) -> Vec<String> {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .map(|d| d.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn lint_reports_every_problem_in_the_config() {
+        let tmp = tempdir().unwrap();
+        let config_file_path = tmp.path().join("config.kdl");
+        fs::File::create(&config_file_path)
+            .unwrap()
+            .write_all(
+                br#"
+                pane_frame false
+                default_shell "zellij-lint-test-missing-shell"
+                load_plugins {
+                    "file:/zellij-lint-test/missing-plugin.wasm"
+                }
+                keybinds clear-defaults=true {
+                    normal {
+                        bind "Ctrl x" { NotAnAction; }
+                        bind "Ctrl y" { AlsoNotAnAction; }
+                        bind "Alt z" { Detach; }
+                        bind "Alt z" { Quit; }
+                    }
+                    tmux {
+                        bind "d" { Detach; }
+                    }
+                }
+            "#,
+            )
+            .unwrap();
+        let diagnostics = lint(Some(&config_file_path), None, None);
+        let errors = lint_messages(&diagnostics, Severity::Error);
+        let warnings = lint_messages(&diagnostics, Severity::Warning);
+        assert!(
+            errors.iter().any(|e| e.contains("NotAnAction"))
+                && errors.iter().any(|e| e.contains("AlsoNotAnAction")),
+            "every invalid action is reported: {:?}",
+            errors
+        );
+        assert!(
+            errors.iter().any(|e| e.contains("missing-plugin.wasm")),
+            "missing plugin file: {:?}",
+            errors
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("Unknown config node: 'pane_frame'")),
+            "unknown node: {:?}",
+            warnings
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("zellij-lint-test-missing-shell")),
+            "missing command: {:?}",
+            warnings
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("'Alt z' is bound again")),
+            "shadowed keybinding: {:?}",
+            warnings
+        );
+        let unknown_node = diagnostics
+            .iter()
+            .find(|d| d.message.contains("pane_frame"))
+            .unwrap();
+        assert_eq!(
+            unknown_node.help_message,
+            Some("Did you mean 'pane_frames'?".to_owned()),
+            "closest option suggested"
+        );
+        assert_eq!(
+            diagnostics.first().map(|d| d.severity),
+            Some(Severity::Error),
+            "errors first"
+        );
+    }
+
+    #[test]
+    fn lint_does_not_report_shared_keybindings_overridden_in_a_mode() {
+        let tmp = tempdir().unwrap();
+        let config_file_path = tmp.path().join("config.kdl");
+        fs::File::create(&config_file_path)
+            .unwrap()
+            .write_all(
+                br#"
+                keybinds clear-defaults=true {
+                    shared_except "locked" {
+                        bind "Ctrl g" { SwitchToMode "locked"; }
+                    }
+                    normal {
+                        bind "Ctrl g" { SwitchToMode "tmux"; }
+                    }
+                    locked {
+                        bind "Ctrl g" { SwitchToMode "normal"; }
+                    }
+                }
+            "#,
+            )
+            .unwrap();
+        let diagnostics = lint(Some(&config_file_path), None, None);
+        let warnings = lint_messages(&diagnostics, Severity::Warning);
+        assert!(
+            !warnings.iter().any(|w| w.contains("is bound again")),
+            "shared keybinding overridden in a mode: {:?}",
+            warnings
+        );
+    }
+
+    #[test]
+    fn lint_reports_unreachable_modes() {
+        let tmp = tempdir().unwrap();
+        let config_file_path = tmp.path().join("config.kdl");
+        fs::File::create(&config_file_path)
+            .unwrap()
+            .write_all(
+                br#"
+                keybinds clear-defaults=true {
+                    normal {
+                        bind "Ctrl p" { SwitchToMode "Pane"; }
+                    }
+                    pane {
+                        bind "Esc" { SwitchToMode "Normal"; }
+                    }
+                    tmux {
+                        bind "d" { Detach; }
+                    }
+                }
+            "#,
+            )
+            .unwrap();
+        let diagnostics = lint(Some(&config_file_path), None, None);
+        let warnings = lint_messages(&diagnostics, Severity::Warning);
+        assert_eq!(
+            warnings,
+            vec!["No keybinding switches to Tmux mode from the Normal mode".to_owned()],
+            "only the mode no keybinding switches to is reported"
+        );
+    }
+
+    #[test]
+    fn lint_reports_problems_in_layouts() {
+        let tmp = tempdir().unwrap();
+        fs::File::create(tmp.path().join("broken.kdl"))
+            .unwrap()
+            .write_all(b"layout {\n    pane split_direction=\"diagonal\"\n}\n")
+            .unwrap();
+        fs::File::create(tmp.path().join("fine.kdl"))
+            .unwrap()
+            .write_all(b"layout {\n    pane command=\"zellij-lint-test-missing-command\"\n}\n")
+            .unwrap();
+        let diagnostics = lint(None, Some(tmp.path()), None);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .filter(|d| d.severity == Severity::Error)
+                .count(),
+            1,
+            "invalid layout: {:?}",
+            diagnostics
+        );
+        assert!(
+            lint_messages(&diagnostics, Severity::Warning)
+                .iter()
+                .any(|w| w.contains("zellij-lint-test-missing-command")),
+            "missing command in layout: {:?}",
+            diagnostics
+        );
+    }
+}
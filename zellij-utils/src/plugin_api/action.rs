@@ -543,7 +543,7 @@ impl TryFrom<ProtobufAction> for Action {
             },
             Some(ProtobufActionName::QueryTabNames) => match protobuf_action.optional_payload {
                 Some(_) => Err("QueryTabNames should not have a payload"),
-                None => Ok(Action::QueryTabNames),
+                None => Ok(Action::QueryTabNames(false)),
             },
             Some(ProtobufActionName::NewTiledPluginPane) => {
                 match protobuf_action.optional_payload {
@@ -1123,7 +1123,7 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::NextSwapLayout as i32,
                 optional_payload: None,
             }),
-            Action::QueryTabNames => Ok(ProtobufAction {
+            Action::QueryTabNames(_json) => Ok(ProtobufAction {
                 name: ProtobufActionName::QueryTabNames as i32,
                 optional_payload: None,
             }),
@@ -1242,11 +1242,56 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::NewInPlacePluginPane(..)
             | Action::Deny
             | Action::Copy
-            | Action::DumpLayout
+            | Action::DumpLayout(..)
             | Action::CliPipe { .. }
-            | Action::ListClients
+            | Action::ListClients(..)
             | Action::StackPanes(..)
             | Action::ChangeFloatingPaneCoordinates(..)
+            | Action::StartRecording(..)
+            | Action::StopRecording(..)
+            | Action::TogglePaneLogging(..)
+            | Action::ReopenClosedPane
+            | Action::ReopenClosedTab
+            | Action::JoinPane(..)
+            | Action::SwapPanes(..)
+            | Action::RotatePanes(..)
+            | Action::MirrorLayout(..)
+            | Action::EqualizePanes
+            | Action::ResizeTo(..)
+            | Action::ToggleGoldenRatio
+            | Action::TogglePaneInSyncGroup
+            | Action::ClearSyncGroup
+            | Action::PaneJump(..)
+            | Action::ToggleScratchpad(..)
+            | Action::GoToTabGroup(..)
+            | Action::GoToNextTabInGroup
+            | Action::GoToPreviousTabInGroup
+            | Action::MoveTabToGroup(..)
+            | Action::ArrangeFloatingPanes(..)
+            | Action::ListTabs(..)
+            | Action::ListPanes(..)
+            | Action::WriteToPaneId(..)
+            | Action::ResizePaneWithId(..)
+            | Action::MovePaneWithPaneId(..)
+            | Action::ClearScreenForPaneId(..)
+            | Action::DumpScreenForPaneId(..)
+            | Action::EditScrollbackForPaneId(..)
+            | Action::ScrollUpInPaneId(..)
+            | Action::ScrollDownInPaneId(..)
+            | Action::ScrollToTopInPaneId(..)
+            | Action::ScrollToBottomInPaneId(..)
+            | Action::PageScrollUpInPaneId(..)
+            | Action::PageScrollDownInPaneId(..)
+            | Action::TogglePaneIdFullscreen(..)
+            | Action::TogglePaneEmbedOrFloatingForPaneId(..)
+            | Action::ClosePaneWithId(..)
+            | Action::RenamePaneWithId(..)
+            | Action::SendKeys(..)
+            | Action::SendKeysToPaneId(..)
+            | Action::WaitFor { .. }
+            | Action::SendWaitForSignal(..)
+            | Action::CloseTabAtPosition(..)
+            | Action::RenameTabAtPosition(..)
             | Action::SkipConfirm(..) => Err("Unsupported action"),
         }
     }
@@ -1331,6 +1376,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             .and_then(|d| d.try_into().ok());
         let hold_on_close = protobuf_run_command_action.hold_on_close;
         let hold_on_start = protobuf_run_command_action.hold_on_start;
+        let close_on_success = protobuf_run_command_action.close_on_success;
         Ok(RunCommandAction {
             command,
             args,
@@ -1338,6 +1384,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            close_on_success,
             ..Default::default()
         })
     }
@@ -1355,6 +1402,7 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
         });
         let hold_on_close = run_command_action.hold_on_close;
         let hold_on_start = run_command_action.hold_on_start;
+        let close_on_success = run_command_action.close_on_success;
         Ok(ProtobufRunCommandAction {
             command,
             args,
@@ -1362,6 +1410,7 @@ impl TryFrom<RunCommandAction> for ProtobufRunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            close_on_success,
             pane_name: None,
         })
     }
@@ -1048,6 +1048,7 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
             terminal_command: protobuf_pane_info.terminal_command,
             plugin_url: protobuf_pane_info.plugin_url,
             is_selectable: protobuf_pane_info.is_selectable,
+            log_file: protobuf_pane_info.log_file,
         })
     }
 }
@@ -1083,6 +1084,7 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
             terminal_command: pane_info.terminal_command,
             plugin_url: pane_info.plugin_url,
             is_selectable: pane_info.is_selectable,
+            log_file: pane_info.log_file,
         })
     }
 }
@@ -1109,6 +1111,7 @@ impl TryFrom<ProtobufTabInfo> for TabInfo {
             viewport_columns: protobuf_tab_info.viewport_columns as usize,
             display_area_rows: protobuf_tab_info.display_area_rows as usize,
             display_area_columns: protobuf_tab_info.display_area_columns as usize,
+            group: protobuf_tab_info.group,
         })
     }
 }
@@ -1135,6 +1138,7 @@ impl TryFrom<TabInfo> for ProtobufTabInfo {
             viewport_columns: tab_info.viewport_columns as u32,
             display_area_rows: tab_info.display_area_rows as u32,
             display_area_columns: tab_info.display_area_columns as u32,
+            group: tab_info.group,
         })
     }
 }
@@ -1540,6 +1544,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             viewport_columns: 10,
             display_area_rows: 10,
             display_area_columns: 10,
+            group: Some("backend".to_owned()),
         },
         TabInfo {
             position: 1,
@@ -1556,6 +1561,7 @@ fn serialize_tab_update_event_with_non_default_values() {
             viewport_columns: 10,
             display_area_rows: 10,
             display_area_columns: 10,
+            group: None,
         },
         TabInfo::default(),
     ]);
@@ -1827,6 +1833,7 @@ fn serialize_session_update_event_with_non_default_values() {
             viewport_columns: 10,
             display_area_rows: 10,
             display_area_columns: 10,
+            group: None,
         },
         TabInfo {
             position: 1,
@@ -1843,6 +1850,7 @@ fn serialize_session_update_event_with_non_default_values() {
             viewport_columns: 10,
             display_area_rows: 10,
             display_area_columns: 10,
+            group: None,
         },
         TabInfo::default(),
     ];
@@ -1871,6 +1879,7 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            log_file: Some("/tmp/pane.log".to_owned()),
         },
         PaneInfo {
             id: 1,
@@ -1895,6 +1904,7 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            log_file: None,
         },
     ];
     panes.insert(0, panes_list);
@@ -10,12 +10,13 @@ pub use super::generated_api::api::{
         FixedOrPercent as ProtobufFixedOrPercent,
         FixedOrPercentValue as ProtobufFixedOrPercentValue,
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates, HidePaneWithIdPayload,
-        HttpVerb as ProtobufHttpVerb, IdAndNewName, KeyToRebind, KeyToUnbind, KillSessionsPayload,
-        LoadNewPluginPayload, MessageToPluginPayload, MovePaneWithPaneIdInDirectionPayload,
-        MovePaneWithPaneIdPayload, MovePayload, NewPluginArgs as ProtobufNewPluginArgs,
-        NewTabsWithLayoutInfoPayload, OpenCommandPaneFloatingNearPluginPayload,
-        OpenCommandPaneInPlaceOfPluginPayload, OpenCommandPaneNearPluginPayload,
-        OpenCommandPanePayload, OpenFileFloatingNearPluginPayload, OpenFileInPlaceOfPluginPayload,
+        HttpVerb as ProtobufHttpVerb, IdAndNewName, JoinPaneToFocusedTabPayload, KeyToRebind,
+        KeyToUnbind, KillSessionsPayload, LoadNewPluginPayload, MessageToPluginPayload,
+        MirrorLayoutPayload, MovePaneWithPaneIdInDirectionPayload, MovePaneWithPaneIdPayload,
+        MovePayload, NewPluginArgs as ProtobufNewPluginArgs, NewTabsWithLayoutInfoPayload,
+        OpenCommandPaneFloatingNearPluginPayload, OpenCommandPaneInPlaceOfPluginPayload,
+        OpenCommandPaneNearPluginPayload, OpenCommandPanePayload,
+        OpenFileFloatingNearPluginPayload, OpenFileInPlaceOfPluginPayload,
         OpenFileNearPluginPayload, OpenFilePayload, OpenTerminalFloatingNearPluginPayload,
         OpenTerminalInPlaceOfPluginPayload, OpenTerminalNearPluginPayload,
         PageScrollDownInPaneIdPayload, PageScrollUpInPaneIdPayload, PaneId as ProtobufPaneId,
@@ -23,12 +24,12 @@ pub use super::generated_api::api::{
         PluginCommand as ProtobufPluginCommand, PluginMessagePayload, RebindKeysPayload,
         ReconfigurePayload, ReloadPluginPayload, RequestPluginPermissionPayload,
         RerunCommandPanePayload, ResizePaneIdWithDirectionPayload, ResizePayload,
-        RunCommandPayload, ScrollDownInPaneIdPayload, ScrollToBottomInPaneIdPayload,
-        ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload, SetFloatingPanePinnedPayload,
-        SetTimeoutPayload, ShowPaneWithIdPayload, StackPanesPayload, SubscribePayload,
-        SwitchSessionPayload, SwitchTabToPayload, TogglePaneEmbedOrEjectForPaneIdPayload,
-        TogglePaneIdFullscreenPayload, UnsubscribePayload, WebRequestPayload,
-        WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
+        RotatePanesPayload, RunCommandPayload, ScrollDownInPaneIdPayload,
+        ScrollToBottomInPaneIdPayload, ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload,
+        SetFloatingPanePinnedPayload, SetTimeoutPayload, ShowPaneWithIdPayload, StackPanesPayload,
+        SubscribePayload, SwapPanesPayload, SwitchSessionPayload, SwitchTabToPayload,
+        TogglePaneEmbedOrEjectForPaneIdPayload, TogglePaneIdFullscreenPayload, UnsubscribePayload,
+        WebRequestPayload, WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
@@ -36,7 +37,8 @@ pub use super::generated_api::api::{
 
 use crate::data::{
     ConnectToSession, FloatingPaneCoordinates, HttpVerb, InputMode, KeyWithModifier,
-    MessageToPlugin, NewPluginArgs, PaneId, PermissionType, PluginCommand,
+    MessageToPlugin, MirrorDirection, NewPluginArgs, PaneId, PermissionType, PluginCommand,
+    RotateDirection,
 };
 use crate::input::actions::Action;
 use crate::input::layout::SplitSize;
@@ -1536,6 +1538,80 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for OpenFileInPlaceOfPlugin"),
             },
+            Some(CommandName::JoinPaneToFocusedTab) => match protobuf_plugin_command.payload {
+                Some(Payload::JoinPaneToFocusedTabPayload(join_pane_to_focused_tab_payload)) => {
+                    match join_pane_to_focused_tab_payload
+                        .pane_id
+                        .and_then(|p| p.try_into().ok())
+                    {
+                        Some(pane_id) => Ok(PluginCommand::JoinPaneToFocusedTab(
+                            pane_id,
+                            join_pane_to_focused_tab_payload.should_float,
+                        )),
+                        None => Err("PaneId not found!"),
+                    }
+                },
+                _ => Err("Mismatched payload for JoinPaneToFocusedTab"),
+            },
+            Some(CommandName::SwapPanes) => match protobuf_plugin_command.payload {
+                Some(Payload::SwapPanesPayload(swap_panes_payload)) => {
+                    match (
+                        swap_panes_payload
+                            .first_pane_id
+                            .and_then(|p| p.try_into().ok()),
+                        swap_panes_payload
+                            .second_pane_id
+                            .and_then(|p| p.try_into().ok()),
+                    ) {
+                        (Some(first_pane_id), Some(second_pane_id)) => {
+                            Ok(PluginCommand::SwapPanes(first_pane_id, second_pane_id))
+                        },
+                        _ => Err("PaneId not found!"),
+                    }
+                },
+                _ => Err("Mismatched payload for SwapPanes"),
+            },
+            Some(CommandName::RotatePanes) => match protobuf_plugin_command.payload {
+                Some(Payload::RotatePanesPayload(rotate_panes_payload)) => {
+                    let rotate_direction = if rotate_panes_payload.counter_clockwise {
+                        RotateDirection::CounterClockwise
+                    } else {
+                        RotateDirection::Clockwise
+                    };
+                    Ok(PluginCommand::RotatePanes(rotate_direction))
+                },
+                _ => Err("Mismatched payload for RotatePanes"),
+            },
+            Some(CommandName::MirrorLayout) => match protobuf_plugin_command.payload {
+                Some(Payload::MirrorLayoutPayload(mirror_layout_payload)) => {
+                    let mirror_direction = if mirror_layout_payload.vertically {
+                        MirrorDirection::Vertical
+                    } else {
+                        MirrorDirection::Horizontal
+                    };
+                    Ok(PluginCommand::MirrorLayout(mirror_direction))
+                },
+                _ => Err("Mismatched payload for MirrorLayout"),
+            },
+            Some(CommandName::OpenCommandPanePopup) => match protobuf_plugin_command.payload {
+                Some(Payload::OpenCommandPanePopupPayload(command_to_run_payload)) => {
+                    match command_to_run_payload.command_to_run {
+                        Some(command_to_run) => {
+                            let context: BTreeMap<String, String> = command_to_run_payload
+                                .context
+                                .into_iter()
+                                .map(|e| (e.name, e.value))
+                                .collect();
+                            Ok(PluginCommand::OpenCommandPanePopup(
+                                command_to_run.try_into()?,
+                                context,
+                            ))
+                        },
+                        None => Err("Malformed open command pane popup payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for OpenCommandPanePopup"),
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -2536,6 +2612,52 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     )),
                 })
             },
+            PluginCommand::JoinPaneToFocusedTab(pane_id, should_float) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::JoinPaneToFocusedTab as i32,
+                    payload: Some(Payload::JoinPaneToFocusedTabPayload(
+                        JoinPaneToFocusedTabPayload {
+                            pane_id: pane_id.try_into().ok(),
+                            should_float,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::SwapPanes(first_pane_id, second_pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::SwapPanes as i32,
+                payload: Some(Payload::SwapPanesPayload(SwapPanesPayload {
+                    first_pane_id: first_pane_id.try_into().ok(),
+                    second_pane_id: second_pane_id.try_into().ok(),
+                })),
+            }),
+            PluginCommand::RotatePanes(rotate_direction) => Ok(ProtobufPluginCommand {
+                name: CommandName::RotatePanes as i32,
+                payload: Some(Payload::RotatePanesPayload(RotatePanesPayload {
+                    counter_clockwise: rotate_direction == RotateDirection::CounterClockwise,
+                })),
+            }),
+            PluginCommand::MirrorLayout(mirror_direction) => Ok(ProtobufPluginCommand {
+                name: CommandName::MirrorLayout as i32,
+                payload: Some(Payload::MirrorLayoutPayload(MirrorLayoutPayload {
+                    vertically: mirror_direction == MirrorDirection::Vertical,
+                })),
+            }),
+            PluginCommand::OpenCommandPanePopup(command_to_run, context) => {
+                let context: Vec<_> = context
+                    .into_iter()
+                    .map(|(name, value)| ContextItem { name, value })
+                    .collect();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::OpenCommandPanePopup as i32,
+                    payload: Some(Payload::OpenCommandPanePopupPayload(
+                        OpenCommandPanePayload {
+                            command_to_run: Some(command_to_run.try_into()?),
+                            floating_pane_coordinates: None,
+                            context,
+                        },
+                    )),
+                })
+            },
         }
     }
 }
@@ -3,10 +3,12 @@ use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use crate::{
+    input::command::RemoteHost,
     input::layout::PluginUserConfiguration,
     input::layout::{
-        FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, Run, RunPluginOrAlias,
-        SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
+        FloatingPaneLayout, Layout, LayoutConstraint, PaneLogging, PercentOrFixed, Run,
+        RunPluginOrAlias, SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout,
+        TiledPaneLayout,
     },
     pane_size::{Constraint, PaneGeom},
 };
@@ -17,6 +19,7 @@ pub struct GlobalLayoutManifest {
     pub default_shell: Option<PathBuf>,
     pub default_layout: Box<Layout>,
     pub tabs: Vec<(String, TabLayoutManifest)>,
+    pub redact_env: bool, // serialize the names of env variables without their values
 }
 
 #[derive(Default, Debug, Clone)]
@@ -25,6 +28,7 @@ pub struct TabLayoutManifest {
     pub floating_panes: Vec<PaneLayoutManifest>,
     pub is_focused: bool,
     pub hide_floating_panes: bool,
+    pub group: Option<String>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -36,6 +40,8 @@ pub struct PaneLayoutManifest {
     pub title: Option<String>,
     pub is_focused: bool,
     pub pane_contents: Option<String>,
+    pub log_to_file: Option<PaneLogging>,
+    pub env: BTreeMap<String, String>,
 }
 
 pub fn serialize_session_layout(
@@ -75,6 +81,10 @@ pub fn serialize_session_layout(
         &mut layout_node_children,
     );
 
+    if global_layout_manifest.redact_env {
+        redact_env_values(&mut layout_node_children);
+    }
+
     layout_node.set_children(layout_node_children);
     document.nodes_mut().push(layout_node);
     Ok((document.to_string(), pane_contents))
@@ -186,12 +196,18 @@ fn serialize_tiled_pane(
         || layout.external_children_index.is_some()
         || !args.is_empty()
         || plugin.is_some()
-        || command.is_some();
+        || command.is_some()
+        || layout.log_to_file.is_some()
+        || !layout.env.is_empty()
+        || extract_remote(&layout.run).is_some();
     if has_child_attributes {
         let mut tiled_pane_node_children = KdlDocument::new();
         serialize_args(args, &mut tiled_pane_node_children);
         serialize_start_suspended(&command, &mut tiled_pane_node_children);
+        serialize_remote(extract_remote(&layout.run), &mut tiled_pane_node_children);
         serialize_plugin(plugin, plugin_config, &mut tiled_pane_node_children);
+        serialize_log_to_file(&layout.log_to_file, &mut tiled_pane_node_children);
+        serialize_env(&layout.env, &mut tiled_pane_node_children);
         if layout.children.is_empty() && layout.external_children_index.is_some() {
             tiled_pane_node_children
                 .nodes_mut()
@@ -215,6 +231,10 @@ fn serialize_tiled_pane(
 
 pub fn extract_command_and_args(layout_run: &Option<Run>) -> (Option<String>, Vec<String>) {
     match layout_run {
+        // a remote pane with no command runs the login shell of its host
+        Some(Run::Command(run_command)) if run_command.command.as_os_str().is_empty() => {
+            (None, vec![])
+        },
         Some(Run::Command(run_command)) => (
             Some(run_command.command.display().to_string()),
             run_command.args.clone(),
@@ -463,6 +483,85 @@ fn serialize_floating_layout_attributes(
     }
 }
 
+fn serialize_log_to_file(log_to_file: &Option<PaneLogging>, pane_node_children: &mut KdlDocument) {
+    if let Some(log_to_file) = log_to_file {
+        let mut log_to_file_node = KdlNode::new("log_to_file");
+        log_to_file_node
+            .entries_mut()
+            .push(KdlEntry::new(log_to_file.file.display().to_string()));
+        if log_to_file.strip_ansi {
+            log_to_file_node
+                .entries_mut()
+                .push(KdlEntry::new_prop("strip_ansi", KdlValue::Bool(true)));
+        }
+        if log_to_file.timestamps {
+            log_to_file_node
+                .entries_mut()
+                .push(KdlEntry::new_prop("timestamps", KdlValue::Bool(true)));
+        }
+        if let Some(max_size) = log_to_file.max_size {
+            log_to_file_node.entries_mut().push(KdlEntry::new_prop(
+                "max_size",
+                KdlValue::Base10(max_size as i64),
+            ));
+        }
+        pane_node_children.nodes_mut().push(log_to_file_node);
+    }
+}
+
+pub fn extract_remote(layout_run: &Option<Run>) -> Option<&RemoteHost> {
+    match layout_run {
+        Some(Run::Command(run_command)) => run_command.remote.as_ref(),
+        _ => None,
+    }
+}
+
+fn serialize_remote(remote: Option<&RemoteHost>, pane_node_children: &mut KdlDocument) {
+    if let Some(remote) = remote {
+        let mut remote_node = KdlNode::new("remote");
+        remote_node
+            .entries_mut()
+            .push(KdlEntry::new(remote.destination()));
+        if let Some(cwd) = &remote.cwd {
+            remote_node
+                .entries_mut()
+                .push(KdlEntry::new_prop("cwd", cwd.display().to_string()));
+        }
+        pane_node_children.nodes_mut().push(remote_node);
+    }
+}
+
+fn serialize_env(env: &BTreeMap<String, String>, pane_node_children: &mut KdlDocument) {
+    if !env.is_empty() {
+        let mut env_node = KdlNode::new("env");
+        let mut env_node_children = KdlDocument::new();
+        for (name, value) in env {
+            let mut env_var_node = KdlNode::new(name.as_str());
+            env_var_node.push(value.clone());
+            env_node_children.nodes_mut().push(env_var_node);
+        }
+        env_node.set_children(env_node_children);
+        pane_node_children.nodes_mut().push(env_node);
+    }
+}
+
+// leaves only the names of the variables, which are then taken from the environment the
+// serialized layout is loaded in
+fn redact_env_values(document: &mut KdlDocument) {
+    for node in document.nodes_mut() {
+        let is_env_node = node.name().value() == "env";
+        if let Some(children) = node.children_mut() {
+            if is_env_node {
+                for env_var_node in children.nodes_mut() {
+                    env_var_node.entries_mut().clear();
+                }
+            } else {
+                redact_env_values(children);
+            }
+        }
+    }
+}
+
 fn serialize_start_suspended(command: &Option<String>, pane_node_children: &mut KdlDocument) {
     if command.is_some() {
         let mut start_suspended_node = KdlNode::new("start_suspended");
@@ -616,6 +715,8 @@ fn serialize_multiple_tabs(
     pane_contents: &mut BTreeMap<String, String>,
 ) -> Result<Vec<KdlNode>, &'static str> {
     let mut serialized_tabs: Vec<KdlNode> = vec![];
+    // consecutive tabs of the same group are serialized inside one tab_group node
+    let mut current_tab_group: Option<(String, KdlDocument)> = None;
     for (tab_name, tab_layout_manifest) in tabs {
         let tiled_panes = tab_layout_manifest.tiled_panes;
         let floating_panes = tab_layout_manifest.floating_panes;
@@ -628,13 +729,44 @@ fn serialize_multiple_tabs(
             &floating_panes,
             pane_contents,
         );
-        if let Some(serialized) = serialized {
-            serialized_tabs.push(serialized);
+        let Some(serialized) = serialized else {
+            continue;
+        };
+        let is_in_current_tab_group = match (&tab_layout_manifest.group, &current_tab_group) {
+            (Some(group), Some((current_group, _))) => group == current_group,
+            _ => false,
+        };
+        if is_in_current_tab_group {
+            if let Some((_, tab_group_children)) = current_tab_group.as_mut() {
+                tab_group_children.nodes_mut().push(serialized);
+            }
+            continue;
+        }
+        if let Some(tab_group) = current_tab_group.take() {
+            serialized_tabs.push(serialize_tab_group(tab_group));
         }
+        match tab_layout_manifest.group {
+            Some(group) => {
+                let mut tab_group_children = KdlDocument::new();
+                tab_group_children.nodes_mut().push(serialized);
+                current_tab_group = Some((group, tab_group_children));
+            },
+            None => serialized_tabs.push(serialized),
+        }
+    }
+    if let Some(tab_group) = current_tab_group.take() {
+        serialized_tabs.push(serialize_tab_group(tab_group));
     }
     Ok(serialized_tabs)
 }
 
+fn serialize_tab_group((group, tab_group_children): (String, KdlDocument)) -> KdlNode {
+    let mut tab_group = KdlNode::new("tab_group");
+    tab_group.push(group);
+    tab_group.set_children(tab_group_children);
+    tab_group
+}
+
 fn serialize_floating_pane(
     layout: &FloatingPaneLayout,
     pane_contents: &mut BTreeMap<String, String>,
@@ -660,7 +792,13 @@ fn serialize_floating_pane(
     serialize_start_suspended(&command, &mut floating_pane_node_children);
     serialize_floating_layout_attributes(&layout, &mut floating_pane_node_children);
     serialize_args(args, &mut floating_pane_node_children);
+    serialize_remote(
+        extract_remote(&layout.run),
+        &mut floating_pane_node_children,
+    );
     serialize_plugin(plugin, plugin_config, &mut floating_pane_node_children);
+    serialize_log_to_file(&layout.log_to_file, &mut floating_pane_node_children);
+    serialize_env(&layout.env, &mut floating_pane_node_children);
     floating_pane_node.set_children(floating_pane_node_children);
     floating_pane_node
 }
@@ -689,6 +827,8 @@ fn tiled_pane_layout_from_manifest(
             )
         })
         .unwrap_or((None, false, false, None, None, None));
+    let log_to_file = manifest.and_then(|m| m.log_to_file.clone());
+    let env = manifest.map(|m| m.env.clone()).unwrap_or_default();
     TiledPaneLayout {
         split_size,
         run,
@@ -697,10 +837,23 @@ fn tiled_pane_layout_from_manifest(
         name,
         focus,
         pane_initial_contents,
+        log_to_file,
+        env,
         ..Default::default()
     }
 }
 
+/// The layouts recreating the panes of this tab (eg. when reopening it after it was closed),
+/// None if its tiled panes cannot be expressed as a layout
+pub fn tab_layout_from_manifest(
+    tab: &TabLayoutManifest,
+) -> Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)> {
+    let mut tiled_panes_layout = get_tiled_panes_layout_from_panegeoms(&tab.tiled_panes, None)?;
+    tiled_panes_layout.tab_group = tab.group.clone();
+    let floating_panes_layout = get_floating_panes_layout_from_panegeoms(&tab.floating_panes);
+    Some((tiled_panes_layout, floating_panes_layout))
+}
+
 /// Tab-level parsing
 fn get_tiled_panes_layout_from_panegeoms(
     geoms: &Vec<PaneLayoutManifest>,
@@ -788,6 +941,8 @@ fn get_floating_panes_layout_from_panegeoms(
                 already_running: false,
                 pane_initial_contents: m.pane_contents.clone(),
                 logical_position: None,
+                log_to_file: m.log_to_file.clone(),
+                env: m.env.clone(),
             }
         })
         .collect()
@@ -1270,6 +1425,23 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_tab_groups() {
+        let tab_in_group = || TabLayoutManifest {
+            group: Some("backend".to_owned()),
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![
+                ("Tab #1".to_owned(), tab_in_group()),
+                ("Tab #2".to_owned(), tab_in_group()),
+                ("Tab #3".to_owned(), TabLayoutManifest::default()),
+            ],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
     fn can_serialize_tab_with_tiled_panes() {
         use crate::input::command::RunCommand;
         use crate::input::layout::RunPlugin;
@@ -1641,6 +1813,181 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_pane_logging() {
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    log_to_file: Some(PaneLogging {
+                        file: PathBuf::from("/tmp/\"my/pane.log"),
+                        strip_ansi: true,
+                        timestamps: true,
+                        max_size: Some(1024),
+                    }),
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 10,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+            ],
+            floating_panes: vec![PaneLayoutManifest {
+                geom: PaneGeom {
+                    x: 0,
+                    y: 0,
+                    rows: Dimension::fixed(10),
+                    cols: Dimension::fixed(10),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                log_to_file: Some(PaneLogging::new(PathBuf::from("/tmp/floating_pane.log"))),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab with logging".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    fn tab_layout_manifest_with_env() -> TabLayoutManifest {
+        let env = BTreeMap::from([
+            ("RUST_LOG".to_owned(), "debug".to_owned()),
+            ("API_TOKEN".to_owned(), "s3cr\"et".to_owned()),
+        ]);
+        TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    env: env.clone(),
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    geom: PaneGeom {
+                        x: 10,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+            ],
+            floating_panes: vec![PaneLayoutManifest {
+                geom: PaneGeom {
+                    x: 0,
+                    y: 0,
+                    rows: Dimension::fixed(10),
+                    cols: Dimension::fixed(10),
+                    stacked: None,
+                    is_pinned: false,
+                    logical_position: None,
+                },
+                env,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+    #[test]
+    fn can_serialize_pane_env() {
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab with env".to_owned(), tab_layout_manifest_with_env())],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
+    fn can_redact_serialized_pane_env() {
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab with env".to_owned(), tab_layout_manifest_with_env())],
+            redact_env: true,
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
+    fn can_serialize_remote_panes() {
+        use crate::input::command::RunCommand;
+        let tab_layout_manifest = TabLayoutManifest {
+            tiled_panes: vec![
+                PaneLayoutManifest {
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("htop"),
+                        remote: Some(RemoteHost {
+                            cwd: Some(PathBuf::from("~/app")),
+                            ..RemoteHost::new("deploy@web1")
+                        }),
+                        ..Default::default()
+                    })),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 0,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+                PaneLayoutManifest {
+                    run: Some(Run::Command(RunCommand::remote_shell(RemoteHost::new(
+                        "db1",
+                    )))),
+                    geom: PaneGeom {
+                        x: 0,
+                        y: 10,
+                        rows: Dimension::fixed(10),
+                        cols: Dimension::fixed(10),
+                        stacked: None,
+                        is_pinned: false,
+                        logical_position: None,
+                    },
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Remote tab".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
     fn can_serialize_multiple_tabs() {
         let tab_1_layout_manifest = TabLayoutManifest {
             tiled_panes: vec![PaneLayoutManifest {
@@ -7,6 +7,7 @@ use crate::{
         FEATURES, SYSTEM_DEFAULT_CONFIG_DIR, SYSTEM_DEFAULT_DATA_DIR_PREFIX, VERSION,
         ZELLIJ_CACHE_DIR, ZELLIJ_DEFAULT_THEMES, ZELLIJ_PROJ_DIR,
     },
+    data::InputMode,
     errors::prelude::*,
     home::*,
     input::{
@@ -14,11 +15,13 @@ use crate::{
         layout::Layout,
         options::Options,
     },
+    lint::lint,
 };
 use clap::{Args, IntoApp};
 use clap_complete::Shell;
 use directories::BaseDirs;
 use log::info;
+use miette::{Report, Severity};
 use serde::{Deserialize, Serialize};
 use std::{
     convert::TryFrom,
@@ -27,6 +30,7 @@ use std::{
     io::Write,
     path::{Path, PathBuf},
     process,
+    str::FromStr,
 };
 
 const CONFIG_NAME: &str = "config.kdl";
@@ -329,6 +333,28 @@ pub struct Setup {
     #[clap(long, value_parser)]
     pub check: bool,
 
+    /// Checks the configuration, layouts and themes and reports every problem found in them,
+    /// exiting with a non-zero status if there were any errors
+    #[clap(long, value_parser)]
+    pub lint: bool,
+
+    /// Also exit with a non-zero status if the lint found warnings
+    #[clap(long, value_parser, requires("lint"))]
+    pub deny_warnings: bool,
+
+    /// Lists the keybindings in effect, flagging the keys they keep from the programs running in
+    /// the terminal
+    #[clap(long, value_parser)]
+    pub list_keys: bool,
+
+    /// Only list the keybindings of this mode
+    #[clap(long, value_parser, requires("list_keys"))]
+    pub mode: Option<String>,
+
+    /// List the keybindings as JSON
+    #[clap(long, value_parser, requires("list_keys"))]
+    pub json: bool,
+
     /// Dump specified layout to stdout
     #[clap(long, value_parser)]
     pub dump_layout: Option<String>,
@@ -414,6 +440,9 @@ impl Setup {
         }
 
         if let Some(Command::Setup(ref setup)) = &cli_args.command {
+            if setup.list_keys {
+                process::exit(setup.list_keys(&config, &config_options));
+            }
             setup
                 .from_cli_with_options(cli_args, &config_options)
                 .map_or_else(
@@ -612,6 +641,109 @@ impl Setup {
 
         Ok(())
     }
+    /// Prints the problems found by [`lint`](crate::lint::lint), returning the exit status
+    pub fn lint(cli_args: &CliArgs, deny_warnings: bool) -> i32 {
+        let config_dir = cli_args.config_dir.clone().or_else(find_default_config_dir);
+        let config_file = Config::config_file_path(cli_args).filter(|path| path.exists());
+        let config_options = config_file
+            .as_ref()
+            .and_then(|config_file| Config::from_path(config_file, None).ok())
+            .map(|config| config.options)
+            .unwrap_or_default();
+        let layout_dir = config_options
+            .layout_dir
+            .or_else(|| get_layout_dir(config_dir.clone()))
+            .filter(|dir| dir.exists());
+        let theme_dir = config_options
+            .theme_dir
+            .or_else(|| get_theme_dir(config_dir))
+            .filter(|dir| dir.exists());
+        let diagnostics = lint(
+            config_file.as_deref(),
+            layout_dir.as_deref(),
+            theme_dir.as_deref(),
+        );
+        let error_count = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .count();
+        let warning_count = diagnostics.len() - error_count;
+        for diagnostic in diagnostics {
+            let report: Report = diagnostic.into();
+            eprintln!("{:?}", report);
+        }
+        println!(
+            "[CONFIG FILE]: {}",
+            config_file
+                .map(|config_file| config_file.display().to_string())
+                .unwrap_or_else(|| String::from("Not Found"))
+        );
+        println!(
+            "[LAYOUT DIR]: {}",
+            layout_dir
+                .map(|layout_dir| layout_dir.display().to_string())
+                .unwrap_or_else(|| String::from("Not Found"))
+        );
+        println!(
+            "[THEME DIR]: {}",
+            theme_dir
+                .map(|theme_dir| theme_dir.display().to_string())
+                .unwrap_or_else(|| String::from("Not Found"))
+        );
+        println!("{} error(s), {} warning(s)", error_count, warning_count);
+        lint_exit_status(error_count, warning_count, deny_warnings)
+    }
+    /// Prints the keybindings in effect, returning the exit status
+    pub fn list_keys(&self, config: &Config, config_options: &Options) -> i32 {
+        let mode = match self.mode.as_deref().map(InputMode::from_str).transpose() {
+            Ok(mode) => mode,
+            Err(e) => {
+                eprintln!("Unknown mode: {}", e);
+                return 2;
+            },
+        };
+        let default_input_mode = config_options.default_mode.unwrap_or(InputMode::Normal);
+        let keybindings = config
+            .keybinds
+            .resolved_keybindings(mode, default_input_mode);
+        if self.json {
+            return match serde_json::to_string_pretty(&keybindings) {
+                Ok(json) => {
+                    println!("{}", json);
+                    0
+                },
+                Err(e) => {
+                    eprintln!("Failed to serialize keybindings: {}", e);
+                    1
+                },
+            };
+        }
+        let key_width = keybindings
+            .iter()
+            .map(|keybinding| keybinding.key.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut current_mode = None;
+        for keybinding in keybindings {
+            if current_mode != Some(keybinding.mode) {
+                if current_mode.is_some() {
+                    println!();
+                }
+                println!("[{:?}]", keybinding.mode);
+                current_mode = Some(keybinding.mode);
+            }
+            println!(
+                "  {:width$}  {}",
+                keybinding.key,
+                keybinding.actions.join("; "),
+                width = key_width
+            );
+            if let Some(shadows) = keybinding.shadows {
+                println!("  {:width$}  ^ shadows {}", "", shadows, width = key_width);
+            }
+        }
+        0
+    }
     fn generate_completion(shell: &str) {
         let shell: Shell = match shell.to_lowercase().parse() {
             Ok(shell) => shell,
@@ -707,6 +839,11 @@ impl Setup {
     }
     fn handle_setup_commands(cli_args: &CliArgs) {
         if let Some(Command::Setup(ref setup)) = &cli_args.command {
+            if setup.lint {
+                // before loading the configuration, so that its problems are reported rather
+                // than failing to load it
+                process::exit(Setup::lint(cli_args, setup.deny_warnings));
+            }
             setup.from_cli().map_or_else(
                 |e| {
                     eprintln!("{:?}", e);
@@ -740,9 +877,17 @@ fn merge_attach_command_options(
     cli_config_options
 }
 
+fn lint_exit_status(error_count: usize, warning_count: usize, deny_warnings: bool) -> i32 {
+    if error_count > 0 || (deny_warnings && warning_count > 0) {
+        1
+    } else {
+        0
+    }
+}
+
 #[cfg(test)]
 mod setup_test {
-    use super::Setup;
+    use super::{lint_exit_status, Setup};
     use crate::cli::{CliArgs, Command};
     use crate::input::options::{CliOptions, Options};
     use insta::assert_snapshot;
@@ -854,4 +999,20 @@ mod setup_test {
         let (config, _layout, _options, _, _) = Setup::from_cli_args(&cli_args).unwrap();
         assert_snapshot!(format!("{:#?}", config));
     }
+    #[test]
+    fn lint_fails_on_warnings_only_when_they_are_denied() {
+        assert_eq!(lint_exit_status(0, 0, false), 0, "nothing found");
+        assert_eq!(lint_exit_status(0, 2, false), 0, "warnings only");
+        assert_eq!(lint_exit_status(1, 2, false), 1, "errors");
+        assert_eq!(
+            lint_exit_status(0, 0, true),
+            0,
+            "nothing found with denied warnings"
+        );
+        assert_eq!(
+            lint_exit_status(0, 2, true),
+            1,
+            "warnings only with denied warnings"
+        );
+    }
 }